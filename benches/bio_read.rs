@@ -0,0 +1,130 @@
+//! Throughput benchmarks. Run with `cargo bench`, or `cargo bench -- <filter>` for some of them.
+
+use bio_read::BioReader;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::{
+    fs::File,
+    hint::black_box,
+    io::{BufReader, Seek, SeekFrom},
+    path::PathBuf,
+};
+
+/// Prose made up of ASCII letters, punctuation and whitespace, repeated to about 1 MiB.
+fn ascii_text() -> String {
+    "The quick brown fox jumps over the lazy dog, doesn't it? Well-known words: 42 of them.\n".repeat(12_000)
+}
+
+/// Prose in several scripts, with accents as both precomposed characters and combining marks, repeated to about 1 MiB.
+fn unicode_text() -> String {
+    "Ça fait déjà naïve cafe\u{301}, Größe über alles. Быстрая лиса. 敏捷的狐狸。 Γρήγορη αλεπού.\n".repeat(9_000)
+}
+
+/// A few words, each hundreds of kilobytes long, about 1 MiB in total.
+fn long_words() -> String {
+    ["a".repeat(300_000), "é".repeat(150_000), "well-known".repeat(40_000)].join(" ")
+}
+
+/// A temporary file, removed when dropped.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    /// Create a temporary file named after `name`, with `content`.
+    fn new(name: &str, content: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("bio-read-bench-{}-{name}", std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        Self(path)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Bio-read `text` into a reused buffer.
+fn bench_text(c: &mut Criterion, group: &str, text: &str) {
+    let mut group = c.benchmark_group(group);
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    for fixation_point in 1..=5 {
+        let reader = BioReader::new().fixation_point(fixation_point);
+        group.bench_with_input(BenchmarkId::new("fixation_point", fixation_point), &reader, |b, reader| {
+            let mut output = Vec::with_capacity(text.len() * 3);
+            b.iter(|| {
+                output.clear();
+                reader.bio_read(black_box(text.as_bytes()), &mut output).unwrap();
+            })
+        });
+    }
+    group.finish();
+}
+
+fn plain_ascii(c: &mut Criterion) {
+    bench_text(c, "plain_ascii", &ascii_text());
+}
+
+fn unicode(c: &mut Criterion) {
+    bench_text(c, "unicode", &unicode_text());
+}
+
+fn pathological_long_words(c: &mut Criterion) {
+    bench_text(c, "long_words", &long_words());
+}
+
+fn large_file(c: &mut Criterion) {
+    let text = ascii_text().repeat(16);
+    let input = TempFile::new("large-input.txt", &text);
+    let output = TempFile::new("large-output.txt", "");
+    let reader = BioReader::new();
+    let mut group = c.benchmark_group("large_file");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    group.sample_size(10);
+    group.bench_function("bio_read", |b| {
+        let mut output = File::create(&output.0).unwrap();
+        b.iter(|| {
+            output.set_len(0).unwrap();
+            output.seek(SeekFrom::Start(0)).unwrap();
+            let input = BufReader::new(File::open(&input.0).unwrap());
+            reader.bio_read(input, &mut output).unwrap();
+        })
+    });
+    group.finish();
+}
+
+fn unbuffered_file(c: &mut Criterion) {
+    let text = ascii_text();
+    let reader = BioReader::new();
+    let output = TempFile::new("unbuffered-output.txt", "");
+    let mut group = c.benchmark_group("unbuffered_file");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    // Every write to the file is a system call
+    group.bench_function("bio_read", |b| {
+        let mut file = File::create(&output.0).unwrap();
+        b.iter(|| {
+            file.set_len(0).unwrap();
+            file.seek(SeekFrom::Start(0)).unwrap();
+            reader.bio_read(black_box(text.as_bytes()), &mut file).unwrap();
+        })
+    });
+    group.finish();
+}
+
+/// Requires the `rayon` feature.
+#[cfg(feature = "rayon")]
+fn parallel(c: &mut Criterion) {
+    // Paragraphs separated by blank lines, about 16 MiB in total
+    let text = ascii_text().replace('\n', "\n\n").repeat(8);
+    let reader = BioReader::new();
+    let mut group = c.benchmark_group("parallel");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    group.sample_size(10);
+    group.bench_function("bio_read_text", |b| b.iter(|| reader.bio_read_text(black_box(&text)).unwrap()));
+    group.bench_function("bio_read_text_parallel", |b| b.iter(|| reader.bio_read_text_parallel(black_box(&text)).unwrap()));
+    group.finish();
+}
+
+#[cfg(not(feature = "rayon"))]
+criterion_group!(benches, plain_ascii, unicode, pathological_long_words, large_file, unbuffered_file);
+#[cfg(feature = "rayon")]
+criterion_group!(benches, plain_ascii, unicode, pathological_long_words, large_file, unbuffered_file, parallel);
+criterion_main!(benches);
@@ -0,0 +1,49 @@
+//! Feeds arbitrary bytes through readers with arbitrary settings in every input format, checking that bio-reading never panics. Errors, like invalid UTF-8 in formats that require valid UTF-8, are fine.
+
+#![no_main]
+
+use bio_read::{
+    segment::{CompoundPolicy, TokenPolicy},
+    BioReader, CodeLanguage, InputFormat,
+};
+use libfuzzer_sys::fuzz_target;
+
+/// The input formats to fuzz, picked by the first byte of the input.
+const FORMATS: [InputFormat; 16] = [
+    InputFormat::Auto,
+    InputFormat::Plain,
+    InputFormat::Markdown,
+    InputFormat::Html,
+    InputFormat::Latex,
+    InputFormat::Rst,
+    InputFormat::AsciiDoc,
+    InputFormat::Org,
+    InputFormat::Man,
+    InputFormat::Diff,
+    InputFormat::Email,
+    InputFormat::Subtitles,
+    InputFormat::Code(CodeLanguage::Rust),
+    InputFormat::Code(CodeLanguage::Python),
+    InputFormat::Code(CodeLanguage::Shell),
+    InputFormat::Code(CodeLanguage::Sql),
+];
+
+fuzz_target!(|data: &[u8]| {
+    // The first two bytes pick the format and the settings
+    let [format, settings, input @ ..] = data else {
+        return;
+    };
+    let flag = |bit: u8| settings & (1 << bit) != 0;
+    let reader = BioReader::new()
+        .format(FORMATS[usize::from(*format) % FORMATS.len()])
+        .fixation_point(usize::from(format / 16 % 5) + 1)
+        .saccade(if flag(0) { 2 } else { 1 })
+        .sentence_boost(usize::from(flag(1)))
+        .reset_at_paragraphs(flag(2))
+        .compound_policy(if flag(3) { CompoundPolicy::EachPart } else { CompoundPolicy::Whole })
+        .token_policy(if flag(4) { TokenPolicy::IncludeDigits } else { TokenPolicy::SplitAtDigits })
+        .skip_acronyms(flag(5))
+        .preserve_urls(flag(6))
+        .join_cursive(flag(7));
+    let _ = reader.bio_read(input, &mut std::io::sink());
+});
@@ -0,0 +1,80 @@
+//! Feeds arbitrary bytes, repeated into enormous words and lines, through the input formats processed as they come, checking that memory usage stays within the bound documented by `BioReader::bio_read`: proportional to the longest word up to the maximum word length, or to the longest line for diffs and man pages.
+
+#![no_main]
+
+use bio_read::{BioReader, InputFormat};
+use libfuzzer_sys::fuzz_target;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Tracks how many bytes are allocated, and the most that were at once.
+struct PeakAllocator;
+
+/// How many bytes are allocated.
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+/// The most bytes allocated at once since last reset.
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+impl PeakAllocator {
+    /// Record that `size` more bytes are allocated.
+    fn grow(size: usize) {
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::grow(layout.size());
+        unsafe { System.alloc(layout) }
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::grow(new_size);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator;
+
+/// The input formats processed as they come, picked by the first byte of the input.
+const FORMATS: [InputFormat; 4] = [InputFormat::Plain, InputFormat::Html, InputFormat::Diff, InputFormat::Man];
+
+/// Memory that may be used regardless of the input, like the buffer of the output.
+const OVERHEAD: usize = 256 * 1024;
+
+/// The default `BioReader::max_word_len`, past which words aren't buffered.
+const MAX_WORD_LEN: usize = 64 * 1024;
+
+/// How many times the longest word (or line) buffers may add up to, as they grow by doubling and a word may be held in more than one.
+const FACTOR: usize = 4;
+
+fuzz_target!(|data: &[u8]| {
+    // The first two bytes pick the format and how many times to repeat the rest
+    let [format, repeat, input @ ..] = data else {
+        return;
+    };
+    let format = FORMATS[usize::from(*format) % FORMATS.len()];
+    let input = input.repeat(1 << (repeat % 12));
+    let longest = match format {
+        InputFormat::Diff | InputFormat::Man => input.split(|&b| b == b'\n').map(<[u8]>::len).max(),
+        _ => input.split(u8::is_ascii_whitespace).map(|word| word.len().min(MAX_WORD_LEN)).max(),
+    };
+    let reader = BioReader::new().format(format);
+    // Warm up, so that lazily initialized statics like compiled patterns aren't counted
+    reader.bio_read(&b"warm <b>up</b>\n"[..], &mut std::io::sink()).unwrap();
+    let before = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    reader.bio_read(&input[..], &mut std::io::sink()).unwrap();
+    let used = PEAK.load(Ordering::Relaxed) - before;
+    let bound = OVERHEAD + FACTOR * longest.unwrap_or(0);
+    assert!(used <= bound, "{format:?} used {used} bytes, more than {bound}");
+});
@@ -0,0 +1,77 @@
+#ifndef BIO_READ_H
+#define BIO_READ_H
+
+/* Generated by cbindgen from src/ffi.rs, don't edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// A BioReader object, allowing for customizing the bio-reading experience.
+typedef struct BioReader BioReader;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Create a new [`BioReader`], writing HTML. Free it with [`br_free`].
+struct BioReader *br_new(void);
+
+// Set the fixation point of `reader`, in range \[1, 5\]. Returns 0 on success, or -1 if `reader` is null or `fixation_point` is out of range.
+//
+// # Safety
+//
+// `reader` must be null or a pointer returned by [`br_new`] that has not been freed.
+int br_set_fixation(struct BioReader *reader,
+                    size_t fixation_point);
+
+// Set the format of the input of `reader`, which is one of `"auto"`, `"plain"`, `"markdown"`, `"html"`, `"latex"`, `"rst"`, `"asciidoc"`, `"org"`, `"man"`, `"diff"`, `"email"`, `"subtitles"`, or `"code:"` followed by a programming language, like `"code:rust"`. Returns 0 on success, or -1 if any argument is null or the format is unknown.
+//
+// # Safety
+//
+// `reader` must be null or a pointer returned by [`br_new`] that has not been freed, and `format` must be null or a NUL-terminated string.
+int br_set_format(struct BioReader *reader,
+                  const char *format);
+
+// Set the format of the output of `reader`, which is one of `"ansi"`, `"html"`, `"markdown"`, `"bbcode"`, `"latex"`, `"typst"`, `"rst"`, `"org"`, `"rtf"`, `"plain"` or `"json"`. Returns 0 on success, or -1 if any argument is null or the format is unknown.
+//
+// # Safety
+//
+// `reader` must be null or a pointer returned by [`br_new`] that has not been freed, and `format` must be null or a NUL-terminated string.
+int br_set_output(struct BioReader *reader,
+                  const char *format);
+
+// Do bio-reading on `len` bytes of UTF-8 `text`, which needs not be NUL-terminated. Invalid UTF-8 is passed through as-is.
+//
+// Returns the output, NUL-terminated for convenience, with its length excluding the terminator written to `output_len`. Free it with [`br_string_free`]. Returns null if any pointer is null, or processing fails.
+//
+// # Safety
+//
+// `reader` must be null or a pointer returned by [`br_new`] that has not been freed, `text` must be null or valid for reading `len` bytes, and `output_len` must be null or valid for writing.
+char *br_process_utf8(const struct BioReader *reader,
+                      const char *text,
+                      size_t len,
+                      size_t *output_len);
+
+// Free an output returned by [`br_process_utf8`], given its length excluding the terminator. Does nothing if `output` is null.
+//
+// # Safety
+//
+// `output` must be null or a pointer returned by [`br_process_utf8`] that has not been freed, and `output_len` must be the length written along with it.
+void br_string_free(char *output,
+                    size_t output_len);
+
+// Free a [`BioReader`] created by [`br_new`]. Does nothing if `reader` is null.
+//
+// # Safety
+//
+// `reader` must be null or a pointer returned by [`br_new`] that has not been freed.
+void br_free(struct BioReader *reader);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* BIO_READ_H */
@@ -0,0 +1,1290 @@
+//! Helpers of the `br` binary, for loading configuration, and collecting and processing input files.
+
+use bio_read::{config::ColorChoice, reading_time::ReadingSpeed, render::{HtmlPage, Rtf}, segment::Language, BioReadStats, BioReader, Config, InputFormat, OutputFormat, SpanKind};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    ffi::OsString,
+    fs::File,
+    io::{BufWriter, Read, Result, Write},
+    path::{Path, PathBuf},
+    process::{Child, ChildStdin, Command, Stdio},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicU8, AtomicUsize, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+/// Load the config file at `path`, or at the default location if not given. A missing file at the default location is treated as empty.
+pub fn load_config(path: Option<&Path>) -> std::result::Result<Config, Failure> {
+    let (path, required) = match path {
+        Some(path) => (path.to_path_buf(), true),
+        None => match default_config_path() {
+            Some(path) => (path, false),
+            None => return Ok(Config::default()),
+        },
+    };
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && !required => return Ok(Config::default()),
+        Err(e) => return Err(read_failure(e, "config", &path)),
+    };
+    toml::from_str(&content).map_err(|e| Failure::usage(format!("Invalid config file {}: {}", path.display(), e.to_string().trim_end())))
+}
+
+/// Load the words listed in the stopwords file at `path`, separated by whitespace, ignoring lines starting with `#`.
+pub fn load_stopwords(path: &Path) -> std::result::Result<Vec<String>, Failure> {
+    let content = std::fs::read_to_string(path).map_err(|e| read_failure(e, "stopwords", path))?;
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .flat_map(str::split_whitespace)
+        .map(String::from)
+        .collect())
+}
+
+/// A failure to read the `kind` file at `path`, like a config file.
+fn read_failure(error: std::io::Error, kind: &str, path: &Path) -> Failure {
+    let error = std::io::Error::new(error.kind(), format!("Cannot read {kind} file: {error}"));
+    bio_read::Error::from(error).with_path(path).into()
+}
+
+/// Get the default location of the config file, `bio-read/config.toml` under `$XDG_CONFIG_HOME`, or `~/.config` if unset.
+fn default_config_path() -> Option<PathBuf> {
+    Some(xdg_home("XDG_CONFIG_HOME", ".config")?.join("bio-read").join("config.toml"))
+}
+
+/// Get the location of the file of reading positions of `br --tui`, `bio-read/positions.toml` under `$XDG_STATE_HOME`, or `~/.local/state` if unset.
+#[cfg(feature = "tui")]
+pub fn positions_path() -> Option<PathBuf> {
+    Some(xdg_home("XDG_STATE_HOME", ".local/state")?.join("bio-read").join("positions.toml"))
+}
+
+/// Get the base directory set with the XDG environment variable `var`, or `default` under the home directory if unset.
+fn xdg_home(var: &str, default: &str) -> Option<PathBuf> {
+    std::env::var_os(var).filter(|dir| !dir.is_empty()).map(PathBuf::from).or_else(|| {
+        std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .map(|home| Path::new(&home).join(default))
+    })
+}
+
+/// Resolve `choice` into [`ColorChoice::Always`] or [`ColorChoice::Never`]. Automatically, colors are used if `CLICOLOR_FORCE` is set to anything but `0`, or else if the output goes to a terminal and neither `NO_COLOR` is set nor `CLICOLOR` is `0`.
+pub fn resolve_color(choice: ColorChoice, to_terminal: bool) -> ColorChoice {
+    let set = |name| std::env::var_os(name).filter(|value| !value.is_empty());
+    let enabled = match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto if set("CLICOLOR_FORCE").is_some_and(|value| value != "0") => true,
+        ColorChoice::Auto => to_terminal && set("NO_COLOR").is_none() && set("CLICOLOR").is_none_or(|value| value != "0"),
+    };
+    if enabled {
+        ColorChoice::Always
+    } else {
+        ColorChoice::Never
+    }
+}
+
+/// The format and language of an input.
+pub type Kind = (InputFormat, Language);
+
+/// Bio-readers for each kind of input, built from the same configuration.
+pub struct Readers {
+    /// The readers, along with the kind of their input.
+    readers: Vec<(Kind, BioReader)>,
+}
+
+impl Readers {
+    /// Build a reader from `config` for each of `kinds`.
+    pub fn new(config: &Config, kinds: impl IntoIterator<Item = Kind>) -> std::result::Result<Self, bio_read::Error> {
+        let mut readers: Vec<(Kind, BioReader)> = Vec::new();
+        for kind in kinds {
+            if readers.iter().all(|(built, _)| *built != kind) {
+                let (format, language) = kind;
+                let config = Config {
+                    format: Some(format),
+                    language: Some(language),
+                    ..config.clone()
+                };
+                readers.push((kind, BioReader::from_config(&config)?));
+            }
+        }
+        Ok(Self { readers })
+    }
+    /// Get the reader for input of `kind`, which must be one of the kinds the readers were built for.
+    pub fn get(&self, kind: Kind) -> &BioReader {
+        let (_, reader) = self.readers.iter().find(|(built, _)| *built == kind).expect("Reader should be built for every kind of input");
+        reader
+    }
+}
+
+/// The language of the input, as given with `--lang`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LanguageChoice {
+    /// Detect the language of each input from its start. Requires the `lang-detect` feature.
+    Auto,
+    /// A fixed language.
+    Fixed(Language),
+}
+
+impl FromStr for LanguageChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("auto") {
+            if cfg!(feature = "lang-detect") {
+                Ok(Self::Auto)
+            } else {
+                Err(String::from("Detecting the language requires the lang-detect feature"))
+            }
+        } else {
+            s.parse().map(Self::Fixed)
+        }
+    }
+}
+
+impl LanguageChoice {
+    /// Get the language of input starting with `head`, falling back to English if it cannot be detected.
+    pub fn resolve(self, head: &[u8]) -> Language {
+        match self {
+            Self::Fixed(language) => language,
+            #[cfg(feature = "lang-detect")]
+            Self::Auto => Language::detect(&String::from_utf8_lossy(head)).unwrap_or_default(),
+            #[cfg(not(feature = "lang-detect"))]
+            Self::Auto => {
+                let _ = head;
+                Language::default()
+            }
+        }
+    }
+}
+
+/// How failures are reported on stderr, as given with `--error-format`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ErrorFormat {
+    /// A line of text for people.
+    #[default]
+    Text,
+    /// A JSON object on a line, for scripts.
+    Json,
+}
+
+impl FromStr for ErrorFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Unknown error format: {s}")),
+        }
+    }
+}
+
+/// A failure of `br`, which decides its exit code.
+#[derive(Debug)]
+pub enum Failure {
+    /// The command line is invalid, like options that cannot be used together.
+    Usage(String),
+    /// Configuring, reading, bio-reading or writing failed.
+    Error(bio_read::Error),
+}
+
+impl Failure {
+    /// Exit code of invalid options, on the command line or in the config file.
+    pub const CONFIG: u8 = 2;
+    /// Exit code of failures to read or write.
+    pub const IO: u8 = 3;
+    /// Exit code of input that is not valid in its format or encoding.
+    pub const FORMAT: u8 = 4;
+    /// Exit code of other failures.
+    pub const OTHER: u8 = 1;
+
+    /// A failure for an invalid command line, described by `message`.
+    pub fn usage(message: impl Into<String>) -> Self {
+        Self::Usage(message.into())
+    }
+    /// Get the exit code of the failure, which scripts can rely on.
+    pub fn exit_code(&self) -> u8 {
+        match self.kind() {
+            "config" => Self::CONFIG,
+            "io" => Self::IO,
+            "format" => Self::FORMAT,
+            _ => Self::OTHER,
+        }
+    }
+    /// Get the kind of the failure, as reported in JSON: `config`, `io`, `format` or `other`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Usage(_) | Self::Error(bio_read::Error::FixationPoint(_) | bio_read::Error::Config { .. }) => "config",
+            Self::Error(bio_read::Error::Io { .. }) => "io",
+            Self::Error(bio_read::Error::Encoding { .. } | bio_read::Error::Format { .. }) => "format",
+            Self::Error(_) => "other",
+        }
+    }
+    /// Report the failure on stderr in `format`.
+    pub fn report(&self, format: ErrorFormat) {
+        let (message, path, option) = match self {
+            Self::Usage(message) => (message.clone(), None, None),
+            Self::Error(error) => {
+                let message = error.to_string();
+                // The path is reported on its own
+                let prefix = error.path().map(|path| format!("{}: ", path.display()));
+                let message = prefix.and_then(|prefix| message.strip_prefix(&prefix).map(str::to_string)).unwrap_or(message);
+                (message, error.path(), error.option())
+            }
+        };
+        match format {
+            ErrorFormat::Text => {
+                let path = path.map(|path| format!("{}: ", path.display())).unwrap_or_default();
+                // Name the option at fault, which may come from the config file as well as the command line
+                let option = option.map(|option| format!(" (option {option})")).unwrap_or_default();
+                eprintln!("{path}{message}{option}");
+            }
+            ErrorFormat::Json => {
+                let mut json = format!("{{\"kind\":\"{}\",\"code\":{},\"message\":\"{}\"", self.kind(), self.exit_code(), escape_json(&message));
+                if let Some(path) = path {
+                    json.push_str(&format!(",\"path\":\"{}\"", escape_json(&path.to_string_lossy())));
+                }
+                if let Some(option) = option {
+                    json.push_str(&format!(",\"option\":\"{option}\""));
+                }
+                eprintln!("{json}}}");
+            }
+        }
+    }
+}
+
+impl From<bio_read::Error> for Failure {
+    fn from(error: bio_read::Error) -> Self {
+        Self::Error(error)
+    }
+}
+
+impl From<std::io::Error> for Failure {
+    fn from(error: std::io::Error) -> Self {
+        Self::Error(error.into())
+    }
+}
+
+/// Escape `text` to be the inside of a JSON string.
+fn escape_json(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            _ if c.is_control() => escaped.push_str(&format!("\\u{:04x}", u32::from(c))),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Detect the language of the file at `path` with `choice`, from the start of its content.
+pub fn detect_language(choice: LanguageChoice, path: &Path) -> Language {
+    match choice {
+        LanguageChoice::Fixed(language) => language,
+        LanguageChoice::Auto => choice.resolve(&read_head(path).unwrap_or_default()),
+    }
+}
+
+/// Read the first [`InputFormat::SNIFF_LEN`] bytes of the file at `path`, once decompressed.
+fn read_head(path: &Path) -> Result<Vec<u8>> {
+    let mut head = Vec::with_capacity(InputFormat::SNIFF_LEN);
+    open(path, None)?.take(InputFormat::SNIFF_LEN as u64).read_to_end(&mut head)?;
+    Ok(head)
+}
+
+/// Detect the format of the file at `path`, from its extension (ignoring that of its compression), or else from the start of its content. Falls back to [`InputFormat::Auto`] if the file cannot be read, leaving the error to whoever reads it next. Articles fetched from URLs are plain text.
+pub fn detect_format(path: &Path) -> InputFormat {
+    if is_url(path) {
+        return InputFormat::Plain;
+    }
+    if let Some(format) = InputFormat::from_path(&decompressed_path(path)) {
+        return format;
+    }
+    match read_head(path) {
+        Ok(head) => InputFormat::sniff(&head),
+        Err(_) => InputFormat::Auto,
+    }
+}
+
+/// A file to be bio-read.
+#[derive(Debug)]
+pub struct Input {
+    /// Where to read the file from.
+    pub path: PathBuf,
+    /// Where to write the output to, relative to the output directory.
+    pub name: PathBuf,
+}
+
+/// Decides which files found in directories are bio-read.
+#[derive(Debug)]
+pub struct Filter {
+    /// Files must match one of these, if any.
+    include: Option<GlobSet>,
+    /// Files and directories must match none of these.
+    exclude: GlobSet,
+}
+
+impl Filter {
+    /// Create a filter from `include` and `exclude` glob patterns.
+    pub fn new(include: &[String], exclude: &[String]) -> std::result::Result<Self, globset::Error> {
+        let include = if include.is_empty() {
+            None
+        } else {
+            Some(build_glob_set(include)?)
+        };
+        Ok(Self {
+            include,
+            exclude: build_glob_set(exclude)?,
+        })
+    }
+    /// Whether to bio-read the file at `relative`, relative to the directory it is found in.
+    fn matches_file(&self, relative: &Path) -> bool {
+        self.include.as_ref().is_none_or(|include| is_match(include, relative)) && !is_match(&self.exclude, relative)
+    }
+    /// Whether to descend into the directory at `relative`, relative to the directory it is found in.
+    fn matches_dir(&self, relative: &Path) -> bool {
+        !is_match(&self.exclude, relative)
+    }
+}
+
+/// Whether `set` matches either the whole `relative` path or its last component.
+fn is_match(set: &GlobSet, relative: &Path) -> bool {
+    set.is_match(relative) || relative.file_name().is_some_and(|name| set.is_match(name))
+}
+
+/// Build a glob set from `patterns`.
+fn build_glob_set(patterns: &[String]) -> std::result::Result<GlobSet, globset::Error> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern)?);
+    }
+    builder.build()
+}
+
+/// Collect the files to be bio-read from `paths`. Directories are walked if `recursive` is set, keeping files that pass `filter`, and are rejected otherwise.
+pub fn collect_inputs(paths: Vec<PathBuf>, recursive: bool, filter: &Filter) -> Result<Vec<Input>> {
+    let mut inputs = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            if !recursive {
+                return Err(std::io::Error::other(format!(
+                    "{} is a directory, use --recursive to read it",
+                    path.display()
+                )));
+            }
+            walk(&path, Path::new(""), filter, &mut inputs)?;
+        } else {
+            let name = path.file_name().map(PathBuf::from).unwrap_or_default();
+            inputs.push(Input { path, name });
+        }
+    }
+    Ok(inputs)
+}
+
+/// Walk the directory at `root.join(relative)`, in sorted order, pushing files that pass `filter` to `inputs`.
+fn walk(root: &Path, relative: &Path, filter: &Filter, inputs: &mut Vec<Input>) -> Result<()> {
+    let mut entries = std::fs::read_dir(root.join(relative))?.collect::<Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let name = relative.join(entry.file_name());
+        // Follows symlinks, like `path.is_dir()` does
+        let file_type = std::fs::metadata(entry.path())?.file_type();
+        if file_type.is_dir() {
+            if filter.matches_dir(&name) {
+                walk(root, &name, filter, inputs)?;
+            }
+        } else if filter.matches_file(&name) {
+            inputs.push(Input {
+                path: entry.path(),
+                name,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Get the output path in `output_dir` for each input processed in `mode`, failing if any of them clash or would overwrite an input. Outputs of PDF documents and standalone documents get the extension of `to`.
+pub fn output_paths(inputs: &[Input], output_dir: &Path, to: OutputFormat, mode: Mode) -> std::result::Result<Vec<PathBuf>, Failure> {
+    let mut seen = HashSet::new();
+    let mut outputs = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        if input.name.as_os_str().is_empty() {
+            return Err(Failure::usage(format!("Invalid input file: {}", input.path.display())));
+        }
+        let mut output = output_dir.join(&input.name);
+        if is_pdf(&input.name) || matches!(mode, Mode::Standalone(_)) {
+            output.set_extension(extension(to));
+        }
+        if !seen.insert(output.clone()) {
+            return Err(Failure::usage(format!("Multiple outputs named {}", output.display())));
+        }
+        let same_file = match (std::fs::canonicalize(&input.path), std::fs::canonicalize(&output)) {
+            (Ok(input), Ok(output)) => input == output,
+            _ => false,
+        };
+        if same_file {
+            return Err(Failure::usage(format!("Output would overwrite input: {}", input.path.display())));
+        }
+        outputs.push(output);
+    }
+    Ok(outputs)
+}
+
+/// Create the file at `output` to write the output of all `inputs` to, failing if it already exists unless `force` is set, or if it is one of the inputs. It is compressed if its extension is that of a compression format.
+pub fn create_output(output: &Path, inputs: &[Input], force: bool) -> std::result::Result<Compressed<File>, Failure> {
+    if let Ok(canonical) = std::fs::canonicalize(output) {
+        if inputs.iter().any(|input| std::fs::canonicalize(&input.path).is_ok_and(|input| input == canonical)) {
+            return Err(Failure::usage(format!("Output would overwrite input: {}", output.display())));
+        }
+    }
+    let file = if force { File::create(output) } else { File::create_new(output) };
+    file.and_then(|file| Compressed::new(file, output)).map_err(|e| match e.kind() {
+        std::io::ErrorKind::AlreadyExists => Failure::usage(format!("{} already exists, use --force to overwrite it", output.display())),
+        _ => bio_read::Error::from(e).with_path(output).into(),
+    })
+}
+
+/// Whether `path` is a PDF document, whose text is extracted before bio-reading. Always `false` without the `pdf` feature.
+pub fn is_pdf(path: &Path) -> bool {
+    cfg!(feature = "pdf") && path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("pdf"))
+}
+
+/// Whether `path` is a URL of a web page, whose article is fetched and extracted before bio-reading. Always `false` without the `http` feature.
+pub fn is_url(path: &Path) -> bool {
+    cfg!(feature = "http") && path.to_str().is_some_and(|path| path.starts_with("http://") || path.starts_with("https://"))
+}
+
+/// Fetch the web page at the URL `url`, and extract the text of its article.
+#[cfg(feature = "http")]
+fn fetch_article(url: &Path) -> Result<String> {
+    let page = bio_read::http::fetch(&url.to_string_lossy())?;
+    Ok(bio_read::http::extract_text(&page))
+}
+
+/// Fetch the RSS or Atom feed at the URL `url`.
+#[cfg(feature = "http")]
+pub fn fetch_feed(url: &str) -> Result<bio_read::http::Feed> {
+    bio_read::http::Feed::parse(&bio_read::http::fetch(url)?)
+}
+
+/// Write the title of `feed` to `writer`, followed by the title of each entry, numbered from 1.
+#[cfg(feature = "http")]
+pub fn list_entries(feed: &bio_read::http::Feed, writer: &mut impl Write) -> Result<()> {
+    if !feed.title.is_empty() {
+        writeln!(writer, "{}", feed.title)?;
+    }
+    let width = feed.entries.len().to_string().len();
+    for (i, entry) in feed.entries.iter().enumerate() {
+        writeln!(writer, "{:>width$}  {}", i + 1, entry.title)?;
+    }
+    Ok(())
+}
+
+/// Get the title and the text of the entry numbered `number` from 1 in `feed`. If the feed has no content for it, the article on its page is fetched instead.
+#[cfg(feature = "http")]
+pub fn entry_text(feed: &bio_read::http::Feed, number: usize) -> Result<(String, String)> {
+    let Some(entry) = number.checked_sub(1).and_then(|i| feed.entries.get(i)) else {
+        return Err(std::io::Error::other(format!("No entry {number}, the feed has {} entries", feed.entries.len())));
+    };
+    let text = match &entry.link {
+        Some(link) if entry.content.trim().is_empty() => bio_read::http::extract_text(&bio_read::http::fetch(link)?),
+        _ => entry.text(),
+    };
+    Ok((entry.title.clone(), text))
+}
+
+/// The usual file extension of output in the format `to`.
+fn extension(to: OutputFormat) -> &'static str {
+    match to {
+        OutputFormat::Html => "html",
+        OutputFormat::Markdown => "md",
+        OutputFormat::Latex => "tex",
+        OutputFormat::Typst => "typ",
+        OutputFormat::Rst => "rst",
+        OutputFormat::Org => "org",
+        OutputFormat::Rtf => "rtf",
+        OutputFormat::Json => "jsonl",
+        OutputFormat::Ansi | OutputFormat::BBCode | OutputFormat::Plain => "txt",
+    }
+}
+
+/// What to do with each input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Bio-read it.
+    BioRead,
+    /// Bio-read it into a standalone document of the given format, which is a web page with `--standalone` for html output, or an RTF document for rtf output.
+    Standalone(OutputFormat),
+    /// Undo bio-reading with `--strip`, removing the markup written by the reader.
+    Strip,
+}
+
+impl Mode {
+    /// Process `input` titled `title` with `reader`, writing to `writer`. Stripping reads the whole input first, which must be valid UTF-8.
+    pub fn run(self, reader: &BioReader, title: &str, mut input: impl Read, writer: &mut impl Write) -> Result<()> {
+        match self {
+            Self::BioRead => Ok(reader.bio_read(input, writer)?),
+            Self::Standalone(to) => write_document(to, title, writer, |writer| Ok(reader.bio_read(input, writer)?)),
+            Self::Strip => {
+                let mut output = String::new();
+                input.read_to_string(&mut output)?;
+                writer.write_all(reader.strip(&output).as_bytes())
+            }
+        }
+    }
+
+    /// Bio-read `input` like [`Mode::run`], adding statistics of its words to `stats`. Stripping is not supported.
+    pub fn run_with_stats(self, reader: &BioReader, title: &str, input: impl Read, writer: &mut impl Write, stats: &mut BioReadStats) -> Result<()> {
+        if let Self::Standalone(to) = self {
+            return write_document(to, title, writer, |writer| Self::BioRead.run_with_stats(reader, title, input, writer, stats));
+        }
+        *stats += reader.bio_read_with_stats(input, writer)?;
+        Ok(())
+    }
+}
+
+/// Bio-read the file at `path` (or strip it, depending on `mode`), writing to `writer` and reporting what is read to `progress`. PDF documents have their text extracted first, and URLs have their article fetched.
+pub fn bio_read_input(reader: &BioReader, mode: Mode, path: &Path, progress: Option<&Progress>, writer: &mut impl Write) -> Result<()> {
+    if let Mode::Standalone(to) = mode {
+        // Wrapped here rather than in `Mode::run`, so that PDF documents get a document too
+        return write_document(to, &title(path), writer, |writer| bio_read_input(reader, Mode::BioRead, path, progress, writer));
+    }
+    #[cfg(feature = "http")]
+    if is_url(path) {
+        if mode == Mode::Strip {
+            return Err(std::io::Error::other("Web pages cannot be stripped"));
+        }
+        return mode.run(reader, &title(path), fetch_article(path)?.as_bytes(), writer);
+    }
+    let input = open(path, progress)?;
+    if is_pdf(path) && mode == Mode::Strip {
+        return Err(std::io::Error::other("PDF documents cannot be stripped"));
+    }
+    #[cfg(feature = "pdf")]
+    if is_pdf(path) {
+        return Ok(bio_read::pdf::bio_read_pdf(reader, input, writer)?);
+    }
+    mode.run(reader, &title(path), input, writer)
+}
+
+/// The title of the standalone document of the file at `path`, which is its name.
+fn title(path: &Path) -> String {
+    path.file_name().unwrap_or(path.as_os_str()).to_string_lossy().into_owned()
+}
+
+/// Write a standalone document in the format `to` titled `title` to `writer`, with the output of `body` in it. RTF documents are untitled, and any other format makes a web page.
+fn write_document<W: Write>(to: OutputFormat, title: &str, writer: &mut W, body: impl FnOnce(&mut W) -> Result<()>) -> Result<()> {
+    if to == OutputFormat::Rtf {
+        Rtf.write_start(writer)?;
+        body(writer)?;
+        return Rtf.write_end(writer);
+    }
+    let page = HtmlPage::new(title);
+    page.write_start(writer)?;
+    body(writer)?;
+    page.write_end(writer)
+}
+
+/// Bio-read the file at `path` in `mode` like [`bio_read_input`], adding statistics of its words to `stats`. The words of PDF documents are not counted.
+pub fn bio_read_input_with_stats(reader: &BioReader, mode: Mode, path: &Path, progress: Option<&Progress>, writer: &mut impl Write, stats: &mut BioReadStats) -> Result<()> {
+    #[cfg(feature = "http")]
+    if is_url(path) {
+        return mode.run_with_stats(reader, &title(path), fetch_article(path)?.as_bytes(), writer, stats);
+    }
+    if is_pdf(path) {
+        return bio_read_input(reader, mode, path, progress, writer);
+    }
+    mode.run_with_stats(reader, &title(path), open(path, progress)?, writer, stats)
+}
+
+/// Print `stats` to stderr, estimating the reading time at `speed`. The time bio-reading saves is shown if it is read at another speed.
+pub fn print_stats(stats: &BioReadStats, speed: ReadingSpeed) {
+    let estimate = stats.estimated_minutes(speed);
+    eprintln!("Words: {} in {} paragraphs", stats.words, stats.paragraphs);
+    eprintln!("Emphasized: {:.1}% of characters", stats.emphasized_ratio() * 100.0);
+    eprintln!("Reading time: {} at {} words per minute", minutes(estimate.original), speed.original);
+    if speed.bio_read != speed.original {
+        let change = if estimate.saved() >= 0.0 { "saving" } else { "taking an extra" };
+        eprintln!(
+            "Bio-read: {} at {} words per minute, {change} {}",
+            minutes(estimate.bio_read),
+            speed.bio_read,
+            minutes(estimate.saved().abs())
+        );
+    }
+    if !stats.longest_word.is_empty() {
+        eprintln!("Longest word: {} ({} characters)", stats.longest_word, stats.longest_word.chars().count());
+    }
+}
+
+/// Format a duration of `minutes` like `3 min 10 s`, rounded to the second.
+fn minutes(minutes: f64) -> String {
+    let seconds = (minutes * 60.0).round() as u64;
+    format!("{} min {} s", seconds / 60, seconds % 60)
+}
+
+/// Read the text of the file at `path`, decompressed and replacing invalid UTF-8. PDF documents have their text extracted, and URLs have their article fetched.
+pub fn read_text(path: &Path) -> Result<String> {
+    #[cfg(feature = "http")]
+    if is_url(path) {
+        return fetch_article(path);
+    }
+    let mut content = Vec::new();
+    open(path, None)?.read_to_end(&mut content)?;
+    #[cfg(feature = "pdf")]
+    if is_pdf(path) {
+        return Ok(bio_read::pdf::extract_text(&content)?);
+    }
+    Ok(String::from_utf8_lossy(&content).into_owned())
+}
+
+/// How many lines of the input `--analyze` shows bio-read with each fixation point.
+const SAMPLE_LINES: usize = 2;
+
+/// How many characters of each sample line `--analyze` shows.
+const SAMPLE_WIDTH: usize = 60;
+
+/// Write a report of how `text` of `kind` would be bio-read with `config` and each fixation point to `writer`: how much of the words is emphasized, how many words get 1 to 5 or more characters emphasized, and the first few lines bio-read. The fixation point of `config` is marked with `*`.
+pub fn analyze(config: &Config, kind: Kind, text: &str, writer: &mut impl Write) -> Result<()> {
+    let (format, language) = kind;
+    let current = config.fixation_ratio.is_none().then(|| config.fixation_point.unwrap_or(3));
+    let readers = (1..=5)
+        .map(|fixation_point| {
+            let config = Config {
+                format: Some(format),
+                language: Some(language),
+                fixation_point: Some(fixation_point),
+                fixation_ratio: None,
+                ..config.clone()
+            };
+            BioReader::from_config(&config).map(|reader| (fixation_point, reader))
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let samples: Vec<String> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .take(SAMPLE_LINES)
+        .map(|line| line.chars().take(SAMPLE_WIDTH).collect())
+        .collect();
+    writeln!(writer, "Words emphasized by 1 to 5+ characters, by fixation point:")?;
+    writeln!(writer, "{:>10}  {:>10}  {:>7}{:>7}{:>7}{:>7}{:>7}", "Fixation", "Emphasized", "1", "2", "3", "4", "5+")?;
+    for (fixation_point, reader) in &readers {
+        let stats = reader.bio_read_with_stats(text.as_bytes(), &mut std::io::sink())?;
+        let mut counts = [0_usize; 5];
+        for span in reader.bio_read_spans(text) {
+            if span.kind == SpanKind::Emphasized {
+                let characters = text[span.range].chars().count();
+                counts[characters.clamp(1, counts.len()) - 1] += 1;
+            }
+        }
+        let marker = if current == Some(*fixation_point) { "*" } else { " " };
+        write!(writer, "{marker:>9}{fixation_point}  {:>9.1}%  ", stats.emphasized_ratio() * 100.0)?;
+        for count in counts {
+            write!(writer, "{count:>7}")?;
+        }
+        writeln!(writer)?;
+    }
+    for (fixation_point, reader) in &readers {
+        writeln!(writer)?;
+        writeln!(writer, "Fixation point {fixation_point}:")?;
+        for sample in &samples {
+            writeln!(writer, "  {}", reader.bio_read_text(sample)?)?;
+        }
+    }
+    Ok(())
+}
+
+/// Run `process` on each input with its index, with `jobs` threads, reporting failures to stderr in `format`. Returns the exit code of the first failure, if any.
+pub fn process_files(inputs: &[Input], jobs: usize, format: ErrorFormat, process: impl Fn(usize, &Input) -> Result<()> + Sync) -> Option<u8> {
+    let next = AtomicUsize::new(0);
+    // No failure yet
+    let exit_code = AtomicU8::new(0);
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.min(inputs.len()) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(input) = inputs.get(i) else {
+                    break;
+                };
+                if let Err(e) = process(i, input) {
+                    let failure = Failure::from(bio_read::Error::from(e).with_path(&input.path));
+                    failure.report(format);
+                    let _ = exit_code.compare_exchange(0, failure.exit_code(), Ordering::Relaxed, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+    Some(exit_code.into_inner()).filter(|&code| code != 0)
+}
+
+/// Bio-read the file at `input` (or strip it, depending on `mode`), writing to the file at `output` and creating its parent directories as needed, and reporting what is read to `progress`. The output is compressed if its extension is that of a compression format.
+pub fn bio_read_file(reader: &BioReader, mode: Mode, input: &Path, output: &Path, progress: Option<&Progress>) -> Result<()> {
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut writer = BufWriter::new(Compressed::new(File::create(output)?, output)?);
+    bio_read_input(reader, mode, input, progress, &mut writer)?;
+    writer.into_inner().map_err(std::io::IntoInnerError::into_error)?.finish()
+}
+
+/// Bio-read the EPUB at `input`, writing to the EPUB at `output`.
+#[cfg(feature = "epub")]
+pub fn bio_read_epub(reader: &BioReader, input: &Path, output: &Path) -> Result<()> {
+    if output.canonicalize().is_ok_and(|output| input.canonicalize().is_ok_and(|input| input == output)) {
+        return Err(std::io::Error::other("output would overwrite the input"));
+    }
+    let input = File::open(input)?;
+    let output = BufWriter::new(File::create(output)?);
+    Ok(bio_read::epub::bio_read_epub(reader, input, output)?)
+}
+
+/// Bio-read the file at `path` in place (or strip it, depending on `mode`), by writing to a temporary file next to it and renaming it over the original, and reporting what is read to `progress`. The original is copied to `path` with `backup` appended first, if given.
+pub fn bio_read_in_place(reader: &BioReader, mode: Mode, path: &Path, backup: Option<&str>, progress: Option<&Progress>) -> Result<()> {
+    let Some(name) = path.file_name() else {
+        return Err(std::io::Error::other("not a file"));
+    };
+    if is_pdf(path) {
+        return Err(std::io::Error::other("PDF documents cannot be edited in place"));
+    }
+    let mut temp_name = OsString::from(".");
+    temp_name.push(name);
+    temp_name.push(format!(".{}.tmp", std::process::id()));
+    let temp = path.with_file_name(temp_name);
+    let result = write_temp(reader, mode, path, &temp, progress).and_then(|()| {
+        if let Some(backup) = backup {
+            let mut backup_name = name.to_os_string();
+            backup_name.push(backup);
+            std::fs::copy(path, path.with_file_name(backup_name))?;
+        }
+        std::fs::rename(&temp, path)
+    });
+    if result.is_err() {
+        // Best effort, since the original error is more useful
+        let _ = std::fs::remove_file(&temp);
+    }
+    result
+}
+
+/// Bio-read the file at `path` (or strip it, depending on `mode`) into a new file at `temp`, with the same permissions, and compressed like `path` is named.
+fn write_temp(reader: &BioReader, mode: Mode, path: &Path, temp: &Path, progress: Option<&Progress>) -> Result<()> {
+    let input = open(path, progress)?;
+    let permissions = input.get_ref().inner.file().metadata()?.permissions();
+    let output = File::create_new(temp)?;
+    let mut writer = BufWriter::new(Compressed::new(output, path)?);
+    mode.run(reader, &title(path), input, &mut writer)?;
+    let mut output = writer.into_inner().map_err(std::io::IntoInnerError::into_error)?;
+    output.finish()?;
+    output.get_ref().set_permissions(permissions)?;
+    output.get_ref().sync_all()
+}
+
+/// Inputs larger than this many bytes in total are paged automatically when writing to a terminal, as they likely don't fit on a screen.
+const PAGE_THRESHOLD: u64 = 16 * 1024;
+
+/// Whether `inputs` are large enough to be paged automatically.
+pub fn is_large(inputs: &[Input]) -> bool {
+    total_size(inputs) > PAGE_THRESHOLD
+}
+
+/// The total size of `inputs` in bytes, skipping those that cannot be read.
+pub fn total_size(inputs: &[Input]) -> u64 {
+    inputs.iter().filter_map(|input| std::fs::metadata(&input.path).ok()).map(|metadata| metadata.len()).sum()
+}
+
+/// A pager that output is piped into, like `less`.
+pub struct Pager {
+    /// The pager process.
+    child: Child,
+}
+
+impl Pager {
+    /// Spawn the pager given by `$PAGER`, or `less` if unset, passing escape codes through to the terminal. Returns `None` if `$PAGER` is empty or `cat`, since there's nothing to pipe into then.
+    pub fn spawn() -> Option<Result<Self>> {
+        let pager = std::env::var("PAGER").unwrap_or_else(|_| String::from("less"));
+        let mut words = pager.split_whitespace();
+        let program = words.next().filter(|program| *program != "cat")?;
+        let mut command = Command::new(program);
+        command.args(words).stdin(Stdio::piped());
+        if std::env::var_os("LESS").is_none() {
+            // Quit if the output fits on one screen, show colors, and leave the output on the screen after quitting
+            command.env("LESS", "FRX");
+        }
+        Some(command.spawn().map(|child| Self { child }))
+    }
+    /// Take the input of the pager, which is closed when dropped.
+    pub fn take_stdin(&mut self) -> ChildStdin {
+        self.child.stdin.take().expect("Pager input should be piped and taken once")
+    }
+    /// Wait for the pager to exit, after its input has been closed.
+    pub fn wait(mut self) -> Result<()> {
+        self.child.wait()?;
+        Ok(())
+    }
+}
+
+/// How often the progress bar is redrawn.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The width of the progress bar, in characters.
+const PROGRESS_WIDTH: usize = 30;
+
+/// A progress bar on stderr, showing how much of the input files has been read, how fast, and how long it will take to read the rest.
+pub struct Progress {
+    /// The state shared with the thread drawing the bar.
+    state: Arc<ProgressState>,
+    /// The thread drawing the bar.
+    drawer: JoinHandle<()>,
+}
+
+/// The state of a [`Progress`].
+struct ProgressState {
+    /// How many bytes have been read.
+    done: AtomicU64,
+    /// How many bytes there are to read.
+    total: u64,
+    /// When reading started.
+    start: Instant,
+    /// Whether reading has finished.
+    finished: AtomicBool,
+}
+
+impl Progress {
+    /// Start drawing the progress of reading `total` bytes.
+    pub fn start(total: u64) -> Self {
+        let state = Arc::new(ProgressState {
+            done: AtomicU64::new(0),
+            total,
+            start: Instant::now(),
+            finished: AtomicBool::new(false),
+        });
+        let drawer = {
+            let state = Arc::clone(&state);
+            std::thread::spawn(move || {
+                while !state.finished.load(Ordering::Relaxed) {
+                    state.draw();
+                    std::thread::park_timeout(PROGRESS_INTERVAL);
+                }
+            })
+        };
+        Self { state, drawer }
+    }
+    /// Record that `amount` more bytes have been read.
+    fn advance(&self, amount: usize) {
+        self.state.done.fetch_add(amount as u64, Ordering::Relaxed);
+    }
+    /// Stop drawing, leaving the final state of the bar on its own line.
+    pub fn finish(self) {
+        self.state.finished.store(true, Ordering::Relaxed);
+        self.drawer.thread().unpark();
+        // Drawing never panics, and there's nothing to do if it did
+        let _ = self.drawer.join();
+        self.state.draw();
+        eprintln!();
+    }
+}
+
+impl ProgressState {
+    /// Draw the bar over the current line of stderr, like `[#####-----]  50% 1.0 MiB / 2.0 MiB, 4.0 MiB/s, 0s left`.
+    fn draw(&self) {
+        let done = self.done.load(Ordering::Relaxed).min(self.total);
+        let fraction = if self.total == 0 { 1.0 } else { done as f64 / self.total as f64 };
+        let filled = ((fraction * PROGRESS_WIDTH as f64) as usize).min(PROGRESS_WIDTH);
+        let elapsed = self.start.elapsed().as_secs_f64();
+        let rate = if elapsed > 0.0 { done as f64 / elapsed } else { 0.0 };
+        let left = if rate > 0.0 {
+            format!("{:.0}s", (self.total - done) as f64 / rate)
+        } else {
+            String::from("?")
+        };
+        // Clear the rest of the line, in case the last bar was longer
+        eprint!(
+            "\r[{}{}] {:>3.0}% {} / {}, {}/s, {left} left\x1b[K",
+            "#".repeat(filled),
+            "-".repeat(PROGRESS_WIDTH - filled),
+            fraction * 100.0,
+            format_size(done),
+            format_size(self.total),
+            format_size(rate as u64),
+        );
+    }
+}
+
+/// Format `bytes` with a binary unit, like `1.5 MiB`.
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next in &UNITS[1..] {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = next;
+    }
+    format!("{size:.1} {unit}")
+}
+
+/// A file being read, reporting how much has been read to a [`Progress`], if any.
+pub struct Counted<'a, R> {
+    /// The file.
+    inner: R,
+    /// Where to report to.
+    progress: Option<&'a Progress>,
+}
+
+impl<R: Read> Read for Counted<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        if let Some(progress) = self.progress {
+            progress.advance(n);
+        }
+        Ok(n)
+    }
+}
+
+/// An opened input file.
+pub enum Source {
+    /// A file read with a system call per chunk.
+    File(File),
+    /// A large regular file mapped into memory, read without system calls, along with the file itself.
+    #[cfg(feature = "mmap")]
+    Mapped(File, std::io::Cursor<memmap2::Mmap>),
+}
+
+impl Source {
+    /// Get the underlying file.
+    fn file(&self) -> &File {
+        match self {
+            Self::File(file) => file,
+            #[cfg(feature = "mmap")]
+            Self::Mapped(file, _) => file,
+        }
+    }
+}
+
+impl Read for Source {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        match self {
+            Self::File(file) => file.read(buf),
+            #[cfg(feature = "mmap")]
+            Self::Mapped(_, mapped) => mapped.read(buf),
+        }
+    }
+}
+
+/// Regular files at least this many bytes large are memory-mapped, while smaller ones are cheaper to read.
+#[cfg(feature = "mmap")]
+const MMAP_THRESHOLD: u64 = 1024 * 1024;
+
+/// Open the file at `path` for reading, reporting how much has been read to `progress`. With the `mmap` feature, large regular files are memory-mapped, falling back to reading them if that fails. With the `compression` feature, regular files compressed with gzip or Zstandard are decompressed, while progress is reported in compressed bytes.
+pub fn open<'a>(path: &Path, progress: Option<&'a Progress>) -> Result<Decompressed<Counted<'a, Source>>> {
+    let file = File::open(path)?;
+    #[cfg(feature = "compression")]
+    let compression = sniff_compression(&file)?;
+    #[cfg(feature = "mmap")]
+    let file = match file.metadata() {
+        Ok(metadata) if metadata.is_file() && metadata.len() >= MMAP_THRESHOLD => {
+            // SAFETY: The file may be truncated or modified by another process while mapped, which is undefined behavior. Like other tools mapping their input, accept that risk for the speed, as input files are not expected to change while being read.
+            match unsafe { memmap2::Mmap::map(&file) } {
+                Ok(mapped) => {
+                    #[cfg(unix)]
+                    let _ = mapped.advise(memmap2::Advice::Sequential);
+                    Source::Mapped(file, std::io::Cursor::new(mapped))
+                }
+                Err(_) => Source::File(file),
+            }
+        }
+        _ => Source::File(file),
+    };
+    #[cfg(not(feature = "mmap"))]
+    let file = Source::File(file);
+    let input = Counted { inner: file, progress };
+    #[cfg(feature = "compression")]
+    let input = match compression {
+        Some(Compression::Gzip) => Decompressed::Gzip(Box::new(flate2::read::MultiGzDecoder::new(input))),
+        Some(Compression::Zstd) => Decompressed::Zstd(zstd::Decoder::new(input)?),
+        None => Decompressed::Plain(input),
+    };
+    #[cfg(not(feature = "compression"))]
+    let input = Decompressed::Plain(input);
+    Ok(input)
+}
+
+/// A compression format of files.
+#[cfg(feature = "compression")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Compression {
+    /// gzip, with the extension `.gz`.
+    Gzip,
+    /// Zstandard, with the extension `.zst`.
+    Zstd,
+}
+
+#[cfg(feature = "compression")]
+impl Compression {
+    /// Detect the compression format of content starting with `head` from its magic bytes.
+    fn sniff(head: &[u8]) -> Option<Self> {
+        if head.starts_with(&[0x1f, 0x8b]) {
+            Some(Self::Gzip)
+        } else if head.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Some(Self::Zstd)
+        } else {
+            None
+        }
+    }
+
+    /// Detect the compression format of the file at `path` from its extension.
+    fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?;
+        if extension.eq_ignore_ascii_case("gz") {
+            Some(Self::Gzip)
+        } else if extension.eq_ignore_ascii_case("zst") {
+            Some(Self::Zstd)
+        } else {
+            None
+        }
+    }
+}
+
+/// Detect the compression format of `file` from its magic bytes, leaving it at its start. Only regular files are sniffed, since others like pipes cannot be read again.
+#[cfg(feature = "compression")]
+fn sniff_compression(mut file: &File) -> Result<Option<Compression>> {
+    use std::io::Seek;
+    if !file.metadata()?.is_file() {
+        return Ok(None);
+    }
+    let mut magic = Vec::with_capacity(4);
+    file.take(4).read_to_end(&mut magic)?;
+    file.rewind()?;
+    Ok(Compression::sniff(&magic))
+}
+
+/// The path of the file at `path` once decompressed, without the extension of its compression format, like `notes.md` for `notes.md.gz`. Always `path` without the `compression` feature.
+fn decompressed_path(path: &Path) -> Cow<'_, Path> {
+    #[cfg(feature = "compression")]
+    if Compression::from_path(path).is_some() {
+        return Cow::Owned(path.with_extension(""));
+    }
+    Cow::Borrowed(path)
+}
+
+/// An input, decompressed if it is compressed.
+pub enum Decompressed<R> {
+    /// An input that isn't compressed.
+    Plain(R),
+    /// A gzip input, possibly made up of several members.
+    #[cfg(feature = "compression")]
+    Gzip(Box<flate2::read::MultiGzDecoder<R>>),
+    /// A Zstandard input.
+    #[cfg(feature = "compression")]
+    Zstd(zstd::Decoder<'static, std::io::BufReader<R>>),
+}
+
+impl<R: Read> Decompressed<R> {
+    /// Get the underlying input.
+    fn get_ref(&self) -> &R {
+        match self {
+            Self::Plain(input) => input,
+            #[cfg(feature = "compression")]
+            Self::Gzip(decoder) => decoder.get_ref(),
+            #[cfg(feature = "compression")]
+            Self::Zstd(decoder) => decoder.get_ref().get_ref(),
+        }
+    }
+}
+
+impl<R: Read> Read for Decompressed<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        match self {
+            Self::Plain(input) => input.read(buf),
+            #[cfg(feature = "compression")]
+            Self::Gzip(decoder) => decoder.read(buf),
+            #[cfg(feature = "compression")]
+            Self::Zstd(decoder) => decoder.read(buf),
+        }
+    }
+}
+
+/// An output, compressed if it is written to a file with the extension of a compression format. Compression is finished when dropped, ignoring errors, so [`Compressed::finish`] should be called to handle them.
+pub enum Compressed<W: Write> {
+    /// An output that isn't compressed.
+    Plain(W),
+    /// A gzip output.
+    #[cfg(feature = "compression")]
+    Gzip(flate2::write::GzEncoder<W>),
+    /// A Zstandard output.
+    #[cfg(feature = "compression")]
+    Zstd(zstd::Encoder<'static, W>),
+}
+
+impl<W: Write> Compressed<W> {
+    /// Write to `writer` of the file at `path`, compressing the output if `path` has the extension of a compression format. Never compresses without the `compression` feature.
+    #[cfg_attr(not(feature = "compression"), allow(unused_variables))]
+    pub fn new(writer: W, path: &Path) -> Result<Self> {
+        #[cfg(feature = "compression")]
+        match Compression::from_path(path) {
+            Some(Compression::Gzip) => return Ok(Self::Gzip(flate2::write::GzEncoder::new(writer, flate2::Compression::default()))),
+            Some(Compression::Zstd) => return Ok(Self::Zstd(zstd::Encoder::new(writer, zstd::DEFAULT_COMPRESSION_LEVEL)?)),
+            None => {}
+        }
+        Ok(Self::Plain(writer))
+    }
+
+    /// Get the underlying output.
+    fn get_ref(&self) -> &W {
+        match self {
+            Self::Plain(writer) => writer,
+            #[cfg(feature = "compression")]
+            Self::Gzip(encoder) => encoder.get_ref(),
+            #[cfg(feature = "compression")]
+            Self::Zstd(encoder) => encoder.get_ref(),
+        }
+    }
+
+    /// Finish the compressed output and flush it. Nothing should be written afterwards.
+    pub fn finish(&mut self) -> Result<()> {
+        match self {
+            Self::Plain(writer) => writer.flush(),
+            #[cfg(feature = "compression")]
+            Self::Gzip(encoder) => encoder.try_finish(),
+            #[cfg(feature = "compression")]
+            Self::Zstd(encoder) => encoder.do_finish(),
+        }
+    }
+}
+
+impl<W: Write> Write for Compressed<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        match self {
+            Self::Plain(writer) => writer.write(buf),
+            #[cfg(feature = "compression")]
+            Self::Gzip(encoder) => encoder.write(buf),
+            #[cfg(feature = "compression")]
+            Self::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        match self {
+            Self::Plain(writer) => writer.flush(),
+            #[cfg(feature = "compression")]
+            Self::Gzip(encoder) => encoder.flush(),
+            #[cfg(feature = "compression")]
+            Self::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
+
+impl<W: Write> Drop for Compressed<W> {
+    fn drop(&mut self) {
+        // Best effort, since errors cannot be returned from here
+        let _ = self.finish();
+    }
+}
+
+/// How often `--follow` checks whether more has been appended to the file.
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How many lines at the end of the file `--follow` shows before following it, like `tail -f`.
+const FOLLOW_LINES: usize = 10;
+
+/// How many bytes at the end of the file `--follow` looks for its last lines in, so that a file with long lines doesn't have to be read as a whole.
+const FOLLOW_TAIL_BYTES: u64 = 64 * 1024;
+
+/// A file being followed, which waits for more to be appended when reaching its end instead of ending, like `tail -f`. Reading starts over if the file is truncated, like a log rotated in place.
+pub struct Follow {
+    /// The file.
+    file: File,
+    /// How far the file has been read.
+    position: u64,
+}
+
+impl Follow {
+    /// Follow the file at `path`, starting from its last [`FOLLOW_LINES`] lines.
+    pub fn open(path: &Path) -> Result<Self> {
+        use std::io::{Seek, SeekFrom};
+        let mut file = File::open(path)?;
+        let start = file.metadata()?.len().saturating_sub(FOLLOW_TAIL_BYTES);
+        file.seek(SeekFrom::Start(start))?;
+        let mut tail = Vec::new();
+        (&mut file).take(FOLLOW_TAIL_BYTES).read_to_end(&mut tail)?;
+        // The last line may not be finished yet
+        let lines = tail.strip_suffix(b"\n").unwrap_or(&tail);
+        let offset = match lines.iter().enumerate().rev().filter(|(_, &b)| b == b'\n').nth(FOLLOW_LINES - 1) {
+            Some((i, _)) => i + 1,
+            None if start == 0 => 0,
+            // Skip the first line of the tail, which is likely cut
+            None => tail.iter().position(|&b| b == b'\n').map_or(tail.len(), |i| i + 1),
+        };
+        let position = file.seek(SeekFrom::Start(start + offset as u64))?;
+        Ok(Self { file, position })
+    }
+}
+
+impl Read for Follow {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        use std::io::Seek;
+        loop {
+            let n = self.file.read(buf)?;
+            if n > 0 || buf.is_empty() {
+                self.position += n as u64;
+                return Ok(n);
+            }
+            if self.file.metadata()?.len() < self.position {
+                self.file.rewind()?;
+                self.position = 0;
+                continue;
+            }
+            std::thread::sleep(FOLLOW_INTERVAL);
+        }
+    }
+}
+
+/// How long to wait for more changes after one is seen, so that a burst of changes, like an editor saving a file, is handled once.
+#[cfg(feature = "watch")]
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Run `render` once, then again whenever one of the files at `paths` changes, until interrupted. Errors of `render` are reported to stderr, as the file may be fixed by the next change.
+#[cfg(feature = "watch")]
+pub fn watch(paths: &[PathBuf], mut render: impl FnMut() -> Result<()>) -> Result<()> {
+    use notify::{RecursiveMode, Watcher};
+    // Editors often save by replacing the file, so watch the directories instead, which outlive it
+    let files = paths.iter().map(std::path::absolute).collect::<Result<HashSet<_>>>()?;
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).map_err(std::io::Error::other)?;
+    let dirs: HashSet<_> = files.iter().filter_map(|file| file.parent()).collect();
+    for dir in dirs {
+        watcher.watch(dir, RecursiveMode::NonRecursive).map_err(std::io::Error::other)?;
+    }
+    let is_relevant = |event: notify::Result<notify::Event>| match event {
+        Ok(event) => !event.kind.is_access() && event.paths.iter().any(|path| files.contains(path)),
+        Err(_) => false,
+    };
+    loop {
+        if let Err(e) = render() {
+            eprintln!("{e}");
+        }
+        loop {
+            let event = receiver.recv().map_err(std::io::Error::other)?;
+            if is_relevant(event) {
+                break;
+            }
+        }
+        while receiver.recv_timeout(WATCH_DEBOUNCE).is_ok() {}
+    }
+}
@@ -0,0 +1,389 @@
+//! The shell completion scripts of `br completions`, generated from the definitions of the arguments so that they never drift from them.
+
+use argh::{CommandInfoWithArgs, FlagInfoKind, Optionality};
+use std::{
+    io::{Result, Write},
+    str::FromStr,
+};
+
+/// The name of the binary that the scripts complete.
+const BINARY: &str = "br";
+/// A value that no option with a fixed set of values accepts, for telling them apart from options taking any value.
+const BOGUS: &str = "\u{0}";
+
+/// A shell to generate a completion script for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    /// Bash, completing with `complete -F`.
+    Bash,
+    /// Zsh, completing with `_arguments`.
+    Zsh,
+    /// fish, completing with `complete -c`.
+    Fish,
+    /// PowerShell, completing with `Register-ArgumentCompleter`.
+    Powershell,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "bash" => Ok(Self::Bash),
+            "zsh" => Ok(Self::Zsh),
+            "fish" => Ok(Self::Fish),
+            "powershell" | "pwsh" => Ok(Self::Powershell),
+            _ => Err(format!("Unknown shell: {s}")),
+        }
+    }
+}
+
+/// An option or switch, as completed.
+struct Flag {
+    /// The long name, like `--format`.
+    long: String,
+    /// The short name, like `-f`, if any.
+    short: Option<String>,
+    /// The start of the description, up to the end of its first sentence.
+    summary: String,
+    /// Whether it takes a value.
+    takes_value: bool,
+    /// Whether it can be given multiple times.
+    repeating: bool,
+    /// The values it accepts, or empty if it accepts any, like file names.
+    values: Vec<String>,
+}
+
+/// A command or subcommand, as completed.
+struct Command {
+    /// The name of the subcommand, or empty for the top-level command.
+    name: String,
+    /// The start of the description, up to the end of its first sentence.
+    summary: String,
+    flags: Vec<Flag>,
+    /// The values that its first positional argument accepts, or empty if it accepts any, like file names.
+    positional_values: Vec<String>,
+    /// Whether it takes positional arguments.
+    has_positionals: bool,
+}
+
+/// Write the completion script for `shell` of the command described by `info`. `accepts` tells whether the command line given to it, without the binary, is valid, which is used to find the values of options among those quoted in their descriptions.
+pub fn write_completions(shell: Shell, info: &CommandInfoWithArgs, accepts: impl Fn(&[&str]) -> bool, output: &mut impl Write) -> Result<()> {
+    let mut commands = vec![command("", info, &accepts)];
+    commands.extend(info.commands.iter().map(|sub| command(sub.name, &sub.command, &accepts)));
+    match shell {
+        Shell::Bash => write_bash(&commands, output),
+        Shell::Zsh => write_zsh(&commands, output),
+        Shell::Fish => write_fish(&commands, output),
+        Shell::Powershell => write_powershell(&commands, output),
+    }
+}
+
+/// Collect the flags and values of the command `name` described by `info`.
+fn command(name: &str, info: &CommandInfoWithArgs, accepts: &impl Fn(&[&str]) -> bool) -> Command {
+    let prefix: Vec<&str> = Some(name).filter(|name| !name.is_empty()).into_iter().collect();
+    let flags = info
+        .flags
+        .iter()
+        .filter(|flag| !flag.hidden)
+        .map(|flag| {
+            let takes_value = matches!(flag.kind, FlagInfoKind::Option { .. });
+            Flag {
+                long: flag.long.to_string(),
+                short: flag.short.map(|short| format!("-{short}")),
+                summary: summary(flag.description),
+                takes_value,
+                repeating: matches!(flag.optionality, Optionality::Repeating),
+                values: if takes_value { values(&[prefix.as_slice(), &[flag.long]].concat(), flag.description, accepts) } else { Vec::new() },
+            }
+        })
+        .collect();
+    let positional_values = match info.positionals.first() {
+        Some(positional) if !prefix.is_empty() => values(&prefix, positional.description, accepts),
+        _ => Vec::new(),
+    };
+    Command {
+        name: name.to_string(),
+        summary: summary(info.description),
+        flags,
+        positional_values,
+        has_positionals: !info.positionals.is_empty(),
+    }
+}
+
+/// Get the values quoted in `description` that are accepted after `prefix`, unless any value is.
+fn values(prefix: &[&str], description: &str, accepts: &impl Fn(&[&str]) -> bool) -> Vec<String> {
+    let accepted = |value: &str| accepts(&[prefix, &[value]].concat());
+    if accepted(BOGUS) {
+        return Vec::new();
+    }
+    let mut values: Vec<String> = Vec::new();
+    for value in description.split('"').skip(1).step_by(2) {
+        if accepted(value) && !values.iter().any(|known| known == value) {
+            values.push(value.to_string());
+        }
+    }
+    values
+}
+
+/// Get the start of `description`, up to the end of its first sentence.
+fn summary(description: &str) -> String {
+    let first = description.split(". ").next().unwrap_or_default();
+    first.trim_end_matches('.').to_string()
+}
+
+impl Command {
+    /// Get the names of the flags, long and short.
+    fn names(&self) -> impl Iterator<Item = &str> {
+        self.flags.iter().flat_map(|flag| flag.short.as_deref().into_iter().chain([flag.long.as_str()]))
+    }
+}
+
+/// Write the completion script for Bash.
+fn write_bash(commands: &[Command], output: &mut impl Write) -> Result<()> {
+    let (top, subcommands) = commands.split_first().expect("There should be a top-level command");
+    let names: Vec<&str> = subcommands.iter().map(|sub| sub.name.as_str()).collect();
+    writeln!(output, "# Bash completion for {BINARY}, generated by `{BINARY} completions bash`.")?;
+    writeln!(output, "_{BINARY}() {{")?;
+    writeln!(output, "    local cur=\"${{COMP_WORDS[COMP_CWORD]}}\" prev=\"${{COMP_WORDS[COMP_CWORD-1]}}\" command=\"\" i")?;
+    if !names.is_empty() {
+        writeln!(output, "    for ((i = 1; i < COMP_CWORD; i++)); do")?;
+        writeln!(output, "        case \"${{COMP_WORDS[i]}}\" in")?;
+        writeln!(output, "            {}) command=\"${{COMP_WORDS[i]}}\"; break ;;", names.join("|"))?;
+        writeln!(output, "        esac")?;
+        writeln!(output, "    done")?;
+    }
+    writeln!(output, "    case \"$command\" in")?;
+    for command in commands {
+        writeln!(output, "        \"{}\")", command.name)?;
+        let with_value: Vec<&Flag> = command.flags.iter().filter(|flag| flag.takes_value).collect();
+        if !with_value.is_empty() {
+            writeln!(output, "            case \"$prev\" in")?;
+            let mut any_value = Vec::new();
+            for flag in with_value {
+                let names = flag.short.as_deref().into_iter().chain([flag.long.as_str()]);
+                if flag.values.is_empty() {
+                    any_value.extend(names);
+                } else {
+                    writeln!(output, "                {}) COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")); return ;;", names.collect::<Vec<_>>().join("|"), flag.values.join(" "))?;
+                }
+            }
+            if !any_value.is_empty() {
+                // Complete file names by default
+                writeln!(output, "                {}) return ;;", any_value.join("|"))?;
+            }
+            writeln!(output, "            esac")?;
+        }
+        writeln!(output, "            if [[ $cur == -* ]]; then")?;
+        writeln!(output, "                COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))", command.names().collect::<Vec<_>>().join(" "))?;
+        if !command.positional_values.is_empty() {
+            writeln!(output, "            else")?;
+            writeln!(output, "                COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))", command.positional_values.join(" "))?;
+        } else if command.name == top.name && !names.is_empty() {
+            // Subcommands and input files alike
+            writeln!(output, "            else")?;
+            writeln!(output, "                COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))", names.join(" "))?;
+            writeln!(output, "                mapfile -t -O \"${{#COMPREPLY[@]}}\" COMPREPLY < <(compgen -f -- \"$cur\")")?;
+            writeln!(output, "                compopt -o filenames 2>/dev/null")?;
+        }
+        writeln!(output, "            fi")?;
+        writeln!(output, "            ;;")?;
+    }
+    writeln!(output, "    esac")?;
+    writeln!(output, "}}")?;
+    writeln!(output, "complete -o default -F _{BINARY} {BINARY}")
+}
+
+/// Write the completion script for Zsh.
+fn write_zsh(commands: &[Command], output: &mut impl Write) -> Result<()> {
+    let (top, subcommands) = commands.split_first().expect("There should be a top-level command");
+    writeln!(output, "#compdef {BINARY}")?;
+    writeln!(output, "# Zsh completion for {BINARY}, generated by `{BINARY} completions zsh`.")?;
+    for command in commands {
+        let function = zsh_function(&command.name);
+        writeln!(output)?;
+        writeln!(output, "{function}() {{")?;
+        if command.name == top.name && !subcommands.is_empty() {
+            let names: Vec<&str> = subcommands.iter().map(|sub| sub.name.as_str()).collect();
+            writeln!(output, "    local i")?;
+            writeln!(output, "    for ((i = 2; i < CURRENT; i++)); do")?;
+            writeln!(output, "        case $words[i] in")?;
+            writeln!(output, "            ({})", names.join("|"))?;
+            writeln!(output, "                words=(\"${{(@)words[i,-1]}}\")")?;
+            writeln!(output, "                (( CURRENT -= i - 1 ))")?;
+            writeln!(output, "                {}_$words[1]", zsh_function(""))?;
+            writeln!(output, "                return")?;
+            writeln!(output, "                ;;")?;
+            writeln!(output, "        esac")?;
+            writeln!(output, "    done")?;
+        }
+        write!(output, "    _arguments -S")?;
+        for flag in &command.flags {
+            let action = match (flag.takes_value, flag.values.is_empty()) {
+                (false, _) => String::new(),
+                (true, true) => String::from(": :_files"),
+                (true, false) => format!(": :({})", flag.values.iter().map(|value| zsh_escape(value)).collect::<Vec<_>>().join(" ")),
+            };
+            let repeat = if flag.repeating { "*" } else { "" };
+            for name in flag.short.iter().chain([&flag.long]) {
+                write!(output, " \\\n        {}", sh_quote(&format!("{repeat}{name}[{}]{action}", zsh_escape(&flag.summary))))?;
+            }
+        }
+        if !command.positional_values.is_empty() {
+            let values: Vec<String> = command.positional_values.iter().map(|value| zsh_escape(value)).collect();
+            write!(output, " \\\n        {}", sh_quote(&format!("1: :({})", values.join(" "))))?;
+        } else if command.name == top.name && !subcommands.is_empty() {
+            write!(output, " \\\n        '*: :{function}_commands'")?;
+        } else if command.has_positionals {
+            write!(output, " \\\n        '*: :_files'")?;
+        }
+        writeln!(output)?;
+        writeln!(output, "}}")?;
+    }
+    if !subcommands.is_empty() {
+        // Subcommands and input files alike
+        let descriptions: Vec<String> = subcommands.iter().map(|sub| format!("{}:{}", sub.name.replace(':', r"\:"), sub.summary)).collect();
+        writeln!(output)?;
+        writeln!(output, "{}_commands() {{", zsh_function(""))?;
+        writeln!(output, "    local -a commands=({})", descriptions.iter().map(|description| sh_quote(description)).collect::<Vec<_>>().join(" "))?;
+        writeln!(output, "    _describe -t commands command commands")?;
+        writeln!(output, "    _files")?;
+        writeln!(output, "}}")?;
+    }
+    // Complete when autoloaded from the function path, or register the completion when sourced
+    let function = zsh_function("");
+    writeln!(output)?;
+    writeln!(output, "if [ \"$funcstack[1]\" = \"{function}\" ]; then")?;
+    writeln!(output, "    {function} \"$@\"")?;
+    writeln!(output, "else")?;
+    writeln!(output, "    compdef {function} {BINARY}")?;
+    writeln!(output, "fi")
+}
+
+/// Get the name of the Zsh function completing the subcommand `name`, or the top-level command if empty.
+fn zsh_function(name: &str) -> String {
+    if name.is_empty() {
+        format!("_{BINARY}")
+    } else {
+        format!("_{BINARY}_{}", name.replace('-', "_"))
+    }
+}
+
+/// Escape the characters of `text` that are special in the specs of Zsh's `_arguments`.
+fn zsh_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '[' | ']' | ':') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Quote `text` as a single word for POSIX shells.
+fn sh_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// Write the completion script for fish.
+fn write_fish(commands: &[Command], output: &mut impl Write) -> Result<()> {
+    let (top, subcommands) = commands.split_first().expect("There should be a top-level command");
+    let names: Vec<&str> = subcommands.iter().map(|sub| sub.name.as_str()).collect();
+    writeln!(output, "# fish completion for {BINARY}, generated by `{BINARY} completions fish`.")?;
+    for command in commands {
+        let condition = if command.name == top.name {
+            if names.is_empty() {
+                String::new()
+            } else {
+                format!(" -n {}", fish_quote(&format!("not __fish_seen_subcommand_from {}", names.join(" "))))
+            }
+        } else {
+            format!(" -n {}", fish_quote(&format!("__fish_seen_subcommand_from {}", command.name)))
+        };
+        if command.name == top.name {
+            for sub in subcommands {
+                writeln!(output, "complete -c {BINARY}{condition} -a {} -d {}", fish_quote(&sub.name), fish_quote(&sub.summary))?;
+            }
+        }
+        if !command.positional_values.is_empty() {
+            writeln!(output, "complete -c {BINARY}{condition} -f -a {}", fish_quote(&command.positional_values.join(" ")))?;
+        }
+        for flag in &command.flags {
+            let mut line = format!("complete -c {BINARY}{condition} -l {}", flag.long.trim_start_matches('-'));
+            if let Some(short) = &flag.short {
+                line.push_str(&format!(" -s {}", short.trim_start_matches('-')));
+            }
+            if flag.takes_value && flag.values.is_empty() {
+                line.push_str(" -r");
+            } else if flag.takes_value {
+                line.push_str(&format!(" -x -a {}", fish_quote(&flag.values.join(" "))));
+            }
+            writeln!(output, "{line} -d {}", fish_quote(&flag.summary))?;
+        }
+    }
+    Ok(())
+}
+
+/// Quote `text` as a single word for fish.
+fn fish_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\\', r"\\").replace('\'', r"\'"))
+}
+
+/// Write the completion script for PowerShell.
+fn write_powershell(commands: &[Command], output: &mut impl Write) -> Result<()> {
+    let (top, subcommands) = commands.split_first().expect("There should be a top-level command");
+    let names: Vec<String> = subcommands.iter().map(|sub| ps_quote(&sub.name)).collect();
+    writeln!(output, "# PowerShell completion for {BINARY}, generated by `{BINARY} completions powershell`.")?;
+    writeln!(output, "Register-ArgumentCompleter -Native -CommandName {} -ScriptBlock {{", ps_quote(BINARY))?;
+    writeln!(output, "    param($wordToComplete, $commandAst, $cursorPosition)")?;
+    writeln!(output, "    $subcommands = @({})", names.join(", "))?;
+    writeln!(output, "    $flags = @{{")?;
+    for command in commands {
+        let flags: Vec<String> = command.names().map(ps_quote).collect();
+        writeln!(output, "        {} = @({})", ps_quote(&command.name), flags.join(", "))?;
+    }
+    writeln!(output, "    }}")?;
+    // Keyed by the subcommand and the option before the word, or the subcommand and a space for its first positional argument
+    writeln!(output, "    $values = @{{")?;
+    for command in commands {
+        if !command.positional_values.is_empty() {
+            let values: Vec<String> = command.positional_values.iter().map(|value| ps_quote(value)).collect();
+            writeln!(output, "        {} = @({})", ps_quote(&format!("{} ", command.name)), values.join(", "))?;
+        }
+        for flag in &command.flags {
+            if flag.takes_value {
+                let values: Vec<String> = flag.values.iter().map(|value| ps_quote(value)).collect();
+                for name in flag.short.iter().chain([&flag.long]) {
+                    writeln!(output, "        {} = @({})", ps_quote(&format!("{} {name}", command.name)), values.join(", "))?;
+                }
+            }
+        }
+    }
+    writeln!(output, "    }}")?;
+    writeln!(output, "    $words = @($commandAst.CommandElements | Where-Object {{ $_.Extent.EndOffset -lt $cursorPosition }} | ForEach-Object {{ $_.Extent.Text }})")?;
+    writeln!(output, "    $command = {}", ps_quote(&top.name))?;
+    writeln!(output, "    foreach ($word in $words | Select-Object -Skip 1) {{")?;
+    writeln!(output, "        if ($subcommands -contains $word) {{ $command = $word; break }}")?;
+    writeln!(output, "    }}")?;
+    writeln!(output, "    $key = \"$command $($words[-1])\"")?;
+    writeln!(output, "    if ($values.ContainsKey($key)) {{")?;
+    writeln!(output, "        # Options taking any value complete file names")?;
+    writeln!(output, "        $candidates = $values[$key]")?;
+    writeln!(output, "    }} elseif ($wordToComplete -like '-*') {{")?;
+    writeln!(output, "        $candidates = $flags[$command]")?;
+    writeln!(output, "    }} elseif ($values.ContainsKey(\"$command \")) {{")?;
+    writeln!(output, "        $candidates = $values[\"$command \"]")?;
+    writeln!(output, "    }} elseif ($command -eq {}) {{", ps_quote(&top.name))?;
+    writeln!(output, "        $candidates = $subcommands")?;
+    writeln!(output, "    }}")?;
+    writeln!(output, "    $candidates | Where-Object {{ $_ -like \"$wordToComplete*\" }} | ForEach-Object {{")?;
+    writeln!(output, "        [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)")?;
+    writeln!(output, "    }}")?;
+    writeln!(output, "}}")
+}
+
+/// Quote `text` as a single word for PowerShell.
+fn ps_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
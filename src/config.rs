@@ -0,0 +1,374 @@
+//! Persistent configuration of a [`BioReader`](crate::BioReader), which can be (de)serialized with [serde](https://serde.rs).
+
+use crate::{
+    encoding::Encoding,
+    render::{Ansi, Gradient, Plain, Theme, Wrap},
+    segment::{Cjk, CompoundPolicy, Language, LongWordPolicy, TokenPolicy},
+    EmphasisRenderer, Error, InputFormat, OutputFormat,
+};
+use anstyle::{AnsiColor, Color, Style};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Configuration of a [`BioReader`](crate::BioReader), with every field being optional. Use [`BioReader::from_config`](crate::BioReader::from_config) to create a [`BioReader`](crate::BioReader) from it.
+///
+/// Fields are named in kebab-case when (de)serialized, matching the command line options of the `br` binary, which reads its configuration from `~/.config/bio-read/config.toml`. Applications can persist and sync the preferences of their users the same way, in any format serde supports. Unset fields are left out when serialized, so that they keep following the defaults. (De)serializing requires the `serde` feature, which is enabled by default, as the binary is built on it.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{BioReader, Config};
+/// let config: Config = toml::from_str(r#"
+///     fixation-point = 1
+///     to = "markdown"
+/// "#).unwrap();
+/// let reader = BioReader::from_config(&config).unwrap();
+/// assert_eq!(reader.bio_read_text("hello world").unwrap(), "**hel**lo **wor**ld");
+/// ```
+///
+/// Saving preferences, to restore them later:
+///
+/// ```rust
+/// use bio_read::{Config, InputFormat};
+/// let config = Config {
+///     saccade: Some(2),
+///     format: Some(InputFormat::Html),
+///     ..Config::default()
+/// };
+/// let saved = toml::to_string(&config).unwrap();
+/// assert_eq!(saved, "saccade = 2\nformat = \"html\"\n");
+/// assert_eq!(toml::from_str::<Config>(&saved).unwrap(), config);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "kebab-case", deny_unknown_fields))]
+pub struct Config {
+    /// The fixation point, in range \[1, 5\]. See [`BioReader::fixation_point`](crate::BioReader::fixation_point).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub fixation_point: Option<usize>,
+    /// The fraction of each word to emphasize, in range \[0, 1\]. Cannot be used together with `fixation_point`. See [`BioReader::fixation_ratio`](crate::BioReader::fixation_ratio).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub fixation_ratio: Option<f32>,
+    /// Emphasize every `saccade`-th word only. See [`BioReader::saccade`](crate::BioReader::saccade).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub saccade: Option<usize>,
+    /// How many more letters to emphasize in the first word of each sentence. See [`BioReader::sentence_boost`](crate::BioReader::sentence_boost).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub sentence_boost: Option<usize>,
+    /// Whether to restart the saccade and sentence tracking at each paragraph. See [`BioReader::reset_at_paragraphs`](crate::BioReader::reset_at_paragraphs).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub reset_at_paragraphs: Option<bool>,
+    /// Regular expressions of text to pass through untouched. See [`BioReader::skip_pattern`](crate::BioReader::skip_pattern).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub skip: Option<Vec<String>>,
+    /// Words to leave un-emphasized, matched case-insensitively. See [`BioReader::exclude_words`](crate::BioReader::exclude_words).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub exclude_words: Option<Vec<String>>,
+    /// Whether to leave words written in all capitals, like acronyms, un-emphasized. See [`BioReader::skip_acronyms`](crate::BioReader::skip_acronyms).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub skip_acronyms: Option<bool>,
+    /// How digits are handled, `split-at-digits`, `include-digits` or `skip-numeric`. See [`BioReader::token_policy`](crate::BioReader::token_policy).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub token_policy: Option<TokenPolicy>,
+    /// How compound words joined by hyphens are emphasized, `whole` or `each-part`. See [`BioReader::compound_policy`](crate::BioReader::compound_policy).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub compound_policy: Option<CompoundPolicy>,
+    /// The maximum length of words in bytes, beyond which they are not buffered whole. See [`BioReader::max_word_len`](crate::BioReader::max_word_len).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max_word_len: Option<usize>,
+    /// What happens to words longer than the maximum length, `plain` or `split`. See [`BioReader::long_word_policy`](crate::BioReader::long_word_policy).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub long_word_policy: Option<LongWordPolicy>,
+    /// The language of the text, as an ISO 639-1 code like `fr`. See [`BioReader::language`](crate::BioReader::language).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub language: Option<Language>,
+    /// Characters kept inside words when between two letters, like `"'’-"`, overriding those of the language. See [`BioReader::word_chars`](crate::BioReader::word_chars).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub joiners: Option<String>,
+    /// How to handle runs of Chinese and Japanese characters, `pass-through` or `segment` (with the `cjk` feature). See [`BioReader::cjk`](crate::BioReader::cjk).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub cjk: Option<Cjk>,
+    /// Whether to keep cursive scripts like Arabic joined where words are split. See [`BioReader::join_cursive`](crate::BioReader::join_cursive).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub join_cursive: Option<bool>,
+    /// Whether to pass URLs, emails and file paths through untouched. See [`BioReader::preserve_urls`](crate::BioReader::preserve_urls).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub preserve_urls: Option<bool>,
+    /// Whether to emphasize long words a second time midway. See [`BioReader::secondary_fixation`](crate::BioReader::secondary_fixation).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub secondary_fixation: Option<bool>,
+    /// The width to reflow plain text to, or 0 to leave lines as they are. See [`BioReader::reflow`](crate::BioReader::reflow).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub reflow: Option<usize>,
+    /// Whether to justify reflowed lines. See [`BioReader::justify`](crate::BioReader::justify).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub justify: Option<bool>,
+    /// How many columns to indent reflowed lines after the first of each paragraph by. See [`BioReader::hanging_indent`](crate::BioReader::hanging_indent).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub hanging_indent: Option<usize>,
+    /// How many columns to lay out reflowed text in. See [`BioReader::columns`](crate::BioReader::columns).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub columns: Option<usize>,
+    /// How many lines each column of a page has, or 0 to balance the whole text over the columns. See [`BioReader::page_height`](crate::BioReader::page_height).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub page_height: Option<usize>,
+    /// The character encoding of the input, like `utf-8` or `latin1` (with the `encoding` feature). See [`BioReader::encoding`](crate::BioReader::encoding).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub encoding: Option<Encoding>,
+    /// Whether to turn CRLF line endings into LF. See [`BioReader::normalize_line_endings`](crate::BioReader::normalize_line_endings).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub normalize_line_endings: Option<bool>,
+    /// Whether to remove whitespace at the ends of lines. See [`BioReader::trim_trailing_whitespace`](crate::BioReader::trim_trailing_whitespace).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub trim_trailing_whitespace: Option<bool>,
+    /// Whether to collapse runs of blank lines into one. See [`BioReader::collapse_blank_lines`](crate::BioReader::collapse_blank_lines).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub collapse_blank_lines: Option<bool>,
+    /// Whether to replace typographic punctuation with ASCII. See [`BioReader::ascii_punctuation`](crate::BioReader::ascii_punctuation).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub ascii_punctuation: Option<bool>,
+    /// How to emphasize the text, with the emphasized text taking the place of `{}`. Takes precedence over `to`.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub emphasize: Option<String>,
+    /// How to de-emphasize the text, with the de-emphasized text taking the place of `{}`. Takes precedence over `to`.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub de_emphasize: Option<String>,
+    /// The format of the input. See [`BioReader::format`](crate::BioReader::format).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub format: Option<InputFormat>,
+    /// The format of the output. Defaults to [`OutputFormat::Html`] for HTML input, [`OutputFormat::Latex`] for LaTeX input, [`OutputFormat::Rst`] for reStructuredText input, [`OutputFormat::Org`] for Org input, [`OutputFormat::Markdown`] for AsciiDoc input, [`OutputFormat::Html`] for subtitles, and [`OutputFormat::Ansi`] otherwise. [`OutputFormat::Json`] and [`OutputFormat::Rtf`] only support plain text and man page input, and `format` then defaults to plain text.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub to: Option<OutputFormat>,
+    /// Whether to render the emphasized part with graded intensity, using [`Gradient::ansi`] for ANSI output or [`Gradient::html`] with prefix `fixation` for HTML output. Other output formats and custom wrappers are not supported.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub gradient: Option<bool>,
+    /// The styles of ANSI output.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub theme: Option<ThemeConfig>,
+    /// Whether to style ANSI output. See [`ColorChoice`].
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub color: Option<ColorChoice>,
+}
+
+/// The configuration of a [`BioReader`](crate::BioReader), under a name that stands out among the types of an application. Same as [`Config`].
+pub type BioReaderConfig = Config;
+
+/// Whether to style ANSI output with escape codes. Other output formats and custom wrappers are unaffected.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{config::ColorChoice, BioReader, Config};
+/// let config = Config {
+///     color: Some(ColorChoice::Never),
+///     ..Config::default()
+/// };
+/// let reader = BioReader::from_config(&config).unwrap();
+/// assert_eq!(reader.bio_read_text("hello").unwrap(), "hello");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ColorChoice {
+    /// Style the output if it goes to a terminal and the environment allows it, honoring `NO_COLOR`, `CLICOLOR` and `CLICOLOR_FORCE`. This is decided by the `br` binary, while [`BioReader::from_config`](crate::BioReader::from_config) treats it like [`ColorChoice::Always`].
+    #[default]
+    Auto,
+    /// Always style the output.
+    Always,
+    /// Never style the output, writing ANSI output as plain text.
+    Never,
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => Err(format!("Unknown color choice: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for ColorChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Auto => "auto",
+            Self::Always => "always",
+            Self::Never => "never",
+        })
+    }
+}
+
+/// The styles of ANSI output, starting from a preset [`Theme`], with each style being a space-separated list of effects (`bold`, `dimmed`, `italic`, `underline`, `blink`, `invert`, `hidden`, `strikethrough`) and colors (`red`, `bright-blue`, `on-white`, ...). Unset styles keep those of the preset, which defaults to bold and dimmed.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{config::ThemeConfig, BioReader, Config};
+/// let config = Config {
+///     theme: Some(ThemeConfig {
+///         emphasize: Some(String::from("bold red")),
+///         de_emphasize: Some(String::new()),
+///         ..ThemeConfig::default()
+///     }),
+///     ..Config::default()
+/// };
+/// let reader = BioReader::from_config(&config).unwrap();
+/// assert_eq!(reader.bio_read_text("hello").unwrap(), "\x1b[1m\x1b[31mhel\x1b[0mlo");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "kebab-case", deny_unknown_fields))]
+pub struct ThemeConfig {
+    /// The preset to start from. See [`BioReader::theme`](crate::BioReader::theme).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub preset: Option<Theme>,
+    /// The style of the emphasized part.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub emphasize: Option<String>,
+    /// The style of the de-emphasized part.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub de_emphasize: Option<String>,
+    /// The style of the secondary emphasized part of long words. See [`BioReader::secondary_fixation`](crate::BioReader::secondary_fixation).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub secondary: Option<String>,
+}
+
+impl ThemeConfig {
+    /// Get the [`Ansi`] renderer with these styles.
+    fn ansi(&self) -> Result<Ansi, Error> {
+        let mut ansi = Ansi::from(self.preset.unwrap_or_default());
+        if let Some(emphasize) = &self.emphasize {
+            ansi.emphasize = parse_style(emphasize).map_err(|e| Error::config("theme", e))?;
+        }
+        if let Some(de_emphasize) = &self.de_emphasize {
+            ansi.de_emphasize = parse_style(de_emphasize).map_err(|e| Error::config("theme", e))?;
+        }
+        if let Some(secondary) = &self.secondary {
+            ansi.secondary = parse_style(secondary).map_err(|e| Error::config("theme", e))?;
+        }
+        Ok(ansi)
+    }
+}
+
+impl Config {
+    /// Get the renderer described by this configuration.
+    pub(crate) fn renderer(&self) -> Result<Box<dyn EmphasisRenderer + Send + Sync>, Error> {
+        let to = self.to.unwrap_or(match self.format {
+            // Ansi escape codes make no sense in markup
+            Some(InputFormat::Html) => OutputFormat::Html,
+            Some(InputFormat::Latex) => OutputFormat::Latex,
+            Some(InputFormat::Rst) => OutputFormat::Rst,
+            Some(InputFormat::Org) => OutputFormat::Org,
+            // Double asterisks are bold even inside words in asciidoc
+            Some(InputFormat::AsciiDoc) => OutputFormat::Markdown,
+            // Subtitle players show <b> tags as bold
+            Some(InputFormat::Subtitles) => OutputFormat::Html,
+            _ => OutputFormat::Ansi,
+        });
+        // Without colors, ansi output is plain text
+        let colorless = to == OutputFormat::Ansi && self.color == Some(ColorChoice::Never);
+        let to = if colorless { OutputFormat::Plain } else { to };
+        let ansi = match &self.theme {
+            Some(theme) => theme.ansi()?,
+            None => Ansi::default(),
+        };
+        if self.gradient == Some(true) {
+            if self.emphasize.is_some() || self.de_emphasize.is_some() {
+                return Err(Error::config("gradient", "Gradient cannot be used together with custom wrappers"));
+            }
+            return match to {
+                OutputFormat::Ansi => {
+                    let de_emphasize = ansi.de_emphasize;
+                    let mut gradient = Gradient::ansi();
+                    gradient.de_emphasize = [format!("{de_emphasize}"), format!("{de_emphasize:#}")];
+                    Ok(Box::new(gradient))
+                }
+                OutputFormat::Html => Ok(Box::new(Gradient::html("fixation"))),
+                OutputFormat::Plain if colorless => Ok(Box::new(Plain)),
+                _ => Err(Error::config("gradient", format!("Gradient is not supported for {to} output"))),
+            };
+        }
+        if self.emphasize.is_none() && self.de_emphasize.is_none() {
+            return Ok(match to {
+                OutputFormat::Ansi => Box::new(ansi),
+                // AsciiDoc has no backslash escapes to speak of, so they would show
+                OutputFormat::Markdown if self.format == Some(InputFormat::AsciiDoc) => Box::new(Wrap {
+                    emphasize: [String::from("**"), String::from("**")],
+                    ..Wrap::default()
+                }),
+                // SubRip has no character references, so text is written as-is
+                OutputFormat::Html if self.format == Some(InputFormat::Subtitles) => Box::new(Wrap {
+                    emphasize: [String::from("<b>"), String::from("</b>")],
+                    ..Wrap::default()
+                }),
+                _ => to.renderer(),
+            });
+        }
+        // Customized wrappers, with the other half defaulting to ansi styles or nothing
+        let mut wrap = match to {
+            OutputFormat::Ansi => Wrap::from(ansi),
+            _ => Wrap::default(),
+        };
+        if let Some(emphasize) = &self.emphasize {
+            let Some((left, right)) = emphasize.split_once("{}") else {
+                return Err(Error::config("emphasize", format!("Invalid emphasize format: {emphasize}")));
+            };
+            wrap.emphasize = [left.to_string(), right.to_string()];
+        }
+        if let Some(de_emphasize) = &self.de_emphasize {
+            let Some((left, right)) = de_emphasize.split_once("{}") else {
+                return Err(Error::config("de-emphasize", format!("Invalid de-emphasize format: {de_emphasize}")));
+            };
+            wrap.de_emphasize = [left.to_string(), right.to_string()];
+        }
+        Ok(Box::new(wrap))
+    }
+}
+
+/// Parse a space-separated list of effects and colors into a style.
+fn parse_style(spec: &str) -> Result<Style, String> {
+    let mut style = Style::new();
+    for word in spec.split_whitespace() {
+        let word = word.to_ascii_lowercase();
+        style = match word.as_str() {
+            "bold" => style.bold(),
+            "dimmed" | "dim" => style.dimmed(),
+            "italic" => style.italic(),
+            "underline" => style.underline(),
+            "blink" => style.blink(),
+            "invert" | "inverse" => style.invert(),
+            "hidden" => style.hidden(),
+            "strikethrough" => style.strikethrough(),
+            _ => match word.strip_prefix("on-") {
+                Some(color) => style.bg_color(Some(parse_color(color, spec)?)),
+                None => style.fg_color(Some(parse_color(&word, spec)?)),
+            },
+        };
+    }
+    Ok(style)
+}
+
+/// Parse a color name, like `red` or `bright-red`. `spec` is the whole style, for error messages.
+fn parse_color(name: &str, spec: &str) -> Result<Color, String> {
+    let (bright, base) = match name.strip_prefix("bright-") {
+        Some(base) => (true, base),
+        None => (false, name),
+    };
+    let color = match base {
+        "black" => AnsiColor::Black,
+        "red" => AnsiColor::Red,
+        "green" => AnsiColor::Green,
+        "yellow" => AnsiColor::Yellow,
+        "blue" => AnsiColor::Blue,
+        "magenta" => AnsiColor::Magenta,
+        "cyan" => AnsiColor::Cyan,
+        "white" => AnsiColor::White,
+        _ => return Err(format!("Invalid style: {spec}")),
+    };
+    Ok(Color::Ansi(color.bright(bright)))
+}
@@ -0,0 +1,127 @@
+//! Export of the words of bio-read text as timed cues, so that text-to-speech or karaoke-style readers can highlight the same fixations while audio plays. See [`write_cues`].
+
+use crate::{render::write_escaped_json, WordSpan};
+use std::{fmt, io::Write, str::FromStr};
+
+/// The format to export cues in. See [`write_cues`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CueFormat {
+    /// [JSON Lines](https://jsonlines.org), an object per word with its offsets and times.
+    #[default]
+    Json,
+    /// [WebVTT](https://www.w3.org/TR/webvtt1/) subtitles, a cue per word with its emphasized start in bold.
+    Vtt,
+    /// SubRip subtitles, like [`CueFormat::Vtt`].
+    Srt,
+}
+
+impl FromStr for CueFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" | "jsonl" => Ok(Self::Json),
+            "vtt" | "webvtt" => Ok(Self::Vtt),
+            "srt" => Ok(Self::Srt),
+            _ => Err(format!("Unknown cue format: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for CueFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Json => "json",
+            Self::Vtt => "vtt",
+            Self::Srt => "srt",
+        })
+    }
+}
+
+/// Write `words` of `text`, as returned by [`BioReader::bio_read_words`](crate::BioReader::bio_read_words), as cues in `format`.
+///
+/// Each word is timed to be read one after another at `wpm` words per minute, for readers without timings of their own, so that TTS engines reporting word boundaries can go by the offsets instead. With [`CueFormat::Json`], each word is an object with the whole `word` and its `emphasized` start, the byte range of the word in the text as `start` and `end`, the range in characters as `char_start` and `char_end`, and its times in seconds as `start_time` and `end_time`.
+///
+/// # Panics
+///
+/// Panics if `wpm` is 0.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{cues::{write_cues, CueFormat}, BioReader};
+/// let text = "Né en 1990";
+/// let words = BioReader::new().bio_read_words(text);
+/// let mut output = Vec::new();
+/// write_cues(text, &words, CueFormat::Json, 120, &mut output).unwrap();
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     concat!(
+///         "{\"word\":\"Né\",\"emphasized\":\"N\",\"start\":0,\"end\":3,\"char_start\":0,\"char_end\":2,\"start_time\":0.000,\"end_time\":0.500}\n",
+///         "{\"word\":\"en\",\"emphasized\":\"e\",\"start\":4,\"end\":6,\"char_start\":3,\"char_end\":5,\"start_time\":0.500,\"end_time\":1.000}\n",
+///     )
+/// );
+/// let mut output = Vec::new();
+/// write_cues(text, &words, CueFormat::Vtt, 120, &mut output).unwrap();
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     "WEBVTT\n\n1\n00:00:00.000 --> 00:00:00.500\n<b>N</b>é\n\n2\n00:00:00.500 --> 00:00:01.000\n<b>e</b>n\n"
+/// );
+/// ```
+pub fn write_cues(text: &str, words: &[WordSpan], format: CueFormat, wpm: u32, writer: &mut impl Write) -> std::io::Result<()> {
+    assert!(wpm > 0, "Words per minute should be positive");
+    // Computed from the start, so that rounding doesn't add up
+    let time = |i: usize| i as u64 * 60_000 / u64::from(wpm);
+    if format == CueFormat::Vtt {
+        writer.write_all(b"WEBVTT\n")?;
+    }
+    for (i, span) in words.iter().enumerate() {
+        let (word, emphasized) = (&text[span.range.clone()], &text[span.emphasized.clone()]);
+        let (start, end) = (time(i), time(i + 1));
+        // Words that aren't emphasized are written without empty tags
+        let bold = if emphasized.is_empty() { ["", ""] } else { ["<b>", "</b>"] };
+        match format {
+            CueFormat::Json => {
+                writer.write_all(b"{\"word\":\"")?;
+                write_escaped_json(writer, word)?;
+                writer.write_all(b"\",\"emphasized\":\"")?;
+                write_escaped_json(writer, emphasized)?;
+                writeln!(
+                    writer,
+                    "\",\"start\":{},\"end\":{},\"char_start\":{},\"char_end\":{},\"start_time\":{}.{:03},\"end_time\":{}.{:03}}}",
+                    span.range.start,
+                    span.range.end,
+                    span.chars.start,
+                    span.chars.end,
+                    start / 1000,
+                    start % 1000,
+                    end / 1000,
+                    end % 1000
+                )?;
+            }
+            CueFormat::Vtt => {
+                write!(writer, "\n{}\n{} --> {}\n", i + 1, Timestamp(start, '.'), Timestamp(end, '.'))?;
+                let escape = |text: &str| text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+                writeln!(writer, "{}{}{}{}", bold[0], escape(emphasized), bold[1], escape(&text[span.emphasized.end..span.range.end]))?;
+            }
+            CueFormat::Srt => {
+                if i > 0 {
+                    writer.write_all(b"\n")?;
+                }
+                write!(writer, "{}\n{} --> {}\n", i + 1, Timestamp(start, ','), Timestamp(end, ','))?;
+                writeln!(writer, "{}{emphasized}{}{}", bold[0], bold[1], &text[span.emphasized.end..span.range.end])?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A time in milliseconds, formatted as a subtitle timestamp with the given separator before the milliseconds.
+struct Timestamp(u64, char);
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(ms, separator) = *self;
+        write!(f, "{:02}:{:02}:{:02}{separator}{:03}", ms / 3_600_000, ms / 60_000 % 60, ms / 1000 % 60, ms % 1000)
+    }
+}
@@ -0,0 +1,87 @@
+//! Incremental UTF-8 decoding.
+
+use std::io::Result;
+
+/// Decodes UTF-8 incrementally, from chunks of bytes that may split multi-byte sequences.
+#[derive(Debug, Default)]
+pub(crate) struct Utf8Decoder {
+    /// An incomplete sequence left over from the last chunk, in a fixed buffer so that decoding never allocates. Only the first `pending_len` bytes are used.
+    pending: [u8; 4],
+    /// How long the incomplete sequence is, or 0 if there is none.
+    pending_len: usize,
+}
+
+impl Utf8Decoder {
+    /// Decode a chunk of bytes, calling `f` with each decoded piece of text (`Ok`), or each invalid byte sequence (`Err`), in order. An incomplete sequence at the end of the chunk is kept until the next call.
+    pub(crate) fn decode(&mut self, mut bytes: &[u8], mut f: impl FnMut(std::result::Result<&str, &[u8]>) -> Result<()>) -> Result<()> {
+        if self.pending_len > 0 {
+            // Complete the pending sequence with the first few bytes of this chunk
+            let old_len = self.pending_len;
+            let take = bytes.len().min(self.pending.len() - old_len);
+            self.pending[old_len..old_len + take].copy_from_slice(&bytes[..take]);
+            let pending = &self.pending[..old_len + take];
+            let sequence_len = match std::str::from_utf8(pending) {
+                Ok(text) => {
+                    let len = text.chars().next().map_or(0, char::len_utf8);
+                    f(Ok(&text[..len]))?;
+                    len
+                }
+                Err(e) if e.valid_up_to() > 0 => {
+                    let text = std::str::from_utf8(&pending[..e.valid_up_to()]).expect("Valid UTF-8 prefix");
+                    let len = text.chars().next().map_or(0, char::len_utf8);
+                    f(Ok(&text[..len]))?;
+                    len
+                }
+                Err(e) => match e.error_len() {
+                    Some(len) => {
+                        f(Err(&pending[..len]))?;
+                        len
+                    }
+                    None => {
+                        // Still incomplete, so this chunk has been consumed entirely
+                        self.pending_len = pending.len();
+                        return Ok(());
+                    }
+                },
+            };
+            // The pending sequence is always a prefix of the first sequence
+            bytes = &bytes[sequence_len - old_len..];
+            self.pending_len = 0;
+        }
+        while !bytes.is_empty() {
+            match std::str::from_utf8(bytes) {
+                Ok(text) => {
+                    f(Ok(text))?;
+                    bytes = &[];
+                }
+                Err(e) => {
+                    let (valid, rest) = bytes.split_at(e.valid_up_to());
+                    if !valid.is_empty() {
+                        f(Ok(std::str::from_utf8(valid).expect("Valid UTF-8 prefix")))?;
+                    }
+                    match e.error_len() {
+                        Some(len) => {
+                            f(Err(&rest[..len]))?;
+                            bytes = &rest[len..];
+                        }
+                        None => {
+                            // Incomplete sequence at the end of the chunk, to be completed by the next call
+                            self.pending[..rest.len()].copy_from_slice(rest);
+                            self.pending_len = rest.len();
+                            bytes = &[];
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Finish decoding, calling `f` with the trailing incomplete sequence, if any.
+    pub(crate) fn finish(&mut self, mut f: impl FnMut(std::result::Result<&str, &[u8]>) -> Result<()>) -> Result<()> {
+        if self.pending_len > 0 {
+            f(Err(&self.pending[..self.pending_len]))?;
+            self.pending_len = 0;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+//! Lazily bio-read text, for embedding it in [`format!`] or [`println!`]. See [`BioReader::display`].
+
+use crate::{processor::Processor, BioReader};
+use std::{fmt, io};
+
+/// Text that is bio-read as it is formatted, without allocating the output. Created by [`BioReader::display`].
+#[derive(Clone, Copy, Debug)]
+pub struct Display<'a> {
+    /// The reader to bio-read with.
+    reader: &'a BioReader,
+    /// The text to bio-read.
+    text: &'a str,
+}
+
+impl<'a> Display<'a> {
+    /// Create a display of `text` bio-read with `reader`.
+    pub(crate) fn new(reader: &'a BioReader, text: &'a str) -> Self {
+        Self { reader, text }
+    }
+}
+
+impl fmt::Display for Display<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut writer = FmtWriter(f);
+        let mut processor = Processor::new(self.reader).decoded();
+        processor
+            .push_bytes(self.text.as_bytes(), &mut writer)
+            .and_then(|()| processor.finish(&mut writer))
+            .map_err(|_| fmt::Error)
+    }
+}
+
+/// Adapts a [`fmt::Formatter`] to [`io::Write`], which renderers write to.
+struct FmtWriter<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+impl io::Write for FmtWriter<'_, '_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Renderers write whole strings, and the input is valid UTF-8, so this only fails for custom renderers writing bytes
+        let text = std::str::from_utf8(buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.0.write_str(text).map_err(io::Error::other)?;
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
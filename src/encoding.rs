@@ -0,0 +1,167 @@
+//! Character encodings of the input, which is transcoded to UTF-8 before it is bio-read. See [`BioReader::encoding`](crate::BioReader::encoding).
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{borrow::Cow, fmt, str::FromStr};
+
+/// The character encoding of the input. Named like `utf-8` or `latin1`, when parsed or (de)serialized. See [`BioReader::encoding`](crate::BioReader::encoding).
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::encoding::Encoding;
+/// assert_eq!("UTF8".parse(), Ok(Encoding::Utf8));
+/// assert_eq!(Encoding::Utf8.to_string(), "utf-8");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Encoding {
+    /// Detect the encoding from the byte order mark at the start of the input, if any, falling back to UTF-8. Without the `encoding` feature, only a UTF-8 byte order mark is detected.
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "auto"))]
+    Auto,
+    /// UTF-8, with or without a byte order mark.
+    #[cfg_attr(feature = "serde", serde(rename = "utf-8"))]
+    Utf8,
+    /// UTF-16 little-endian, with or without a byte order mark. Requires the `encoding` feature.
+    #[cfg(feature = "encoding")]
+    #[cfg_attr(feature = "serde", serde(rename = "utf-16le"))]
+    Utf16Le,
+    /// UTF-16 big-endian, with or without a byte order mark. Requires the `encoding` feature.
+    #[cfg(feature = "encoding")]
+    #[cfg_attr(feature = "serde", serde(rename = "utf-16be"))]
+    Utf16Be,
+    /// Latin-1 (ISO-8859-1), read as its superset Windows-1252 like browsers do. Requires the `encoding` feature.
+    #[cfg(feature = "encoding")]
+    #[cfg_attr(feature = "serde", serde(rename = "latin1"))]
+    Latin1,
+}
+
+impl FromStr for Encoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "utf-8" | "utf8" => Ok(Self::Utf8),
+            #[cfg(feature = "encoding")]
+            "utf-16le" | "utf16le" | "utf-16" | "utf16" => Ok(Self::Utf16Le),
+            #[cfg(feature = "encoding")]
+            "utf-16be" | "utf16be" => Ok(Self::Utf16Be),
+            #[cfg(feature = "encoding")]
+            "latin1" | "latin-1" | "iso-8859-1" | "windows-1252" | "cp1252" => Ok(Self::Latin1),
+            _ => Err(format!("Unknown encoding: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Auto => "auto",
+            Self::Utf8 => "utf-8",
+            #[cfg(feature = "encoding")]
+            Self::Utf16Le => "utf-16le",
+            #[cfg(feature = "encoding")]
+            Self::Utf16Be => "utf-16be",
+            #[cfg(feature = "encoding")]
+            Self::Latin1 => "latin1",
+        })
+    }
+}
+
+/// The UTF-8 byte order mark.
+const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// The byte order marks detected by [`Encoding::Auto`].
+#[cfg(feature = "encoding")]
+const BOMS: &[&[u8]] = &[UTF8_BOM, b"\xff\xfe", b"\xfe\xff"];
+
+/// The byte order marks detected by [`Encoding::Auto`].
+#[cfg(not(feature = "encoding"))]
+const BOMS: &[&[u8]] = &[UTF8_BOM];
+
+/// Transcodes input to UTF-8 incrementally, from chunks of bytes that may end anywhere, stripping its byte order mark.
+///
+/// UTF-8 input is passed through as it is, invalid sequences and all, once the start of the input tells whether it has a byte order mark. Other encodings are decoded with [encoding_rs](https://docs.rs/encoding_rs), replacing malformed sequences with U+FFFD.
+#[derive(Debug)]
+pub(crate) struct Transcoder {
+    /// The start of the input, held back until it tells whether it starts with a byte order mark, or `None` once it has.
+    start: Option<Vec<u8>>,
+    /// Whether to detect UTF-16 byte order marks as well.
+    #[cfg(feature = "encoding")]
+    detect: bool,
+    /// The decoder of input that isn't UTF-8.
+    #[cfg(feature = "encoding")]
+    decoder: Option<encoding_rs::Decoder>,
+}
+
+impl Transcoder {
+    /// Create a transcoder of input in `encoding`.
+    pub(crate) fn new(encoding: Encoding) -> Self {
+        #[cfg(feature = "encoding")]
+        let decoder = match encoding {
+            Encoding::Auto | Encoding::Utf8 => None,
+            Encoding::Utf16Le => Some(encoding_rs::UTF_16LE.new_decoder_with_bom_removal()),
+            Encoding::Utf16Be => Some(encoding_rs::UTF_16BE.new_decoder_with_bom_removal()),
+            Encoding::Latin1 => Some(encoding_rs::WINDOWS_1252.new_decoder_without_bom_handling()),
+        };
+        // Without other encodings, UTF-8 is all there is to detect
+        #[cfg(not(feature = "encoding"))]
+        let _ = encoding;
+        Self {
+            #[cfg(feature = "encoding")]
+            start: decoder.is_none().then(Vec::new),
+            #[cfg(not(feature = "encoding"))]
+            start: Some(Vec::new()),
+            #[cfg(feature = "encoding")]
+            detect: encoding == Encoding::Auto,
+            #[cfg(feature = "encoding")]
+            decoder,
+        }
+    }
+    /// Transcode a chunk of bytes, returning what is ready.
+    pub(crate) fn push<'b>(&mut self, bytes: &'b [u8]) -> Cow<'b, [u8]> {
+        let Some(start) = &mut self.start else {
+            return self.decode(bytes, false);
+        };
+        start.extend_from_slice(bytes);
+        if BOMS.iter().any(|bom| bom.len() > start.len() && bom.starts_with(start)) {
+            // Still too short to tell
+            return Cow::Borrowed(&[]);
+        }
+        let start = self.start.take().expect("Start of the input");
+        let rest = self.sniff(&start);
+        Cow::Owned(self.decode(rest, false).into_owned())
+    }
+    /// Return what is held back, at the end of the input.
+    pub(crate) fn finish(&mut self) -> Vec<u8> {
+        let start = self.start.take().unwrap_or_default();
+        let rest = self.sniff(&start);
+        self.decode(rest, true).into_owned()
+    }
+    /// Detect the byte order mark at the start of the input, returning the input after it.
+    fn sniff<'b>(&mut self, start: &'b [u8]) -> &'b [u8] {
+        #[cfg(feature = "encoding")]
+        if self.detect {
+            if let Some((encoding, len)) = encoding_rs::Encoding::for_bom(start).filter(|(encoding, _)| *encoding != encoding_rs::UTF_8) {
+                self.decoder = Some(encoding.new_decoder_without_bom_handling());
+                return &start[len..];
+            }
+        }
+        start.strip_prefix(UTF8_BOM).unwrap_or(start)
+    }
+    /// Decode `bytes` if the input isn't UTF-8, flushing the decoder if they are the `last` of the input.
+    fn decode<'b>(&mut self, bytes: &'b [u8], last: bool) -> Cow<'b, [u8]> {
+        #[cfg(feature = "encoding")]
+        if let Some(decoder) = &mut self.decoder {
+            let mut output = String::with_capacity(decoder.max_utf8_buffer_length(bytes.len()).expect("Decoded length overflows"));
+            // The output has room for all of the input, so it is always decoded entirely
+            let _ = decoder.decode_to_string(bytes, &mut output, last);
+            return Cow::Owned(output.into_bytes());
+        }
+        #[cfg(not(feature = "encoding"))]
+        let _ = last;
+        Cow::Borrowed(bytes)
+    }
+}
@@ -0,0 +1,173 @@
+//! EPUB conversion, bio-reading every document in the spine of an EPUB. Requires the `epub` feature.
+
+use crate::{processor::Processor, BioReader, InputFormat};
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader, XmlVersion,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    io::{Error, Read, Seek, Write},
+};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+/// Do bio-reading on the EPUB `input`, writing the result to `output`.
+///
+/// Each XHTML document in the spine is processed as HTML with the renderer of `reader`, regardless of its [format](BioReader::format), so it should write HTML, like [`Html`](crate::render::Html). Other files are copied as-is.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use bio_read::{epub, render::Html, BioReader};
+/// use std::fs::File;
+/// let reader = BioReader::new().renderer(Html::with_class("fixation"));
+/// let input = File::open("book.epub").unwrap();
+/// let output = File::create("book.bionic.epub").unwrap();
+/// epub::bio_read_epub(&reader, input, output).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Fails with [`Error::Format`](crate::Error::Format) if `input` is not a valid EPUB, and [`Error::Io`](crate::Error::Io) if reading or writing fails.
+pub fn bio_read_epub(reader: &BioReader, input: impl Read + Seek, output: impl Write + Seek) -> Result<(), crate::Error> {
+    Ok(convert(reader, input, output)?)
+}
+
+/// Do bio-reading on the EPUB `input` like [`bio_read_epub`], failing with an I/O error.
+fn convert(reader: &BioReader, input: impl Read + Seek, output: impl Write + Seek) -> std::io::Result<()> {
+    let mut archive = ZipArchive::new(input).map_err(invalid)?;
+    let container = read_to_string(&mut archive, "META-INF/container.xml")?;
+    let package_path = rootfile(&container)?;
+    let package = read_to_string(&mut archive, &package_path)?;
+    let spine = spine(&package, &package_path)?;
+    let mut writer = ZipWriter::new(output);
+    // The mimetype must come first, uncompressed
+    writer.start_file("mimetype", SimpleFileOptions::default().compression_method(CompressionMethod::Stored))?;
+    writer.write_all(b"application/epub+zip")?;
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        let name = file.name()?.into_owned();
+        if name == "mimetype" {
+            continue;
+        }
+        if spine.contains(&name) {
+            let mut document = Vec::new();
+            file.read_to_end(&mut document)?;
+            writer.start_file(name, options)?;
+            // EPUB documents are always UTF-8, whatever the encoding of the reader
+            let mut processor = Processor::with_format(reader, reader.renderer_ref(), InputFormat::Html).decoded();
+            processor.push_bytes(&document, &mut writer)?;
+            processor.finish(&mut writer)?;
+        } else {
+            writer.raw_copy_file(file)?;
+        }
+    }
+    writer.finish()?.flush()
+}
+
+/// Read the file named `name` in `archive` as a string.
+fn read_to_string(archive: &mut ZipArchive<impl Read + Seek>, name: &str) -> std::io::Result<String> {
+    let mut file = archive.by_name(name).map_err(|_| invalid(format!("missing {name}")))?;
+    let mut content = String::new();
+    file.read_to_string(&mut content)?;
+    Ok(content)
+}
+
+/// Get the path of the package document from the content of `META-INF/container.xml`.
+fn rootfile(container: &str) -> std::io::Result<String> {
+    let mut reader = Reader::from_str(container);
+    loop {
+        match reader.read_event().map_err(invalid)? {
+            Event::Start(element) | Event::Empty(element) if element.local_name().as_ref() == "rootfile" => {
+                if let Some(path) = attribute(&element, "full-path")? {
+                    return Ok(path);
+                }
+            }
+            Event::Eof => return Err(invalid("no rootfile in META-INF/container.xml")),
+            _ => {}
+        }
+    }
+}
+
+/// Get the paths in the archive of the documents in the spine of the package document at `package_path`.
+fn spine(package: &str, package_path: &str) -> std::io::Result<HashSet<String>> {
+    // Paths of the manifest items, by id
+    let mut items = HashMap::new();
+    // Ids of the items in the spine
+    let mut itemrefs = Vec::new();
+    let mut reader = Reader::from_str(package);
+    loop {
+        match reader.read_event().map_err(invalid)? {
+            Event::Start(element) | Event::Empty(element) => match element.local_name().as_ref() {
+                "item" => {
+                    if let (Some(id), Some(href)) = (attribute(&element, "id")?, attribute(&element, "href")?) {
+                        items.insert(id, href);
+                    }
+                }
+                "itemref" => itemrefs.extend(attribute(&element, "idref")?),
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    // Hrefs are relative to the package document
+    let base = package_path.rsplit_once('/').map_or("", |(base, _)| base);
+    Ok(itemrefs
+        .iter()
+        .filter_map(|id| items.get(id))
+        .map(|href| resolve(base, href))
+        .collect())
+}
+
+/// Resolve `href` relative to the directory `base`, giving a path in the archive.
+fn resolve(base: &str, href: &str) -> String {
+    let href = href.split('#').next().unwrap_or_default();
+    let mut segments: Vec<_> = base.split('/').filter(|segment| !segment.is_empty()).collect();
+    for segment in href.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    percent_decode(&segments.join("/"))
+}
+
+/// Decode percent-encoded bytes in `path`, leaving invalid sequences as they are.
+fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes.get(i + 1..i + 3).and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match (bytes[i], escaped) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Get the unescaped value of the attribute `name` of `element`, if present.
+fn attribute(element: &BytesStart, name: &str) -> std::io::Result<Option<String>> {
+    match element.try_get_attribute(name).map_err(invalid)? {
+        Some(attribute) => Ok(Some(attribute.normalized_value(XmlVersion::Implicit1_0).map_err(invalid)?.into_owned())),
+        None => Ok(None),
+    }
+}
+
+/// Create an error for an invalid EPUB.
+fn invalid(reason: impl Display) -> Error {
+    crate::Error::format(format!("Invalid EPUB: {reason}")).into()
+}
@@ -0,0 +1,178 @@
+//! Errors returned when configuring a [`BioReader`](crate::BioReader) or bio-reading.
+
+use std::{
+    fmt,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+/// An error returned by this crate.
+///
+/// Errors of input and output carry the path of the file they happened on, when known, which is added with [`Error::with_path`]. They convert to and from [`std::io::Error`], so that they can go through readers and writers and come back intact:
+///
+/// ```rust
+/// use bio_read::{BioReader, Error, InputFormat};
+/// let reader = BioReader::new().format(InputFormat::Markdown);
+/// let error = reader.bio_read(&b"\xff"[..], &mut std::io::sink()).unwrap_err();
+/// assert!(matches!(error, Error::Encoding { .. }));
+/// let error = std::io::Error::from(error.with_path("notes.md"));
+/// assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+/// assert_eq!(Error::from(error).to_string(), "notes.md: Markdown input is not valid UTF-8");
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The fixation point is out of range.
+    FixationPoint(FixationPointError),
+    /// An option of the configuration is invalid.
+    Config {
+        /// The name of the option, as in config files, like `saccade`.
+        option: &'static str,
+        /// What is wrong with it.
+        message: String,
+    },
+    /// The input is not valid in the encoding its format requires, like UTF-8.
+    Encoding {
+        /// The file the input was read from, if known.
+        path: Option<PathBuf>,
+        /// What is wrong with it.
+        message: String,
+    },
+    /// The input cannot be parsed as its format, like a corrupt document.
+    Format {
+        /// The file the input was read from, if known.
+        path: Option<PathBuf>,
+        /// What is wrong with it.
+        message: String,
+    },
+    /// Reading or writing failed.
+    Io {
+        /// The file being read or written, if known.
+        path: Option<PathBuf>,
+        /// The underlying error.
+        source: std::io::Error,
+    },
+}
+
+impl Error {
+    /// An error for the invalid `option` of the configuration.
+    pub(crate) fn config(option: &'static str, message: impl Into<String>) -> Self {
+        Self::Config { option, message: message.into() }
+    }
+    /// An error for input that is not valid in the encoding of its format.
+    pub(crate) fn encoding(message: impl Into<String>) -> Self {
+        Self::Encoding { path: None, message: message.into() }
+    }
+    /// An error for input that cannot be parsed as its format.
+    pub(crate) fn format(message: impl Into<String>) -> Self {
+        Self::Format { path: None, message: message.into() }
+    }
+    /// Set the file that the input or output of the error is, unless it is known already. Other errors are returned as-is.
+    pub fn with_path(mut self, file: impl AsRef<Path>) -> Self {
+        if let Self::Encoding { path, .. } | Self::Format { path, .. } | Self::Io { path, .. } = &mut self {
+            path.get_or_insert_with(|| file.as_ref().to_path_buf());
+        }
+        self
+    }
+    /// Get the file that the input or output of the error is, if known.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::Encoding { path, .. } | Self::Format { path, .. } | Self::Io { path, .. } => path.as_deref(),
+            _ => None,
+        }
+    }
+    /// Get the name of the invalid option of the configuration, as in config files, if the error is about one.
+    pub fn option(&self) -> Option<&str> {
+        match self {
+            Self::FixationPoint(_) => Some("fixation-point"),
+            Self::Config { option, .. } => Some(option),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(path) = self.path() {
+            write!(f, "{}: ", path.display())?;
+        }
+        match self {
+            Self::FixationPoint(error) => error.fmt(f),
+            Self::Config { message, .. } | Self::Encoding { message, .. } | Self::Format { message, .. } => f.write_str(message),
+            Self::Io { source, .. } => source.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::FixationPoint(error) => Some(error),
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<FixationPointError> for Error {
+    fn from(error: FixationPointError) -> Self {
+        Self::FixationPoint(error)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    /// Convert an I/O error, unwrapping the [`Error`] it carries if it was converted from one.
+    fn from(error: std::io::Error) -> Self {
+        if error.get_ref().is_some_and(|inner| inner.is::<Self>()) {
+            let inner = error.into_inner().expect("Error should have an inner error");
+            return *inner.downcast::<Self>().expect("Inner error should be an Error");
+        }
+        Self::Io { path: None, source: error }
+    }
+}
+
+impl From<Error> for std::io::Error {
+    /// Convert an error into an I/O error of the matching kind, carrying it. I/O errors without a path are unwrapped.
+    fn from(error: Error) -> Self {
+        let kind = match &error {
+            Error::Io { path: None, .. } => {
+                let Error::Io { source, .. } = error else { unreachable!() };
+                return source;
+            }
+            Error::Io { source, .. } => source.kind(),
+            Error::FixationPoint(_) | Error::Config { .. } => ErrorKind::InvalidInput,
+            Error::Encoding { .. } | Error::Format { .. } => ErrorKind::InvalidData,
+        };
+        Self::new(kind, error)
+    }
+}
+
+/// The error returned by [`BioReader::try_fixation_point`](crate::BioReader::try_fixation_point) when the fixation point is not in range \[1, 5\].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FixationPointError {
+    /// The rejected fixation point.
+    fixation_point: usize,
+}
+
+impl FixationPointError {
+    /// Check that `fixation_point` is in range \[1, 5\].
+    pub(crate) fn check(fixation_point: usize) -> Result<(), Self> {
+        if (1..=5).contains(&fixation_point) {
+            Ok(())
+        } else {
+            Err(Self { fixation_point })
+        }
+    }
+    /// Get the rejected fixation point.
+    pub fn fixation_point(&self) -> usize {
+        self.fixation_point
+    }
+}
+
+impl fmt::Display for FixationPointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fixation point should be in range [1, 5], but got {}", self.fixation_point)
+    }
+}
+
+impl std::error::Error for FixationPointError {}
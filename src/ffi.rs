@@ -0,0 +1,162 @@
+//! C bindings, for embedding the engine in applications written in C or C++. Requires the `capi` feature.
+//!
+//! The header is at `include/bio_read.h`, and can be regenerated with `cbindgen --config cbindgen.toml --output include/bio_read.h`.
+//!
+//! ```c
+//! #include "bio_read.h"
+//!
+//! BioReader *reader = br_new();
+//! br_set_fixation(reader, 2);
+//! size_t output_len;
+//! char *output = br_process_utf8(reader, "hello world", 11, &output_len);
+//! // output is "<b>hel</b>lo <b>wor</b>ld"
+//! br_string_free(output, output_len);
+//! br_free(reader);
+//! ```
+
+use crate::{BioReader, InputFormat, OutputFormat};
+use std::{
+    ffi::{c_char, c_int, CStr},
+    ptr,
+};
+
+/// Create a new [`BioReader`], writing HTML. Free it with [`br_free`].
+#[no_mangle]
+pub extern "C" fn br_new() -> *mut BioReader {
+    let reader = BioReader::new().renderer(OutputFormat::Html.renderer());
+    Box::into_raw(Box::new(reader))
+}
+
+/// Set the fixation point of `reader`, in range \[1, 5\]. Returns 0 on success, or -1 if `reader` is null or `fixation_point` is out of range.
+///
+/// # Safety
+///
+/// `reader` must be null or a pointer returned by [`br_new`] that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn br_set_fixation(reader: *mut BioReader, fixation_point: usize) -> c_int {
+    if !(1..=5).contains(&fixation_point) {
+        return -1;
+    }
+    // SAFETY: Guaranteed by the caller
+    match unsafe { reader.as_mut() } {
+        Some(reader) => {
+            *reader = std::mem::take(reader).fixation_point(fixation_point);
+            0
+        }
+        None => -1,
+    }
+}
+
+/// Set the format of the input of `reader`, which is one of `"auto"`, `"plain"`, `"markdown"`, `"html"`, `"latex"`, `"rst"`, `"asciidoc"`, `"org"`, `"man"`, `"diff"`, `"email"`, `"subtitles"`, or `"code:"` followed by a programming language, like `"code:rust"`. Returns 0 on success, or -1 if any argument is null or the format is unknown.
+///
+/// # Safety
+///
+/// `reader` must be null or a pointer returned by [`br_new`] that has not been freed, and `format` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn br_set_format(reader: *mut BioReader, format: *const c_char) -> c_int {
+    // SAFETY: Guaranteed by the caller
+    let Some(format) = (unsafe { parse::<InputFormat>(format) }) else {
+        return -1;
+    };
+    // SAFETY: Guaranteed by the caller
+    match unsafe { reader.as_mut() } {
+        Some(reader) => {
+            *reader = std::mem::take(reader).format(format);
+            0
+        }
+        None => -1,
+    }
+}
+
+/// Set the format of the output of `reader`, which is one of `"ansi"`, `"html"`, `"markdown"`, `"bbcode"`, `"latex"`, `"typst"`, `"rst"`, `"org"`, `"rtf"`, `"plain"` or `"json"`. Returns 0 on success, or -1 if any argument is null or the format is unknown.
+///
+/// # Safety
+///
+/// `reader` must be null or a pointer returned by [`br_new`] that has not been freed, and `format` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn br_set_output(reader: *mut BioReader, format: *const c_char) -> c_int {
+    // SAFETY: Guaranteed by the caller
+    let Some(format) = (unsafe { parse::<OutputFormat>(format) }) else {
+        return -1;
+    };
+    // SAFETY: Guaranteed by the caller
+    match unsafe { reader.as_mut() } {
+        Some(reader) => {
+            *reader = std::mem::take(reader).renderer(format.renderer());
+            0
+        }
+        None => -1,
+    }
+}
+
+/// Do bio-reading on `len` bytes of UTF-8 `text`, which needs not be NUL-terminated. Invalid UTF-8 is passed through as-is.
+///
+/// Returns the output, NUL-terminated for convenience, with its length excluding the terminator written to `output_len`. Free it with [`br_string_free`]. Returns null if any pointer is null, or processing fails.
+///
+/// # Safety
+///
+/// `reader` must be null or a pointer returned by [`br_new`] that has not been freed, `text` must be null or valid for reading `len` bytes, and `output_len` must be null or valid for writing.
+#[no_mangle]
+pub unsafe extern "C" fn br_process_utf8(
+    reader: *const BioReader,
+    text: *const c_char,
+    len: usize,
+    output_len: *mut usize,
+) -> *mut c_char {
+    if text.is_null() || output_len.is_null() {
+        return ptr::null_mut();
+    }
+    // SAFETY: Guaranteed by the caller
+    let Some(reader) = (unsafe { reader.as_ref() }) else {
+        return ptr::null_mut();
+    };
+    // SAFETY: Guaranteed by the caller
+    let text = unsafe { std::slice::from_raw_parts(text.cast::<u8>(), len) };
+    let mut output = Vec::with_capacity(len + len / 2 + 1);
+    if reader.bio_read(text, &mut output).is_err() {
+        return ptr::null_mut();
+    }
+    // SAFETY: Guaranteed by the caller
+    unsafe { output_len.write(output.len()) };
+    output.push(0);
+    Box::into_raw(output.into_boxed_slice()).cast::<c_char>()
+}
+
+/// Free an output returned by [`br_process_utf8`], given its length excluding the terminator. Does nothing if `output` is null.
+///
+/// # Safety
+///
+/// `output` must be null or a pointer returned by [`br_process_utf8`] that has not been freed, and `output_len` must be the length written along with it.
+#[no_mangle]
+pub unsafe extern "C" fn br_string_free(output: *mut c_char, output_len: usize) {
+    if !output.is_null() {
+        // SAFETY: Guaranteed by the caller, with the terminator counted
+        drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(output.cast::<u8>(), output_len + 1)) });
+    }
+}
+
+/// Free a [`BioReader`] created by [`br_new`]. Does nothing if `reader` is null.
+///
+/// # Safety
+///
+/// `reader` must be null or a pointer returned by [`br_new`] that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn br_free(reader: *mut BioReader) {
+    if !reader.is_null() {
+        // SAFETY: Guaranteed by the caller
+        drop(unsafe { Box::from_raw(reader) });
+    }
+}
+
+/// Parse a NUL-terminated string, returning `None` if it is null, not UTF-8 or invalid.
+///
+/// # Safety
+///
+/// `s` must be null or a NUL-terminated string.
+unsafe fn parse<T: std::str::FromStr>(s: *const c_char) -> Option<T> {
+    if s.is_null() {
+        return None;
+    }
+    // SAFETY: Guaranteed by the caller
+    unsafe { CStr::from_ptr(s) }.to_str().ok()?.parse().ok()
+}
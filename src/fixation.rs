@@ -0,0 +1,255 @@
+//! Fixation strategies, determining how much of each word is emphasized.
+//!
+//! By default, a [`BioReader`](crate::BioReader) uses the fixation point set with [`BioReader::fixation_point`](crate::BioReader::fixation_point), which looks up how many letters to emphasize in a table of word lengths. Set a [`FixationStrategy`] with [`BioReader::fixation_strategy`](crate::BioReader::fixation_strategy) to experiment with other approaches.
+//!
+//! Built-in strategies include:
+//!
+//! - [`Ratio`]: A fixed fraction of each word.
+//! - [`Frequency`]: A fraction depending on how common each word is, according to a word-frequency list.
+//! - `Syllable`: The first syllable of each word, with the `syllable` feature.
+
+use crate::Error;
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+/// Determines how many letters of each word are emphasized.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{fixation::{letters, FixationStrategy}, BioReader};
+///
+/// /// Emphasize the first half of each word, rounded down
+/// struct Half;
+///
+/// impl FixationStrategy for Half {
+///     fn emphasized_len(&self, word: &str) -> usize {
+///         letters(word) / 2
+///     }
+/// }
+///
+/// let reader = BioReader::new()
+///     .emphasize(String::from("<em>"), String::from("</em>"))
+///     .de_emphasize(String::from(""), String::from(""))
+///     .fixation_strategy(Half);
+/// assert_eq!(reader.bio_read_text("a bionic reader").unwrap(), "a <em>bio</em>nic <em>rea</em>der");
+/// ```
+pub trait FixationStrategy {
+    /// Get how many letters at the start of `word` to emphasize. Besides letters, `word` may contain combining marks and joiners (see [`WordChars`](crate::segment::WordChars)), which don't count as letters. Values greater than the number of letters are clamped.
+    fn emphasized_len(&self, word: &str) -> usize;
+}
+
+impl<S: FixationStrategy + ?Sized> FixationStrategy for Box<S> {
+    fn emphasized_len(&self, word: &str) -> usize {
+        (**self).emphasized_len(word)
+    }
+}
+
+/// Count the letters in a word given to [`FixationStrategy::emphasized_len`], which are its characters other than combining marks and joiners.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::fixation::letters;
+/// assert_eq!(letters("don't"), 4);
+/// assert_eq!(letters("cafe\u{301}"), 4);
+/// ```
+pub fn letters(word: &str) -> usize {
+    word.chars().filter(|c| c.is_alphabetic() && !crate::is_mark(*c)).count()
+}
+
+/// Emphasizes a fixed fraction of the letters of each word, rounded up. See [`BioReader::fixation_ratio`](crate::BioReader::fixation_ratio).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ratio(f32);
+
+impl Ratio {
+    /// Create a strategy emphasizing `ratio` of each word, rounded up.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is not in range \[0, 1\].
+    pub fn new(ratio: f32) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&ratio),
+            "Fixation ratio should be in range [0, 1], but got {}",
+            ratio
+        );
+        Self(ratio)
+    }
+    /// Get the ratio.
+    pub fn ratio(self) -> f32 {
+        self.0
+    }
+}
+
+impl FixationStrategy for Ratio {
+    fn emphasized_len(&self, word: &str) -> usize {
+        let scaled = self.0 * letters(word) as f32;
+        let nearest = scaled.round();
+        // Don't round up because of floating point errors, e.g. 0.3 * 10 being slightly more than 3
+        if (scaled - nearest).abs() < 1e-4 {
+            nearest as usize
+        } else {
+            scaled.ceil() as usize
+        }
+    }
+}
+
+/// Emphasizes rare words more than common ones, according to a word-frequency list. See [`BioReader::frequency_model`](crate::BioReader::frequency_model).
+///
+/// The list has a word and how often it occurs per line, separated by whitespace, like `the 23135851162`. Blank lines and lines starting with `#` are ignored. Words are matched case-insensitively, with counts of the same word in different cases added up.
+///
+/// How common a word is, from 0 for words missing from the list to 1 for the most common one, is measured on a logarithmic scale of its count. The fraction of the letters of a word that are emphasized, rounded up, goes from the rare ratio for the rarest words down to the common ratio for the most common one. Words with letters always get at least one letter emphasized.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{fixation::{FixationStrategy, Frequency}, BioReader};
+/// let model = Frequency::from_reader("the 1000000\nreading 1000\n".as_bytes()).unwrap();
+/// assert_eq!(model.emphasized_len("the"), 1);
+/// assert_eq!(model.emphasized_len("reading"), 4);
+/// assert_eq!(model.emphasized_len("ravioli"), 5);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frequency {
+    /// How common each word in lowercase is, in range \[0, 1\].
+    commonness: HashMap<String, f32>,
+    /// The fraction of the most common word to emphasize.
+    common_ratio: f32,
+    /// The fraction of words missing from the list to emphasize.
+    rare_ratio: f32,
+}
+
+impl Frequency {
+    /// The default fraction of the most common word to emphasize.
+    pub const COMMON_RATIO: f32 = 0.3;
+    /// The default fraction of words missing from the list to emphasize.
+    pub const RARE_RATIO: f32 = 0.7;
+
+    /// Load the word-frequency list at `path`. Errors carry the path.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        File::open(path).map_err(Error::from).and_then(|file| Self::from_reader(BufReader::new(file))).map_err(|e| e.with_path(path))
+    }
+    /// Read a word-frequency list from `reader`. Fails with [`Error::Format`] if a line has no valid count after its word.
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, Error> {
+        let mut counts: HashMap<String, u64> = HashMap::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            let (Some(word), Some(count), None) = (fields.next(), fields.next(), fields.next()) else {
+                return Err(Error::format(format!("Expected a word and its count on line {}", i + 1)));
+            };
+            let count: u64 = count.parse().map_err(|_| Error::format(format!("Invalid count on line {}: {count}", i + 1)))?;
+            *counts.entry(word.to_lowercase()).or_default() += count;
+        }
+        // Words occurring once are as rare as missing ones
+        let max = counts.values().copied().max().unwrap_or(0).max(1) as f32;
+        let commonness = counts
+            .into_iter()
+            .map(|(word, count)| {
+                let commonness = if max > 1.0 { (count.max(1) as f32).ln() / max.ln() } else { 0.0 };
+                (word, commonness)
+            })
+            .collect();
+        Ok(Self {
+            commonness,
+            common_ratio: Self::COMMON_RATIO,
+            rare_ratio: Self::RARE_RATIO,
+        })
+    }
+    /// Set the fractions of the most common word and of words missing from the list to emphasize, defaulting to [`Frequency::COMMON_RATIO`] and [`Frequency::RARE_RATIO`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if either ratio is not in range \[0, 1\].
+    pub fn ratios(mut self, common: f32, rare: f32) -> Self {
+        for ratio in [common, rare] {
+            assert!((0.0..=1.0).contains(&ratio), "Fixation ratio should be in range [0, 1], but got {}", ratio);
+        }
+        self.common_ratio = common;
+        self.rare_ratio = rare;
+        self
+    }
+    /// Get how common `word` is, in range \[0, 1\], or 0 if it is missing from the list.
+    pub fn commonness(&self, word: &str) -> f32 {
+        self.commonness.get(&word.to_lowercase()).copied().unwrap_or(0.0)
+    }
+}
+
+impl FixationStrategy for Frequency {
+    fn emphasized_len(&self, word: &str) -> usize {
+        let letters = letters(word);
+        let ratio = self.rare_ratio + (self.common_ratio - self.rare_ratio) * self.commonness(word);
+        if letters == 0 {
+            0
+        } else {
+            Ratio(ratio).emphasized_len(word).max(1)
+        }
+    }
+}
+
+/// Emphasizes the first syllable of each word, like "read" in "reading", using hyphenation patterns of a language. Words of a single syllable get their first half emphasized, rounded up, instead. Requires the `syllable` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{fixation::Syllable, BioReader};
+/// let reader = BioReader::new()
+///     .emphasize(String::from("<em>"), String::from("</em>"))
+///     .de_emphasize(String::from(""), String::from(""))
+///     .fixation_strategy(Syllable::default());
+/// assert_eq!(reader.bio_read_text("reading is wonderful").unwrap(), "<em>read</em>ing <em>i</em>s <em>won</em>derful");
+/// ```
+#[cfg(feature = "syllable")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Syllable {
+    /// The language of the hyphenation patterns.
+    lang: hypher::Lang,
+}
+
+#[cfg(feature = "syllable")]
+impl Syllable {
+    /// Create a strategy for the language with ISO 639-1 code `code`, like `en` or `de`, if hyphenation patterns are available for it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::fixation::{FixationStrategy, Syllable};
+    /// let german = Syllable::with_language("de").unwrap();
+    /// assert_eq!(german.emphasized_len("Lesefluss"), 2);
+    /// assert!(Syllable::with_language("xx").is_none());
+    /// ```
+    pub fn with_language(code: &str) -> Option<Self> {
+        let code: [u8; 2] = code.to_ascii_lowercase().as_bytes().try_into().ok()?;
+        hypher::Lang::from_iso(code).map(|lang| Self { lang })
+    }
+}
+
+/// English.
+#[cfg(feature = "syllable")]
+impl Default for Syllable {
+    fn default() -> Self {
+        Self { lang: hypher::Lang::English }
+    }
+}
+
+#[cfg(feature = "syllable")]
+impl FixationStrategy for Syllable {
+    fn emphasized_len(&self, word: &str) -> usize {
+        // Break anywhere a syllable ends, unlike typesetting, which keeps a few letters on each side
+        let mut syllables = hypher::hyphenate_bounded(word, self.lang, 1, 1);
+        match (syllables.next(), syllables.next()) {
+            (Some(first), Some(_)) => letters(first),
+            _ => letters(word).div_ceil(2),
+        }
+    }
+}
@@ -0,0 +1,403 @@
+//! Input formats supported by [`BioReader`].
+
+pub(crate) mod asciidoc;
+pub(crate) mod code;
+pub(crate) mod diff;
+pub(crate) mod email;
+#[cfg(feature = "docx")]
+pub(crate) mod docx;
+pub(crate) mod html;
+pub(crate) mod latex;
+pub(crate) mod man;
+pub(crate) mod markdown;
+pub(crate) mod org;
+pub(crate) mod reflow;
+pub(crate) mod rst;
+pub(crate) mod subtitles;
+
+use crate::{BioReader, State};
+use regex::Regex;
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, io::Write, ops::Range, path::Path, str::FromStr, sync::LazyLock};
+
+/// The format of the input, determining which parts of it are bio-read. Named like `markdown` or `code:rust`, when parsed or (de)serialized.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum InputFormat {
+    /// Detect the format from the start of the input, with [`InputFormat::sniff`]. Output is held back until [`InputFormat::SNIFF_LEN`] bytes, a whole line with [`BioReader::bio_read_lines`], or the end of the input has been read.
+    Auto,
+    /// Plain text. Every word is bio-read, while terminal escape sequences of operating system commands, like OSC 8 hyperlinks, are passed through untouched.
+    #[default]
+    Plain,
+    /// Markdown. Only prose is bio-read, leaving code blocks, inline code, link destinations, HTML and existing markup intact.
+    Markdown,
+    /// HTML. Only text nodes are bio-read, leaving tags, comments, character references and the content of `<script>`, `<style>`, `<pre>`, `<code>` and similar elements intact.
+    Html,
+    /// LaTeX. Only prose is bio-read, leaving commands, comments, math, the preamble, the arguments of commands like `\label` and `\cite`, and the content of environments like `equation` and `verbatim` intact.
+    Latex,
+    /// reStructuredText. Only prose is bio-read, leaving directives other than admonitions, comments, targets, literal blocks, field names, and inline markup like literals, roles, references and substitutions intact.
+    Rst,
+    /// AsciiDoc. Only prose is bio-read, leaving listing, literal, passthrough and comment blocks, attributes, macros, and inline markup like monospace, passthroughs, cross references and attribute references intact.
+    AsciiDoc,
+    /// Org, as used by Emacs. Only prose is bio-read, leaving headline stars, keywords, priorities and tags, blocks other than quotes and verses, drawers, tables, keywords, and inline markup like links, bold text, verbatim, code and timestamps intact.
+    Org,
+    /// Terminal output of `man` or `nroff`, like `man ls | br`, where bold and underlined text is written by overstriking characters with backspaces. Overstriking is removed, like `col -b` does, and bold text is emphasized as a whole instead of being bio-read.
+    Man,
+    /// A unified diff, like the output of `git diff` or `diff -u`. Only the content of changed lines is bio-read, leaving headers, context lines and the `+` and `-` markers intact. Colors, like those of `git diff --color`, are kept for the markers, and removed from the content of changed lines.
+    Diff,
+    /// An email message, or an mbox file of them, like those written by `mu` and `notmuch`. Only plain text and HTML bodies are bio-read, decoding quoted-printable and base64 first, and leaving headers, attachments and bodies in character sets other than UTF-8 and US-ASCII intact.
+    Email,
+    /// Subtitles in SubRip (SRT) or WebVTT format. Only the text of cues is bio-read, leaving numbers, identifiers, timings, cue settings, headers, notes and inline markup like `<i>` tags intact.
+    Subtitles,
+    /// Source code, named like `code:rust`, and detected from the extension of files like `main.rs`. Only comments and string literals are bio-read, leaving identifiers, keywords and other syntax, character literals, inline code in comments, and escape sequences and placeholders in string literals intact.
+    Code(CodeLanguage),
+    /// A Word document. The text of each paragraph is bio-read, making the emphasized part of each word bold, and the document is written back out with the renderer unused. Only the main document is processed, leaving headers, footers and notes intact. Requires the `docx` feature.
+    #[cfg(feature = "docx")]
+    Docx,
+}
+
+/// A programming language, whose comments and string literals are bio-read with [`InputFormat::Code`].
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{CodeLanguage, InputFormat};
+/// assert_eq!("code:rust".parse(), Ok(InputFormat::Code(CodeLanguage::Rust)));
+/// assert_eq!("py".parse(), Ok(CodeLanguage::Python));
+/// assert_eq!(CodeLanguage::Cpp.to_string(), "cpp");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CodeLanguage {
+    /// Rust, with `//` and `/* */` comments, and raw strings like `r#"..."#`.
+    Rust,
+    /// C, with `//` and `/* */` comments.
+    C,
+    /// C++, with `//` and `/* */` comments.
+    Cpp,
+    /// C#, with `//` and `/* */` comments, and `"""` raw strings.
+    CSharp,
+    /// Go, with `//` and `/* */` comments, and `` ` `` raw strings.
+    Go,
+    /// Java, with `//` and `/* */` comments, and `"""` text blocks.
+    Java,
+    /// Kotlin, with `//` and `/* */` comments, and `"""` raw strings.
+    Kotlin,
+    /// Scala, with `//` and `/* */` comments, and `"""` multi-line strings.
+    Scala,
+    /// Swift, with `//` and `/* */` comments, and `"""` multi-line strings.
+    Swift,
+    /// JavaScript, with `//` and `/* */` comments, and `'`, `"` and `` ` `` strings.
+    JavaScript,
+    /// TypeScript, with `//` and `/* */` comments, and `'`, `"` and `` ` `` strings.
+    TypeScript,
+    /// Python, with `#` comments, and `'`, `"` and triple-quoted strings, like docstrings.
+    Python,
+    /// Ruby, with `#` comments, and `'` and `"` strings.
+    Ruby,
+    /// Shell scripts, with `#` comments, and `'` and `"` strings.
+    Shell,
+    /// Haskell, with `--` and `{- -}` comments.
+    Haskell,
+    /// Lua, with `--` and `--[[ ]]` comments, and `'` and `"` strings.
+    Lua,
+    /// SQL, with `--` and `/* */` comments, and `'` strings.
+    Sql,
+}
+
+impl FromStr for CodeLanguage {
+    type Err = String;
+
+    /// Parse the name of a language, like `rust`, or the extension of its files, like `rs`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "rust" | "rs" => Ok(Self::Rust),
+            "c" | "h" => Ok(Self::C),
+            "cpp" | "c++" | "cc" | "cxx" | "hpp" | "hh" | "hxx" => Ok(Self::Cpp),
+            "csharp" | "c#" | "cs" => Ok(Self::CSharp),
+            "go" => Ok(Self::Go),
+            "java" => Ok(Self::Java),
+            "kotlin" | "kt" | "kts" => Ok(Self::Kotlin),
+            "scala" | "sc" => Ok(Self::Scala),
+            "swift" => Ok(Self::Swift),
+            "javascript" | "js" | "mjs" | "cjs" | "jsx" => Ok(Self::JavaScript),
+            "typescript" | "ts" | "mts" | "cts" | "tsx" => Ok(Self::TypeScript),
+            "python" | "py" | "pyi" => Ok(Self::Python),
+            "ruby" | "rb" => Ok(Self::Ruby),
+            "shell" | "sh" | "bash" | "zsh" => Ok(Self::Shell),
+            "haskell" | "hs" => Ok(Self::Haskell),
+            "lua" => Ok(Self::Lua),
+            "sql" => Ok(Self::Sql),
+            _ => Err(format!("Unknown programming language: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for CodeLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Rust => "rust",
+            Self::C => "c",
+            Self::Cpp => "cpp",
+            Self::CSharp => "csharp",
+            Self::Go => "go",
+            Self::Java => "java",
+            Self::Kotlin => "kotlin",
+            Self::Scala => "scala",
+            Self::Swift => "swift",
+            Self::JavaScript => "javascript",
+            Self::TypeScript => "typescript",
+            Self::Python => "python",
+            Self::Ruby => "ruby",
+            Self::Shell => "shell",
+            Self::Haskell => "haskell",
+            Self::Lua => "lua",
+            Self::Sql => "sql",
+        })
+    }
+}
+
+/// Matches characters overstruck with backspaces.
+static OVERSTRIKE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[^\x08]\x08[^\x08]").unwrap());
+
+/// Matches the headers of unified diffs, possibly colored.
+static DIFF: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^(?:\x1b\[[0-9;]*m)*(?:diff --git |--- \S.*\n(?:\x1b\[[0-9;]*m)*\+\+\+ \S|@@ -\d+(?:,\d+)? \+\d+(?:,\d+)? @@)").unwrap());
+
+/// Matches the start of WebVTT files, and the first cue of SubRip files.
+static SUBTITLES: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\u{feff}?(?:WEBVTT(?:[ \t]|\r?\n|$)|\s*\d+\r?\n\d{2}:\d{2}:\d{2}[,.]\d{3} --> )").unwrap());
+
+/// Matches the start of email messages and mbox files, which begin with headers.
+static EMAIL_START: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(?:From \S.*\r?\n)?[A-Za-z][\w-]*: ").unwrap());
+
+/// Matches headers that only email messages have.
+static EMAIL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?mi)^(?:message-id|mime-version|received|return-path|delivered-to): ").unwrap());
+
+/// Matches the start of HTML documents and common block-level tags.
+static HTML: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)^\s*(?:<!doctype\s+html|<html|<\?xml[^>]*>\s*<!doctype\s+html)|<(?:head|body|p|div|table|ul|article)[\s>]").unwrap());
+
+/// Matches LaTeX document structure and sectioning commands.
+static LATEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^\s*\\(?:documentclass|begin\{document\}|(?:sub)*section\*?\{|chapter\*?\{)").unwrap());
+
+/// Matches reStructuredText directives and targets.
+static RST: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^\.\. (?:[\w:-]+::|_[^:]+:)").unwrap());
+
+/// Matches AsciiDoc document titles followed by attribute entries, and block delimiters with attributes.
+static ASCIIDOC: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^= \S.*\n:[\w-]+:|^\[(?:source|NOTE|TIP|WARNING)[^\]]*\]\n(?:----|====)$|^:toc:").unwrap());
+
+/// Matches Org keywords, blocks and drawers.
+static ORG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?mi)^#\+(?:title|author|startup|options|begin_src)[:\s]|^\*+ \S.*\n\s*(?::properties:|scheduled:|deadline:)").unwrap());
+
+/// Matches Markdown headings, fenced code blocks and links.
+static MARKDOWN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^#{1,6}\s+\S|^(?:```|~~~)|\[[^\]\n]+\]\([^)\s]+\)").unwrap());
+
+impl InputFormat {
+    /// How many bytes at the start of the input [`InputFormat::Auto`] looks at.
+    pub const SNIFF_LEN: usize = 1024;
+
+    /// Get the format of the file at `path` from its extension, if it names one, like `md` or `html`, or a programming language, like `rs`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{CodeLanguage, InputFormat};
+    /// use std::path::Path;
+    /// assert_eq!(InputFormat::from_path(Path::new("README.md")), Some(InputFormat::Markdown));
+    /// assert_eq!(InputFormat::from_path(Path::new("src/main.rs")), Some(InputFormat::Code(CodeLanguage::Rust)));
+    /// assert_eq!(InputFormat::from_path(Path::new("notes")), None);
+    /// ```
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?;
+        match extension.parse() {
+            Ok(Self::Auto) => None,
+            Ok(format) => Some(format),
+            Err(_) => extension.parse().ok().map(Self::Code),
+        }
+    }
+    /// Guess the format of the input from `head`, its first bytes, falling back to [`InputFormat::Plain`]. Never returns [`InputFormat::Auto`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::InputFormat;
+    /// assert_eq!(InputFormat::sniff(b"<!DOCTYPE html>\n<p>Hello</p>"), InputFormat::Html);
+    /// assert_eq!(InputFormat::sniff(b"# Title\n\nSome text"), InputFormat::Markdown);
+    /// assert_eq!(InputFormat::sniff(b"N\x08NA\x08AM\x08ME\x08E\n     ls"), InputFormat::Man);
+    /// assert_eq!(InputFormat::sniff(b"diff --git a/README.md b/README.md\n"), InputFormat::Diff);
+    /// assert_eq!(InputFormat::sniff(b"Just some text"), InputFormat::Plain);
+    /// ```
+    pub fn sniff(head: &[u8]) -> Self {
+        #[cfg(feature = "docx")]
+        if head.starts_with(b"PK\x03\x04") && head.windows(5).any(|window| window == b"word/") {
+            return Self::Docx;
+        }
+        // Invalid sequences, like a multi-byte character cut off at the end, are replaced
+        let head = String::from_utf8_lossy(head);
+        if OVERSTRIKE.is_match(&head) {
+            Self::Man
+        } else if DIFF.is_match(&head) {
+            Self::Diff
+        } else if SUBTITLES.is_match(&head) {
+            Self::Subtitles
+        } else if EMAIL_START.is_match(&head) && EMAIL.is_match(&head) {
+            Self::Email
+        } else if HTML.is_match(&head) {
+            Self::Html
+        } else if LATEX.is_match(&head) {
+            Self::Latex
+        } else if RST.is_match(&head) {
+            Self::Rst
+        } else if ASCIIDOC.is_match(&head) {
+            Self::AsciiDoc
+        } else if ORG.is_match(&head) {
+            Self::Org
+        } else if MARKDOWN.is_match(&head) {
+            Self::Markdown
+        } else {
+            Self::Plain
+        }
+    }
+}
+
+impl FromStr for InputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "plain" | "text" | "txt" => Ok(Self::Plain),
+            "markdown" | "md" => Ok(Self::Markdown),
+            "html" | "htm" | "xhtml" => Ok(Self::Html),
+            "latex" | "tex" => Ok(Self::Latex),
+            "rst" | "rest" | "restructuredtext" => Ok(Self::Rst),
+            "asciidoc" | "adoc" => Ok(Self::AsciiDoc),
+            "org" => Ok(Self::Org),
+            "man" => Ok(Self::Man),
+            "diff" | "patch" => Ok(Self::Diff),
+            "email" | "eml" | "mbox" => Ok(Self::Email),
+            "subtitles" | "srt" | "vtt" | "webvtt" => Ok(Self::Subtitles),
+            #[cfg(feature = "docx")]
+            "docx" => Ok(Self::Docx),
+            format => match format.split_once(':') {
+                Some(("code", language)) => language.parse().map(Self::Code),
+                _ => Err(format!("Unknown input format: {s}")),
+            },
+        }
+    }
+}
+
+impl fmt::Display for InputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Code(language) => return write!(f, "code:{language}"),
+            Self::Auto => "auto",
+            Self::Plain => "plain",
+            Self::Markdown => "markdown",
+            Self::Html => "html",
+            Self::Latex => "latex",
+            Self::Rst => "rst",
+            Self::AsciiDoc => "asciidoc",
+            Self::Org => "org",
+            Self::Man => "man",
+            Self::Diff => "diff",
+            Self::Email => "email",
+            Self::Subtitles => "subtitles",
+            #[cfg(feature = "docx")]
+            Self::Docx => "docx",
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for InputFormat {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for InputFormat {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Get `input` as text, failing with an [`Error::Encoding`](crate::Error::Encoding) naming the format `name` if it is not valid UTF-8.
+pub(crate) fn utf8<'a>(input: &'a [u8], name: &str) -> std::io::Result<&'a str> {
+    std::str::from_utf8(input).map_err(|_| crate::Error::encoding(format!("{name} input is not valid UTF-8")).into())
+}
+
+/// Bio-read the `prose` byte ranges of `text`, which are sorted and don't overlap, writing the rest of it as-is.
+pub(crate) fn bio_read_prose(bio_reader: &BioReader, text: &str, prose: Vec<Range<usize>>, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+    // How many bytes of `text` have been written
+    let mut written = 0;
+    for range in prose {
+        if written < range.start {
+            bio_reader.push_raw(&text.as_bytes()[written..range.start], writer, state)?;
+        }
+        bio_reader.push_str(&text[range.clone()], writer, state)?;
+        written = range.end;
+    }
+    bio_reader.push_raw(&text.as_bytes()[written..], writer, state)
+}
+
+/// Remove the matches of `markup` from the `prose` byte ranges of `text`, merging adjacent ranges first so that markup can span lines.
+pub(crate) fn without_markup(text: &str, prose: Vec<Range<usize>>, markup: &Regex) -> Vec<Range<usize>> {
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(prose.len());
+    for range in prose {
+        match merged.last_mut() {
+            Some(last) if last.end == range.start => last.end = range.end,
+            _ => merged.push(range),
+        }
+    }
+    let mut ranges = Vec::with_capacity(merged.len());
+    for range in merged {
+        let mut start = range.start;
+        for found in markup.find_iter(&text[range.clone()]) {
+            if range.start + found.start() > start {
+                ranges.push(start..range.start + found.start());
+            }
+            start = range.start + found.end();
+        }
+        if start < range.end {
+            ranges.push(start..range.end);
+        }
+    }
+    ranges
+}
+
+/// A line of text, for line-based formats.
+pub(crate) struct Line<'a> {
+    /// Byte offset of the line in the text.
+    pub(crate) start: usize,
+    /// Byte offset of the end of the line, including its line ending.
+    pub(crate) end: usize,
+    /// The content of the line, without its line ending.
+    pub(crate) content: &'a str,
+}
+
+impl Line<'_> {
+    /// Whether the line is made up of whitespace only.
+    pub(crate) fn is_blank(&self) -> bool {
+        self.content.trim().is_empty()
+    }
+    /// How many bytes of whitespace the line starts with.
+    pub(crate) fn indent(&self) -> usize {
+        self.content.len() - self.content.trim_start().len()
+    }
+}
+
+/// Split `text` into lines.
+pub(crate) fn lines(text: &str) -> Vec<Line<'_>> {
+    let mut start = 0;
+    text.split_inclusive('\n')
+        .map(|line| {
+            let end = start + line.len();
+            let line = Line {
+                start,
+                end,
+                content: line.trim_end_matches(['\n', '\r']),
+            };
+            start = end;
+            line
+        })
+        .collect()
+}
@@ -0,0 +1,97 @@
+//! AsciiDoc input format.
+
+use super::{bio_read_prose, lines, utf8, without_markup};
+use crate::{BioReader, State};
+use regex::Regex;
+use std::{
+    io::Write,
+    sync::LazyLock,
+};
+
+/// Matches lines left untouched: attribute entries, block attribute lists, anchors, block macros like `image::` and `include::`, and single-line comments.
+static VERBATIM_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"^(?::!?[\w-]+!?:(?:\s|$)",
+        r"|\[.*\]\s*$",
+        r"|\w[\w-]*::\S*\[.*\]\s*$",
+        r"|//)",
+    ))
+    .unwrap()
+});
+
+/// Matches block attribute lists that make the following paragraph verbatim, like `[source,rust]`.
+static VERBATIM_STYLE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\[(?:source|listing|literal|pass|stem|latexmath|asciimath)\b").unwrap());
+
+/// Matches admonition labels, like `NOTE:`.
+static ADMONITION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(?:NOTE|TIP|IMPORTANT|WARNING|CAUTION):\s").unwrap());
+
+/// Matches inline markup that is left untouched.
+static MARKUP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        // Monospace and passthroughs
+        r"`[^`\n]+`|\+\+\+.+?\+\+\+|\+\+.+?\+\+|\+[^+\s](?:[^+\n]*[^+\s])?\+",
+        // Strong text, as bold can't be nested
+        r"|\*\*.+?\*\*|\*[^*\s](?:[^*\n]*[^*\s])?\*",
+        // Inline macros and URLs with text, like link:index.html[Home], xref:intro.adoc[] and kbd:[Ctrl+C]
+        r"|\b[a-zA-Z][\w-]*:[^\s\[]*\[[^\]]*\]",
+        // Cross references, anchors and attribute references
+        r"|<<[^>]*>>|\[\[[^\]]*\]\]|\[#[^\]]*\]|\{[\w-]+\}",
+        // Bare URLs
+        r#"|\b[a-zA-Z][a-zA-Z0-9+.-]*://\S*[^\s.,;:!?'")\]}>]"#,
+    ))
+    .unwrap()
+});
+
+/// Do bio-reading on the whole AsciiDoc `input`, only emphasizing prose, and write the result to `writer`.
+///
+/// Listing, literal, passthrough and comment blocks, literal paragraphs, attribute entries, block attributes and macros, admonition labels, and inline markup like strong text, monospace, passthroughs, macros, cross references and attribute references are left untouched.
+pub(crate) fn bio_read(bio_reader: &BioReader, input: &[u8], writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+    let text = utf8(input, "AsciiDoc")?;
+    let lines = lines(text);
+    let mut prose = Vec::new();
+    // Whether the current paragraph is verbatim, like a literal paragraph or one styled as source
+    let mut verbatim = false;
+    // Whether the next paragraph is verbatim
+    let mut verbatim_next = false;
+    // Whether a paragraph is open
+    let mut in_paragraph = false;
+    let mut i = 0;
+    while i < lines.len() {
+        let line = &lines[i];
+        i += 1;
+        if line.is_blank() {
+            in_paragraph = false;
+            verbatim = false;
+            continue;
+        }
+        let content = line.content.trim_end();
+        if let Some(delimiter) = verbatim_delimiter(content) {
+            // Skip to the closing delimiter
+            i += lines[i..].iter().position(|line| line.content.trim_end() == delimiter).map_or(lines.len() - i, |end| end + 1);
+            verbatim_next = false;
+            continue;
+        }
+        if VERBATIM_LINE.is_match(content) {
+            verbatim_next |= VERBATIM_STYLE.is_match(content);
+            continue;
+        }
+        if !in_paragraph {
+            in_paragraph = true;
+            verbatim = std::mem::take(&mut verbatim_next) || line.indent() > 0;
+        }
+        if !verbatim {
+            let start = ADMONITION.find(content).map_or(0, |label| label.end());
+            prose.push(line.start + start..line.end);
+        }
+    }
+    bio_read_prose(bio_reader, text, without_markup(text, prose, &MARKUP), writer, state)
+}
+
+/// Get the delimiter of the verbatim block opened by `line`, if any: listing, literal, passthrough and comment blocks, and fenced code blocks.
+fn verbatim_delimiter(line: &str) -> Option<&str> {
+    if line.starts_with("```") {
+        return Some("```");
+    }
+    let first = line.chars().next()?;
+    (line.len() >= 4 && matches!(first, '-' | '.' | '+' | '/') && line.chars().all(|c| c == first)).then_some(line)
+}
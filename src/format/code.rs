@@ -0,0 +1,209 @@
+//! Source code input format.
+
+use super::{bio_read_prose, utf8, without_markup, CodeLanguage};
+use crate::{BioReader, State};
+use regex::Regex;
+use std::{
+    io::Write,
+    ops::Range,
+    sync::LazyLock,
+};
+
+/// The syntax of the comments and string literals of a language.
+struct Syntax {
+    /// Markers starting comments that run to the end of the line, like `//`.
+    line_comments: &'static [&'static str],
+    /// Markers starting and ending block comments, like `/*` and `*/`.
+    block_comments: &'static [(&'static str, &'static str)],
+    /// Quotes delimiting string literals, longest first, along with whether backslashes escape characters in them.
+    strings: &'static [(&'static str, bool)],
+    /// Whether single quotes delimit character literals, which are left untouched, and are used on their own otherwise, like for Rust lifetimes.
+    char_literals: bool,
+    /// Whether line comments only start at the start of a word, like `#` in shell scripts, but not in `$#`.
+    word_comments: bool,
+    /// Whether there are raw string literals like `r#"..."#`, in which backslashes and quotes are literal.
+    raw_strings: bool,
+}
+
+/// C and the languages that took after it.
+const C: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comments: &[("/*", "*/")],
+    strings: &[("\"", true)],
+    char_literals: true,
+    word_comments: false,
+    raw_strings: false,
+};
+
+/// C-like languages with multi-line string literals, like Java text blocks.
+const TEXT_BLOCKS: Syntax = Syntax {
+    strings: &[("\"\"\"", true), ("\"", true)],
+    ..C
+};
+
+/// Python and the other languages with `#` comments.
+const HASH: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comments: &[],
+    strings: &[("\"\"\"", true), ("'''", true), ("\"", true), ("'", true)],
+    char_literals: false,
+    word_comments: false,
+    raw_strings: false,
+};
+
+impl CodeLanguage {
+    /// Get the syntax of comments and string literals of the language.
+    fn syntax(self) -> Syntax {
+        match self {
+            Self::Rust => Syntax { raw_strings: true, ..C },
+            Self::C | Self::Cpp => C,
+            Self::CSharp | Self::Java | Self::Kotlin | Self::Scala | Self::Swift => TEXT_BLOCKS,
+            Self::Go => Syntax {
+                strings: &[("\"", true), ("`", false)],
+                ..C
+            },
+            Self::JavaScript | Self::TypeScript => Syntax {
+                strings: &[("\"", true), ("'", true), ("`", true)],
+                char_literals: false,
+                ..C
+            },
+            Self::Haskell => Syntax {
+                line_comments: &["--"],
+                block_comments: &[("{-", "-}")],
+                ..C
+            },
+            Self::Lua => Syntax {
+                line_comments: &["--"],
+                block_comments: &[("--[[", "]]")],
+                strings: &[("\"", true), ("'", true)],
+                char_literals: false,
+                ..C
+            },
+            Self::Sql => Syntax {
+                line_comments: &["--"],
+                strings: &[("'", false)],
+                char_literals: false,
+                ..C
+            },
+            Self::Python => HASH,
+            Self::Ruby => Syntax {
+                strings: &[("\"", true), ("'", true)],
+                ..HASH
+            },
+            Self::Shell => Syntax {
+                strings: &[("\"", true), ("'", false)],
+                word_comments: true,
+                ..HASH
+            },
+        }
+    }
+}
+
+/// Matches inline code in comments, which is left untouched.
+static COMMENT_MARKUP: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"`[^`\n]+`").unwrap());
+
+/// Matches escape sequences and format placeholders in string literals, which are left untouched.
+static STRING_MARKUP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        // Escape sequences, like \n, \x7f and \u{1F600}
+        r"\\(?:u\{[0-9A-Fa-f]*\}|u[0-9A-Fa-f]{4}|x[0-9A-Fa-f]{2}|.)",
+        // Placeholders, like {} and {name:>8}
+        r"|\{[^{}\s]*\}",
+        // printf-style conversions, like %s and %-5.2f
+        r"|%[-+#0]*\d*(?:\.\d+)?[a-zA-Z]",
+    ))
+    .unwrap()
+});
+
+/// Do bio-reading on the whole source code `input` in `language`, only emphasizing comments and string literals, and write the result to `writer`.
+///
+/// Identifiers, keywords and other syntax, character literals, inline code in comments, and escape sequences and placeholders in string literals are left untouched.
+pub(crate) fn bio_read(bio_reader: &BioReader, input: &[u8], language: CodeLanguage, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+    let text = utf8(input, "Source code")?;
+    let (comments, strings) = literals(text, &language.syntax());
+    let mut prose = without_markup(text, comments, &COMMENT_MARKUP);
+    prose.extend(without_markup(text, strings, &STRING_MARKUP));
+    prose.sort_unstable_by_key(|range| range.start);
+    bio_read_prose(bio_reader, text, prose, writer, state)
+}
+
+/// Find the byte ranges of the content of comments and string literals in `text`.
+fn literals(text: &str, syntax: &Syntax) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+    let bytes = text.as_bytes();
+    let mut comments = Vec::new();
+    let mut strings = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &text[i..];
+        if let Some((open, close)) = syntax.block_comments.iter().find(|(open, _)| rest.starts_with(open)) {
+            let start = i + open.len();
+            let content_end = text[start..].find(close).map_or(text.len(), |end| start + end);
+            comments.push(start..content_end);
+            i = (content_end + close.len()).min(text.len());
+        } else if let Some(marker) = syntax.line_comments.iter().find(|marker| rest.starts_with(*marker)).filter(|_| !syntax.word_comments || i == 0 || bytes[i - 1].is_ascii_whitespace() || bytes[i - 1] == b';') {
+            let end = rest.find('\n').map_or(text.len(), |end| i + end);
+            comments.push(i + marker.len()..end);
+            i = end;
+        } else if let Some((content, end)) = syntax.raw_strings.then(|| raw_string(text, i)).flatten() {
+            strings.push(content);
+            i = end;
+        } else if syntax.char_literals && bytes[i] == b'\'' {
+            i = char_literal(text, i).unwrap_or(i + 1);
+        } else if let Some((quote, escapes)) = syntax.strings.iter().find(|(quote, _)| rest.starts_with(quote)) {
+            let start = i + quote.len();
+            let (content_end, end) = string_end(text, start, quote, *escapes).unwrap_or((text.len(), text.len()));
+            strings.push(start..content_end);
+            i = end;
+        } else {
+            i += rest.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    (comments, strings)
+}
+
+/// Get the byte range of the content and the end of the raw string literal starting at `i`, like `r#"..."#`, if any.
+fn raw_string(text: &str, i: usize) -> Option<(Range<usize>, usize)> {
+    let bytes = text.as_bytes();
+    // Not the end of an identifier, but possibly a byte string, like `br"..."`
+    let before = bytes[..i].strip_suffix(b"b").unwrap_or(&bytes[..i]);
+    if bytes[i] != b'r' || before.last().is_some_and(|&b| b.is_ascii_alphanumeric() || b == b'_') {
+        return None;
+    }
+    let hashes = bytes[i + 1..].iter().take_while(|&&b| b == b'#').count();
+    let start = i + 1 + hashes;
+    if bytes.get(start) != Some(&b'"') {
+        return None;
+    }
+    let close = format!("\"{}", "#".repeat(hashes));
+    let content_end = text[start + 1..].find(&close).map_or(text.len(), |end| start + 1 + end);
+    Some((start + 1..content_end, (content_end + close.len()).min(text.len())))
+}
+
+/// Get the end of the character literal starting at `i`, like `'a'` or `'\n'`, if it is one.
+fn char_literal(text: &str, i: usize) -> Option<usize> {
+    match text[i + 1..].chars().next()? {
+        '\\' => {
+            // After the quote, the backslash and the escaped character. Escape sequences are short, like `\u{1F600}`
+            let start = i + 3;
+            let end = text.get(start..)?.bytes().take(10).position(|b| b == b'\'' || b == b'\n')?;
+            (text.as_bytes()[start + end] == b'\'').then_some(start + end + 1)
+        }
+        '\'' | '\n' => None,
+        c => (text[i + 1 + c.len_utf8()..].starts_with('\'')).then_some(i + c.len_utf8() + 2),
+    }
+}
+
+/// Get the end of the content and the end of the string literal whose content starts at `i`, closed by `quote`.
+fn string_end(text: &str, mut i: usize, quote: &str, escapes: bool) -> Option<(usize, usize)> {
+    let bytes = text.as_bytes();
+    while i < bytes.len() {
+        if escapes && bytes[i] == b'\\' {
+            i += 2;
+        } else if bytes[i..].starts_with(quote.as_bytes()) {
+            return Some((i, i + quote.len()));
+        } else {
+            i += 1;
+        }
+    }
+    None
+}
@@ -0,0 +1,109 @@
+//! Unified diff input format, as written by `diff -u` and `git diff`.
+
+use crate::{BioReader, State};
+use regex::Regex;
+use std::{io::Write, sync::LazyLock};
+
+/// Matches ANSI color escape sequences, like those of `git diff --color`.
+static COLOR: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\x1b\[[0-9;]*m").unwrap());
+
+/// Matches hunk headers, like `@@ -1,2 +1,3 @@`, capturing how many lines of the old and the new file there are, if not one.
+static HUNK: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^@@ -\d+(?:,(\d+))? \+\d+(?:,(\d+))? @@").unwrap());
+
+/// A streaming scanner of unified diffs, bio-reading the content of changed lines, and leaving headers, context lines and the `+` and `-` markers intact.
+///
+/// Colors of the input, like those of `git diff --color`, are kept for the markers and removed from the content of changed lines, so that they don't get in the way of emphasis.
+#[derive(Debug, Default)]
+pub(crate) struct Diff {
+    /// The start of a line that hasn't ended yet.
+    line: String,
+    /// How many lines of the old file are left in the current hunk.
+    old: usize,
+    /// How many lines of the new file are left in the current hunk.
+    new: usize,
+}
+
+impl Diff {
+    /// Process a piece of decoded text, a line at a time.
+    pub(crate) fn push_str(&mut self, bio_reader: &BioReader, text: &str, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        let mut rest = text;
+        while let Some(end) = rest.find('\n') {
+            let (line, after) = rest.split_at(end + 1);
+            if self.line.is_empty() {
+                self.push_line(bio_reader, line, writer, state)?;
+            } else {
+                let mut held = std::mem::take(&mut self.line);
+                held.push_str(line);
+                self.push_line(bio_reader, &held, writer, state)?;
+                // Reuse the allocation
+                self.line = held;
+                self.line.clear();
+            }
+            rest = after;
+        }
+        self.line.push_str(rest);
+        Ok(())
+    }
+    /// Process the last line, if it hasn't ended.
+    pub(crate) fn finish(&mut self, bio_reader: &BioReader, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        let line = std::mem::take(&mut self.line);
+        if !line.is_empty() {
+            self.push_line(bio_reader, &line, writer, state)?;
+        }
+        Ok(())
+    }
+    /// Process a whole line, including its line ending, if any.
+    fn push_line(&mut self, bio_reader: &BioReader, line: &str, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        let plain = COLOR.replace_all(line, "");
+        if self.old > 0 || self.new > 0 {
+            match plain.as_bytes().first() {
+                Some(b'-') if self.old > 0 => {
+                    self.old -= 1;
+                    return push_changed(bio_reader, line, writer, state);
+                }
+                Some(b'+') if self.new > 0 => {
+                    self.new -= 1;
+                    return push_changed(bio_reader, line, writer, state);
+                }
+                // Context lines, which may have lost their leading space to trailing whitespace trimming
+                Some(b' ' | b'\n' | b'\r') => {
+                    self.old = self.old.saturating_sub(1);
+                    self.new = self.new.saturating_sub(1);
+                    return bio_reader.push_raw(line.as_bytes(), writer, state);
+                }
+                // "\ No newline at end of file"
+                Some(b'\\') => return bio_reader.push_raw(line.as_bytes(), writer, state),
+                // The hunk ended early
+                _ => (self.old, self.new) = (0, 0),
+            }
+        }
+        if let Some(captures) = HUNK.captures(&plain) {
+            let count = |i| captures.get(i).map_or(1, |count| count.as_str().parse().unwrap_or(0));
+            (self.old, self.new) = (count(1), count(2));
+        }
+        bio_reader.push_raw(line.as_bytes(), writer, state)
+    }
+}
+
+/// Write the marker of a changed line, along with its colors, if any, and bio-read the content after it.
+fn push_changed(bio_reader: &BioReader, line: &str, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+    // The marker, along with the colors before and after it
+    let marker_end = colors_end(line, colors_end(line, 0) + 1);
+    bio_reader.push_raw(&line.as_bytes()[..marker_end], writer, state)?;
+    let content = &line[marker_end..];
+    if !content.contains('\x1b') && marker_end == 1 {
+        return bio_reader.push_str(content, writer, state);
+    }
+    // Reset the colors of the marker, which would otherwise clash with emphasis
+    bio_reader.push_raw(b"\x1b[m", writer, state)?;
+    bio_reader.push_str(&COLOR.replace_all(content, ""), writer, state)
+}
+
+/// Get the end of the colors at `start` in `line`, if any.
+fn colors_end(line: &str, start: usize) -> usize {
+    let mut end = start;
+    while let Some(found) = COLOR.find_at(line, end).filter(|found| found.start() == end) {
+        end = found.end();
+    }
+    end
+}
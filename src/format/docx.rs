@@ -0,0 +1,256 @@
+//! Bio-reading Word documents, by making the emphasized part of each word bold.
+
+use crate::{BioReader, InputFormat, SpanKind};
+use quick_xml::{
+    escape::resolve_predefined_entity,
+    events::{BytesEnd, BytesStart, BytesText, Event},
+    Reader, Writer,
+};
+use std::{
+    fmt::Display,
+    io::{Cursor, Error, Read, Write},
+    ops::Range,
+};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+/// Path of the main document in the archive.
+const DOCUMENT: &str = "word/document.xml";
+
+/// Do bio-reading on the Word document `input`, writing the result to `writer`.
+pub(crate) fn bio_read(bio_reader: &BioReader, input: &[u8], writer: &mut impl Write) -> std::io::Result<()> {
+    let mut archive = ZipArchive::new(Cursor::new(input)).map_err(invalid)?;
+    let mut output = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut found = false;
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        if file.name()? == DOCUMENT {
+            let mut document = String::new();
+            file.read_to_string(&mut document)?;
+            output.start_file(DOCUMENT, options)?;
+            output.write_all(&bio_read_document(bio_reader, &document)?)?;
+            found = true;
+        } else {
+            output.raw_copy_file(file)?;
+        }
+    }
+    if !found {
+        return Err(invalid(format!("missing {DOCUMENT}")));
+    }
+    writer.write_all(&output.finish()?.into_inner())
+}
+
+/// Do bio-reading on the XML of the main document, one paragraph at a time.
+fn bio_read_document(bio_reader: &BioReader, document: &str) -> std::io::Result<Vec<u8>> {
+    let mut reader = Reader::from_str(document);
+    let mut writer = Writer::new(Vec::with_capacity(document.len() * 2));
+    // Events of the current outermost paragraph
+    let mut paragraph = Vec::new();
+    // How many paragraphs are open, as they can be nested in text boxes
+    let mut depth = 0_usize;
+    loop {
+        let event = reader.read_event().map_err(invalid)?;
+        match &event {
+            Event::Eof => break,
+            Event::Start(element) if element.name().as_ref() == "w:p" => depth += 1,
+            Event::End(element) if element.name().as_ref() == "w:p" => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        if depth == 0 && paragraph.is_empty() {
+            writer.write_event(event)?;
+            continue;
+        }
+        paragraph.push(event.into_owned());
+        if depth == 0 {
+            write_paragraph(bio_reader, &paragraph, &mut writer)?;
+            paragraph.clear();
+        }
+    }
+    Ok(writer.into_inner())
+}
+
+/// A part of a paragraph.
+enum Item<'a> {
+    /// A run of text with only text in it, with its properties and the byte range of its text in the text of the paragraph.
+    Text {
+        start: &'a BytesStart<'static>,
+        properties: &'a [Event<'static>],
+        range: Range<usize>,
+    },
+    /// Anything else, written as-is.
+    Other(&'a [Event<'static>]),
+}
+
+/// Bio-read the text runs of the paragraph made up of `events`, writing it to `writer`. The text of all text runs is bio-read as a whole, so words split across runs are handled correctly.
+fn write_paragraph(bio_reader: &BioReader, events: &[Event<'static>], writer: &mut Writer<Vec<u8>>) -> std::io::Result<()> {
+    let mut items = Vec::new();
+    let mut text = String::new();
+    let mut i = 0;
+    while i < events.len() {
+        let Event::Start(start) = &events[i] else {
+            items.push(Item::Other(&events[i..=i]));
+            i += 1;
+            continue;
+        };
+        if start.name().as_ref() != "w:r" {
+            items.push(Item::Other(&events[i..=i]));
+            i += 1;
+            continue;
+        }
+        let end = i + matching_end(&events[i..]);
+        let run = &events[i..=end];
+        match run_text(&run[1..run.len() - 1]) {
+            Some((properties, run_text)) => {
+                let range = text.len()..text.len() + run_text.len();
+                text.push_str(&run_text);
+                items.push(Item::Text { start, properties, range });
+            }
+            None => {
+                // Tabs, breaks, drawings and the like separate words
+                text.push('\n');
+                items.push(Item::Other(run));
+            }
+        }
+        i = end + 1;
+    }
+    let emphasized: Vec<_> = bio_reader
+        .spans_with_format(text.as_bytes(), InputFormat::Plain)
+        .expect("Recording spans of valid UTF-8 should never fail")
+        .into_iter()
+        .filter(|span| span.kind == SpanKind::Emphasized)
+        .map(|span| span.range)
+        .collect();
+    for item in items {
+        match item {
+            Item::Other(events) => {
+                for event in events {
+                    writer.write_event(event.borrow())?;
+                }
+            }
+            Item::Text { start, properties, range } => {
+                // End of the text of the run that has been written
+                let mut written = range.start;
+                for span in emphasized.iter().filter(|span| span.start < range.end && span.end > range.start) {
+                    let span = span.start.max(range.start)..span.end.min(range.end);
+                    if written < span.start {
+                        write_run(writer, start, properties, false, &text[written..span.start])?;
+                    }
+                    write_run(writer, start, properties, true, &text[span.clone()])?;
+                    written = span.end;
+                }
+                if written < range.end || range.is_empty() {
+                    write_run(writer, start, properties, false, &text[written..range.end])?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Get the properties and text of a run with `children`, if it only has text in it.
+fn run_text<'a>(children: &'a [Event<'static>]) -> Option<(&'a [Event<'static>], String)> {
+    let properties_len = match children.first() {
+        Some(Event::Start(element)) if element.name().as_ref() == "w:rPr" => matching_end(children) + 1,
+        Some(Event::Empty(element)) if element.name().as_ref() == "w:rPr" => 1,
+        _ => 0,
+    };
+    let mut text = String::new();
+    let mut in_text = false;
+    for event in &children[properties_len..] {
+        match event {
+            Event::Start(element) if !in_text && element.name().as_ref() == "w:t" => in_text = true,
+            Event::End(element) if in_text && element.name().as_ref() == "w:t" => in_text = false,
+            Event::Empty(element) if !in_text && element.name().as_ref() == "w:t" => {}
+            Event::Text(content) if in_text => text.push_str(&content.xml10_content()),
+            // Indentation between elements
+            Event::Text(content) if content.xml10_content().trim().is_empty() => {}
+            Event::GeneralRef(reference) if in_text => match reference.resolve_char_ref().ok()? {
+                Some(c) => text.push(c),
+                None => text.push_str(resolve_predefined_entity(&reference.xml10_content())?),
+            },
+            _ => return None,
+        }
+    }
+    Some((&children[..properties_len], text))
+}
+
+/// Write a run with `text`, with the properties and attributes of the original run, made bold if `bold`.
+fn write_run(writer: &mut Writer<Vec<u8>>, start: &BytesStart, properties: &[Event<'static>], bold: bool, text: &str) -> std::io::Result<()> {
+    writer.write_event(Event::Start(start.borrow()))?;
+    if bold {
+        write_bold_properties(writer, properties)?;
+    } else {
+        for event in properties {
+            writer.write_event(event.borrow())?;
+        }
+    }
+    writer.write_event(Event::Start(BytesStart::new("w:t").with_attributes([("xml:space", "preserve")])))?;
+    writer.write_event(Event::Text(BytesText::new(text)))?;
+    writer.write_event(Event::End(BytesEnd::new("w:t")))?;
+    writer.write_event(Event::End(BytesEnd::new("w:r")))
+}
+
+/// Write run `properties` with bold turned on, replacing existing bold settings. Bold goes after the style and fonts, as the order of properties is fixed.
+fn write_bold_properties(writer: &mut Writer<Vec<u8>>, properties: &[Event<'static>]) -> std::io::Result<()> {
+    let bold = [Event::Empty(BytesStart::new("w:b")), Event::Empty(BytesStart::new("w:bCs"))];
+    let children = match properties {
+        [Event::Start(start), children @ .., _] => {
+            writer.write_event(Event::Start(start.borrow()))?;
+            children
+        }
+        _ => {
+            writer.write_event(Event::Start(BytesStart::new("w:rPr")))?;
+            &[]
+        }
+    };
+    let mut inserted = false;
+    let mut i = 0;
+    while i < children.len() {
+        let (name, len) = match &children[i] {
+            Event::Start(element) => (element.name().as_ref().to_string(), matching_end(&children[i..]) + 1),
+            Event::Empty(element) => (element.name().as_ref().to_string(), 1),
+            _ => (String::new(), 1),
+        };
+        if !inserted && !name.is_empty() && name != "w:rStyle" && name != "w:rFonts" {
+            for event in &bold {
+                writer.write_event(event.borrow())?;
+            }
+            inserted = true;
+        }
+        if name != "w:b" && name != "w:bCs" {
+            for event in &children[i..i + len] {
+                writer.write_event(event.borrow())?;
+            }
+        }
+        i += len;
+    }
+    if !inserted {
+        for event in &bold {
+            writer.write_event(event.borrow())?;
+        }
+    }
+    writer.write_event(Event::End(BytesEnd::new("w:rPr")))
+}
+
+/// Get the index of the end of the element started by the first of `events`.
+fn matching_end(events: &[Event]) -> usize {
+    let mut depth = 0_usize;
+    for (i, event) in events.iter().enumerate() {
+        match event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => {
+                depth -= 1;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+    }
+    events.len() - 1
+}
+
+/// Create an error for an invalid Word document.
+fn invalid(reason: impl Display) -> Error {
+    crate::Error::format(format!("Invalid DOCX: {reason}")).into()
+}
@@ -0,0 +1,202 @@
+//! Email input format, for RFC 822 messages and mbox files.
+
+use super::html::Scanner;
+use crate::{BioReader, State};
+use std::io::Write;
+
+/// Do bio-reading on the whole email `input`, a single message or an mbox file of them, only emphasizing text bodies, and write the result to `writer`.
+///
+/// Headers are left untouched, except for the `Content-Transfer-Encoding` of text bodies encoded with quoted-printable or base64, which are decoded and then written as `8bit`. Plain text bodies are bio-read as a whole and HTML ones like [`InputFormat::Html`](crate::InputFormat::Html), in UTF-8 or US-ASCII only. Attachments, other parts and bodies in other character sets are left untouched.
+pub(crate) fn bio_read(bio_reader: &BioReader, input: &[u8], writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+    // Messages of mbox files start with a "From " line, after a blank line
+    let mut start = 0;
+    let mut blank = true;
+    for (i, line) in lines(input) {
+        if blank && i > 0 && line.starts_with(b"From ") {
+            message(bio_reader, &input[start..i], writer, state)?;
+            start = i;
+        }
+        blank = line.trim_ascii().is_empty();
+    }
+    message(bio_reader, &input[start..], writer, state)
+}
+
+/// Bio-read a single message, which may start with the "From " line of mbox files.
+fn message(bio_reader: &BioReader, input: &[u8], writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+    let from_end = if input.starts_with(b"From ") { lines(input).next().map_or(0, |(_, line)| line.len()) } else { 0 };
+    bio_reader.push_raw(&input[..from_end], writer, state)?;
+    entity(bio_reader, &input[from_end..], writer, state)
+}
+
+/// Bio-read a MIME entity, like a message or a part of a multipart one, made up of headers, a blank line and a body.
+fn entity(bio_reader: &BioReader, input: &[u8], writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+    let Some(body_start) = lines(input).find(|(_, line)| line.trim_ascii().is_empty()).map(|(i, line)| i + line.len()) else {
+        // Headers only
+        return bio_reader.push_raw(input, writer, state);
+    };
+    let (headers, body) = input.split_at(body_start);
+    let content_type = header(headers, "content-type").unwrap_or_default();
+    let mime = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    let param = |name: &str| {
+        content_type.split(';').skip(1).find_map(|param| {
+            let (key, value) = param.split_once('=')?;
+            key.trim().eq_ignore_ascii_case(name).then(|| value.trim().trim_matches('"').to_string())
+        })
+    };
+    if let Some(boundary) = param("boundary").filter(|_| mime.starts_with("multipart/")) {
+        bio_reader.push_raw(headers, writer, state)?;
+        return multipart(bio_reader, body, &boundary, writer, state);
+    }
+    let is_attachment = header(headers, "content-disposition").is_some_and(|disposition| disposition.trim_start().to_ascii_lowercase().starts_with("attachment"));
+    let is_text = matches!(mime.as_str(), "" | "text/plain" | "text/html") && !is_attachment;
+    let is_utf8 = param("charset").is_none_or(|charset| ["utf-8", "utf8", "us-ascii", "ascii"].contains(&charset.to_ascii_lowercase().as_str()));
+    let encoding = header(headers, "content-transfer-encoding").map(|encoding| encoding.trim().to_ascii_lowercase());
+    let decoded = match encoding.as_deref() {
+        Some("quoted-printable") => Some(decode_quoted_printable(body)),
+        Some("base64") => decode_base64(body),
+        _ => None,
+    };
+    let Some(text) = (is_text && is_utf8).then(|| std::str::from_utf8(decoded.as_deref().unwrap_or(body)).ok()).flatten() else {
+        return bio_reader.push_raw(input, writer, state);
+    };
+    if decoded.is_some() {
+        write_decoded_headers(bio_reader, headers, writer, state)?;
+    } else {
+        bio_reader.push_raw(headers, writer, state)?;
+    }
+    if mime == "text/html" {
+        Scanner::default().push_str(bio_reader, text, writer, state)?;
+    } else {
+        bio_reader.push_str(text, writer, state)?;
+    }
+    bio_reader.end_text(writer, state)
+}
+
+/// Bio-read the parts of a multipart body, whose delimiter lines are made of `boundary`, leaving the delimiters, the preamble and the epilogue untouched.
+fn multipart(bio_reader: &BioReader, body: &[u8], boundary: &str, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+    let delimiter = format!("--{boundary}");
+    // Start, end and whether it's the closing delimiter, of each delimiter line
+    let delimiters: Vec<_> = lines(body)
+        .filter(|(_, line)| line.starts_with(delimiter.as_bytes()))
+        .map(|(i, line)| (i, i + line.len(), line[delimiter.len()..].starts_with(b"--")))
+        .collect();
+    let Some(&(first, _, _)) = delimiters.first() else {
+        return bio_reader.push_raw(body, writer, state);
+    };
+    bio_reader.push_raw(&body[..first], writer, state)?;
+    for (n, &(start, end, closing)) in delimiters.iter().enumerate() {
+        bio_reader.push_raw(&body[start..end], writer, state)?;
+        if closing {
+            return bio_reader.push_raw(&body[end..], writer, state);
+        }
+        let part_end = delimiters.get(n + 1).map_or(body.len(), |&(next, _, _)| next);
+        // The line ending before a delimiter belongs to it
+        let part = &body[end..part_end];
+        let content = part.strip_suffix(b"\n").map_or(part, |part| part.strip_suffix(b"\r").unwrap_or(part));
+        entity(bio_reader, content, writer, state)?;
+        bio_reader.push_raw(&part[content.len()..], writer, state)?;
+    }
+    Ok(())
+}
+
+/// Get the value of the header `name` in `headers`, with folded lines unfolded, if there is one.
+fn header(headers: &[u8], name: &str) -> Option<String> {
+    let mut value: Option<String> = None;
+    for (_, line) in lines(headers) {
+        let line = String::from_utf8_lossy(line);
+        if line.starts_with([' ', '\t']) {
+            if let Some(value) = &mut value {
+                value.push_str(line.trim_end());
+            }
+            continue;
+        }
+        if value.is_some() {
+            break;
+        }
+        value = line.split_once(':').filter(|(key, _)| key.trim().eq_ignore_ascii_case(name)).map(|(_, value)| value.trim_end().to_string());
+    }
+    value
+}
+
+/// Write `headers`, saying that the body is no longer encoded.
+fn write_decoded_headers(bio_reader: &BioReader, headers: &[u8], writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+    let mut in_encoding = false;
+    for (_, line) in lines(headers) {
+        if in_encoding && matches!(line.first(), Some(b' ' | b'\t')) {
+            // A folded line of the encoding
+            continue;
+        }
+        in_encoding = false;
+        let name_len = line.iter().position(|&b| b == b':');
+        if let Some(name) = name_len.map(|len| &line[..len]).filter(|name| name.trim_ascii().eq_ignore_ascii_case(b"content-transfer-encoding")) {
+            in_encoding = true;
+            let line_ending = if line.ends_with(b"\r\n") { "\r\n" } else { "\n" };
+            bio_reader.push_raw(name, writer, state)?;
+            bio_reader.push_raw(format!(": 8bit{line_ending}").as_bytes(), writer, state)?;
+        } else {
+            bio_reader.push_raw(line, writer, state)?;
+        }
+    }
+    Ok(())
+}
+
+/// Decode a quoted-printable body, removing soft line breaks.
+fn decode_quoted_printable(body: &[u8]) -> Vec<u8> {
+    let mut decoded = Vec::with_capacity(body.len());
+    let mut i = 0;
+    while i < body.len() {
+        if body[i] != b'=' {
+            decoded.push(body[i]);
+            i += 1;
+            continue;
+        }
+        let rest = &body[i + 1..];
+        if let Some(soft_break) = [&b"\r\n"[..], b"\n"].into_iter().find(|line_ending| rest.starts_with(line_ending)) {
+            i += 1 + soft_break.len();
+        } else if let Some(byte) = rest.get(..2).and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()) {
+            decoded.push(byte);
+            i += 3;
+        } else {
+            // Not an escape after all
+            decoded.push(b'=');
+            i += 1;
+        }
+    }
+    decoded
+}
+
+/// Decode a base64 body, ignoring line breaks, if it is valid.
+fn decode_base64(body: &[u8]) -> Option<Vec<u8>> {
+    let mut decoded = Vec::with_capacity(body.len() / 4 * 3);
+    // Bits decoded but not written yet, and how many of them
+    let (mut bits, mut len) = (0_u32, 0);
+    for &byte in body {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => break,
+            _ if byte.is_ascii_whitespace() => continue,
+            _ => return None,
+        };
+        bits = (bits << 6 | u32::from(value)) & 0xffff;
+        len += 6;
+        if len >= 8 {
+            len -= 8;
+            decoded.push((bits >> len) as u8);
+        }
+    }
+    Some(decoded)
+}
+
+/// Split `bytes` into lines, including their line endings, along with their byte offsets.
+fn lines(bytes: &[u8]) -> impl Iterator<Item = (usize, &[u8])> {
+    let mut start = 0;
+    bytes.split_inclusive(|&b| b == b'\n').map(move |line| {
+        let i = start;
+        start += line.len();
+        (i, line)
+    })
+}
@@ -0,0 +1,228 @@
+//! HTML input format.
+
+use crate::{BioReader, State};
+use std::io::Write;
+
+/// Elements whose content is left untouched.
+const SKIPPED_ELEMENTS: [&str; 6] = ["pre", "code", "kbd", "samp", "var", "textarea"];
+/// Elements whose content is raw text, which can only be ended by the corresponding end tag.
+const RAW_TEXT_ELEMENTS: [&str; 2] = ["script", "style"];
+/// Maximum length of a character reference, after which it is considered as text.
+const MAX_ENTITY_LENGTH: usize = 32;
+
+/// Where the scanner currently is.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Position {
+    /// In text content.
+    #[default]
+    Text,
+    /// In a character reference, after `&`, with the given length so far.
+    Entity(usize),
+    /// After `<`.
+    TagOpen,
+    /// After `</`.
+    EndTagOpen,
+    /// In the name of a tag.
+    TagName,
+    /// In a tag, after its name.
+    Tag,
+    /// In a quoted attribute value, quoted by the given character.
+    Quoted(char),
+    /// After `<!`, with the given number of dashes seen.
+    MarkupDeclaration(usize),
+    /// In a comment, with the given number of consecutive dashes seen.
+    Comment(usize),
+    /// In a doctype, processing instruction or other declaration, until `>`.
+    Declaration,
+    /// In the content of a raw text element, with the given number of characters of its end tag (`</name`) matched.
+    RawText(usize),
+}
+
+/// A streaming HTML scanner, telling text nodes apart from markup.
+#[derive(Debug, Default)]
+pub(crate) struct Scanner {
+    /// Where the scanner currently is.
+    position: Position,
+    /// Name of the current tag, in lowercase.
+    tag_name: String,
+    /// Whether the current tag is an end tag.
+    end_tag: bool,
+    /// Whether the current tag is self-closing.
+    self_closing: bool,
+    /// How many skipped elements are currently open.
+    skip_depth: usize,
+    /// The raw text element currently open, if any.
+    raw_text: Option<&'static str>,
+}
+
+impl Scanner {
+    /// Process a piece of decoded HTML, passing prose to `bio_reader` and everything else through untouched.
+    pub(crate) fn push_str(&mut self, bio_reader: &BioReader, text: &str, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        let mut run_start = 0;
+        let mut run_is_prose = false;
+        for (i, c) in text.char_indices() {
+            let is_prose = self.feed(c);
+            if is_prose != run_is_prose {
+                Self::flush(bio_reader, &text[run_start..i], run_is_prose, writer, state)?;
+                run_start = i;
+                run_is_prose = is_prose;
+            }
+        }
+        Self::flush(bio_reader, &text[run_start..], run_is_prose, writer, state)
+    }
+    /// Write a run of text, bio-reading it if it is prose.
+    fn flush(bio_reader: &BioReader, run: &str, is_prose: bool, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        if run.is_empty() {
+            Ok(())
+        } else if is_prose {
+            bio_reader.push_str(run, writer, state)
+        } else {
+            bio_reader.push_raw(run.as_bytes(), writer, state)
+        }
+    }
+    /// Advance the scanner by a character, returning whether it is part of prose.
+    fn feed(&mut self, c: char) -> bool {
+        loop {
+            match self.position {
+                Position::Text => {
+                    match c {
+                        '<' => self.position = Position::TagOpen,
+                        '&' => self.position = Position::Entity(0),
+                        _ => return self.skip_depth == 0,
+                    }
+                    return false;
+                }
+                Position::Entity(len) => {
+                    if c == ';' {
+                        self.position = Position::Text;
+                    } else if (c.is_ascii_alphanumeric() || c == '#') && len < MAX_ENTITY_LENGTH {
+                        self.position = Position::Entity(len + 1);
+                    } else {
+                        // Not (or no longer) a character reference - process `c` as text
+                        self.position = Position::Text;
+                        continue;
+                    }
+                    return false;
+                }
+                Position::TagOpen => {
+                    match c {
+                        '/' => self.position = Position::EndTagOpen,
+                        '!' => self.position = Position::MarkupDeclaration(0),
+                        '?' => self.position = Position::Declaration,
+                        c if c.is_ascii_alphabetic() => self.start_tag(c, false),
+                        _ => {
+                            // A lone `<` - process `c` as text
+                            self.position = Position::Text;
+                            continue;
+                        }
+                    }
+                    return false;
+                }
+                Position::EndTagOpen => {
+                    if c.is_ascii_alphabetic() {
+                        self.start_tag(c, true);
+                    } else {
+                        self.position = Position::Declaration;
+                        continue;
+                    }
+                    return false;
+                }
+                Position::TagName => {
+                    if c.is_ascii_whitespace() || c == '/' || c == '>' {
+                        self.position = Position::Tag;
+                        continue;
+                    }
+                    self.tag_name.push(c.to_ascii_lowercase());
+                    return false;
+                }
+                Position::Tag => {
+                    match c {
+                        '"' | '\'' => self.position = Position::Quoted(c),
+                        '/' => self.self_closing = true,
+                        '>' => self.end_tag(),
+                        c if !c.is_ascii_whitespace() => self.self_closing = false,
+                        _ => {}
+                    }
+                    return false;
+                }
+                Position::Quoted(quote) => {
+                    if c == quote {
+                        self.position = Position::Tag;
+                    }
+                    return false;
+                }
+                Position::MarkupDeclaration(dashes) => {
+                    self.position = match (c, dashes) {
+                        ('-', 0) => Position::MarkupDeclaration(1),
+                        ('-', _) => Position::Comment(0),
+                        ('>', _) => Position::Text,
+                        _ => Position::Declaration,
+                    };
+                    return false;
+                }
+                Position::Comment(dashes) => {
+                    self.position = match c {
+                        '-' => Position::Comment(dashes + 1),
+                        '>' if dashes >= 2 => Position::Text,
+                        _ => Position::Comment(0),
+                    };
+                    return false;
+                }
+                Position::Declaration => {
+                    if c == '>' {
+                        self.position = Position::Text;
+                    }
+                    return false;
+                }
+                Position::RawText(matched) => {
+                    let name = self.raw_text.unwrap_or_default();
+                    let expected = match matched {
+                        0 => Some('<'),
+                        1 => Some('/'),
+                        n => name[n - 2..].chars().next(),
+                    };
+                    if expected.is_some_and(|e| e.eq_ignore_ascii_case(&c)) {
+                        self.position = Position::RawText(matched + 1);
+                    } else if expected.is_none() && (c.is_ascii_whitespace() || c == '/' || c == '>') {
+                        // Matched the whole end tag
+                        self.raw_text = None;
+                        self.tag_name.clear();
+                        self.tag_name.push_str(name);
+                        self.end_tag = true;
+                        self.self_closing = false;
+                        self.position = Position::Tag;
+                        continue;
+                    } else {
+                        self.position = Position::RawText(usize::from(c == '<'));
+                    }
+                    return false;
+                }
+            }
+        }
+    }
+    /// Start a tag whose name starts with `c`.
+    fn start_tag(&mut self, c: char, end_tag: bool) {
+        self.tag_name.clear();
+        self.tag_name.push(c.to_ascii_lowercase());
+        self.end_tag = end_tag;
+        self.self_closing = false;
+        self.position = Position::TagName;
+    }
+    /// End the current tag, updating which elements are open.
+    fn end_tag(&mut self) {
+        self.position = Position::Text;
+        let name = self.tag_name.as_str();
+        if SKIPPED_ELEMENTS.contains(&name) {
+            if self.end_tag {
+                self.skip_depth = self.skip_depth.saturating_sub(1);
+            } else if !self.self_closing {
+                self.skip_depth += 1;
+            }
+        } else if !self.end_tag && !self.self_closing {
+            if let Some(raw_text) = RAW_TEXT_ELEMENTS.iter().find(|e| **e == name) {
+                self.raw_text = Some(raw_text);
+                self.position = Position::RawText(0);
+            }
+        }
+    }
+}
@@ -0,0 +1,253 @@
+//! LaTeX input format.
+
+use super::{bio_read_prose, utf8};
+use crate::{BioReader, State};
+use std::{
+    io::Write,
+    ops::Range,
+};
+
+/// Environments whose content is left untouched: math, verbatim text, code and drawings.
+const VERBATIM_ENVIRONMENTS: [&str; 20] = [
+    "equation",
+    "align",
+    "alignat",
+    "flalign",
+    "gather",
+    "multline",
+    "eqnarray",
+    "math",
+    "displaymath",
+    "split",
+    "verbatim",
+    "Verbatim",
+    "lstlisting",
+    "minted",
+    "comment",
+    "tikzpicture",
+    "filecontents",
+    "thebibliography",
+    "code",
+    "alltt",
+];
+
+/// Environments with arguments that are left untouched, along with how many of them.
+const ENVIRONMENT_ARGUMENTS: [(&str, usize); 8] = [
+    ("tabular", 1),
+    ("tabular*", 2),
+    ("tabularx", 2),
+    ("longtable", 1),
+    ("array", 1),
+    ("minipage", 1),
+    ("multicols", 1),
+    ("wrapfigure", 2),
+];
+
+/// Commands whose arguments are left untouched, like labels, keys, URLs and file names, along with how many of them. Optional arguments are left untouched as well.
+const VERBATIM_COMMANDS: [(&str, usize); 40] = [
+    ("label", 1),
+    ("ref", 1),
+    ("eqref", 1),
+    ("pageref", 1),
+    ("autoref", 1),
+    ("cref", 1),
+    ("Cref", 1),
+    ("nameref", 1),
+    ("cite", 1),
+    ("citep", 1),
+    ("citet", 1),
+    ("citealp", 1),
+    ("citeauthor", 1),
+    ("citeyear", 1),
+    ("nocite", 1),
+    ("bibitem", 1),
+    ("url", 1),
+    ("href", 1),
+    ("hyperref", 0),
+    ("includegraphics", 1),
+    ("input", 1),
+    ("include", 1),
+    ("bibliography", 1),
+    ("bibliographystyle", 1),
+    ("usepackage", 1),
+    ("documentclass", 1),
+    ("newcommand", 2),
+    ("renewcommand", 2),
+    ("providecommand", 2),
+    ("newenvironment", 3),
+    ("renewenvironment", 3),
+    ("setlength", 2),
+    ("addtolength", 2),
+    ("setcounter", 2),
+    ("addtocounter", 2),
+    ("hspace", 1),
+    ("vspace", 1),
+    ("color", 1),
+    ("textcolor", 1),
+    ("pagestyle", 1),
+];
+
+/// Do bio-reading on the whole LaTeX `input`, only emphasizing prose, and write the result to `writer`.
+///
+/// Commands, comments, math, the arguments of commands like `\label` and `\cite`, and the content of environments like `equation` and `verbatim` are left untouched. So is the preamble, if the input starts with `\documentclass`.
+pub(crate) fn bio_read(bio_reader: &BioReader, input: &[u8], writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+    let text = utf8(input, "LaTeX")?;
+    bio_read_prose(bio_reader, text, prose(text), writer, state)
+}
+
+/// Find the byte ranges of prose in `text`.
+fn prose(text: &str) -> Vec<Range<usize>> {
+    let bytes = text.as_bytes();
+    let (mut i, end) = body(text);
+    let mut ranges = Vec::new();
+    // Start of the current run of prose
+    let mut start = i;
+    while i < end {
+        let next = match bytes[i] {
+            b'%' => find(text, i, "\n").unwrap_or(end),
+            b'$' if bytes.get(i + 1) == Some(&b'$') => find_unescaped(text, i + 2, "$$").unwrap_or(end),
+            b'$' => find_unescaped(text, i + 1, "$").unwrap_or(end),
+            b'\\' => command(text, i, end),
+            b'{' | b'}' | b'&' | b'~' | b'#' | b'^' | b'_' => i + 1,
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        if start < i {
+            ranges.push(start..i);
+        }
+        i = next.min(end);
+        start = i;
+    }
+    if start < end {
+        ranges.push(start..end);
+    }
+    ranges
+}
+
+/// Get the byte range of the document body in `text`. If `text` starts with `\documentclass`, the body lies between `\begin{document}` and `\end{document}`, and is empty if there's none. Otherwise, it's the whole text.
+fn body(text: &str) -> (usize, usize) {
+    let is_document = text
+        .lines()
+        .map(str::trim_start)
+        .find(|line| !line.is_empty() && !line.starts_with('%'))
+        .is_some_and(|line| line.starts_with("\\documentclass"));
+    if !is_document {
+        return (0, text.len());
+    }
+    let Some(start) = text.find("\\begin{document}").map(|start| start + "\\begin{document}".len()) else {
+        return (text.len(), text.len());
+    };
+    let end = text[start..].find("\\end{document}").map_or(text.len(), |end| start + end);
+    (start, end)
+}
+
+/// Get the end of the command starting at `i`, along with the arguments and environment content to be left untouched, but no further than `end`.
+fn command(text: &str, i: usize, end: usize) -> usize {
+    let bytes = text.as_bytes();
+    let name_start = i + 1;
+    let name_end = name_start + bytes[name_start..end].iter().take_while(|b| b.is_ascii_alphabetic()).count();
+    if name_end == name_start {
+        return match bytes.get(name_start) {
+            Some(b'(') => find(text, name_start, "\\)").unwrap_or(end),
+            Some(b'[') => find(text, name_start, "\\]").unwrap_or(end),
+            // Control symbols like `\\`, `\%` and `\,`
+            Some(_) => name_start + text[name_start..].chars().next().map_or(0, char::len_utf8),
+            None => name_start,
+        };
+    }
+    // Starred variants, like `\section*`
+    let name_end = name_end + usize::from(bytes.get(name_end) == Some(&b'*'));
+    let name = text[name_start..name_end].trim_end_matches('*');
+    match name {
+        "begin" => {
+            let Some((environment, after)) = argument(text, name_end) else {
+                return name_end;
+            };
+            if VERBATIM_ENVIRONMENTS.contains(&environment.trim_end_matches('*')) {
+                find(text, after, &format!("\\end{{{environment}}}")).unwrap_or(end)
+            } else {
+                // Options and column specifications, like `[htbp]` and `{|c|c|}`
+                let count = ENVIRONMENT_ARGUMENTS.iter().find(|(name, _)| *name == environment).map_or(0, |(_, count)| *count);
+                arguments(text, after, count)
+            }
+        }
+        "end" => argument(text, name_end).map_or(name_end, |(_, after)| after),
+        "verb" => match text[name_end..].chars().next() {
+            Some(delimiter) => find(text, name_end + delimiter.len_utf8(), delimiter.encode_utf8(&mut [0; 4])).unwrap_or(end),
+            None => name_end,
+        },
+        _ => match VERBATIM_COMMANDS.iter().find(|(command, _)| *command == name) {
+            Some((_, count)) => arguments(text, name_end, *count),
+            None => name_end,
+        },
+    }
+}
+
+/// Get the end of the optional arguments and up to `count` mandatory arguments right after `i`. Optional arguments after the mandatory ones are included as well.
+fn arguments(text: &str, mut i: usize, count: usize) -> usize {
+    let mut mandatory = 0;
+    loop {
+        let close = match text.as_bytes().get(i) {
+            Some(b'[') => b']',
+            Some(b'{') if mandatory < count => {
+                mandatory += 1;
+                b'}'
+            }
+            _ => return i,
+        };
+        match matching(text, i, close) {
+            Some(after) => i = after,
+            None => return i,
+        }
+    }
+}
+
+/// Get the content of the mandatory argument starting at `i`, possibly after whitespace, and the end of it.
+fn argument(text: &str, i: usize) -> Option<(&str, usize)> {
+    let j = i + text[i..].len() - text[i..].trim_start().len();
+    if text.as_bytes().get(j) != Some(&b'{') {
+        return None;
+    }
+    let after = matching(text, j, b'}')?;
+    Some((&text[j + 1..after - 1], after))
+}
+
+/// Get the end of the group opened at `open`, closed by `close`, skipping nested braces and escaped characters.
+fn matching(text: &str, open: usize, close: u8) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut depth = 0_usize;
+    let mut i = open + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b'{' => depth += 1,
+            b'}' if depth > 0 => depth -= 1,
+            b if b == close && depth == 0 => return Some(i + 1),
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Get the end of the first `pattern` in `text` from `i`.
+fn find(text: &str, i: usize, pattern: &str) -> Option<usize> {
+    text[i..].find(pattern).map(|found| i + found + pattern.len())
+}
+
+/// Get the end of the first `pattern` in `text` from `i` not escaped by a backslash.
+fn find_unescaped(text: &str, mut i: usize, pattern: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    while i < bytes.len() {
+        if bytes[i] == b'\\' {
+            i += 2;
+        } else if bytes[i..].starts_with(pattern.as_bytes()) {
+            return Some(i + pattern.len());
+        } else {
+            i += 1;
+        }
+    }
+    None
+}
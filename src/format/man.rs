@@ -0,0 +1,94 @@
+//! Man page input format, as written by `man` and `nroff` for terminals.
+
+use crate::{BioReader, State};
+use std::io::Write;
+
+/// A character cell, which may be overstruck by backspacing over it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Cell {
+    /// The character shown.
+    c: char,
+    /// Whether it is bold, by being overstruck with itself.
+    bold: bool,
+}
+
+/// A streaming scanner removing overstriking, like `col -b` does, and telling bold text apart.
+///
+/// Bold text is written as a character, a backspace and the same character again, and underlined text as an underscore, a backspace and the character. Other overstriking, like `+` over `o` for a bullet, shows the last character.
+#[derive(Debug, Default)]
+pub(crate) struct Overstrike {
+    /// The last cell, held back until it's known whether it is overstruck.
+    cell: Option<Cell>,
+    /// Whether a backspace follows the last cell.
+    backspace: bool,
+    /// Text not written yet, whose characters are all bold, or all not.
+    run: String,
+    /// Whether the text not written yet is bold.
+    bold: bool,
+}
+
+impl Overstrike {
+    /// Process a piece of decoded text, bio-reading text that isn't bold, and writing bold text emphasized as a whole.
+    pub(crate) fn push_str(&mut self, bio_reader: &BioReader, text: &str, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        for c in text.chars() {
+            match (c, &mut self.cell) {
+                ('\x08', Some(_)) => self.backspace = true,
+                // Nothing to backspace over
+                ('\x08', None) => {}
+                (c, Some(cell)) if self.backspace => {
+                    cell.bold |= cell.c == c;
+                    // Underlining keeps the character underlined
+                    if c != '_' {
+                        cell.c = c;
+                    }
+                    self.backspace = false;
+                }
+                (c, _) => {
+                    self.push_cell(bio_reader, writer, state)?;
+                    self.cell = Some(Cell { c, bold: false });
+                    if c == '\n' {
+                        // Never overstruck, so that whole lines are written as they come
+                        self.push_cell(bio_reader, writer, state)?;
+                    }
+                }
+            }
+        }
+        // Text is bio-read as it comes, but bold text is held back until it ends, to be emphasized as a whole
+        if !self.bold {
+            self.flush(bio_reader, writer, state)?;
+        }
+        Ok(())
+    }
+    /// Write the rest of the text.
+    pub(crate) fn finish(&mut self, bio_reader: &BioReader, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        self.push_cell(bio_reader, writer, state)?;
+        self.flush(bio_reader, writer, state)
+    }
+    /// Add the held back cell, if any, to the text not written yet, writing that text first if the cell is bold and it isn't, or the other way around.
+    fn push_cell(&mut self, bio_reader: &BioReader, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        let Some(cell) = self.cell.take() else {
+            return Ok(());
+        };
+        self.backspace = false;
+        if cell.bold != self.bold {
+            self.flush(bio_reader, writer, state)?;
+            self.bold = cell.bold;
+        }
+        self.run.push(cell.c);
+        Ok(())
+    }
+    /// Write the text not written yet.
+    fn flush(&mut self, bio_reader: &BioReader, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        if self.run.is_empty() {
+            Ok(())
+        } else if self.bold {
+            bio_reader.push_emphasized(&self.run, writer, state)?;
+            self.run.clear();
+            Ok(())
+        } else {
+            bio_reader.push_str(&self.run, writer, state)?;
+            self.run.clear();
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Markdown input format.
+
+use super::utf8;
+use crate::{BioReader, State};
+use pulldown_cmark::{Event, LinkType, Options, Parser, Tag};
+use std::io::Write;
+
+/// Do bio-reading on the whole Markdown `input`, only emphasizing prose, and write the result to `writer`.
+///
+/// Unlike plain text, Markdown cannot be parsed in a streaming fashion, so the whole input has to be collected before processing.
+pub(crate) fn bio_read(bio_reader: &BioReader, input: &[u8], writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+    let text = utf8(input, "Markdown")?;
+    // How many bytes of `text` have been written
+    let mut written = 0;
+    // Whether each of the currently open tags should be left untouched
+    let mut verbatim_stack = Vec::new();
+    // How many of the currently open tags should be left untouched
+    let mut verbatim_depth = 0;
+    for (event, range) in Parser::new_ext(text, options()).into_offset_iter() {
+        match event {
+            Event::Start(tag) => {
+                let verbatim = is_verbatim(&tag);
+                verbatim_stack.push(verbatim);
+                verbatim_depth += verbatim as usize;
+            }
+            Event::End(_) => {
+                let verbatim = verbatim_stack.pop().unwrap_or_default();
+                verbatim_depth -= verbatim as usize;
+            }
+            // Only process text that appears as-is in the source, so escapes and entities are kept intact
+            Event::Text(prose) if verbatim_depth == 0 && text[range.clone()] == *prose => {
+                // A character escaped with a backslash starts a piece of text without its backslash, so it is written as it is rather than escaped again
+                let start = if text[..range.start].ends_with('\\') {
+                    range.start + prose.chars().next().map_or(0, char::len_utf8)
+                } else {
+                    range.start
+                };
+                if written < start {
+                    bio_reader.push_raw(&text.as_bytes()[written..start], writer, state)?;
+                }
+                bio_reader.push_str(&text[start..range.end], writer, state)?;
+                written = range.end;
+            }
+            _ => {}
+        }
+    }
+    bio_reader.push_raw(&text.as_bytes()[written..], writer, state)
+}
+
+/// Markdown extensions to recognize, so that their syntax is not mistaken for prose.
+fn options() -> Options {
+    Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_HEADING_ATTRIBUTES
+        | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS
+        | Options::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS
+        | Options::ENABLE_MATH
+}
+
+/// Whether the text inside `tag` should be left untouched.
+fn is_verbatim(tag: &Tag) -> bool {
+    match tag {
+        Tag::CodeBlock(_) | Tag::HtmlBlock | Tag::MetadataBlock(_) | Tag::Image { .. } => true,
+        // The text of autolinks is the destination itself
+        Tag::Link { link_type, .. } => matches!(link_type, LinkType::Autolink | LinkType::Email),
+        _ => false,
+    }
+}
@@ -0,0 +1,81 @@
+//! Org input format.
+
+use super::{bio_read_prose, lines, utf8, without_markup};
+use crate::{BioReader, State};
+use regex::Regex;
+use std::{
+    io::Write,
+    sync::LazyLock,
+};
+
+/// Blocks whose content is prose, which is bio-read.
+const PROSE_BLOCKS: [&str; 3] = ["quote", "center", "verse"];
+
+/// Matches the start of blocks, like `#+BEGIN_SRC rust`, capturing their name.
+static BLOCK: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)^\s*#\+begin_(\S+)").unwrap());
+
+/// Matches the start of drawers, like `:PROPERTIES:`.
+static DRAWER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*:[\w-]+:\s*$").unwrap());
+
+/// Matches the end of drawers.
+static DRAWER_END: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)^\s*:end:\s*$").unwrap());
+
+/// Matches headlines, capturing their title, without the stars, the keyword, the priority and the tags.
+static HEADLINE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\*+\s+(?:(?:TODO|DONE)\s+)?(?:\[#[A-Za-z0-9]\]\s+)?(.*?)(?:\s+:[\w@#%:]+:)?\s*$").unwrap());
+
+/// Matches lines that are left untouched: comments, keywords like `#+TITLE:`, tables, fixed-width lines and planning lines like `SCHEDULED: <2024-01-01>`.
+static VERBATIM_LINE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*(?:#(?:\s|\+|$)|\||:(?:\s|$)|(?:SCHEDULED|DEADLINE|CLOSED):)").unwrap());
+
+/// Matches inline markup that is left untouched.
+static MARKUP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        // Links, leaving their description to be bio-read, like [[https://example.com][Example]]
+        r"\[\[[^\]\n]*\](?:\[|\])|\]\]",
+        // Bold text, which can't be nested, and verbatim and code
+        r"|\*[^*\s](?:[^*\n]*[^*\s])?\*|=[^=\s](?:[^=\n]*[^=\s])?=|~[^~\s](?:[^~\n]*[^~\s])?~",
+        // Timestamps, like <2024-01-01 Mon>
+        r"|[<\[]\d{4}-\d{2}-\d{2}[^>\]\n]*[>\]]",
+        // Checkboxes, footnote references, targets and macros
+        r"|\[[ Xx-]\]|\[fn:[^\]\n]*\]|<<[^>\n]*>>|\{\{\{.*?\}\}\}",
+    ))
+    .unwrap()
+});
+
+/// Do bio-reading on the whole Org `input`, only emphasizing prose, and write the result to `writer`.
+///
+/// Headline stars, keywords, priorities and tags, blocks other than quotes, centered text and verses, drawers, comments, keywords, tables, fixed-width lines, planning lines, and inline markup like links, bold text, verbatim, code and timestamps are left untouched. The descriptions of links are bio-read.
+pub(crate) fn bio_read(bio_reader: &BioReader, input: &[u8], writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+    let text = utf8(input, "Org")?;
+    let lines = lines(text);
+    let mut prose = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = &lines[i];
+        if let Some(captures) = BLOCK.captures(line.content) {
+            let end_marker = format!("#+end_{}", captures[1].to_ascii_lowercase());
+            let end = lines[i + 1..]
+                .iter()
+                .position(|line| line.content.trim_start().to_ascii_lowercase().starts_with(&end_marker))
+                .map_or(lines.len(), |end| i + 1 + end);
+            if PROSE_BLOCKS.contains(&captures[1].to_ascii_lowercase().as_str()) {
+                prose.extend(lines[i + 1..end].iter().map(|line| line.start..line.start + line.content.len()));
+            }
+            i = end + 1;
+            continue;
+        }
+        if DRAWER.is_match(line.content) && !DRAWER_END.is_match(line.content) {
+            // Drawers that are never closed are just text
+            if let Some(end) = lines[i + 1..].iter().position(|line| DRAWER_END.is_match(line.content)) {
+                i += end + 2;
+                continue;
+            }
+        }
+        if let Some(title) = HEADLINE.captures(line.content).and_then(|captures| captures.get(1)) {
+            prose.push(line.start + title.start()..line.start + title.end());
+        } else if !VERBATIM_LINE.is_match(line.content) {
+            prose.push(line.start..line.start + line.content.len());
+        }
+        i += 1;
+    }
+    bio_read_prose(bio_reader, text, without_markup(text, prose, &MARKUP), writer, state)
+}
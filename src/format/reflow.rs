@@ -0,0 +1,277 @@
+//! Reflowing of plain text input to fit a width, optionally justified, with a hanging indent and laid out in columns. See [`BioReader::reflow`](crate::BioReader::reflow).
+
+use crate::{BioReader, State};
+use regex::Regex;
+use std::{collections::VecDeque, io::Write, sync::LazyLock};
+use unicode_width::UnicodeWidthStr;
+
+/// Matches terminal escape sequences, like ANSI styles and OSC 8 hyperlinks, which take up no room.
+static ESCAPE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\x1b\[[0-?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)").unwrap());
+
+/// The width of the gutter between columns, in columns of the terminal.
+const GUTTER: usize = 4;
+
+/// A streaming scanner joining the lines of each paragraph and breaking them again to fit a width, before the text is bio-read, so that the width of the output doesn't depend on the markup added to it.
+///
+/// Paragraphs are separated by blank lines, which are kept as they are, along with the indentation of their first lines. Each run of non-whitespace characters is held back until it ends, to tell whether it fits on the current line, and so is the whitespace before it. Justified lines are held back until they are full.
+#[derive(Debug)]
+pub(crate) struct Reflow {
+    /// The width to fit lines into, in columns.
+    width: usize,
+    /// Whether to justify lines, stretching the spaces between words so that lines other than the last of each paragraph fill the width.
+    justify: bool,
+    /// How many columns to indent the lines of each paragraph after the first by.
+    hanging_indent: usize,
+    /// The width of the current line of output so far.
+    column: usize,
+    /// The current line of output, held back until it is full if lines are justified.
+    line: String,
+    /// Where the spaces between words are in the current line.
+    gaps: Vec<usize>,
+    /// Whether a run of non-whitespace characters has been written in the current paragraph.
+    started: bool,
+    /// The whitespace after the last run of non-whitespace characters.
+    space: String,
+    /// The current run of non-whitespace characters.
+    run: String,
+    /// The reflowed text to bio-read, reused across pieces of text.
+    output: String,
+    /// The layout of the bio-read lines in columns, if there are several.
+    columns: Option<Columns>,
+}
+
+impl Reflow {
+    /// Create a scanner reflowing text as `bio_reader` is set to. With several columns, each gets its share of the width, less the gutters between them.
+    pub(crate) fn new(bio_reader: &BioReader) -> Self {
+        let columns = (bio_reader.columns > 1).then(|| Columns::new(bio_reader.reflow, bio_reader.columns, bio_reader.page_height));
+        Self {
+            width: columns.as_ref().map_or(bio_reader.reflow, |columns| columns.width),
+            justify: bio_reader.justify,
+            hanging_indent: bio_reader.hanging_indent,
+            column: 0,
+            line: String::new(),
+            gaps: Vec::new(),
+            started: false,
+            space: String::new(),
+            run: String::new(),
+            output: String::new(),
+            columns,
+        }
+    }
+    /// Process a piece of decoded text, bio-reading it reflowed.
+    pub(crate) fn push_str(&mut self, bio_reader: &BioReader, text: &str, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        for c in text.chars() {
+            if c.is_whitespace() {
+                self.end_run();
+                self.space.push(c);
+            } else {
+                self.run.push(c);
+            }
+        }
+        if self.run.len() > bio_reader.max_word_len {
+            // Written as it is, with the rest of the run following it on the same line
+            self.end_run();
+        }
+        self.write(bio_reader, writer, state)
+    }
+    /// Pass input that isn't text through untouched, like invalid UTF-8, after the held back run and the whitespace before it.
+    pub(crate) fn push_raw(&mut self, bio_reader: &BioReader, bytes: &[u8], writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        self.flush(bio_reader, writer, state)?;
+        match &mut self.columns {
+            Some(columns) => {
+                // Taken to be one column per byte, like the replacement characters a terminal would show
+                columns.current += bytes.len();
+                bio_reader.push_raw(bytes, &mut columns.output, state)?;
+                columns.write_pages(writer)
+            }
+            None => bio_reader.push_raw(bytes, writer, state),
+        }
+    }
+    /// Write the held back run and the whitespace before it.
+    fn flush(&mut self, bio_reader: &BioReader, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        self.end_run();
+        self.end_line(false);
+        self.output.push_str(&self.space);
+        self.space.clear();
+        self.write(bio_reader, writer, state)
+    }
+    /// Write the rest of the text, with the whitespace at its end as it is.
+    pub(crate) fn finish(&mut self, bio_reader: &BioReader, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        self.flush(bio_reader, writer, state)?;
+        if let Some(columns) = &mut self.columns {
+            // The last word is held back until the end of the text, and belongs to the last page
+            bio_reader.end_text(&mut columns.output, state)?;
+            columns.finish(writer)?;
+        }
+        Ok(())
+    }
+    /// Bio-read the reflowed text.
+    fn write(&mut self, bio_reader: &BioReader, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        if !self.justify {
+            // Nothing to stretch, so the line can be written as it comes
+            self.end_line(false);
+        }
+        if !self.output.is_empty() {
+            match &mut self.columns {
+                Some(columns) => {
+                    columns.measure(&self.output);
+                    bio_reader.push_str(&self.output, &mut columns.output, state)?;
+                    columns.write_pages(writer)?;
+                }
+                None => bio_reader.push_str(&self.output, writer, state)?,
+            }
+            self.output.clear();
+        }
+        Ok(())
+    }
+    /// End the current run (if any), adding it to the current line after a space, or to a new line if it doesn't fit.
+    fn end_run(&mut self) {
+        if self.run.is_empty() {
+            return;
+        }
+        let width = display_width(&self.run);
+        if !self.started || self.space.matches('\n').count() >= 2 {
+            // The start of a paragraph, after the blank lines and indentation before it
+            self.end_line(false);
+            self.line.push_str(&self.space);
+            self.column = match self.space.rsplit_once('\n') {
+                Some((_, indentation)) => display_width(indentation),
+                None => self.column + display_width(&self.space),
+            };
+        } else if self.space.is_empty() {
+            // The rest of a run cut short
+        } else if self.column + 1 + width > self.width {
+            self.end_line(true);
+            self.output.push('\n');
+            self.output.extend(std::iter::repeat_n(' ', self.hanging_indent));
+            self.column = self.hanging_indent;
+        } else {
+            self.gaps.push(self.line.len());
+            self.line.push(' ');
+            self.column += 1;
+        }
+        self.line.push_str(&self.run);
+        self.column += width;
+        self.started = true;
+        self.space.clear();
+        self.run.clear();
+    }
+    /// Add the current line to the output, justified if lines are and `full`, i.e. broken because the next run didn't fit.
+    fn end_line(&mut self, full: bool) {
+        if full && self.justify && !self.gaps.is_empty() {
+            // Spread the missing columns over the gaps, the first ones getting one more if they don't divide evenly
+            let missing = self.width.saturating_sub(self.column);
+            let (each, more) = (missing / self.gaps.len(), missing % self.gaps.len());
+            for (i, &gap) in self.gaps.iter().enumerate().rev() {
+                let extra = each + usize::from(i < more);
+                self.line.insert_str(gap, &" ".repeat(extra));
+            }
+        }
+        self.output.push_str(&self.line);
+        self.line.clear();
+        self.gaps.clear();
+    }
+}
+
+/// A layout of bio-read lines in columns side by side, like a newspaper, filling the columns of each page one after another.
+///
+/// The widths of the lines are measured on the reflowed text before it is bio-read, and matched with the lines of the output in order, so that columns line up whatever markup the renderer adds. Lines are held back until a page is full, or until the end of the text, whose lines are balanced over the columns.
+#[derive(Debug)]
+struct Columns {
+    /// How many columns there are.
+    count: usize,
+    /// The width of each column.
+    width: usize,
+    /// How many lines each column of a page has, or 0 to lay out the whole text as a single page.
+    page_height: usize,
+    /// The widths of the reflowed lines whose output isn't complete yet.
+    widths: VecDeque<usize>,
+    /// The width of the current reflowed line so far.
+    current: usize,
+    /// The bio-read output not split into lines yet.
+    output: Vec<u8>,
+    /// The bio-read lines of the current page, with their widths.
+    lines: Vec<(Vec<u8>, usize)>,
+}
+
+impl Columns {
+    /// Create a layout of `count` columns sharing `width`, with pages of `page_height` lines.
+    fn new(width: usize, count: usize, page_height: usize) -> Self {
+        Self {
+            count,
+            width: (width.saturating_sub(GUTTER * (count - 1)) / count).max(1),
+            page_height,
+            widths: VecDeque::new(),
+            current: 0,
+            output: Vec::new(),
+            lines: Vec::new(),
+        }
+    }
+    /// Measure the lines of a piece of reflowed text, about to be bio-read.
+    fn measure(&mut self, text: &str) {
+        let mut lines = text.split('\n');
+        self.current += lines.next().map_or(0, display_width);
+        for line in lines {
+            self.widths.push_back(self.current);
+            self.current = display_width(line);
+        }
+    }
+    /// Split the complete lines off the output, and write the pages they fill.
+    fn write_pages(&mut self, writer: &mut impl Write) -> std::io::Result<()> {
+        let mut start = 0;
+        while let Some(end) = self.output[start..].iter().position(|&byte| byte == b'\n') {
+            let width = self.widths.pop_front().unwrap_or_default();
+            self.lines.push((self.output[start..start + end].to_vec(), width));
+            start += end + 1;
+        }
+        self.output.drain(..start);
+        let page = self.count * self.page_height;
+        while page > 0 && self.lines.len() >= page {
+            let lines: Vec<_> = self.lines.drain(..page).collect();
+            self.write_page(&lines, true, writer)?;
+        }
+        Ok(())
+    }
+    /// Write the rest of the lines, including the last one if it doesn't end with a line break, in which case neither does the last row.
+    fn finish(&mut self, writer: &mut impl Write) -> std::io::Result<()> {
+        self.write_pages(writer)?;
+        let line_break = self.output.is_empty();
+        if !line_break {
+            self.lines.push((std::mem::take(&mut self.output), self.current));
+        }
+        let lines = std::mem::take(&mut self.lines);
+        self.write_page(&lines, line_break, writer)
+    }
+    /// Write `lines` as a page, as few lines tall as the columns allow, padding the lines of each row to the width of their columns, unless nothing follows them. Columns holding a word too long to fit are widened to it on the page, so that the rows still line up. Rows end with a line break, except for the last one unless `line_break`.
+    fn write_page(&self, lines: &[(Vec<u8>, usize)], line_break: bool, writer: &mut impl Write) -> std::io::Result<()> {
+        let height = lines.len().div_ceil(self.count);
+        if height == 0 {
+            return Ok(());
+        }
+        let widths: Vec<_> = lines.chunks(height).map(|column| column.iter().map(|(_, width)| *width).fold(self.width, usize::max)).collect();
+        for row in 0..height {
+            let mut padding = 0;
+            for ((line, width), column_width) in lines.iter().skip(row).step_by(height).zip(&widths) {
+                if !line.is_empty() {
+                    write!(writer, "{:padding$}", "")?;
+                    writer.write_all(line)?;
+                    padding = 0;
+                }
+                padding += column_width.saturating_sub(*width) + GUTTER;
+            }
+            if line_break || row + 1 < height {
+                writer.write_all(b"\n")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Get the width of `text` in columns, leaving out terminal escape sequences.
+fn display_width(text: &str) -> usize {
+    if text.contains('\x1b') {
+        ESCAPE.replace_all(text, "").width()
+    } else {
+        text.width()
+    }
+}
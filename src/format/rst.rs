@@ -0,0 +1,134 @@
+//! reStructuredText input format.
+
+use super::{bio_read_prose, lines, utf8, without_markup, Line};
+use crate::{BioReader, State};
+use regex::Regex;
+use std::{
+    io::Write,
+    ops::Range,
+    sync::LazyLock,
+};
+
+/// Directives whose content is prose, which is bio-read.
+const ADMONITIONS: [&str; 14] = [
+    "admonition",
+    "attention",
+    "caution",
+    "danger",
+    "error",
+    "hint",
+    "important",
+    "note",
+    "tip",
+    "warning",
+    "seealso",
+    "topic",
+    "sidebar",
+    "rubric",
+];
+
+/// Matches the start of explicit markup, like directives, comments, targets and footnotes, capturing the name of the directive, if any.
+static EXPLICIT_MARKUP: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*\.\.(?:\s+(?:([\w:+-]+)::)?|$)").unwrap());
+
+/// Matches the name of a field, like `:param x:`.
+static FIELD: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*:[^:\s][^:]*:(?:\s|$)").unwrap());
+
+/// Matches inline markup that is left untouched.
+static MARKUP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        // Inline literals
+        r"(?s)``.+?``",
+        // Roles, interpreted text and references, like :ref:`intro`, `text`_ and `text <url>`__
+        r"|(?::[\w.+:-]+:)?`[^`]+`(?::[\w.+:-]+:|__?)?",
+        // Strong emphasis and emphasis, which can't be nested
+        r"|\*\*[^*\s](?:[^*]*[^*\s])?\*\*|\*[^*\s](?:[^*]*[^*\s])?\*",
+        // Substitution, footnote and citation references
+        r"|\|[^|\s](?:[^|]*[^|\s])?\|(?:__?)?|\[[\w#*.-]+\]_",
+        // Simple references, like Python_
+        r"|\b\w[\w.-]*_\b",
+        // Options, like --format
+        r"|\B--?[a-zA-Z][\w-]*",
+        // Standalone hyperlinks
+        r#"|\b[a-zA-Z][a-zA-Z0-9+.-]*://\S*[^\s.,;:!?'")\]}>]"#,
+    ))
+    .unwrap()
+});
+
+/// Do bio-reading on the whole reStructuredText `input`, only emphasizing prose, and write the result to `writer`.
+///
+/// Section titles, directives other than admonitions, comments, targets, literal and doctest blocks, field names, and inline markup like literals, roles, references and substitutions are left untouched.
+pub(crate) fn bio_read(bio_reader: &BioReader, input: &[u8], writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+    let text = utf8(input, "reStructuredText")?;
+    let mut prose = Vec::new();
+    block(&lines(text), &mut prose);
+    bio_read_prose(bio_reader, text, without_markup(text, prose, &MARKUP), writer, state)
+}
+
+/// Push the byte ranges of prose in `lines` to `prose`.
+fn block(lines: &[Line], prose: &mut Vec<Range<usize>>) {
+    // Indentation of the paragraph introducing a literal block with "::", if any
+    let mut literal = None;
+    let mut i = 0;
+    while i < lines.len() {
+        let line = &lines[i];
+        if line.is_blank() {
+            i += 1;
+            continue;
+        }
+        let indent = line.indent();
+        if let Some(literal) = literal.take().filter(|&literal| indent > literal) {
+            i = indented_end(lines, i, literal);
+            continue;
+        }
+        if let Some(captures) = EXPLICIT_MARKUP.captures(line.content) {
+            let end = indented_end(lines, i + 1, indent);
+            let name = captures.get(1).map(|name| name.as_str().to_ascii_lowercase());
+            if name.is_some_and(|name| ADMONITIONS.contains(&name.as_str())) {
+                // The rest of the line is a title or the start of the content
+                prose.push(line.start + captures[0].len()..line.end);
+                // Skip options, like `:class: note`
+                let body = i + 1 + lines[i + 1..end].iter().take_while(|line| FIELD.is_match(line.content)).count();
+                block(&lines[body..end], prose);
+            }
+            i = end;
+            continue;
+        }
+        if line.content.trim_start().starts_with(">>>") {
+            // Doctest blocks end at a blank line
+            i += lines[i..].iter().take_while(|line| !line.is_blank()).count();
+            continue;
+        }
+        if lines.get(i + 1).is_some_and(|next| is_adornment(next.content)) {
+            // Section titles can't be longer than their underline
+            i += 2;
+            continue;
+        }
+        let start = FIELD.find(line.content).map_or(0, |field| field.end());
+        prose.push(line.start + start..line.end);
+        if line.content.trim_end().ends_with("::") {
+            literal = Some(indent);
+        }
+        i += 1;
+    }
+}
+
+/// Get the index of the end of the block starting at `i`, made up of lines indented more than `indent`, leaving out trailing blank lines.
+fn indented_end(lines: &[Line], i: usize, indent: usize) -> usize {
+    let mut end = i;
+    for (j, line) in lines.iter().enumerate().skip(i) {
+        if line.is_blank() {
+            continue;
+        }
+        if line.indent() <= indent {
+            break;
+        }
+        end = j + 1;
+    }
+    end
+}
+
+/// Whether `line` is made up of a repeated punctuation character, like the underline of a section title.
+fn is_adornment(line: &str) -> bool {
+    let line = line.trim_end();
+    line.chars().next().is_some_and(|first| first.is_ascii_punctuation() && line.chars().all(|c| c == first))
+}
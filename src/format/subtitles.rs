@@ -0,0 +1,32 @@
+//! Subtitle input format, for SubRip (SRT) and WebVTT files.
+
+use super::{bio_read_prose, lines, utf8, without_markup};
+use crate::{BioReader, State};
+use regex::Regex;
+use std::{
+    io::Write,
+    sync::LazyLock,
+};
+
+/// Matches inline markup that is left untouched: tags like `<i>`, `<v Speaker>` and `<00:01.000>`, character references, and SubRip positioning like `{\an8}`.
+static MARKUP: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^<>\n]*>|&(?:#\d+|#x[0-9A-Fa-f]+|[A-Za-z]+);|\{\\[^{}\n]*\}").unwrap());
+
+/// Do bio-reading on the whole SubRip or WebVTT `input`, only emphasizing the text of cues, and write the result to `writer`.
+///
+/// Cue numbers and identifiers, timings, cue settings, WebVTT headers, notes, styles and regions, and inline markup like tags are left untouched.
+pub(crate) fn bio_read(bio_reader: &BioReader, input: &[u8], writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+    let text = utf8(input, "Subtitle")?;
+    let mut prose = Vec::new();
+    // Whether the lines are the text of a cue, which follows its timing up to a blank line
+    let mut in_cue = false;
+    for line in lines(text) {
+        if line.is_blank() {
+            in_cue = false;
+        } else if line.content.contains("-->") {
+            in_cue = true;
+        } else if in_cue {
+            prose.push(line.start..line.start + line.content.len());
+        }
+    }
+    bio_read_prose(bio_reader, text, without_markup(text, prose, &MARKUP), writer, state)
+}
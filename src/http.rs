@@ -0,0 +1,642 @@
+//! Fetching articles and feeds from the web, and extracting the main content of pages like the reader view of a browser does. Requires the `http` feature.
+
+use quick_xml::{
+    escape::resolve_predefined_entity,
+    events::{BytesStart, Event},
+    Reader, XmlVersion,
+};
+use regex::Regex;
+use std::{
+    fmt::Display,
+    io::{Error, ErrorKind, Result},
+    sync::LazyLock,
+};
+
+/// Comments, and elements whose content is never text of the page.
+static HIDDEN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?is)<!--.*?-->|<script\b.*?</script\s*>|<style\b.*?</style\s*>|<noscript\b.*?</noscript\s*>|<template\b.*?</template\s*>|<svg\b.*?</svg\s*>").unwrap()
+});
+/// A start or end tag, capturing the slash of end tags, the name and the attributes, or a declaration.
+static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"(?s)<(/?)([a-zA-Z][a-zA-Z0-9-]*)((?:[^>"']|"[^"]*"|'[^']*')*)>|<[!?][^>]*>"#).unwrap());
+/// The value of a class or id attribute.
+static CLASS_OR_ID: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"(?i)(?:^|\s)(?:class|id)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'=<>`]+))"#).unwrap());
+/// A character reference.
+static ENTITY: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"&(?:#([0-9]{1,7})|#[xX]([0-9a-fA-F]{1,6})|([a-zA-Z]+));").unwrap());
+/// Classes and ids of elements that are unlikely to be part of the content, like comments and sidebars.
+static UNLIKELY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"-ad-|ai2html|banner|breadcrumbs|combx|comment|community|cover-wrap|disqus|extra|footer|gdpr|header|legends|menu|related|remark|replies|rss|shoutbox|sidebar|skyscraper|social|sponsor|supplemental|ad-break|agegate|pagination|pager|popup|yom-remote").unwrap()
+});
+/// Classes and ids that make an element likely to be part of the content after all.
+static MAYBE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"and|article|body|column|content|main|shadow").unwrap());
+/// Classes and ids of elements that usually hold the content.
+static POSITIVE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"article|body|content|entry|hentry|h-entry|main|page|pagination|post|text|blog|story").unwrap());
+/// Classes and ids of elements that usually don't hold the content.
+static NEGATIVE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"-ad-|hidden|^hid$| hid$| hid |^hid |banner|combx|comment|com-|contact|foot|footer|footnote|gdpr|masthead|media|meta|outbrain|promo|related|scroll|share|shoutbox|sidebar|skyscraper|sponsor|shopping|tags|tool|widget").unwrap()
+});
+
+/// Elements that are never part of the content.
+const EXCLUDED_ELEMENTS: [&str; 8] = ["nav", "aside", "footer", "form", "button", "select", "dialog", "menu"];
+/// Elements that never have content, so that they need no end tag.
+const VOID_ELEMENTS: [&str; 14] = ["area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source", "track", "wbr"];
+/// Elements that start a paragraph of their own.
+const PARAGRAPH_ELEMENTS: [&str; 23] = [
+    "address", "article", "aside", "blockquote", "details", "div", "dl", "figcaption", "figure", "footer", "h1", "h2", "h3", "h4", "h5", "h6", "header", "hr", "main", "ol", "p", "pre", "section",
+];
+/// Elements that start a line of their own.
+const LINE_ELEMENTS: [&str; 6] = ["dd", "dt", "li", "summary", "table", "tr"];
+/// Paragraphs shorter than this many characters are not counted when looking for the content.
+const MIN_PARAGRAPH_LENGTH: usize = 25;
+
+/// Fetch the page at `url` over HTTP or HTTPS, following redirects, and read it as text, replacing invalid UTF-8. Fails on error statuses, and on pages over 10 MiB.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use bio_read::{http, BioReader};
+/// let page = http::fetch("https://example.com/article").unwrap();
+/// let text = http::extract_text(&page);
+/// println!("{}", BioReader::new().bio_read_text(&text).unwrap());
+/// ```
+pub fn fetch(url: &str) -> Result<String> {
+    let mut response = ureq::get(url)
+        .header("User-Agent", concat!("bio-read/", env!("CARGO_PKG_VERSION")))
+        .call()
+        .map_err(ureq::Error::into_io)?;
+    response.body_mut().read_to_string().map_err(ureq::Error::into_io)
+}
+
+/// Extract the main content of the HTML page `html` as plain text, leaving out navigation, sidebars, comments and the like.
+///
+/// Like the reader view of browsers, each element is scored by the paragraphs in it, favoring long paragraphs with few links, and the best one is taken, along with siblings that look like content too. Without any paragraphs, the whole body is taken. The result starts with the headline of the page, from its first `<h1>` or else its `<title>`, and has a blank line between paragraphs, with list items on lines of their own.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::http::extract_text;
+/// let page = r#"<title>Fish</title>
+/// <nav><a href="/">Home</a></nav>
+/// <article><h1>Fish &amp; chips</h1><p>A hot dish of fried fish and chips, served with salt and vinegar.</p></article>
+/// <div class="comments"><p>First!</p></div>"#;
+/// assert_eq!(extract_text(page), "Fish & chips\n\nA hot dish of fried fish and chips, served with salt and vinegar.\n");
+/// ```
+pub fn extract_text(html: &str) -> String {
+    let document = Document::parse(html);
+    let mut writer = TextWriter::default();
+    for id in document.content() {
+        document.write_element(id, &mut writer);
+    }
+    headed(&document.headline(), writer.text)
+}
+
+/// Convert the HTML `html`, like the content of a feed entry, to plain text as a whole, with a blank line between paragraphs and list items on lines of their own. Unlike [`extract_text`], nothing is left out but navigation, forms and elements classed like comments or sidebars.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::http::html_to_text;
+/// assert_eq!(html_to_text("<p>Fish &amp; chips</p><ul><li>Salt<li>Vinegar</ul>"), "Fish & chips\n\n- Salt\n- Vinegar\n");
+/// ```
+pub fn html_to_text(html: &str) -> String {
+    let document = Document::parse(html);
+    let mut writer = TextWriter::default();
+    document.write_element(0, &mut writer);
+    headed("", writer.text)
+}
+
+/// Put `headline` before `text` with a blank line in between, unless `text` starts with it already, and end the result with a line break.
+fn headed(headline: &str, mut text: String) -> String {
+    if !headline.is_empty() && text.lines().next() != Some(headline) {
+        text = if text.is_empty() { headline.to_string() } else { format!("{headline}\n\n{text}") };
+    }
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    text
+}
+
+/// A news feed, like the posts of a blog, in RSS or Atom format.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::http::Feed;
+/// let feed = Feed::parse(r#"<rss version="2.0"><channel><title>Ocean Weekly</title>
+/// <item><title>Octopuses</title><link>https://example.com/octopuses</link><description>&lt;p&gt;They dream.&lt;/p&gt;</description></item>
+/// </channel></rss>"#).unwrap();
+/// assert_eq!(feed.title, "Ocean Weekly");
+/// assert_eq!(feed.entries[0].link.as_deref(), Some("https://example.com/octopuses"));
+/// assert_eq!(feed.entries[0].text(), "Octopuses\n\nThey dream.\n");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Feed {
+    /// The title of the feed.
+    pub title: String,
+    /// The entries, in the order of the feed, which is usually newest first.
+    pub entries: Vec<Entry>,
+}
+
+/// An entry of a [`Feed`], like a blog post.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Entry {
+    /// The title of the entry.
+    pub title: String,
+    /// The URL of the page of the entry, if any.
+    pub link: Option<String>,
+    /// The content of the entry as HTML, or else its summary, which may be empty.
+    pub content: String,
+}
+
+impl Feed {
+    /// Parse the RSS or Atom feed `xml`. Fails if it is not well-formed XML.
+    pub fn parse(xml: &str) -> Result<Self> {
+        let mut feed = Self::default();
+        let mut reader = Reader::from_str(xml);
+        // The entry being read, and whether it has content rather than just a summary
+        let mut entry: Option<(Entry, bool)> = None;
+        let mut text = String::new();
+        loop {
+            match reader.read_event().map_err(invalid)? {
+                Event::Start(element) => {
+                    if matches!(element.local_name().as_ref(), "item" | "entry") {
+                        entry = Some((Entry::default(), false));
+                    }
+                    if let Some((entry, _)) = &mut entry {
+                        if element.local_name().as_ref() == "link" {
+                            entry.link = entry.link.take().or(link_href(&element)?);
+                        }
+                    }
+                    text.clear();
+                }
+                Event::Empty(element) => {
+                    if let Some((entry, _)) = &mut entry {
+                        if element.local_name().as_ref() == "link" {
+                            entry.link = entry.link.take().or(link_href(&element)?);
+                        }
+                    }
+                }
+                Event::Text(content) => text.push_str(&content.xml10_content()),
+                Event::CData(content) => text.push_str(&content.xml10_content()),
+                Event::GeneralRef(reference) => match reference.resolve_char_ref().map_err(invalid)? {
+                    Some(c) => text.push(c),
+                    None => match resolve_predefined_entity(&reference.xml10_content()) {
+                        Some(resolved) => text.push_str(resolved),
+                        // Left for HTML content to resolve
+                        None => {
+                            text.push('&');
+                            text.push_str(&reference.xml10_content());
+                            text.push(';');
+                        }
+                    },
+                },
+                Event::End(element) => {
+                    let text = std::mem::take(&mut text);
+                    match (&mut entry, element.local_name().as_ref()) {
+                        (Some(_), "item" | "entry") => feed.entries.extend(entry.take().map(|(entry, _)| entry)),
+                        (Some((entry, _)), "title") => entry.title = text.trim().to_string(),
+                        (Some((entry, _)), "link") if entry.link.is_none() && !text.trim().is_empty() => entry.link = Some(text.trim().to_string()),
+                        (Some((entry, has_content)), "content" | "encoded") => {
+                            entry.content = text;
+                            *has_content = true;
+                        }
+                        (Some((entry, false)), "description" | "summary") => entry.content = text,
+                        (None, "title") if feed.title.is_empty() => feed.title = text.trim().to_string(),
+                        _ => {}
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        Ok(feed)
+    }
+}
+
+impl Entry {
+    /// The title and content of the entry as plain text, converted with [`html_to_text`].
+    pub fn text(&self) -> String {
+        headed(&self.title, html_to_text(&self.content).trim_end().to_string())
+    }
+}
+
+/// Get the URL of an Atom link to the page of an entry, which has no `rel` or the `alternate` one.
+fn link_href(element: &BytesStart) -> Result<Option<String>> {
+    if attribute(element, "rel")?.is_some_and(|rel| rel != "alternate") {
+        return Ok(None);
+    }
+    Ok(attribute(element, "href")?.map(|href| href.trim().to_string()))
+}
+
+/// Get the unescaped value of the attribute `name` of `element`, if present.
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>> {
+    match element.try_get_attribute(name).map_err(invalid)? {
+        Some(attribute) => Ok(Some(attribute.normalized_value(XmlVersion::Implicit1_0).map_err(invalid)?.into_owned())),
+        None => Ok(None),
+    }
+}
+
+/// Create an error for an invalid feed.
+fn invalid(reason: impl Display) -> Error {
+    Error::new(ErrorKind::InvalidData, format!("Invalid feed: {reason}"))
+}
+
+/// A node in the tree of a page.
+#[derive(Debug)]
+enum Node {
+    /// An element, by its index.
+    Element(usize),
+    /// Text, with character references decoded.
+    Text(String),
+}
+
+/// An element of a page.
+#[derive(Debug)]
+struct Element {
+    /// The name, in lowercase.
+    name: String,
+    /// The class and id, in lowercase.
+    class_and_id: String,
+    /// The index of the parent element. The root is its own parent.
+    parent: usize,
+    /// The child nodes.
+    children: Vec<Node>,
+}
+
+/// The tree of a page, parsed leniently.
+#[derive(Debug)]
+struct Document {
+    /// The elements, in document order, so that parents come before their children. The first is the root, containing the whole page.
+    elements: Vec<Element>,
+    /// Whether each element is left out of the content, with its descendants.
+    excluded: Vec<bool>,
+    /// The number of characters of text in each element, leaving out excluded ones.
+    text_len: Vec<usize>,
+    /// The number of characters of text in links in each element, leaving out excluded ones.
+    link_len: Vec<usize>,
+}
+
+impl Document {
+    /// Parse `html` into a tree, closing elements implicitly where needed.
+    fn parse(html: &str) -> Self {
+        let html = HIDDEN.replace_all(html, "");
+        let mut elements = vec![Element {
+            name: String::new(),
+            class_and_id: String::new(),
+            parent: 0,
+            children: Vec::new(),
+        }];
+        // The open elements, innermost last
+        let mut open = vec![0];
+        let mut last = 0;
+        for tag in TAG.captures_iter(&html) {
+            let whole = tag.get(0).unwrap();
+            push_text(&mut elements, &open, &html[last..whole.start()]);
+            last = whole.end();
+            let Some(name) = tag.get(2) else {
+                continue;
+            };
+            let name = name.as_str().to_ascii_lowercase();
+            if !tag[1].is_empty() {
+                // Close the innermost open element of that name, and everything in it
+                if let Some(i) = open.iter().rposition(|&id| elements[id].name == name).filter(|&i| i > 0) {
+                    open.truncate(i);
+                }
+                continue;
+            }
+            let current = *open.last().unwrap();
+            if elements[current].name == "p" && (PARAGRAPH_ELEMENTS.contains(&name.as_str()) || LINE_ELEMENTS.contains(&name.as_str())) {
+                open.pop();
+            }
+            if matches!(name.as_str(), "li" | "dt" | "dd") {
+                // A list item ends the previous one, unless in a nested list
+                let item = open.iter().rposition(|&id| matches!(elements[id].name.as_str(), "li" | "dt" | "dd"));
+                let list = open.iter().rposition(|&id| matches!(elements[id].name.as_str(), "ul" | "ol" | "dl"));
+                if let Some(item) = item.filter(|&item| list.is_none_or(|list| item > list)) {
+                    open.truncate(item);
+                }
+            }
+            let attributes = &tag[3];
+            let class_and_id = CLASS_OR_ID
+                .captures_iter(attributes)
+                .filter_map(|value| value.get(1).or(value.get(2)).or(value.get(3)))
+                .map(|value| value.as_str().to_lowercase())
+                .collect::<Vec<_>>()
+                .join(" ");
+            let parent = *open.last().unwrap();
+            let id = elements.len();
+            elements[parent].children.push(Node::Element(id));
+            let void = VOID_ELEMENTS.contains(&name.as_str()) || attributes.trim_end().ends_with('/');
+            elements.push(Element {
+                name,
+                class_and_id,
+                parent,
+                children: Vec::new(),
+            });
+            if !void {
+                open.push(id);
+            }
+        }
+        push_text(&mut elements, &open, &html[last..]);
+        Self::new(elements)
+    }
+
+    /// Create a document of `elements`, working out what is excluded and how long the text of each element is.
+    fn new(elements: Vec<Element>) -> Self {
+        let mut excluded = vec![false; elements.len()];
+        for (id, element) in elements.iter().enumerate().skip(1) {
+            let unlikely = !matches!(element.name.as_str(), "html" | "body" | "article" | "main")
+                && UNLIKELY.is_match(&element.class_and_id)
+                && !MAYBE.is_match(&element.class_and_id);
+            excluded[id] = excluded[element.parent] || unlikely || EXCLUDED_ELEMENTS.contains(&element.name.as_str());
+        }
+        let mut text_len = vec![0; elements.len()];
+        let mut link_len = vec![0; elements.len()];
+        // Children come after their parents, so they are done first backwards
+        for (id, element) in elements.iter().enumerate().rev() {
+            if excluded[id] {
+                continue;
+            }
+            for child in &element.children {
+                match child {
+                    Node::Element(child) => {
+                        text_len[id] += text_len[*child];
+                        link_len[id] += link_len[*child];
+                    }
+                    Node::Text(text) => text_len[id] += text.trim().chars().count(),
+                }
+            }
+            if element.name == "a" {
+                link_len[id] = text_len[id];
+            }
+        }
+        Self {
+            elements,
+            excluded,
+            text_len,
+            link_len,
+        }
+    }
+
+    /// The headline of the page, which is the text of its first `<h1>`, or else of its `<title>`.
+    fn headline(&self) -> String {
+        let find = |name| (0..self.elements.len()).find(|&id| self.elements[id].name == name && !self.excluded[id]);
+        let Some(id) = find("h1").or_else(|| find("title")) else {
+            return String::new();
+        };
+        let mut text = TextWriter::default();
+        self.write_children(id, &mut text);
+        text.text
+    }
+
+    /// The elements making up the content of the page, in document order.
+    fn content(&self) -> Vec<usize> {
+        let mut scores: Vec<Option<f64>> = vec![None; self.elements.len()];
+        for (id, element) in self.elements.iter().enumerate() {
+            if self.excluded[id] || !matches!(element.name.as_str(), "p" | "pre" | "td") || self.text_len[id] < MIN_PARAGRAPH_LENGTH {
+                continue;
+            }
+            let mut commas = 0;
+            self.for_each_text(id, &mut |text| commas += text.matches([',', '，', '、']).count());
+            let score = 1.0 + commas as f64 + (self.text_len[id] / 100).min(3) as f64;
+            // The parent gets the score of the paragraph, and the grandparent half of it
+            let parent = element.parent;
+            let grandparent = self.elements[parent].parent;
+            for (ancestor, share) in [(parent, 1.0), (grandparent, 0.5)] {
+                if ancestor > 0 && !self.excluded[ancestor] {
+                    *scores[ancestor].get_or_insert_with(|| self.initial_score(ancestor)) += score * share;
+                }
+            }
+        }
+        let scores: Vec<Option<f64>> = scores.into_iter().enumerate().map(|(id, score)| score.map(|score| score * (1.0 - self.link_density(id)))).collect();
+        let best = scores
+            .iter()
+            .enumerate()
+            .filter_map(|(id, score)| score.map(|score| (id, score)))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b));
+        let Some((best, best_score)) = best else {
+            let body = self.elements.iter().position(|element| element.name == "body").unwrap_or(0);
+            return vec![body];
+        };
+        // Siblings of the best element are taken if they look like content too, like an introduction in an element of its own
+        let threshold = (best_score * 0.2).max(10.0);
+        let parent = self.elements[best].parent;
+        if parent == 0 || self.excluded[parent] {
+            return vec![best];
+        }
+        self.elements[parent]
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                Node::Element(id) => Some(*id),
+                Node::Text(_) => None,
+            })
+            .filter(|&id| {
+                let paragraph = self.elements[id].name == "p" && self.text_len[id] > 80 && self.link_density(id) < 0.25;
+                id == best || (!self.excluded[id] && (scores[id].is_some_and(|score| score >= threshold) || paragraph))
+            })
+            .collect()
+    }
+
+    /// The score an element starts with, from its name, class and id.
+    fn initial_score(&self, id: usize) -> f64 {
+        let element = &self.elements[id];
+        let mut score = match element.name.as_str() {
+            "div" => 5.0,
+            "pre" | "td" | "blockquote" => 3.0,
+            "address" | "ol" | "ul" | "dl" | "dd" | "dt" | "li" | "form" => -3.0,
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.0,
+            _ => 0.0,
+        };
+        if NEGATIVE.is_match(&element.class_and_id) {
+            score -= 25.0;
+        }
+        if POSITIVE.is_match(&element.class_and_id) {
+            score += 25.0;
+        }
+        score
+    }
+
+    /// The share of the text of the element `id` that is in links.
+    fn link_density(&self, id: usize) -> f64 {
+        match self.text_len[id] {
+            0 => 0.0,
+            len => self.link_len[id] as f64 / len as f64,
+        }
+    }
+
+    /// Call `f` with each text node in the element `id`, leaving out excluded elements.
+    fn for_each_text(&self, id: usize, f: &mut impl FnMut(&str)) {
+        for child in &self.elements[id].children {
+            match child {
+                Node::Element(child) if !self.excluded[*child] => self.for_each_text(*child, f),
+                Node::Element(_) => {}
+                Node::Text(text) => f(text),
+            }
+        }
+    }
+
+    /// Write the text of the element `id` to `writer`, breaking lines around blocks.
+    fn write_element(&self, id: usize, writer: &mut TextWriter) {
+        if self.excluded[id] {
+            return;
+        }
+        let element = &self.elements[id];
+        let name = element.name.as_str();
+        if PARAGRAPH_ELEMENTS.contains(&name) {
+            writer.line_break(2);
+        } else if LINE_ELEMENTS.contains(&name) {
+            writer.line_break(1);
+        }
+        match name {
+            "br" => writer.force_line_break(),
+            "li" => {
+                let siblings = &self.elements[element.parent];
+                if siblings.name == "ol" {
+                    let number = siblings
+                        .children
+                        .iter()
+                        .take_while(|child| !matches!(child, Node::Element(child) if *child == id))
+                        .filter(|child| matches!(child, Node::Element(child) if self.elements[*child].name == "li"))
+                        .count()
+                        + 1;
+                    writer.push_str(&format!("{number}."));
+                } else {
+                    writer.push_str("-");
+                }
+                writer.space();
+            }
+            "td" | "th" => writer.space(),
+            _ => {}
+        }
+        if name == "pre" {
+            self.for_each_text(id, &mut |text| writer.push_preformatted(text));
+        } else {
+            self.write_children(id, writer);
+        }
+        if PARAGRAPH_ELEMENTS.contains(&name) {
+            writer.line_break(2);
+        } else if LINE_ELEMENTS.contains(&name) {
+            writer.line_break(1);
+        }
+    }
+
+    /// Write the text of the children of the element `id` to `writer`.
+    fn write_children(&self, id: usize, writer: &mut TextWriter) {
+        for child in &self.elements[id].children {
+            match child {
+                Node::Element(child) => self.write_element(*child, writer),
+                Node::Text(text) => writer.push_str(text),
+            }
+        }
+    }
+}
+
+/// Add the HTML text `text` to the innermost of the `open` elements, decoding character references.
+fn push_text(elements: &mut [Element], open: &[usize], text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    let text = ENTITY.replace_all(text, |entity: &regex::Captures| {
+        let decoded = match (entity.get(1), entity.get(2), entity.get(3)) {
+            (Some(decimal), _, _) => decimal.as_str().parse().ok().and_then(char::from_u32),
+            (_, Some(hex), _) => u32::from_str_radix(hex.as_str(), 16).ok().and_then(char::from_u32),
+            (_, _, Some(name)) => named_entity(name.as_str()),
+            _ => None,
+        };
+        decoded.map_or_else(|| entity[0].to_string(), String::from)
+    });
+    let id = *open.last().unwrap();
+    elements[id].children.push(Node::Text(text.into_owned()));
+}
+
+/// Decode the common named character reference `name`.
+fn named_entity(name: &str) -> Option<char> {
+    Some(match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "shy" => '\u{ad}',
+        "ndash" => '–',
+        "mdash" => '—',
+        "hellip" => '…',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "laquo" => '«',
+        "raquo" => '»',
+        "middot" => '·',
+        "bull" => '•',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        "times" => '×',
+        _ => return None,
+    })
+}
+
+/// Builds plain text from HTML text, collapsing whitespace like browsers do.
+#[derive(Debug, Default)]
+struct TextWriter {
+    /// The text so far.
+    text: String,
+    /// Whether a space goes before the next word.
+    space: bool,
+    /// How many line breaks go before the next word, up to 2 for a blank line.
+    line_breaks: usize,
+}
+
+impl TextWriter {
+    /// Add HTML text, collapsing its whitespace.
+    fn push_str(&mut self, text: &str) {
+        let mut words = text.split(|c: char| c.is_ascii_whitespace());
+        // Whitespace is empty words
+        if let Some(first) = words.next() {
+            self.push_word(first);
+        }
+        for word in words {
+            self.space();
+            self.push_word(word);
+        }
+    }
+    /// Add preformatted text as-is.
+    fn push_preformatted(&mut self, text: &str) {
+        // A line break right after the start tag is ignored
+        let text = if self.line_breaks > 0 { text.trim_start_matches(['\r', '\n']) } else { text };
+        let text = text.trim_end_matches(['\r', '\n']);
+        if !text.is_empty() {
+            self.push_word(text);
+        }
+    }
+    /// Add a word, after the space or line breaks before it.
+    fn push_word(&mut self, word: &str) {
+        if word.is_empty() {
+            return;
+        }
+        if !self.text.is_empty() {
+            if self.line_breaks > 0 {
+                self.text.extend(std::iter::repeat_n('\n', self.line_breaks));
+            } else if self.space {
+                self.text.push(' ');
+            }
+        }
+        self.text.push_str(word);
+        self.space = false;
+        self.line_breaks = 0;
+    }
+    /// Put a space before the next word.
+    fn space(&mut self) {
+        self.space = true;
+    }
+    /// Put at least `count` line breaks before the next word.
+    fn line_break(&mut self, count: usize) {
+        self.line_breaks = self.line_breaks.max(count);
+    }
+    /// Put another line break before the next word, for `<br>`.
+    fn force_line_break(&mut self) {
+        self.line_breaks = (self.line_breaks + 1).min(2);
+    }
+}
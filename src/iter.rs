@@ -0,0 +1,113 @@
+//! Iterator adapters, for bio-reading lines of text as they flow through a pipeline.
+
+use crate::BioReader;
+
+/// Adapts iterators of text, like [`str::lines`] or [`std::io::Lines`], to yield their items bio-read. See [`BioReadExt::bio_read`].
+pub trait BioReadExt: Iterator + Sized {
+    /// Bio-read each item with `reader`, yielding `String`s, or `Result`s of them for fallible iterators like [`std::io::Lines`], whose errors are passed through.
+    ///
+    /// Each item is bio-read on its own, like with [`BioReader::bio_read_text`], so the saccade starts over for every item.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{iter::BioReadExt, BioReader};
+    /// use std::io::BufRead;
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""));
+    /// let lines: Vec<String> = "hello\nworld".lines().bio_read(&reader).collect();
+    /// assert_eq!(lines, ["<em>hel</em>lo", "<em>wor</em>ld"]);
+    /// let lines: Vec<String> = "hello\nworld".as_bytes().lines().bio_read(&reader).collect::<std::io::Result<_>>().unwrap();
+    /// assert_eq!(lines, ["<em>hel</em>lo", "<em>wor</em>ld"]);
+    /// ```
+    fn bio_read(self, reader: &BioReader) -> BioRead<'_, Self> {
+        BioRead { iter: self, reader }
+    }
+}
+
+impl<I: Iterator> BioReadExt for I {}
+
+/// An item of text that can be bio-read by [`BioRead`].
+pub trait Text {
+    /// The bio-read item.
+    type Output;
+    /// Bio-read the text with `reader`.
+    fn bio_read(self, reader: &BioReader) -> Self::Output;
+}
+
+impl Text for &str {
+    type Output = String;
+    fn bio_read(self, reader: &BioReader) -> String {
+        reader.bio_read_text(self).expect("Bio-reading into a String should never fail")
+    }
+}
+
+impl Text for String {
+    type Output = String;
+    fn bio_read(self, reader: &BioReader) -> String {
+        self.as_str().bio_read(reader)
+    }
+}
+
+impl Text for &String {
+    type Output = String;
+    fn bio_read(self, reader: &BioReader) -> String {
+        self.as_str().bio_read(reader)
+    }
+}
+
+impl<T: Text, E> Text for Result<T, E> {
+    type Output = Result<T::Output, E>;
+    fn bio_read(self, reader: &BioReader) -> Self::Output {
+        self.map(|text| text.bio_read(reader))
+    }
+}
+
+/// An iterator yielding the items of another iterator bio-read. Created by [`BioReadExt::bio_read`].
+#[derive(Clone, Debug)]
+pub struct BioRead<'a, I> {
+    /// The underlying iterator.
+    iter: I,
+    /// The reader to bio-read items with.
+    reader: &'a BioReader,
+}
+
+impl<I> Iterator for BioRead<'_, I>
+where
+    I: Iterator,
+    I::Item: Text,
+{
+    type Item = <I::Item as Text>::Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|text| text.bio_read(self.reader))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> DoubleEndedIterator for BioRead<'_, I>
+where
+    I: DoubleEndedIterator,
+    I::Item: Text,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|text| text.bio_read(self.reader))
+    }
+}
+
+impl<I> ExactSizeIterator for BioRead<'_, I>
+where
+    I: ExactSizeIterator,
+    I::Item: Text,
+{
+}
+
+impl<I> std::iter::FusedIterator for BioRead<'_, I>
+where
+    I: std::iter::FusedIterator,
+    I::Item: Text,
+{
+}
@@ -1,37 +1,1552 @@
 //! # Bio Read Library
 //!
 //! The `bio-read` library is an open-source implementation of the Bionic Reading method. Taking inspiration from [text-vide](https://github.com/Gumball12/text-vide/blob/main/HOW.md), this library ports the Bionic Reading method to Rust and provides a CLI for bio-reading text files right from the terminal.
+//!
+//! ## Features
+//!
+//! - `async`: Enables [`BioReader::bio_read_async`], for bio-reading [tokio](https://tokio.rs) streams.
+//! - `capi`: Enables the `ffi` module, with C bindings for embedding this library in C or C++ applications.
+//! - `cjk`: Enables [`segment::Cjk::Segment`], for splitting Chinese text into words with a dictionary.
+//! - `compression`: Decompresses input files compressed with gzip or Zstandard, and compresses output files named with the `.gz` or `.zst` extension. Only affects the binary.
+//! - `docx`: Enables [`InputFormat::Docx`], for bio-reading Word documents.
+//! - `encoding`: Enables UTF-16 and Latin-1 [`encoding::Encoding`]s, for bio-reading input that isn't UTF-8 with [encoding_rs](https://docs.rs/encoding_rs).
+//! - `epub`: Enables the [`epub`] module, for bio-reading EPUB e-books.
+//! - `http`: Enables the [`http`] module, for fetching web pages and feeds and extracting their articles, and `br <URL>` and `br feed`.
+//! - `lang-detect`: Enables [`segment::Language::detect`], for detecting the language of text.
+//! - `mmap`: Memory-maps large input files instead of reading them chunk by chunk, which is faster. Only affects the binary.
+//! - `pdf`: Enables the [`pdf`] module, for bio-reading the text of PDF documents.
+//! - `rayon`: Enables [`BioReader::bio_read_text_parallel`], for bio-reading large texts on multiple threads with [rayon](https://docs.rs/rayon).
+//! - `serde` (default): Implements `Serialize` and `Deserialize` for [`Config`] and the types in it, with [serde](https://serde.rs). Required by the binary.
+//! - `syllable`: Enables [`fixation::Syllable`], for emphasizing the first syllable of each word.
+//! - `tui`: Enables `br --tui`, for reading files in a terminal reader app with scrolling and adjustable emphasis. Only affects the binary.
+//! - `unicode`: Counts letters by grapheme cluster, so that emphasis never splits a cluster, like a Hangul syllable made up of conjoining jamo.
+//! - `wasm-bindgen`: Enables the `wasm` module, with bindings for using this library from JavaScript via [wasm-bindgen](https://rustwasm.github.io/docs/wasm-bindgen/).
+//! - `watch`: Enables `br --watch`, for bio-reading files again whenever they change. Only affects the binary.
 
-use anstyle::Style;
+pub mod config;
+pub mod cues;
+mod decode;
+pub mod display;
+pub mod encoding;
+#[cfg(feature = "epub")]
+pub mod epub;
+pub mod error;
+#[cfg(feature = "capi")]
+pub mod ffi;
+pub mod fixation;
+pub mod format;
+#[cfg(feature = "http")]
+pub mod http;
+pub mod iter;
+mod normalize;
+pub mod observer;
+#[cfg(feature = "pdf")]
+pub mod pdf;
+pub mod processor;
+pub mod reader;
+pub mod reading_time;
+pub mod render;
+pub mod segment;
+mod span;
+pub mod stats;
+pub mod style;
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
+pub mod writer;
+
+pub use config::{BioReaderConfig, Config};
+use encoding::Encoding;
+pub use error::{Error, FixationPointError};
+use fixation::{FixationStrategy, Frequency, Ratio};
+pub use format::{CodeLanguage, InputFormat};
+pub use iter::BioReadExt;
+use observer::Observer;
+pub use processor::BioReadProcessor;
+use processor::Processor;
+pub use reader::BioRead;
+use render::{Ansi, Theme, Wrap};
+use segment::{Cjk, CompoundPolicy, Language, LongWordPolicy, Osc, Sentence, TokenPolicy, WordChars};
+use style::{StyleOverride, WordInfo, WordStyleHook};
+pub use regex::Regex;
+pub use render::{EmphasisRenderer, OutputFormat};
+use span::{CollectingWriter, PositionWriter, SpanRecorder, WordObserver, WordRecorder};
+pub use span::{EmphasisSpan, Segment, SpanKind, Token, WordSpan};
+pub use stats::BioReadStats;
+pub use writer::BioWriter;
 use std::{
-    collections::VecDeque,
-    io::{Read, Write},
+    collections::HashSet,
+    fmt,
+    io::{BufRead, BufWriter, ErrorKind, IntoInnerError, Read, Write},
+    ops::Range,
+    path::Path,
+    sync::Arc,
 };
+use unicode_properties::{GeneralCategoryGroup, UnicodeGeneralCategory};
+#[cfg(feature = "unicode")]
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Size of the chunks read from the underlying reader, and written to the underlying writer.
+const CHUNK_SIZE: usize = 8192;
+/// Minimum size of the pieces of text bio-read on separate threads by [`BioReader::bio_read_text_parallel`].
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_SIZE: usize = 256 * 1024;
+
+/// The default maximum length of words in bytes, far longer than any real word.
+const DEFAULT_MAX_WORD_LEN: usize = 64 * 1024;
+
+/// The number of letters past which words get a secondary emphasis with [`BioReader::secondary_fixation`].
+const SECONDARY_FIXATION_LETTERS: usize = 12;
 
 /// A BioReader object, allowing for customizing the bio-reading experience.
+///
+/// Cloning is cheap: custom renderers and fixation strategies are shared between clones, rather than copied.
+///
+/// # Customization
+///
+/// Methods that can be used to customize the [`BioReader`], most of which have a non-consuming version, or all at once with [`BioReader::from_config`]:
+///
+/// - [`BioReader::emphasize`]
+/// - [`BioReader::de_emphasize`]
+/// - [`BioReader::renderer`]
+/// - [`BioReader::theme`]
+/// - [`BioReader::fixation_point`]
+/// - [`BioReader::try_fixation_point`]
+/// - [`BioReader::fixation_ratio`]
+/// - [`BioReader::fixation_strategy`]
+/// - [`BioReader::frequency_model`]
+/// - [`BioReader::saccade`]
+/// - [`BioReader::sentence_boost`]
+/// - [`BioReader::reset_at_paragraphs`]
+/// - [`BioReader::unicode`]
+/// - [`BioReader::word_chars`]
+/// - [`BioReader::token_policy`]
+/// - [`BioReader::compound_policy`]
+/// - [`BioReader::max_word_len`]
+/// - [`BioReader::long_word_policy`]
+/// - [`BioReader::language`]
+/// - [`BioReader::cjk`]
+/// - [`BioReader::join_cursive`]
+/// - [`BioReader::skip_pattern`]
+/// - [`BioReader::exclude_words`]
+/// - [`BioReader::skip_acronyms`]
+/// - [`BioReader::preserve_urls`]
+/// - [`BioReader::word_style_hook`]
+/// - [`BioReader::secondary_fixation`]
+/// - [`BioReader::reflow`]
+/// - [`BioReader::justify`]
+/// - [`BioReader::hanging_indent`]
+/// - [`BioReader::columns`]
+/// - [`BioReader::page_height`]
+/// - [`BioReader::encoding`]
+/// - [`BioReader::normalize_line_endings`]
+/// - [`BioReader::trim_trailing_whitespace`]
+/// - [`BioReader::collapse_blank_lines`]
+/// - [`BioReader::ascii_punctuation`]
+/// - [`BioReader::format`]
+#[derive(Clone)]
 pub struct BioReader {
-    /// The strings to be wrapped around the emphasized part of a word.
-    emphasize: [String; 2],
-    /// The strings to be wrapped around the de-emphasized part of a word.
-    de_emphasize: [String; 2],
+    /// The renderer to write words with.
+    renderer: Renderer,
     /// Reverse map of fixation boundaries for quick lookup. A word of length `i` or less will be emphasized except for the last `reverse_fixation_boundaries[i]` characters. If the word is longer than `reverse_fixation_boundaries.len()`, `reverse_fixation_boundaries.last().unwrap() + 1` will be used (one more than the last).
     reverse_fixation_boundaries: Vec<usize>,
+    /// The fixation strategy set with [`BioReader::fixation_strategy`], used instead of the fixation boundaries if set.
+    fixation_strategy: Option<Arc<dyn FixationStrategy + Send + Sync>>,
+    /// Whether to treat Unicode letters (instead of ASCII letters only) as word characters.
+    unicode: bool,
+    /// Emphasize every `saccade`-th word only.
+    saccade: usize,
+    /// How many more letters to emphasize in the first word of each sentence.
+    sentence_boost: usize,
+    /// Whether to restart the saccade and sentence tracking at each paragraph.
+    reset_at_paragraphs: bool,
+    /// Which characters, other than letters, make up words.
+    word_chars: WordChars,
+    /// How digits are handled.
+    token_policy: TokenPolicy,
+    /// How compound words joined by hyphens are emphasized.
+    compound_policy: CompoundPolicy,
+    /// The maximum length of words in bytes, beyond which they are not buffered whole.
+    max_word_len: usize,
+    /// What happens to words longer than the maximum length.
+    long_word_policy: LongWordPolicy,
+    /// Patterns of text to pass through untouched.
+    skip_patterns: Vec<Regex>,
+    /// Words to leave un-emphasized, as given.
+    excluded_words: HashSet<String>,
+    /// Words to leave un-emphasized, lowercased following the casing rules of the language.
+    folded_excluded_words: HashSet<String>,
+    /// Whether to leave all-uppercase words like acronyms un-emphasized.
+    skip_acronyms: bool,
+    /// The language of the text.
+    language: Language,
+    /// How to handle runs of Chinese and Japanese characters.
+    cjk: Cjk,
+    /// Whether to keep cursive scripts like Arabic joined where words are split.
+    join_cursive: bool,
+    /// Whether to pass URLs, emails and file paths through untouched.
+    preserve_urls: bool,
+    /// The format of the input.
+    format: InputFormat,
+    /// The character encoding of the input.
+    encoding: Encoding,
+    /// The hook set with [`BioReader::word_style_hook`], overriding how words are written.
+    word_style_hook: Option<WordStyleHook>,
+    /// Whether to emphasize long words a second time midway.
+    secondary_fixation: bool,
+    /// The width to reflow plain text to, or 0 to leave lines as they are.
+    reflow: usize,
+    /// Whether to justify reflowed lines.
+    justify: bool,
+    /// How many columns to indent reflowed lines after the first of each paragraph by.
+    hanging_indent: usize,
+    /// How many columns to lay out reflowed text in.
+    columns: usize,
+    /// How many lines each column of a page has, or 0 to balance the whole text over the columns.
+    page_height: usize,
+    /// Whether to turn CRLF line endings into LF.
+    normalize_line_endings: bool,
+    /// Whether to remove whitespace at the ends of lines.
+    trim_trailing_whitespace: bool,
+    /// Whether to collapse runs of blank lines into one.
+    collapse_blank_lines: bool,
+    /// Whether to replace typographic punctuation with ASCII.
+    ascii_punctuation: bool,
+}
+
+impl fmt::Debug for BioReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("BioReader");
+        match &self.renderer {
+            Renderer::Wrap(wrap) => debug.field("renderer", wrap),
+            Renderer::Custom(_) => debug.field("renderer", &format_args!("<custom>")),
+        };
+        match &self.fixation_strategy {
+            Some(_) => debug.field("fixation_strategy", &format_args!("<custom>")),
+            None => debug.field("fixation_boundaries", &self.reverse_fixation_boundaries),
+        };
+        debug
+            .field("unicode", &self.unicode)
+            .field("saccade", &self.saccade)
+            .field("sentence_boost", &self.sentence_boost)
+            .field("reset_at_paragraphs", &self.reset_at_paragraphs)
+            .field("word_chars", &self.word_chars)
+            .field("token_policy", &self.token_policy)
+            .field("compound_policy", &self.compound_policy)
+            .field("max_word_len", &self.max_word_len)
+            .field("long_word_policy", &self.long_word_policy)
+            .field("skip_patterns", &self.skip_patterns)
+            .field("excluded_words", &self.excluded_words)
+            .field("skip_acronyms", &self.skip_acronyms)
+            .field("language", &self.language)
+            .field("cjk", &self.cjk)
+            .field("join_cursive", &self.join_cursive)
+            .field("preserve_urls", &self.preserve_urls)
+            .field("format", &self.format)
+            .field("word_style_hook", &self.word_style_hook.as_ref().map(|_| format_args!("<custom>")))
+            .field("secondary_fixation", &self.secondary_fixation)
+            .field("reflow", &self.reflow)
+            .field("justify", &self.justify)
+            .field("hanging_indent", &self.hanging_indent)
+            .field("columns", &self.columns)
+            .field("page_height", &self.page_height)
+            .field("encoding", &self.encoding)
+            .field("normalize_line_endings", &self.normalize_line_endings)
+            .field("trim_trailing_whitespace", &self.trim_trailing_whitespace)
+            .field("collapse_blank_lines", &self.collapse_blank_lines)
+            .field("ascii_punctuation", &self.ascii_punctuation)
+            .finish()
+    }
+}
+
+impl Default for BioReader {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl BioReader {
     /// Create a new BioReader object.
     pub fn new() -> Self {
-        let bold = Style::new().bold();
-        let dim = Style::new().dimmed();
         Self {
-            emphasize: [format!("{bold}"), format!("{bold:#}")],
-            de_emphasize: [format!("{dim}"), format!("{dim:#}")],
+            renderer: Renderer::Wrap(Wrap::from(Ansi::default())),
             reverse_fixation_boundaries: Self::reverse_fixation_boundaries(3),
+            fixation_strategy: None,
+            saccade: 1,
+            sentence_boost: 0,
+            reset_at_paragraphs: false,
+            unicode: true,
+            word_chars: WordChars::default(),
+            token_policy: TokenPolicy::SplitAtDigits,
+            compound_policy: CompoundPolicy::Whole,
+            max_word_len: DEFAULT_MAX_WORD_LEN,
+            long_word_policy: LongWordPolicy::Plain,
+            skip_patterns: Vec::new(),
+            excluded_words: HashSet::new(),
+            folded_excluded_words: HashSet::new(),
+            skip_acronyms: false,
+            language: Language::English,
+            cjk: Cjk::PassThrough,
+            join_cursive: true,
+            preserve_urls: false,
+            format: InputFormat::Plain,
+            word_style_hook: None,
+            secondary_fixation: false,
+            reflow: 0,
+            justify: false,
+            hanging_indent: 0,
+            columns: 1,
+            page_height: 0,
+            encoding: Encoding::Auto,
+            normalize_line_endings: false,
+            trim_trailing_whitespace: false,
+            collapse_blank_lines: false,
+            ascii_punctuation: false,
+        }
+    }
+    /// Create a new BioReader object from a [`Config`], leaving unset fields at their defaults. Fails with [`Error::FixationPoint`] or [`Error::Config`], naming the offending option, if the fixation point or ratio is out of range, both are set, the saccade, the maximum word length or the number of columns is 0, a skip pattern is invalid, a wrapper lacks `{}`, or a style in the theme is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{BioReader, Config, InputFormat};
+    /// let config = Config {
+    ///     emphasize: Some(String::from("<em>{}</em>")),
+    ///     de_emphasize: Some(String::from("{}")),
+    ///     format: Some(InputFormat::Html),
+    ///     ..Config::default()
+    /// };
+    /// let reader = BioReader::from_config(&config).unwrap();
+    /// assert_eq!(reader.bio_read_text("<p>hello</p>").unwrap(), "<p><em>hel</em>lo</p>");
+    /// let config = Config {
+    ///     fixation_point: Some(6),
+    ///     ..Config::default()
+    /// };
+    /// assert!(BioReader::from_config(&config).is_err());
+    /// ```
+    pub fn from_config(config: &Config) -> Result<Self, Error> {
+        let mut reader = Self::new();
+        if let Some(fixation_point) = config.fixation_point {
+            reader = reader.try_fixation_point(fixation_point)?;
+        }
+        if let Some(ratio) = config.fixation_ratio {
+            if config.fixation_point.is_some() {
+                return Err(Error::config("fixation-ratio", "Fixation point and fixation ratio cannot be used together"));
+            }
+            if !(0.0..=1.0).contains(&ratio) {
+                return Err(Error::config("fixation-ratio", format!("Fixation ratio should be in range [0, 1], but got {}", ratio)));
+            }
+            reader = reader.fixation_ratio(ratio);
+        }
+        if let Some(saccade) = config.saccade {
+            if saccade == 0 {
+                return Err(Error::config("saccade", "Saccade should be at least 1"));
+            }
+            reader = reader.saccade(saccade);
+        }
+        if let Some(sentence_boost) = config.sentence_boost {
+            reader = reader.sentence_boost(sentence_boost);
+        }
+        if let Some(reset_at_paragraphs) = config.reset_at_paragraphs {
+            reader = reader.reset_at_paragraphs(reset_at_paragraphs);
+        }
+        if let Some(token_policy) = config.token_policy {
+            reader = reader.token_policy(token_policy);
+        }
+        if let Some(compound_policy) = config.compound_policy {
+            reader = reader.compound_policy(compound_policy);
+        }
+        if let Some(max_word_len) = config.max_word_len {
+            if max_word_len == 0 {
+                return Err(Error::config("max-word-len", "Maximum word length should be at least 1"));
+            }
+            reader = reader.max_word_len(max_word_len);
         }
+        if let Some(long_word_policy) = config.long_word_policy {
+            reader = reader.long_word_policy(long_word_policy);
+        }
+        if let Some(language) = config.language {
+            reader = reader.language(language);
+        }
+        if let Some(joiners) = &config.joiners {
+            reader = reader.word_chars(WordChars { joiners: joiners.chars().collect() });
+        }
+        if let Some(cjk) = config.cjk {
+            reader = reader.cjk(cjk);
+        }
+        if let Some(join_cursive) = config.join_cursive {
+            reader = reader.join_cursive(join_cursive);
+        }
+        if let Some(words) = &config.exclude_words {
+            reader = reader.exclude_words(words.iter().cloned().collect());
+        }
+        if let Some(skip_acronyms) = config.skip_acronyms {
+            reader = reader.skip_acronyms(skip_acronyms);
+        }
+        if let Some(preserve_urls) = config.preserve_urls {
+            reader = reader.preserve_urls(preserve_urls);
+        }
+        if let Some(secondary_fixation) = config.secondary_fixation {
+            reader = reader.secondary_fixation(secondary_fixation);
+        }
+        if let Some(reflow) = config.reflow {
+            reader = reader.reflow(reflow);
+        }
+        if let Some(justify) = config.justify {
+            reader = reader.justify(justify);
+        }
+        if let Some(hanging_indent) = config.hanging_indent {
+            reader = reader.hanging_indent(hanging_indent);
+        }
+        if let Some(columns) = config.columns {
+            if columns == 0 {
+                return Err(Error::config("columns", "Number of columns should be at least 1"));
+            }
+            reader = reader.columns(columns);
+        }
+        if let Some(page_height) = config.page_height {
+            reader = reader.page_height(page_height);
+        }
+        if let Some(encoding) = config.encoding {
+            reader = reader.encoding(encoding);
+        }
+        if let Some(normalize_line_endings) = config.normalize_line_endings {
+            reader = reader.normalize_line_endings(normalize_line_endings);
+        }
+        if let Some(trim_trailing_whitespace) = config.trim_trailing_whitespace {
+            reader = reader.trim_trailing_whitespace(trim_trailing_whitespace);
+        }
+        if let Some(collapse_blank_lines) = config.collapse_blank_lines {
+            reader = reader.collapse_blank_lines(collapse_blank_lines);
+        }
+        if let Some(ascii_punctuation) = config.ascii_punctuation {
+            reader = reader.ascii_punctuation(ascii_punctuation);
+        }
+        for pattern in config.skip.iter().flatten() {
+            let pattern = Regex::new(pattern).map_err(|e| Error::config("skip", format!("Invalid skip pattern: {e}")))?;
+            reader = reader.skip_pattern(pattern);
+        }
+        match (config.to, config.format) {
+            // The markup of other formats would end up in the output as-is
+            (Some(OutputFormat::Json | OutputFormat::Rtf), None | Some(InputFormat::Auto)) => reader = reader.format(InputFormat::Plain),
+            (Some(to @ (OutputFormat::Json | OutputFormat::Rtf)), Some(format)) if !matches!(format, InputFormat::Plain | InputFormat::Man) => {
+                return Err(Error::config("format", format!("{to} output only supports plain text input, not {format}")));
+            }
+            (_, Some(format)) => reader = reader.format(format),
+            (_, None) => {}
+        }
+        reader.renderer = Renderer::Custom(Arc::from(config.renderer()?));
+        Ok(reader)
     }
 
     /// Set the strings to be wrapped around the emphasized part of a word. Default to bold if environment supports it.
     ///
+    /// This is a shorthand for using a [`Wrap`] renderer. If another renderer has been set with [`BioReader::renderer`], it is replaced, and the de-emphasized part defaults to dimmed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""));
+    /// assert_eq!(reader.bio_read_text("hello world").unwrap(), "<em>hel</em>lo <em>wor</em>ld");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn emphasize(mut self, left: String, right: String) -> Self {
+        self.set_emphasize(left, right);
+        self
+    }
+    /// Non-consuming version of [`BioReader::emphasize`].
+    pub fn set_emphasize(&mut self, left: String, right: String) -> &mut Self {
+        self.wrap_mut().emphasize = [left, right];
+        self
+    }
+    /// Set the strings to be wrapped around the de-emphasized part of a word. Default to dimmed if environment supports it.
+    ///
+    /// This is a shorthand for using a [`Wrap`] renderer. If another renderer has been set with [`BioReader::renderer`], it is replaced, and the emphasized part defaults to bold.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new()
+    ///    .emphasize(String::from(""), String::from(""))
+    ///     .de_emphasize(String::from("<de>"), String::from("</de>"));
+    /// assert_eq!(reader.bio_read_text("hello world").unwrap(), "hel<de>lo</de> wor<de>ld</de>");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn de_emphasize(mut self, left: String, right: String) -> Self {
+        self.set_de_emphasize(left, right);
+        self
+    }
+    /// Non-consuming version of [`BioReader::de_emphasize`].
+    pub fn set_de_emphasize(&mut self, left: String, right: String) -> &mut Self {
+        self.wrap_mut().de_emphasize = [left, right];
+        self
+    }
+    /// Set the renderer, which determines how the emphasized and de-emphasized parts of words are written. Default to a [`Wrap`] renderer with ANSI bold and dimmed. See the [`render`] module for built-in renderers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{render::BBCode, BioReader};
+    /// let reader = BioReader::new().renderer(BBCode);
+    /// assert_eq!(reader.bio_read_text("hello world").unwrap(), "[b]hel[/b]lo [b]wor[/b]ld");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn renderer(mut self, renderer: impl EmphasisRenderer + Send + Sync + 'static) -> Self {
+        self.set_renderer(renderer);
+        self
+    }
+    /// Non-consuming version of [`BioReader::renderer`].
+    pub fn set_renderer(&mut self, renderer: impl EmphasisRenderer + Send + Sync + 'static) -> &mut Self {
+        self.renderer = Renderer::Custom(Arc::new(renderer));
+        self
+    }
+    /// Set the styles of the emphasized and de-emphasized parts to a preset [`Theme`], like [`Theme::Underline`] for terminals where dimmed text is hard to read. Default to [`Theme::BoldDim`].
+    ///
+    /// This replaces the current renderer with a [`Wrap`] renderer, which can be further customized with [`BioReader::emphasize`] and [`BioReader::de_emphasize`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{render::Theme, BioReader};
+    /// let reader = BioReader::new().theme(Theme::Inverse);
+    /// assert_eq!(reader.bio_read_text("hello world").unwrap(), "\x1b[7mhel\x1b[0mlo \x1b[7mwor\x1b[0mld");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.set_theme(theme);
+        self
+    }
+    /// Non-consuming version of [`BioReader::theme`].
+    pub fn set_theme(&mut self, theme: Theme) -> &mut Self {
+        self.renderer = Renderer::Wrap(Wrap::from(Ansi::from(theme)));
+        self
+    }
+    /// Set the fixation point. The lower the fixation point, the more characters will be emphasized. The `fixation_point` should be in range \[1, 5\], defaulting to 3 when not specified.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let markdownBold = String::from("**");
+    /// let empty = String::from("");
+    /// let reader = BioReader::new()
+    ///     .emphasize(markdownBold.clone(), markdownBold.clone())
+    ///     .de_emphasize(empty.clone(), empty.clone())
+    ///     .fixation_point(1); // Set fixation point to 1
+    /// assert_eq!(reader.bio_read_text("pneumonoultramicroscopicsilicovolcanoconiosis").unwrap(), "**pneumonoultramicroscopicsilicovolcano**coniosis");
+    /// let reader = BioReader::new()
+    ///     .emphasize(markdownBold.clone(), markdownBold.clone())
+    ///     .de_emphasize(empty.clone(), empty.clone())
+    ///     .fixation_point(5); // Set fixation point to 5
+    /// assert_eq!(reader.bio_read_text("pneumonoultramicroscopicsilicovolcanoconiosis").unwrap(), "**pneumonoult**ramicroscopicsilicovolcanoconiosis");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fixation_point` is not in range \[1, 5\]. Use [`BioReader::try_fixation_point`] to handle this as an error instead.
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn fixation_point(mut self, fixation_point: usize) -> Self {
+        self.set_fixation_point(fixation_point);
+        self
+    }
+    /// Set the fixation point like [`BioReader::fixation_point`], but return an error instead of panicking if it is not in range \[1, 5\], for fixation points coming from user input.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// assert!(BioReader::new().try_fixation_point(5).is_ok());
+    /// let error = BioReader::new().try_fixation_point(6).unwrap_err();
+    /// assert_eq!(error.fixation_point(), 6);
+    /// assert_eq!(error.to_string(), "Fixation point should be in range [1, 5], but got 6");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn try_fixation_point(self, fixation_point: usize) -> Result<Self, FixationPointError> {
+        FixationPointError::check(fixation_point)?;
+        Ok(self.fixation_point(fixation_point))
+    }
+    /// Non-consuming version of [`BioReader::fixation_point`], for reconfiguring an existing [`BioReader`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let mut reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""));
+    /// assert_eq!(reader.bio_read_text("reading").unwrap(), "<em>read</em>ing");
+    /// reader.set_fixation_point(1).set_saccade(2);
+    /// assert_eq!(reader.bio_read_text("reading again").unwrap(), "<em>readi</em>ng again");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fixation_point` is not in range \[1, 5\].
+    pub fn set_fixation_point(&mut self, fixation_point: usize) -> &mut Self {
+        if let Err(error) = FixationPointError::check(fixation_point) {
+            panic!("{error}");
+        }
+        self.reverse_fixation_boundaries = Self::reverse_fixation_boundaries(fixation_point);
+        self.fixation_strategy = None;
+        self
+    }
+    /// Set the fraction of each word to emphasize, rounded up, replacing the fixation point. For example, `0.5` emphasizes the first half of each word. This is a shorthand for using a [`Ratio`] fixation strategy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""))
+    ///     .fixation_ratio(0.5);
+    /// assert_eq!(reader.bio_read_text("a bionic reading").unwrap(), "<em>a</em> <em>bio</em>nic <em>read</em>ing");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is not in range \[0, 1\].
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn fixation_ratio(self, ratio: f32) -> Self {
+        self.fixation_strategy(Ratio::new(ratio))
+    }
+    /// Non-consuming version of [`BioReader::fixation_ratio`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is not in range \[0, 1\].
+    pub fn set_fixation_ratio(&mut self, ratio: f32) -> &mut Self {
+        self.set_fixation_strategy(Ratio::new(ratio))
+    }
+    /// Set the fixation strategy, which determines how many letters of each word are emphasized, replacing the fixation point. See the [`fixation`] module for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{fixation::FixationStrategy, BioReader};
+    ///
+    /// /// Emphasize the first two letters of each word
+    /// struct FirstTwo;
+    ///
+    /// impl FixationStrategy for FirstTwo {
+    ///     fn emphasized_len(&self, _word: &str) -> usize {
+    ///         2
+    ///     }
+    /// }
+    ///
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""))
+    ///     .fixation_strategy(FirstTwo);
+    /// assert_eq!(reader.bio_read_text("a bionic reader").unwrap(), "<em>a</em> <em>bi</em>onic <em>re</em>ader");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn fixation_strategy(mut self, strategy: impl FixationStrategy + Send + Sync + 'static) -> Self {
+        self.set_fixation_strategy(strategy);
+        self
+    }
+    /// Non-consuming version of [`BioReader::fixation_strategy`].
+    pub fn set_fixation_strategy(&mut self, strategy: impl FixationStrategy + Send + Sync + 'static) -> &mut Self {
+        self.fixation_strategy = Some(Arc::new(strategy));
+        self
+    }
+    /// Load the word-frequency list at `path` and emphasize rare words more than common ones, replacing the fixation point. This is a shorthand for using a [`Frequency`] fixation strategy, whose documentation describes the format of the list. The list is loaded once, and shared between clones of the [`BioReader`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let path = std::env::temp_dir().join("bio-read-doctest-frequency.txt");
+    /// std::fs::write(&path, "# word count\nthe 1000000\nreading 1000\n").unwrap();
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""))
+    ///     .frequency_model(&path)
+    ///     .unwrap();
+    /// assert_eq!(reader.bio_read_text("the reading ravioli").unwrap(), "<em>t</em>he <em>read</em>ing <em>ravio</em>li");
+    /// # std::fs::remove_file(path).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::Io`] if the file cannot be read, or [`Error::Format`] if a line has no valid count after its word, with the path of the file.
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn frequency_model(mut self, path: impl AsRef<Path>) -> Result<Self, Error> {
+        self.set_frequency_model(path)?;
+        Ok(self)
+    }
+    /// Non-consuming version of [`BioReader::frequency_model`], leaving the [`BioReader`] unchanged if loading fails.
+    pub fn set_frequency_model(&mut self, path: impl AsRef<Path>) -> Result<&mut Self, Error> {
+        Ok(self.set_fixation_strategy(Frequency::load(path)?))
+    }
+    /// Set the saccade, emphasizing every `saccade`-th word only, starting from the first, and leaving the others as-is. Default to 1, emphasizing every word.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""))
+    ///     .saccade(2);
+    /// assert_eq!(reader.bio_read_text("every other word is emphasized").unwrap(), "<em>eve</em>ry other <em>wo</em>rd is <em>empha</em>sized");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `saccade` is 0.
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn saccade(mut self, saccade: usize) -> Self {
+        self.set_saccade(saccade);
+        self
+    }
+    /// Non-consuming version of [`BioReader::saccade`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `saccade` is 0.
+    pub fn set_saccade(&mut self, saccade: usize) -> &mut Self {
+        assert!(saccade != 0, "Saccade should be at least 1");
+        self.saccade = saccade;
+        self
+    }
+    /// Set how many more letters to emphasize in the first word of each sentence, anchoring the eye at sentence starts. Sentences end at `.`, `!`, `?` or `…` followed by whitespace, possibly after closing quotes or brackets, so abbreviations like "e.g." end sentences too. The boost only applies to words that are emphasized, and never emphasizes more than the whole word. Default to 0, disabling the boost.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""))
+    ///     .sentence_boost(1);
+    /// assert_eq!(reader.bio_read_text("Reading is fun. Writing too.").unwrap(), "<em>Readi</em>ng <em>i</em>s <em>f</em>un. <em>Writi</em>ng <em>t</em>oo.");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn sentence_boost(mut self, sentence_boost: usize) -> Self {
+        self.set_sentence_boost(sentence_boost);
+        self
+    }
+    /// Non-consuming version of [`BioReader::sentence_boost`].
+    pub fn set_sentence_boost(&mut self, sentence_boost: usize) -> &mut Self {
+        self.sentence_boost = sentence_boost;
+        self
+    }
+    /// Set whether to restart at each paragraph, so that its first word is emphasized regardless of the [`BioReader::saccade`], and boosted by [`BioReader::sentence_boost`] even if the previous paragraph didn't end a sentence, like after a heading. Paragraphs are separated by blank lines, which are always kept as they are. Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""))
+    ///     .saccade(2);
+    /// assert_eq!(reader.bio_read_text("one two three\n\nfour").unwrap(), "<em>o</em>ne two <em>thr</em>ee\n\nfour");
+    /// let reader = reader.reset_at_paragraphs(true);
+    /// assert_eq!(reader.bio_read_text("one two three\n\nfour").unwrap(), "<em>o</em>ne two <em>thr</em>ee\n\n<em>fo</em>ur");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn reset_at_paragraphs(mut self, reset_at_paragraphs: bool) -> Self {
+        self.set_reset_at_paragraphs(reset_at_paragraphs);
+        self
+    }
+    /// Non-consuming version of [`BioReader::reset_at_paragraphs`].
+    pub fn set_reset_at_paragraphs(&mut self, reset_at_paragraphs: bool) -> &mut Self {
+        self.reset_at_paragraphs = reset_at_paragraphs;
+        self
+    }
+    /// Set whether Unicode letters are treated as word characters. When enabled (the default), any alphabetic Unicode scalar value (and combining marks attached to it) is part of a word, so words like "café" or "naïve" are emphasized as a whole. When disabled, only ASCII letters are word characters, and other characters are passed through as-is.
+    ///
+    /// Regardless of this setting, the input is decoded as UTF-8, and multi-byte sequences are never split. Invalid UTF-8 sequences are passed through untouched.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""));
+    /// assert_eq!(reader.bio_read_text("naïve café").unwrap(), "<em>naï</em>ve <em>ca</em>fé");
+    /// let reader = reader.unicode(false);
+    /// assert_eq!(reader.bio_read_text("naïve café").unwrap(), "<em>n</em>aï<em>v</em>e <em>c</em>afé");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn unicode(mut self, unicode: bool) -> Self {
+        self.set_unicode(unicode);
+        self
+    }
+    /// Non-consuming version of [`BioReader::unicode`].
+    pub fn set_unicode(&mut self, unicode: bool) -> &mut Self {
+        self.unicode = unicode;
+        self
+    }
+    /// Set which characters, other than letters, make up words. By default, apostrophes and hyphens between two letters are kept inside the word, so "don't" and "well-known" are emphasized as a whole, with only their letters counting towards their length. Use [`WordChars::none`] to split words at them instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{segment::WordChars, BioReader};
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""));
+    /// assert_eq!(reader.bio_read_text("it's well-known").unwrap(), "<em>i</em>t's <em>well-k</em>nown");
+    /// let reader = reader.word_chars(WordChars { joiners: vec!['\''] });
+    /// assert_eq!(reader.bio_read_text("it's well-known").unwrap(), "<em>i</em>t's <em>we</em>ll-<em>kno</em>wn");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn word_chars(mut self, word_chars: WordChars) -> Self {
+        self.set_word_chars(word_chars);
+        self
+    }
+    /// Non-consuming version of [`BioReader::word_chars`].
+    pub fn set_word_chars(&mut self, word_chars: WordChars) -> &mut Self {
+        self.word_chars = word_chars;
+        self
+    }
+    /// Set how digits are handled. By default, words are split at digits, so "IPv6" is read as "IPv" and "6", and numbers are left as they are. [`TokenPolicy::IncludeDigits`] reads digits like letters instead, while [`TokenPolicy::SkipNumeric`] leaves words with digits in them un-emphasized. Default to [`TokenPolicy::SplitAtDigits`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{segment::TokenPolicy, BioReader};
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""))
+    ///     .token_policy(TokenPolicy::IncludeDigits);
+    /// assert_eq!(reader.bio_read_text("x86 chips").unwrap(), "<em>x</em>86 <em>chi</em>ps");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn token_policy(mut self, token_policy: TokenPolicy) -> Self {
+        self.set_token_policy(token_policy);
+        self
+    }
+    /// Non-consuming version of [`BioReader::token_policy`].
+    pub fn set_token_policy(&mut self, token_policy: TokenPolicy) -> &mut Self {
+        self.token_policy = token_policy;
+        self
+    }
+    /// Set how compound words joined by hyphens, like "state-of-the-art", are emphasized. By default, they are emphasized as a whole, while [`CompoundPolicy::EachPart`] emphasizes each part on its own. Hyphens only join words if they are among the joiners set with [`BioReader::word_chars`]. Default to [`CompoundPolicy::Whole`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{segment::CompoundPolicy, BioReader};
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""))
+    ///     .compound_policy(CompoundPolicy::EachPart);
+    /// assert_eq!(reader.bio_read_text("well-known facts").unwrap(), "<em>we</em>ll-<em>kno</em>wn <em>fac</em>ts");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn compound_policy(mut self, compound_policy: CompoundPolicy) -> Self {
+        self.set_compound_policy(compound_policy);
+        self
+    }
+    /// Non-consuming version of [`BioReader::compound_policy`].
+    pub fn set_compound_policy(&mut self, compound_policy: CompoundPolicy) -> &mut Self {
+        self.compound_policy = compound_policy;
+        self
+    }
+    /// Set the maximum length of words in bytes. Longer words are not buffered whole, but handled following the [`BioReader::long_word_policy`] as soon as they reach it, so that memory stays bounded whatever the input, like a huge line of letters. Runs of Chinese and Japanese characters segmented into words, and runs of non-whitespace characters matched against skip patterns or preserved URLs, are processed in pieces of this length too. Default to 65536 bytes, far longer than any real word.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""))
+    ///     .max_word_len(8);
+    /// let word = "a".repeat(1_000_000);
+    /// assert_eq!(reader.bio_read_text(&format!("{word} word")).unwrap(), format!("{word} <em>wo</em>rd"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_word_len` is 0.
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn max_word_len(mut self, max_word_len: usize) -> Self {
+        self.set_max_word_len(max_word_len);
+        self
+    }
+    /// Non-consuming version of [`BioReader::max_word_len`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_word_len` is 0.
+    pub fn set_max_word_len(&mut self, max_word_len: usize) -> &mut Self {
+        assert!(max_word_len != 0, "Maximum word length should be at least 1");
+        self.max_word_len = max_word_len;
+        self
+    }
+    /// Set what happens to words longer than the [`BioReader::max_word_len`]: written as they are with [`LongWordPolicy::Plain`], or split into pieces emphasized as words with [`LongWordPolicy::Split`]. Default to [`LongWordPolicy::Plain`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{segment::LongWordPolicy, BioReader};
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""))
+    ///     .max_word_len(4)
+    ///     .long_word_policy(LongWordPolicy::Split);
+    /// assert_eq!(reader.bio_read_text("wonderful").unwrap(), "<em>wo</em>nd<em>er</em>fu<em>l</em>");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn long_word_policy(mut self, long_word_policy: LongWordPolicy) -> Self {
+        self.set_long_word_policy(long_word_policy);
+        self
+    }
+    /// Non-consuming version of [`BioReader::long_word_policy`].
+    pub fn set_long_word_policy(&mut self, long_word_policy: LongWordPolicy) -> &mut Self {
+        self.long_word_policy = long_word_policy;
+        self
+    }
+    /// Set the language of the text, which determines which characters make up words, replacing those set with [`BioReader::word_chars`], and how words excluded with [`BioReader::exclude_words`] are matched case-insensitively. Default to [`Language::English`].
+    ///
+    /// For hyphenation data in the same language, use a [`Syllable`](fixation::Syllable) fixation strategy created with [`Language::code`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{segment::Language, BioReader};
+    /// use std::collections::HashSet;
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""))
+    ///     .exclude_words(HashSet::from([String::from("ile")]))
+    ///     .language(Language::Turkish);
+    /// assert_eq!(reader.bio_read_text("İLE Ankara'da").unwrap(), "İLE <em>Ank</em>ara'<em>d</em>a");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn language(mut self, language: Language) -> Self {
+        self.set_language(language);
+        self
+    }
+    /// Non-consuming version of [`BioReader::language`].
+    pub fn set_language(&mut self, language: Language) -> &mut Self {
+        self.language = language;
+        self.word_chars = language.word_chars();
+        self.fold_excluded_words();
+        self
+    }
+    /// Set how to handle runs of Chinese and Japanese characters, which aren't separated by spaces. By default ([`Cjk::PassThrough`]), they are passed through untouched, rather than emphasizing a whole paragraph as one word. With the `cjk` feature, [`Cjk::Segment`](segment::Cjk) splits them into words with a Chinese dictionary, emphasizing each word.
+    ///
+    /// Only takes effect with [`BioReader::unicode`] enabled, since these characters aren't word characters otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{segment::Cjk, BioReader};
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""))
+    ///     .cjk(Cjk::PassThrough);
+    /// assert_eq!(reader.bio_read_text("Hello 世界, hello").unwrap(), "<em>Hel</em>lo 世界, <em>hel</em>lo");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn cjk(mut self, cjk: Cjk) -> Self {
+        self.set_cjk(cjk);
+        self
+    }
+    /// Non-consuming version of [`BioReader::cjk`].
+    pub fn set_cjk(&mut self, cjk: Cjk) -> &mut Self {
+        self.cjk = cjk;
+        self
+    }
+    /// Set whether to keep cursive scripts like Arabic joined where a word is split into its emphasized and de-emphasized parts. Enabled by default, inserting a zero-width joiner (U+200D) on both sides of the split when the letters around it connect, so that they keep their joined forms even when styled differently, instead of breaking the word apart mid-ligature.
+    ///
+    /// Words are always split in logical order, i.e. the order they are typed in, so right-to-left scripts like Hebrew and Arabic get their first letters emphasized, and the markup doesn't change how mixed left-to-right and right-to-left text is ordered. [`BioReader::bio_read_spans`] is unaffected, since its spans are ranges into the input.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<b>"), String::from("</b>"))
+    ///     .de_emphasize(String::from(""), String::from(""));
+    /// assert_eq!(reader.bio_read_text("كتاب").unwrap(), "<b>كت\u{200D}</b>\u{200D}اب");
+    /// let reader = reader.join_cursive(false);
+    /// assert_eq!(reader.bio_read_text("كتاب").unwrap(), "<b>كت</b>اب");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn join_cursive(mut self, join_cursive: bool) -> Self {
+        self.set_join_cursive(join_cursive);
+        self
+    }
+    /// Non-consuming version of [`BioReader::join_cursive`].
+    pub fn set_join_cursive(&mut self, join_cursive: bool) -> &mut Self {
+        self.join_cursive = join_cursive;
+        self
+    }
+    /// Pass text matching `pattern` through untouched, like URLs or identifiers. Can be called multiple times to skip text matching any of the patterns.
+    ///
+    /// Patterns are matched against runs of non-whitespace characters, so a match never spans whitespace. Where matches overlap, the one starting first (or the longer one, if they start together) wins.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{BioReader, Regex};
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""))
+    ///     .skip_pattern(Regex::new(r"\w+_\w+").unwrap());
+    /// assert_eq!(reader.bio_read_text("call snake_case now").unwrap(), "<em>ca</em>ll snake_case <em>n</em>ow");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn skip_pattern(mut self, pattern: Regex) -> Self {
+        self.add_skip_pattern(pattern);
+        self
+    }
+    /// Non-consuming version of [`BioReader::skip_pattern`].
+    pub fn add_skip_pattern(&mut self, pattern: Regex) -> &mut Self {
+        self.skip_patterns.push(pattern);
+        self
+    }
+    /// Set the words to leave un-emphasized, like common short words such as "the", "a" and "of", to reduce visual noise. Words are matched case-insensitively, and excluded words don't count towards the [`BioReader::saccade`]. Replaces previously excluded words, defaulting to none.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// use std::collections::HashSet;
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""))
+    ///     .exclude_words(HashSet::from([String::from("the"), String::from("of")]));
+    /// assert_eq!(reader.bio_read_text("The tip of the iceberg").unwrap(), "The <em>t</em>ip of the <em>iceb</em>erg");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn exclude_words(mut self, words: HashSet<String>) -> Self {
+        self.set_exclude_words(words);
+        self
+    }
+    /// Non-consuming version of [`BioReader::exclude_words`].
+    pub fn set_exclude_words(&mut self, words: HashSet<String>) -> &mut Self {
+        self.excluded_words = words;
+        self.fold_excluded_words();
+        self
+    }
+    /// Set whether to leave words written in all capitals, like "NASA" or "HTTP", un-emphasized, as acronyms are read letter by letter. Words need at least two letters to count, so "I" and "A" are emphasized as usual. Like excluded words, they don't count towards the [`BioReader::saccade`]. Default to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""))
+    ///     .skip_acronyms(true);
+    /// assert_eq!(reader.bio_read_text("NASA speaks HTTP").unwrap(), "NASA <em>spe</em>aks HTTP");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn skip_acronyms(mut self, skip_acronyms: bool) -> Self {
+        self.set_skip_acronyms(skip_acronyms);
+        self
+    }
+    /// Non-consuming version of [`BioReader::skip_acronyms`].
+    pub fn set_skip_acronyms(&mut self, skip_acronyms: bool) -> &mut Self {
+        self.skip_acronyms = skip_acronyms;
+        self
+    }
+    /// Set whether to pass URLs, emails and file paths through untouched. Default to `false`.
+    ///
+    /// URLs are recognized by their scheme (like `https://`) or a leading `www.`, and paths by a leading `/`, `~/`, `./`, `../` or drive letter. Trailing punctuation is not considered part of them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""))
+    ///     .preserve_urls(true);
+    /// assert_eq!(
+    ///     reader.bio_read_text("visit https://example.com/docs, mail me@example.com or see ~/notes.txt").unwrap(),
+    ///     "<em>vis</em>it https://example.com/docs, <em>ma</em>il me@example.com <em>o</em>r <em>s</em>ee ~/notes.txt"
+    /// );
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn preserve_urls(mut self, preserve_urls: bool) -> Self {
+        self.set_preserve_urls(preserve_urls);
+        self
+    }
+    /// Non-consuming version of [`BioReader::preserve_urls`].
+    pub fn set_preserve_urls(&mut self, preserve_urls: bool) -> &mut Self {
+        self.preserve_urls = preserve_urls;
+        self
+    }
+    /// Set a hook called with each word before it is written, which can return a [`StyleOverride`] to skip it, emphasize another number of its letters, or write it with another renderer, like coloring proper nouns differently or leaving the words of a personal dictionary un-emphasized. Returning `None` writes the word as usual. Words are given whole, after [`BioReader::exclude_words`], [`BioReader::skip_acronyms`] and the [`BioReader::saccade`] are applied, which the hook is told about.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{render::Html, style::StyleOverride, BioReader};
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""))
+    ///     .word_style_hook(|info| match info.word {
+    ///         "rustacean" => Some(StyleOverride::skip()),
+    ///         word if !info.sentence_start && word.starts_with(char::is_uppercase) => {
+    ///             Some(StyleOverride::emphasize(1).with_renderer(Html::with_class("name")))
+    ///         }
+    ///         _ => None,
+    ///     });
+    /// assert_eq!(
+    ///     reader.bio_read_text("Hello Ferris the rustacean").unwrap(),
+    ///     "<em>Hel</em>lo <b class=\"name\">F</b>erris <em>t</em>he rustacean"
+    /// );
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn word_style_hook(mut self, hook: impl Fn(&WordInfo<'_>) -> Option<StyleOverride> + Send + Sync + 'static) -> Self {
+        self.set_word_style_hook(hook);
+        self
+    }
+    /// Non-consuming version of [`BioReader::word_style_hook`].
+    pub fn set_word_style_hook(&mut self, hook: impl Fn(&WordInfo<'_>) -> Option<StyleOverride> + Send + Sync + 'static) -> &mut Self {
+        self.word_style_hook = Some(Arc::new(hook));
+        self
+    }
+    /// Set whether to emphasize words longer than 12 letters a second time midway, as a secondary fixation point for the eyes to land on. Default to `false`. The secondary emphasis is a third of the letters after the emphasized part, centered among them so that letters on both sides stay de-emphasized. It is written with [`EmphasisRenderer::secondary`], which built-in renderers style more weakly than the emphasized part.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{render::Html, BioReader};
+    /// let reader = BioReader::new().renderer(Html::default()).secondary_fixation(true);
+    /// assert_eq!(
+    ///     reader.bio_read_text("a comprehensible explanation").unwrap(),
+    ///     "<b>a</b> <b>compreh</b>en<b class=\"secondary\">si</b>ble <b>explan</b>ation"
+    /// );
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn secondary_fixation(mut self, secondary_fixation: bool) -> Self {
+        self.set_secondary_fixation(secondary_fixation);
+        self
+    }
+    /// Non-consuming version of [`BioReader::secondary_fixation`].
+    pub fn set_secondary_fixation(&mut self, secondary_fixation: bool) -> &mut Self {
+        self.secondary_fixation = secondary_fixation;
+        self
+    }
+    /// Set the width to reflow plain text to, in columns, or 0 to leave lines as they are, which is the default. The lines of each paragraph are joined and broken again between words, so that they fit the width, unless a word is wider. Paragraphs are separated by blank lines, which are kept, along with the indentation of their first lines.
+    ///
+    /// The width is that of the text as displayed, counting wide characters like Chinese ones as two columns, and leaving out terminal escape sequences of the input, so lines are reflowed before the markup of the renderer is added, and fit the width whatever it is. Only applies to [`InputFormat::Plain`] input, and not to [`BioReader::bio_read_spans`] and [`BioReader::bio_read_visit`], whose ranges are into the input as it is.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new().reflow(12);
+    /// assert_eq!(
+    ///     reader.strip(&reader.bio_read_text("Short lines\nare joined, and long ones are broken.\n\nNew paragraph").unwrap()),
+    ///     "Short lines\nare joined,\nand long\nones are\nbroken.\n\nNew\nparagraph"
+    /// );
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn reflow(mut self, width: usize) -> Self {
+        self.set_reflow(width);
+        self
+    }
+    /// Non-consuming version of [`BioReader::reflow`].
+    pub fn set_reflow(&mut self, width: usize) -> &mut Self {
+        self.reflow = width;
+        self
+    }
+    /// Set whether to justify the lines of text reflowed with [`BioReader::reflow`], stretching the spaces between words so that each line but the last of a paragraph fills the width, like a typeset page. Default to `false`. Lines are held back until they are full, to tell how much to stretch them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new().reflow(12).justify(true);
+    /// assert_eq!(
+    ///     reader.strip(&reader.bio_read_text("Lines are stretched to fill the width.").unwrap()),
+    ///     "Lines    are\nstretched to\nfill     the\nwidth."
+    /// );
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn justify(mut self, justify: bool) -> Self {
+        self.set_justify(justify);
+        self
+    }
+    /// Non-consuming version of [`BioReader::justify`].
+    pub fn set_justify(&mut self, justify: bool) -> &mut Self {
+        self.justify = justify;
+        self
+    }
+    /// Set how many columns to indent the lines of each paragraph after the first by, when text is reflowed with [`BioReader::reflow`], as a hanging indent. Default to 0. The indentation counts towards the width.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new().reflow(12).hanging_indent(2);
+    /// assert_eq!(
+    ///     reader.strip(&reader.bio_read_text("- A list item, wrapped\n\n- Another").unwrap()),
+    ///     "- A list\n  item,\n  wrapped\n\n- Another"
+    /// );
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn hanging_indent(mut self, hanging_indent: usize) -> Self {
+        self.set_hanging_indent(hanging_indent);
+        self
+    }
+    /// Non-consuming version of [`BioReader::hanging_indent`].
+    pub fn set_hanging_indent(&mut self, hanging_indent: usize) -> &mut Self {
+        self.hanging_indent = hanging_indent;
+        self
+    }
+    /// Set how many columns to lay out text reflowed with [`BioReader::reflow`] in, side by side with a gutter of 4 spaces between them, like a newspaper. Should be at least 1, which is the default, laying out a single column. The columns share the width, so each is as wide as it is once the gutters are taken out of it, divided by their number.
+    ///
+    /// The columns of a page are filled one after another, and each line is padded to the width of its column as measured before the markup of the renderer is added, so they line up in terminals and other plain text output, but not in formats like HTML. Pages are as tall as [`BioReader::page_height`], and the text is held back until one is full.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new().reflow(20).columns(2);
+    /// assert_eq!(
+    ///     reader.strip(&reader.bio_read_text("Narrow columns are quick to skim.").unwrap()),
+    ///     "Narrow      quick to\ncolumns     skim.\nare"
+    /// );
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `columns` is 0.
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn columns(mut self, columns: usize) -> Self {
+        self.set_columns(columns);
+        self
+    }
+    /// Non-consuming version of [`BioReader::columns`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `columns` is 0.
+    pub fn set_columns(&mut self, columns: usize) -> &mut Self {
+        assert!(columns != 0, "Number of columns should be at least 1");
+        self.columns = columns;
+        self
+    }
+    /// Set how many lines tall each page of the columns set with [`BioReader::columns`] is, like the height of the terminal, or 0 to balance the whole text over the columns as a single page, which is the default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new().reflow(14).columns(2).page_height(1);
+    /// assert_eq!(
+    ///     reader.strip(&reader.bio_read_text("one two three four").unwrap()),
+    ///     "one      two\nthree    four"
+    /// );
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn page_height(mut self, page_height: usize) -> Self {
+        self.set_page_height(page_height);
+        self
+    }
+    /// Non-consuming version of [`BioReader::page_height`].
+    pub fn set_page_height(&mut self, page_height: usize) -> &mut Self {
+        self.page_height = page_height;
+        self
+    }
+    /// Set the character encoding of the input, which is transcoded to UTF-8 before it is bio-read, so that the output is always UTF-8. By default ([`Encoding::Auto`]), input is taken to be UTF-8, and a UTF-8 byte order mark at its start is stripped. With the `encoding` feature, input starting with a UTF-16 byte order mark is read as UTF-16 as well, and other encodings can be set, like Latin-1 for older documents.
+    ///
+    /// Only applies to input read as bytes, like with [`BioReader::bio_read`], [`BioWriter`] and [`BioReadProcessor`], but not Word documents. Text given as a `str`, like to [`BioReader::bio_read_text`], is decoded already, and so is left as it is.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{encoding::Encoding, BioReader};
+    /// let reader = BioReader::new().encoding(Encoding::Utf8);
+    /// let mut output = Vec::new();
+    /// reader.bio_read(&b"\xef\xbb\xbfhello"[..], &mut output).unwrap();
+    /// assert_eq!(reader.strip(&String::from_utf8(output).unwrap()), "hello");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn encoding(mut self, encoding: Encoding) -> Self {
+        self.set_encoding(encoding);
+        self
+    }
+    /// Non-consuming version of [`BioReader::encoding`].
+    pub fn set_encoding(&mut self, encoding: Encoding) -> &mut Self {
+        self.encoding = encoding;
+        self
+    }
+    /// Set whether to turn CRLF line endings of the input into LF, like those of files written on Windows, whose carriage returns would otherwise be written as they are and may show up in styled output in some terminals. Default to `false`, which preserves the input exactly. Carriage returns that don't end lines are kept.
+    ///
+    /// Normalization happens before the input is bio-read, so it applies to all formats but Word documents, and not to [`BioReader::bio_read_spans`] and [`BioReader::bio_read_visit`], whose ranges are into the input as it is.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new().normalize_line_endings(true);
+    /// assert_eq!(reader.strip(&reader.bio_read_text("one\r\ntwo\r\n").unwrap()), "one\ntwo\n");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn normalize_line_endings(mut self, normalize_line_endings: bool) -> Self {
+        self.set_normalize_line_endings(normalize_line_endings);
+        self
+    }
+    /// Non-consuming version of [`BioReader::normalize_line_endings`].
+    pub fn set_normalize_line_endings(&mut self, normalize_line_endings: bool) -> &mut Self {
+        self.normalize_line_endings = normalize_line_endings;
+        self
+    }
+    /// Set whether to remove the whitespace at the ends of lines of the input, like spaces, tabs and stray carriage returns. Default to `false`. The carriage returns of CRLF line endings are kept, unless [`BioReader::normalize_line_endings`] is set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new().trim_trailing_whitespace(true);
+    /// assert_eq!(reader.strip(&reader.bio_read_text("one \t\ntwo  \r\nthree ").unwrap()), "one\ntwo\r\nthree");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn trim_trailing_whitespace(mut self, trim_trailing_whitespace: bool) -> Self {
+        self.set_trim_trailing_whitespace(trim_trailing_whitespace);
+        self
+    }
+    /// Non-consuming version of [`BioReader::trim_trailing_whitespace`].
+    pub fn set_trim_trailing_whitespace(&mut self, trim_trailing_whitespace: bool) -> &mut Self {
+        self.trim_trailing_whitespace = trim_trailing_whitespace;
+        self
+    }
+    /// Set whether to collapse runs of blank lines in the input into a single one, like `cat -s`. Lines with only whitespace count as blank. Default to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new().collapse_blank_lines(true);
+    /// assert_eq!(reader.strip(&reader.bio_read_text("one\n\n  \n\ntwo").unwrap()), "one\n\ntwo");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn collapse_blank_lines(mut self, collapse_blank_lines: bool) -> Self {
+        self.set_collapse_blank_lines(collapse_blank_lines);
+        self
+    }
+    /// Non-consuming version of [`BioReader::collapse_blank_lines`].
+    pub fn set_collapse_blank_lines(&mut self, collapse_blank_lines: bool) -> &mut Self {
+        self.collapse_blank_lines = collapse_blank_lines;
+        self
+    }
+    /// Set whether to replace typographic punctuation in the input with ASCII, for terminals and fonts that can't show it: curly quotes (‘ ’ “ ”) with straight ones, en dashes (–) with `-`, em dashes (—) with `--` and ellipses (…) with `...`. Default to `false`.
+    ///
+    /// Words are split the same either way, as curly apostrophes are kept inside words like straight ones (see [`BioReader::word_chars`]), and dashes, quotes and ellipses split words like their ASCII counterparts. Like [`BioReader::normalize_line_endings`], punctuation is replaced before the input is bio-read.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""));
+    /// assert_eq!(reader.bio_read_text("“Don’t”—wait…").unwrap(), "“<em>Do</em>n’t”—<em>wa</em>it…");
+    /// let reader = reader.ascii_punctuation(true);
+    /// assert_eq!(reader.bio_read_text("“Don’t”—wait…").unwrap(), "\"<em>Do</em>n't\"--<em>wa</em>it...");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn ascii_punctuation(mut self, ascii_punctuation: bool) -> Self {
+        self.set_ascii_punctuation(ascii_punctuation);
+        self
+    }
+    /// Non-consuming version of [`BioReader::ascii_punctuation`].
+    pub fn set_ascii_punctuation(&mut self, ascii_punctuation: bool) -> &mut Self {
+        self.ascii_punctuation = ascii_punctuation;
+        self
+    }
+    /// Set the format of the input, defaulting to [`InputFormat::Plain`]. With [`InputFormat::Markdown`], only prose is bio-read, leaving code blocks, inline code, link destinations, HTML and existing emphasis markers intact. With [`InputFormat::Html`], only text nodes are bio-read, so you'd likely want to use HTML tags for [`BioReader::emphasize`] and [`BioReader::de_emphasize`] as well. With [`InputFormat::Latex`], only prose is bio-read, leaving commands, math and the preamble intact, which goes well with [`render::Latex`]. With [`InputFormat::Auto`], the format is detected from the start of the input.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{BioReader, InputFormat};
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""))
+    ///     .format(InputFormat::Markdown);
+    /// assert_eq!(
+    ///     reader.bio_read_text("*Read* the [docs](https://docs.rs) or run `cargo doc`").unwrap(),
+    ///     "*<em>Re</em>ad* <em>t</em>he [<em>do</em>cs](https://docs.rs) <em>o</em>r <em>r</em>un `cargo doc`"
+    /// );
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<b>"), String::from("</b>"))
+    ///     .de_emphasize(String::from(""), String::from(""))
+    ///     .format(InputFormat::Html);
+    /// assert_eq!(
+    ///     reader.bio_read_text("<p class=\"intro\">Hello <code>world</code></p>").unwrap(),
+    ///     "<p class=\"intro\"><b>Hel</b>lo <code>world</code></p>"
+    /// );
+    /// ```
+    ///
+    /// # Performance
+    ///
+    /// HTML is processed in a streaming fashion, just like plain text. Markdown and LaTeX cannot be parsed in a streaming fashion, so the whole input is read into memory before processing.
+    ///
+    /// # See also
+    ///
+    /// [Customization](BioReader#customization): The other methods that can be used to customize the [`BioReader`].
+    pub fn format(mut self, format: InputFormat) -> Self {
+        self.set_format(format);
+        self
+    }
+    /// Non-consuming version of [`BioReader::format`].
+    pub fn set_format(&mut self, format: InputFormat) -> &mut Self {
+        self.format = format;
+        self
+    }
+
+    /// Do bio-reading on `reader` and write the result to `writer`.
+    ///
+    /// # Performance
+    ///
+    /// This method guarantees linear time complexity for plain text, and memory usage proportional to the longest word, whatever the input, including invalid UTF-8. Memory stays bounded even then, since words longer than the [`BioReader::max_word_len`] aren't buffered whole. Buffers are reused from word to word, so once they fit the longest word, no more allocations are made. The same goes for HTML, while diffs and man pages take memory proportional to the longest line. See [`BioReader::format`] for other formats.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// use std::io::Write;
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from("<de>"), String::from("</de>"));
+    /// let mut output_buffer = Vec::new();
+    /// reader.bio_read("hello world".as_bytes(), &mut output_buffer).unwrap();
+    /// let output = String::from_utf8(output_buffer).unwrap();
+    /// assert_eq!(output, "<em>hel</em><de>lo</de> <em>wor</em><de>ld</de>");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::Io`] if reading or writing fails, [`Error::Encoding`] if the input is not valid in the encoding its format requires, like UTF-8 for Markdown, and [`Error::Format`] if it cannot be parsed as its format, like a corrupt Word document.
+    ///
+    /// # See also
+    ///
+    /// [`BioReader::bio_read_text`]: A simple wrapper around [`BioReader::bio_read`] for processing short strings.
+    pub fn bio_read(&self, reader: impl Read, writer: &mut impl Write) -> Result<(), Error> {
+        Ok(self.process(Processor::new(self), reader, writer)?)
+    }
+    /// Do bio-reading on `reader` like [`BioReader::bio_read`], and return statistics of the words in it, like how many there are and how long they take to read. Words in Word documents are not counted.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -39,150 +1554,136 @@ impl BioReader {
     /// let reader = BioReader::new()
     ///     .emphasize(String::from("<em>"), String::from("</em>"))
     ///     .de_emphasize(String::from(""), String::from(""));
-    /// assert_eq!(reader.bio_read_text("hello world").unwrap(), "<em>hel</em>lo <em>wor</em>ld");
+    /// let mut output_buffer = Vec::new();
+    /// let stats = reader.bio_read_with_stats("hello world".as_bytes(), &mut output_buffer).unwrap();
+    /// assert_eq!(String::from_utf8(output_buffer).unwrap(), "<em>hel</em>lo <em>wor</em>ld");
+    /// assert_eq!(stats.words, 2);
+    /// assert_eq!(stats.emphasized_ratio(), 0.6);
     /// ```
     ///
     /// # See also
     ///
-    /// Other methods that can be used to customize the [`BioReader`]:
-    ///
-    /// - [`BioReader::de_emphasize`]
-    /// - [`BioReader::fixation_point`]
-    pub fn emphasize(mut self, left: String, right: String) -> Self {
-        self.emphasize = [left, right];
-        self
+    /// [`BioReadStats`]: The statistics returned.
+    pub fn bio_read_with_stats(&self, reader: impl Read, writer: &mut impl Write) -> Result<BioReadStats, Error> {
+        let mut stats = BioReadStats::default();
+        self.bio_read_with_observer(reader, writer, &mut stats)?;
+        Ok(stats)
     }
-    /// Set the strings to be wrapped around the de-emphasized part of a word. Default to dimmed if environment supports it.
+    /// Do bio-reading on `reader` like [`BioReader::bio_read`], notifying `observer` of each word and paragraph. Words in Word documents are not reported.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use bio_read::BioReader;
-    /// let reader = BioReader::new()
-    ///    .emphasize(String::from(""), String::from(""))
-    ///     .de_emphasize(String::from("<de>"), String::from("</de>"));
-    /// assert_eq!(reader.bio_read_text("hello world").unwrap(), "hel<de>lo</de> wor<de>ld</de>");
+    /// use bio_read::{observer::Observer, BioReader};
+    /// struct Emphasized(Vec<usize>);
+    /// impl Observer for Emphasized {
+    ///     fn on_word(&mut self, _word: &str, emphasized: usize) {
+    ///         self.0.push(emphasized);
+    ///     }
+    /// }
+    /// let mut emphasized = Emphasized(Vec::new());
+    /// BioReader::new().bio_read_with_observer("hello wonderful world".as_bytes(), &mut Vec::new(), &mut emphasized).unwrap();
+    /// assert_eq!(emphasized.0, [3, 5, 3]);
     /// ```
     ///
     /// # See also
     ///
-    /// Other methods that can be used to customize the [`BioReader`]:
-    ///
-    /// - [`BioReader::emphasize`]
-    /// - [`BioReader::fixation_point`]
-    pub fn de_emphasize(mut self, left: String, right: String) -> Self {
-        self.de_emphasize = [left, right];
-        self
+    /// [`Observer`]: The callbacks notified.
+    pub fn bio_read_with_observer(&self, reader: impl Read, writer: &mut impl Write, observer: &mut (impl Observer + Send)) -> Result<(), Error> {
+        Ok(self.process(Processor::new(self).with_observer(observer), reader, writer)?)
     }
-    /// Set the fixation point. The lower the fixation point, the more characters will be emphasized. The `fixation_point` should be in range \[1, 5\], defaulting to 3 when not specified.
+    /// Feed `reader` to `processor` a chunk at a time, writing to `writer`.
+    fn process(&self, mut processor: Processor<'_>, mut reader: impl Read, writer: &mut impl Write) -> std::io::Result<()> {
+        // Words are written piece by piece, so collect them to write a chunk at a time
+        let mut writer = BufWriter::with_capacity(CHUNK_SIZE, writer);
+        let mut chunk = [0_u8; CHUNK_SIZE];
+        loop {
+            match reader.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(n) => processor.push_bytes(&chunk[..n], &mut writer)?,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        processor.finish(&mut writer)?;
+        writer.into_inner().map_err(IntoInnerError::into_error)?;
+        Ok(())
+    }
+    /// Do bio-reading on `reader` line by line, flushing `writer` after each line. Useful for interactive use, where output of each line should show up as soon as the line is entered.
+    ///
+    /// Markdown is processed a block at a time instead, with each block ending at a blank line, so constructs containing blank lines (like fenced code blocks) might not be recognized.
     ///
     /// # Example
     ///
     /// ```rust
     /// use bio_read::BioReader;
-    /// let markdownBold = String::from("**");
-    /// let empty = String::from("");
-    /// let reader = BioReader::new()
-    ///     .emphasize(markdownBold.clone(), markdownBold.clone())
-    ///     .de_emphasize(empty.clone(), empty.clone())
-    ///     .fixation_point(1); // Set fixation point to 1
-    /// assert_eq!(reader.bio_read_text("pneumonoultramicroscopicsilicovolcanoconiosis").unwrap(), "**pneumonoultramicroscopicsilicovolcano**coniosis");
     /// let reader = BioReader::new()
-    ///     .emphasize(markdownBold.clone(), markdownBold.clone())
-    ///     .de_emphasize(empty.clone(), empty.clone())
-    ///     .fixation_point(5); // Set fixation point to 5
-    /// assert_eq!(reader.bio_read_text("pneumonoultramicroscopicsilicovolcanoconiosis").unwrap(), "**pneumonoult**ramicroscopicsilicovolcanoconiosis");
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from("<de>"), String::from("</de>"));
+    /// let mut output_buffer = Vec::new();
+    /// reader.bio_read_lines("hello\nworld".as_bytes(), &mut output_buffer).unwrap();
+    /// let output = String::from_utf8(output_buffer).unwrap();
+    /// assert_eq!(output, "<em>hel</em><de>lo</de>\n<em>wor</em><de>ld</de>");
     /// ```
     ///
-    /// # Panics
-    ///
-    /// Panics if `fixation_point` is not in range \[1, 5\].
-    ///
     /// # See also
     ///
-    /// Other methods that can be used to customize the [`BioReader`]:
-    ///
-    /// - [`BioReader::emphasize`]
-    /// - [`BioReader::de_emphasize`]
-    pub fn fixation_point(mut self, fixation_point: usize) -> Self {
-        assert!(
-            1 <= fixation_point && fixation_point <= 5,
-            "Fixation point should be in range [1, 5], but got {}",
-            fixation_point
-        );
-        self.reverse_fixation_boundaries = Self::reverse_fixation_boundaries(fixation_point);
-        self
+    /// [`BioReader::bio_read`]: Do bio-reading on `reader` without flushing, which is more efficient for non-interactive use.
+    pub fn bio_read_lines(&self, mut reader: impl BufRead, writer: &mut impl Write) -> Result<(), Error> {
+        let mut writer = BufWriter::with_capacity(CHUNK_SIZE, writer);
+        let mut processor = Processor::new(self);
+        let mut line = Vec::new();
+        while reader.read_until(b'\n', &mut line)? != 0 {
+            processor.push_line(&line, &mut writer)?;
+            writer.flush()?;
+            line.clear();
+        }
+        processor.finish(&mut writer)?;
+        Ok(writer.flush()?)
     }
-
-    /// Do bio-reading on `reader` and write the result to `writer`.
-    ///
-    /// # Performance
+    /// Do bio-reading on an async `reader` and write the result to an async `writer`. Requires the `async` feature.
     ///
-    /// This method guarantees linear time complexity and constant memory usage.
+    /// Input is processed chunk by chunk as it arrives, so this works well for large documents and network streams. Just like [`BioReader::bio_read`], the `writer` is not flushed.
     ///
     /// # Example
     ///
     /// ```rust
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
     /// use bio_read::BioReader;
-    /// use std::io::Write;
     /// let reader = BioReader::new()
     ///     .emphasize(String::from("<em>"), String::from("</em>"))
     ///     .de_emphasize(String::from("<de>"), String::from("</de>"));
     /// let mut output_buffer = Vec::new();
-    /// reader.bio_read("hello world".as_bytes(), &mut output_buffer).unwrap();
+    /// reader.bio_read_async("hello world".as_bytes(), &mut output_buffer).await.unwrap();
     /// let output = String::from_utf8(output_buffer).unwrap();
     /// assert_eq!(output, "<em>hel</em><de>lo</de> <em>wor</em><de>ld</de>");
+    /// # });
     /// ```
     ///
     /// # See also
     ///
-    /// [`BioReader::bio_read_text`]: A simple wrapper around [`BioReader::bio_read`] for processing short strings.
-    pub fn bio_read(&self, reader: impl Read, writer: &mut impl Write) -> std::io::Result<()> {
-        let mut state = State {
-            read: 0,
-            written: 0,
-        };
-        // The buffer size is at most `self.reverse_fixation_boundaries.last().unwrap()`
-        let rev_boundaries = &self.reverse_fixation_boundaries;
-        let last = rev_boundaries.last().expect("Invalid fixation boundaries");
-        let mut buffer = VecDeque::with_capacity(*last);
-        // Iterate over the reader
-        for c in reader.bytes() {
-            let c = c? as char;
-            if c.is_ascii_alphabetic() {
-                // A letter
-                state.read += 1;
-                if state.read == 1 {
-                    // Start of a word
-                    // Write emphasize start
-                    writer.write_all(self.emphasize[0].as_bytes())?;
-                } else {
-                    // Middle of a word
-                    self.try_write(writer, &mut buffer, &mut state)?;
-                }
-                buffer.push_back(c);
-            } else {
-                // Not a letter - special character
-                if state.read != 0 {
-                    // End of a word
-                    self.try_write(writer, &mut buffer, &mut state)?;
-                    // Write emphasize end
-                    writer.write_all(self.emphasize[1].as_bytes())?;
-                    self.de_emphasize_buffer(writer, &mut buffer)?;
-                    state.read = 0;
-                    state.written = 0;
-                }
-                // Write the special character
-                writer.write_all(&[c as u8])?;
+    /// [`BioReader::bio_read`]: The blocking counterpart of this method.
+    #[cfg(feature = "async")]
+    pub async fn bio_read_async(
+        &self,
+        mut reader: impl tokio::io::AsyncRead + Unpin,
+        writer: &mut (impl tokio::io::AsyncWrite + Unpin),
+    ) -> Result<(), Error> {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let mut processor = Processor::new(self);
+        let mut chunk = vec![0_u8; CHUNK_SIZE];
+        let mut output = Vec::with_capacity(CHUNK_SIZE);
+        loop {
+            let n = reader.read(&mut chunk).await?;
+            if n == 0 {
+                break;
             }
+            processor.push_bytes(&chunk[..n], &mut output)?;
+            writer.write_all(&output).await?;
+            output.clear();
         }
-        // Write the unfinished word
-        if state.read > 0 {
-            // Write emphasize end
-            writer.write_all(self.emphasize[1].as_bytes())?;
-            self.de_emphasize_buffer(writer, &mut buffer)?;
-        }
-        Ok(())
+        processor.finish(&mut output)?;
+        Ok(writer.write_all(&output).await?)
     }
     /// Do bio-reading on a piece of text. This is a simple wrapper for processing short strings. If you intend to process large files or work with streams, use [`BioReader::bio_read`] instead.
     ///
@@ -200,11 +1701,245 @@ impl BioReader {
     /// # See also
     ///
     /// [`BioReader::bio_read`]: Do bio-reading on `reader` and write the result to `writer`.
-    pub fn bio_read_text(&self, text: &str) -> Result<String, std::io::Error> {
+    pub fn bio_read_text(&self, text: &str) -> Result<String, Error> {
         let mut output_buffer = Vec::new();
-        self.bio_read(text.as_bytes(), &mut output_buffer)?;
+        self.process(Processor::new(self).decoded(), text.as_bytes(), &mut output_buffer)?;
         Ok(String::from_utf8(output_buffer).unwrap())
     }
+    /// Do bio-reading on a piece of text like [`BioReader::bio_read_text`], splitting it into pieces at paragraph boundaries (blank lines) and bio-reading them on multiple threads. Useful for large documents, of several megabytes or more.
+    ///
+    /// The output is always the same as that of [`BioReader::bio_read_text`]. So text is only split for plain text input, and if nothing carries over from one paragraph to the next: that is, if every word is emphasized and the first words of sentences aren't boosted, or if [`BioReader::reset_at_paragraphs`] is enabled. Otherwise, or if the text is too short to be worth splitting, it is bio-read on the current thread.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from("<de>"), String::from("</de>"));
+    /// let text = "hello world\n\n".repeat(100_000);
+    /// let output = reader.bio_read_text_parallel(&text).unwrap();
+    /// assert_eq!(output, reader.bio_read_text(&text).unwrap());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [`BioReader::bio_read_text`]: The single-threaded counterpart of this method.
+    #[cfg(feature = "rayon")]
+    pub fn bio_read_text_parallel(&self, text: &str) -> Result<String, Error> {
+        use rayon::prelude::*;
+        // Detect the format from the first chunk, like `bio_read` does
+        let format = match self.format {
+            InputFormat::Auto => InputFormat::sniff(&text.as_bytes()[..text.len().min(CHUNK_SIZE)]),
+            format => format,
+        };
+        let independent = self.reset_at_paragraphs || (self.saccade == 1 && self.sentence_boost == 0);
+        if format != InputFormat::Plain || !independent {
+            return self.bio_read_text(text);
+        }
+        let outputs = segment::paragraph_chunks(text, PARALLEL_CHUNK_SIZE)
+            .into_par_iter()
+            .map(|chunk| {
+                let mut output = Vec::with_capacity(chunk.len() * 2);
+                let processor = Processor::with_format(self, self.renderer_ref(), InputFormat::Plain).decoded();
+                self.process(processor, chunk.as_bytes(), &mut output)?;
+                Ok(output)
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
+        Ok(String::from_utf8(outputs.concat()).unwrap())
+    }
+
+    /// Do bio-reading on a piece of text lazily, returning a value implementing [`Display`](fmt::Display) that writes the output as it is formatted. Useful for embedding bio-read text in [`format!`] or [`println!`] without allocating an intermediate `String`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from(""), String::from(""));
+    /// assert_eq!(format!("> {}!", reader.display("hello world")), "> <em>hel</em>lo <em>wor</em>ld!");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [`BioReader::bio_read_text`]: Do bio-reading on a piece of text, returning a string.
+    pub fn display<'a>(&'a self, text: &'a str) -> display::Display<'a> {
+        display::Display::new(self, text)
+    }
+    /// Do bio-reading on a piece of text, returning the spans of emphasized and de-emphasized text as byte ranges into `text`, instead of writing wrappers around them. Useful for applying custom styling, e.g. in GUIs or editors. The renderer is not used.
+    ///
+    /// Spans are in order, and respect [`BioReader::format`], so only prose is covered for formats other than plain text. Text can't be a Word document, so with [`InputFormat::Docx`] it is read as plain text.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{BioReader, EmphasisSpan, SpanKind};
+    /// let reader = BioReader::new();
+    /// let spans = reader.bio_read_spans("hello world");
+    /// assert_eq!(spans, vec![
+    ///     EmphasisSpan { range: 0..3, kind: SpanKind::Emphasized },
+    ///     EmphasisSpan { range: 3..5, kind: SpanKind::DeEmphasized },
+    ///     EmphasisSpan { range: 6..9, kind: SpanKind::Emphasized },
+    ///     EmphasisSpan { range: 9..11, kind: SpanKind::DeEmphasized },
+    /// ]);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [`BioReader::bio_read_text`]: Do bio-reading on a piece of text, returning a string.
+    pub fn bio_read_spans(&self, text: &str) -> Vec<EmphasisSpan> {
+        self.spans_with_format(text.as_bytes(), self.text_format()).expect("Recording spans of valid UTF-8 should never fail")
+    }
+    /// Do bio-reading on a slice of bytes in memory, returning an iterator over its segments, which borrow from `input` instead of copying it. Useful for integrating bio-reading into parsers and other zero-copy pipelines, without allocating strings for the output. The renderer is not used.
+    ///
+    /// The segments make up the input, in order, and respect [`BioReader::format`] like [`BioReader::bio_read_spans`], which they are made from. Words that aren't emphasized, like those skipped by the saccade, are part of the separators. Invalid UTF-8 in plain text is given as [`Segment::Invalid`], and input that the format can't process, like Markdown that isn't valid UTF-8, is given as it is, without emphasis.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{BioReader, Segment};
+    /// let reader = BioReader::new();
+    /// let segments: Vec<_> = reader.bio_read_segments(b"hello, \xffworld").collect();
+    /// assert_eq!(segments, vec![
+    ///     Segment::Emphasized("hel"),
+    ///     Segment::Plain("lo"),
+    ///     Segment::Separator(", "),
+    ///     Segment::Invalid(b"\xff"),
+    ///     Segment::Emphasized("wor"),
+    ///     Segment::Plain("ld"),
+    /// ]);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [`BioReader::bio_read_spans`]: Do bio-reading on a piece of text, returning the spans of emphasized and de-emphasized text.
+    pub fn bio_read_segments<'a>(&self, input: &'a [u8]) -> impl Iterator<Item = Segment<'a>> + 'a {
+        let spans = self.spans_with_format(input, self.text_format()).unwrap_or_default();
+        let mut position = 0;
+        // The text between spans is separators, and so is the text after the last one
+        spans.into_iter().map(Some).chain([None]).flat_map(move |span| {
+            let start = span.as_ref().map_or(input.len(), |span| span.range.start);
+            let gap = input[position..start].utf8_chunks().flat_map(|chunk| [Segment::Separator(chunk.valid()), Segment::Invalid(chunk.invalid())]);
+            position = span.as_ref().map_or(input.len(), |span| span.range.end);
+            let span = span.map(|span| {
+                let text = std::str::from_utf8(&input[span.range]).expect("Spans should be valid UTF-8");
+                match span.kind {
+                    SpanKind::Emphasized => Segment::Emphasized(text),
+                    SpanKind::DeEmphasized => Segment::Plain(text),
+                    SpanKind::Secondary => Segment::Secondary(text),
+                }
+            });
+            gap.chain(span).filter(|segment| !segment.is_empty())
+        })
+    }
+    /// Do bio-reading on a piece of text, returning the words as byte and character ranges into `text`, with the byte range of their emphasized starts. Useful for highlighting words as text-to-speech or karaoke-style readers play them, which [`cues::write_cues`] exports them for. The renderer is not used.
+    ///
+    /// Words are in order, and respect [`BioReader::format`] like [`BioReader::bio_read_visit`], which they are made from, so words that aren't emphasized have an empty emphasized range.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{BioReader, WordSpan};
+    /// let words = BioReader::new().bio_read_words("Olá mundo");
+    /// assert_eq!(words, vec![
+    ///     WordSpan { range: 0..4, chars: 0..3, emphasized: 0..1 },
+    ///     WordSpan { range: 5..10, chars: 4..9, emphasized: 5..8 },
+    /// ]);
+    /// ```
+    pub fn bio_read_words(&self, text: &str) -> Vec<WordSpan> {
+        let mut words = Vec::new();
+        let (mut position, mut chars) = (0, 0);
+        self.bio_read_visit(text, |token| match token {
+            Token::Word { emphasized, rest } => {
+                let len = emphasized.chars().count() + rest.chars().count();
+                let end = position + emphasized.len() + rest.len();
+                words.push(WordSpan {
+                    range: position..end,
+                    chars: chars..chars + len,
+                    emphasized: position..position + emphasized.len(),
+                });
+                (position, chars) = (end, chars + len);
+            }
+            Token::Separator(separator) => {
+                position += separator.len();
+                chars += separator.chars().count();
+            }
+        });
+        words
+    }
+    /// Do bio-reading on a piece of text, calling `visitor` with each word, split into its emphasized start and the rest, and with the text between words, in order. Useful for custom renderers, statistics, or aligning text-to-speech with the words, without writing wrappers around them. The renderer is not used.
+    ///
+    /// The tokens make up the text, and respect [`BioReader::format`], so markup of formats other than plain text is given as separators. With [`InputFormat::Docx`], it is read as plain text. Words that aren't emphasized, like those skipped by the saccade, are given with nothing emphasized, while compound words emphasized part by part with [`CompoundPolicy::EachPart`] are given part by part.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::{BioReader, Token};
+    /// let reader = BioReader::new().saccade(2);
+    /// let mut output = String::new();
+    /// reader.bio_read_visit("hello, big world", |token| match token {
+    ///     Token::Word { emphasized, rest } => output.push_str(&format!("[{emphasized}|{rest}]")),
+    ///     Token::Separator(text) => output.push_str(text),
+    /// });
+    /// assert_eq!(output, "[hel|lo], [|big] [wor|ld]");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [`BioReader::bio_read_spans`]: Do bio-reading on a piece of text, returning the spans of emphasized and de-emphasized text.
+    pub fn bio_read_visit(&self, text: &str, visitor: impl FnMut(Token<'_>)) {
+        let recorder = WordRecorder::default();
+        let mut writer = CollectingWriter::new(&recorder.position);
+        let mut observer = WordObserver(&recorder);
+        let mut processor = Processor::with_format(self, &recorder, self.text_format()).verbatim().with_observer(&mut observer);
+        processor
+            .push_bytes(text.as_bytes(), &mut writer)
+            .and_then(|()| processor.finish(&mut writer))
+            .expect("Recording words of valid UTF-8 should never fail");
+        let output = String::from_utf8(writer.output).expect("Output of valid UTF-8 should be valid UTF-8");
+        recorder.visit(&output, visitor);
+    }
+    /// Undo bio-reading, removing the markup written by the renderer from `output` to recover the original text. Useful for round-trip testing, and for un-processing files that were converted in place.
+    ///
+    /// With wrappers set by [`BioReader::emphasize`] and [`BioReader::de_emphasize`] (including the default ANSI styles), the wrappers are removed. With other renderers, ANSI escape codes are removed, so [`Ansi`] and [`Gradient`](render::Gradient) output can be stripped, while other markup, like HTML tags, is left as-is unless the renderer overrides [`EmphasisRenderer::strip`]. The zero-width joiners inserted by [`BioReader::join_cursive`] are removed as well.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::BioReader;
+    /// let reader = BioReader::new()
+    ///     .emphasize(String::from("<em>"), String::from("</em>"))
+    ///     .de_emphasize(String::from("<de>"), String::from("</de>"));
+    /// let output = reader.bio_read_text("hello world").unwrap();
+    /// assert_eq!(reader.strip(&output), "hello world");
+    /// let reader = BioReader::new();
+    /// assert_eq!(reader.strip(&reader.bio_read_text("مرحبا").unwrap()), "مرحبا");
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// [`BioReader::bio_read_text`]: Do bio-reading on a piece of text, returning a string.
+    pub fn strip(&self, output: &str) -> String {
+        self.renderer_ref().strip(output)
+    }
+    /// Get the format to read text in for spans and tokens, which is [`BioReader::format`] unless that is [`InputFormat::Docx`], as Word documents can't be given as text. Their text is read as plain text instead.
+    fn text_format(&self) -> InputFormat {
+        match self.format {
+            #[cfg(feature = "docx")]
+            InputFormat::Docx => InputFormat::Plain,
+            format => format,
+        }
+    }
+    /// Get the spans of emphasized and de-emphasized text in `input`, reading it in `format` instead of [`BioReader::format`]. Fails if the format requires valid UTF-8 and `input` isn't.
+    fn spans_with_format(&self, input: &[u8], format: InputFormat) -> std::io::Result<Vec<EmphasisSpan>> {
+        let recorder = SpanRecorder::default();
+        let mut writer = PositionWriter(&recorder.position);
+        let mut processor = Processor::with_format(self, &recorder, format).verbatim();
+        processor.push_bytes(input, &mut writer)?;
+        processor.finish(&mut writer)?;
+        Ok(recorder.into_spans())
+    }
 
     /// Get the fixation boundaries given a fixation point. A word of length `fixation_boundaries[i]` or less will be emphasized except for the last `i` characters. If the word is longer than `fixation_boundaries.last()`, `fixation_boundaries.len()` will be used (one more than the last boundary).
     fn fixation_boundaries(fixation_point: usize) -> Vec<usize> {
@@ -236,8 +1971,8 @@ impl BioReader {
         let last = fixation_boundaries.last().expect("Invalid fixation boundaries");
         let mut fixation = 0;
         let mut result = vec![0; *last + 1];
-        for i in 0_usize..=*last {
-            result[i] = fixation;
+        for (i, slot) in result.iter_mut().enumerate() {
+            *slot = fixation;
             if i >= fixation_boundaries[fixation] {
                 fixation += 1;
             }
@@ -252,40 +1987,600 @@ impl BioReader {
             *self.reverse_fixation_boundaries.last().unwrap() + 1 // Longer words default to the last plus one
         }
     }
-    /// Write the buffer wrapped with de-emphasize tags
-    fn de_emphasize_buffer(&self, writer: &mut impl Write, buffer: &mut VecDeque<char>) -> std::io::Result<()> {
-        // Skip if the buffer is empty
-        if buffer.is_empty() {
+    /// Get the [`Wrap`] renderer to customize, replacing the current renderer if it is not one.
+    fn wrap_mut(&mut self) -> &mut Wrap {
+        if let Renderer::Custom(_) = self.renderer {
+            self.renderer = Renderer::Wrap(Wrap::from(Ansi::default()));
+        }
+        match &mut self.renderer {
+            Renderer::Wrap(wrap) => wrap,
+            Renderer::Custom(_) => unreachable!(),
+        }
+    }
+    /// Get the renderer.
+    fn renderer_ref(&self) -> &(dyn EmphasisRenderer + Sync) {
+        match &self.renderer {
+            Renderer::Wrap(wrap) => wrap,
+            Renderer::Custom(renderer) => renderer.as_ref(),
+        }
+    }
+    /// Whether `c` is a letter.
+    fn is_letter(&self, c: char) -> bool {
+        if self.unicode {
+            c.is_alphabetic() || (self.token_policy != TokenPolicy::SplitAtDigits && c.is_numeric())
+        } else {
+            c.is_ascii_alphabetic() || (self.token_policy != TokenPolicy::SplitAtDigits && c.is_ascii_digit())
+        }
+    }
+    /// Whether `c` is a combining mark or another character attached to the preceding letter.
+    fn is_mark(&self, c: char) -> bool {
+        self.unicode && (is_mark(c) || segment::is_attached(c))
+    }
+    /// Whether `c` counts towards the length of a word, i.e. is a letter but not a combining mark.
+    fn counts_as_letter(&self, c: char) -> bool {
+        self.is_letter(c) && !self.is_mark(c)
+    }
+    /// Process a piece of decoded text, passing terminal escape sequences of operating system commands through, so that OSC 8 hyperlinks keep working and only their text is bio-read.
+    fn push_str(&self, text: &str, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        let mut rest = text;
+        loop {
+            if state.osc == Osc::Text {
+                let Some(start) = rest.find('\x1b') else {
+                    return self.push_text(rest, writer, state);
+                };
+                self.push_text(&rest[..start], writer, state)?;
+                rest = &rest[start..];
+            }
+            let (len, osc) = state.osc.sequence_len(rest);
+            state.osc = osc;
+            if len > 0 {
+                // Sequences split words, but are not text, so they leave sentences and paragraphs as they are
+                self.end_text(writer, state)?;
+                state.renderer.plain(writer, &rest[..len])?;
+            }
+            rest = &rest[len..];
+            if rest.is_empty() {
+                return Ok(());
+            }
+        }
+    }
+    /// Process a piece of decoded text without escape sequences.
+    fn push_text(&self, text: &str, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        if self.skip_patterns.is_empty() && !self.preserve_urls {
+            return self.push_words(text, writer, state);
+        }
+        // Buffer runs of non-whitespace characters, so that skip patterns can be matched against them
+        let mut rest = text;
+        while let Some(start) = rest.find(char::is_whitespace) {
+            self.push_token(&rest[..start], writer, state)?;
+            self.end_token(writer, state)?;
+            let end = rest[start..].find(|c: char| !c.is_whitespace()).map_or(rest.len(), |n| start + n);
+            self.push_words(&rest[start..end], writer, state)?;
+            rest = &rest[end..];
+        }
+        self.push_token(rest, writer, state)
+    }
+    /// Buffer `text`, a part of a run of non-whitespace characters. Runs longer than the maximum word length are processed in pieces, without matching skip patterns or preserved URLs across them.
+    fn push_token(&self, text: &str, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        if state.token.len() + text.len() > self.max_word_len {
+            self.end_token(writer, state)?;
+            if text.len() > self.max_word_len {
+                return self.push_words(text, writer, state);
+            }
+        }
+        state.token.push_str(text);
+        Ok(())
+    }
+    /// Process the buffered run of non-whitespace characters (if any), passing text matching skip patterns or preserved URLs through.
+    fn end_token(&self, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        if state.token.is_empty() {
             return Ok(());
         }
-        // Write de-emphasize start
-        writer.write_all(self.de_emphasize[0].as_bytes())?;
-        // Write unwritten word characters
-        let to_write = buffer.drain(..).map(|c| c as u8).collect::<Vec<_>>();
-        writer.write_all(&to_write)?;
-        // Write de-emphasize end
-        writer.write_all(self.de_emphasize[1].as_bytes())?;
+        let token = std::mem::take(&mut state.token);
+        let preserved = self.preserve_urls.then(|| &*segment::PRESERVED);
+        let mut matches: Vec<_> = self
+            .skip_patterns
+            .iter()
+            .chain(preserved)
+            .flat_map(|pattern| pattern.find_iter(&token))
+            .filter(|m| !m.is_empty())
+            .map(|m| m.range())
+            .collect();
+        matches.sort_unstable_by_key(|range| (range.start, std::cmp::Reverse(range.end)));
+        // End of the text that has been processed
+        let mut processed = 0;
+        for range in matches {
+            if range.start < processed {
+                // Overlaps with a previous match
+                continue;
+            }
+            self.push_words(&token[processed..range.start], writer, state)?;
+            self.end_word(writer, state)?;
+            state.renderer.plain(writer, &token[range.clone()])?;
+            state.sentence = Sentence::Middle;
+            state.line_breaks = 0;
+            processed = range.end;
+        }
+        self.push_words(&token[processed..], writer, state)?;
+        // Reuse the allocation
+        state.token = token;
+        state.token.clear();
+        Ok(())
+    }
+    /// Process a piece of decoded text, word by word.
+    fn push_words(&self, text: &str, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        let bytes = text.as_bytes();
+        // Start of the text between words that has not been written yet
+        let mut plain_start = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i].is_ascii_alphabetic() {
+                // Fast path for runs of ASCII letters, which are letters in any mode
+                self.end_cjk(writer, state)?;
+                if plain_start < i {
+                    state.renderer.plain(writer, &text[plain_start..i])?;
+                }
+                if let Some(joiner) = state.joiner.take() {
+                    state.word.push(joiner);
+                }
+                // Up to the length limit, but at least one letter so that the word grows
+                let room = self.word_limit().saturating_sub(state.word.len()).max(1);
+                let end = i + segment::ascii_letters_len(&bytes[i..]).min(room);
+                state.word.push_str(&text[i..end]);
+                state.letters += end - i;
+                self.limit_word(writer, state)?;
+                plain_start = end;
+                i = end;
+                continue;
+            }
+            if state.word.is_empty() && !state.long_word && state.cjk.is_empty() && bytes[i].is_ascii() {
+                // Fast path for runs of other ASCII characters between words, which are written as-is
+                let mut len = segment::ascii_non_letters_len(&bytes[i..]);
+                if self.token_policy != TokenPolicy::SplitAtDigits {
+                    // Unless digits start words too
+                    len = bytes[i..i + len].iter().position(u8::is_ascii_digit).unwrap_or(len);
+                }
+                if len > 0 {
+                    if self.sentence_boost > 0 || self.reset_at_paragraphs || self.word_style_hook.is_some() || state.observer.is_some() {
+                        text[i..i + len].chars().for_each(|c| state.pass(c));
+                    }
+                    i += len;
+                    continue;
+                }
+            }
+            let start = i;
+            let c = text[start..].chars().next().expect("Index should be at a char boundary");
+            i += c.len_utf8();
+            if self.unicode && segment::is_cjk(c) {
+                // Chinese or Japanese, which isn't separated into words by spaces
+                if state.cjk.is_empty() {
+                    self.end_word(writer, state)?;
+                }
+                state.line_breaks = 0;
+                match self.cjk {
+                    Cjk::PassThrough => {}
+                    #[cfg(feature = "cjk")]
+                    Cjk::Segment => {
+                        if plain_start < start {
+                            state.renderer.plain(writer, &text[plain_start..start])?;
+                        }
+                        state.cjk.push(c);
+                        if state.cjk.len() >= self.max_word_len {
+                            self.end_cjk(writer, state)?;
+                        }
+                        plain_start = i;
+                    }
+                }
+                continue;
+            }
+            let is_mark = self.is_mark(c);
+            let in_word = !state.word.is_empty() || state.long_word;
+            if (self.is_letter(c) && !is_mark) || (in_word && state.joiner.is_none() && is_mark) {
+                // A letter, or a combining mark in the middle of a word. Marks never start a word, since they belong with whatever comes before them
+                self.end_cjk(writer, state)?;
+                if plain_start < start {
+                    state.renderer.plain(writer, &text[plain_start..start])?;
+                }
+                if let Some(joiner) = state.joiner.take() {
+                    // The joiner is between two letters after all
+                    state.word.push(joiner);
+                }
+                state.word.push(c);
+                state.letters += self.counts_as_letter(c) as usize;
+                self.limit_word(writer, state)?;
+                plain_start = i;
+            } else if in_word && state.joiner.is_none() && self.word_chars.is_joiner(c) {
+                // Possibly inside the word, depending on what follows
+                state.joiner = Some(c);
+                plain_start = i;
+            } else {
+                // Not a letter - special character
+                self.end_word(writer, state)?;
+                state.pass(c);
+            }
+        }
+        if plain_start < text.len() {
+            state.renderer.plain(writer, &text[plain_start..])?;
+        }
+        Ok(())
+    }
+    /// End the current text (if any), then write `bytes` as-is.
+    fn push_raw(&self, bytes: &[u8], writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        self.end_text(writer, state)?;
+        // Markup, like that of Markdown, may hold the blank lines between paragraphs
+        for &byte in bytes {
+            match byte {
+                b'\n' => state.pass('\n'),
+                _ if !byte.is_ascii_whitespace() => state.line_breaks = 0,
+                _ => {}
+            }
+        }
+        writer.write_all(bytes)
+    }
+    /// End the current text (if any), then write `text` emphasized as a whole, like the bold text of man pages.
+    fn push_emphasized(&self, text: &str, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        self.end_text(writer, state)?;
+        if !text.trim().is_empty() {
+            state.sentence = Sentence::Middle;
+            state.line_breaks = 0;
+        }
+        state.renderer.emphasized(writer, text)
+    }
+    /// End the buffered run of non-whitespace characters and the current word, if any.
+    fn end_text(&self, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        self.end_token(writer, state)?;
+        self.end_word(writer, state)
+    }
+    /// End the current word, if any, writing it with the renderer, followed by the joiner after it, if any.
+    fn end_word(&self, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        self.end_cjk(writer, state)?;
+        if std::mem::take(&mut state.long_word) {
+            // The rest of a word too long to be emphasized
+            state.renderer.plain(writer, &state.word)?;
+            state.word.clear();
+            state.letters = 0;
+        }
+        if !state.word.is_empty() {
+            if state.paragraph {
+                state.paragraph = false;
+                if self.reset_at_paragraphs {
+                    state.words = 0;
+                    state.sentence = Sentence::Start;
+                }
+                if let Some(observer) = &mut state.observer {
+                    observer.on_paragraph();
+                }
+            }
+            let skipped = self.is_excluded(&state.word, &mut state.folded)
+                || (self.skip_acronyms && is_acronym(&state.word))
+                || (self.token_policy == TokenPolicy::SkipNumeric && state.word.chars().any(char::is_numeric));
+            let style = self.word_style_hook.as_ref().and_then(|hook| {
+                hook(&WordInfo {
+                    word: &state.word,
+                    letters: self.word_letters(&state.word, state.letters),
+                    sentence_start: state.sentence == Sentence::Start,
+                    plain: skipped || !state.words.is_multiple_of(self.saccade),
+                })
+            });
+            let style = style.unwrap_or_default();
+            // Spans and tokens are recorded by the renderer of the state, so other renderers are not used then
+            let renderer = match &style.renderer {
+                Some(renderer) if !state.verbatim => &**renderer,
+                _ => state.renderer,
+            };
+            let emphasized = if style.skip || (skipped && style.emphasized_letters.is_none()) {
+                // Excluded words, acronyms and numbers don't count towards the saccade
+                renderer.plain(writer, &state.word)?;
+                0
+            } else {
+                let emphasized = if let Some(emphasized_letters) = style.emphasized_letters {
+                    let word = std::mem::take(&mut state.word);
+                    let emphasized_letters = emphasized_letters.min(self.word_letters(&word, state.letters));
+                    let emphasized = self.write_word(writer, state, renderer, &word, emphasized_letters)?;
+                    state.word = word;
+                    emphasized
+                } else if state.words.is_multiple_of(self.saccade) {
+                    let mut boost = if state.sentence == Sentence::Start { self.sentence_boost } else { 0 };
+                    // Taken out of the state while written, and put back to reuse its allocation
+                    let word = std::mem::take(&mut state.word);
+                    let emphasized = if self.compound_policy == CompoundPolicy::EachPart && word.contains(segment::is_hyphen) {
+                        let mut emphasized = 0;
+                        for part in word.split_inclusive(segment::is_hyphen) {
+                            let (part, hyphen) = part.split_at(part.trim_end_matches(segment::is_hyphen).len());
+                            // Only the first part is boosted
+                            let emphasized_letters = self.emphasized_letters(part, self.count_letters(part), std::mem::take(&mut boost));
+                            emphasized += self.write_word(writer, state, renderer, part, emphasized_letters)?;
+                            renderer.plain(writer, hyphen)?;
+                        }
+                        emphasized
+                    } else {
+                        let emphasized_letters = self.emphasized_letters(&word, self.word_letters(&word, state.letters), boost);
+                        self.write_word(writer, state, renderer, &word, emphasized_letters)?
+                    };
+                    state.word = word;
+                    emphasized
+                } else {
+                    // Skipped by the saccade
+                    renderer.plain(writer, &state.word)?;
+                    0
+                };
+                state.words += 1;
+                emphasized
+            };
+            if let Some(observer) = &mut state.observer {
+                observer.on_word(&state.word, emphasized);
+            }
+            state.sentence = Sentence::Middle;
+            state.line_breaks = 0;
+            state.word.clear();
+            state.letters = 0;
+        }
+        if let Some(joiner) = state.joiner.take() {
+            state.renderer.plain(writer, joiner.encode_utf8(&mut [0; 4]))?;
+        }
         Ok(())
     }
-    /// Try to write a part of the buffer, with respect to the current state
-    fn try_write(&self, writer: &mut impl Write, buffer: &mut VecDeque<char>, state: &mut State) -> std::io::Result<()> {
-        let fixation_length_from_last = self.get_fixation_length_from_last(state.read);
-        // At least `least_emphasize_length` characters should be emphasized
-        let least_emphasize_length = state.read - fixation_length_from_last;
-        if state.written < least_emphasize_length {
-            // Write word[written, least_emphasize_length], which should be buffer[0, least_emphasize_length - written]
-            let to_write = buffer.drain(0..least_emphasize_length - state.written).map(|c| c as u8).collect::<Vec<_>>();
-            writer.write_all(&to_write)?;
-            state.written = least_emphasize_length;
+    /// The length at which the current word is handled following the long word policy: right after the maximum length when long words are written as-is, and at it when they are split into pieces of it.
+    fn word_limit(&self) -> usize {
+        match self.long_word_policy {
+            LongWordPolicy::Plain => self.max_word_len + 1,
+            LongWordPolicy::Split => self.max_word_len,
+        }
+    }
+    /// Handle the current word following the long word policy if it has reached the length limit, so that it is not buffered any further.
+    fn limit_word(&self, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        if state.word.len() < self.word_limit() {
+            return Ok(());
+        }
+        match self.long_word_policy {
+            LongWordPolicy::Plain => {
+                state.renderer.plain(writer, &state.word)?;
+                state.word.clear();
+                state.letters = 0;
+                state.long_word = true;
+                state.sentence = Sentence::Middle;
+                state.line_breaks = 0;
+                Ok(())
+            }
+            LongWordPolicy::Split => self.end_word(writer, state),
+        }
+    }
+    /// Get how many letters of `word`, which has `letters` letters, to emphasize, plus `boost` more letters.
+    fn emphasized_letters(&self, word: &str, letters: usize, boost: usize) -> usize {
+        let emphasized_letters = match &self.fixation_strategy {
+            Some(strategy) => strategy.emphasized_len(word),
+            None => letters - self.get_fixation_length_from_last(letters),
+        };
+        emphasized_letters.saturating_add(boost).min(letters)
+    }
+    /// Write `word` with `renderer`, with its first `emphasized_letters` letters emphasized, returning how many characters are emphasized.
+    fn write_word(&self, writer: &mut impl Write, state: &mut State<'_>, renderer: &(dyn EmphasisRenderer + Sync), word: &str, emphasized_letters: usize) -> std::io::Result<usize> {
+        let split = self.split_after_letters(word, emphasized_letters);
+        if let Some(secondary) = self.secondary_range(word, emphasized_letters) {
+            let parts = [&word[..split], &word[split..secondary.start], &word[secondary.clone()], &word[secondary.end..]];
+            if self.join_cursive && !state.verbatim && parts.windows(2).any(|pair| joins_across(pair[0], pair[1])) {
+                // Keep the letters around each split in their joined forms, like a word with one split
+                state.joined.clear();
+                let mut splits = [0; 3];
+                for (i, part) in parts.iter().enumerate() {
+                    if i > 0 {
+                        let joins = joins_across(parts[i - 1], part);
+                        if joins {
+                            state.joined.push('\u{200D}');
+                        }
+                        splits[i - 1] = state.joined.len();
+                        if joins {
+                            state.joined.push('\u{200D}');
+                        }
+                    }
+                    state.joined.push_str(part);
+                }
+                let joined = state.joined.as_str();
+                renderer.word_with_secondary(writer, &joined[..splits[0]], &joined[splits[0]..splits[1]], &joined[splits[1]..splits[2]], &joined[splits[2]..])?;
+            } else {
+                renderer.word_with_secondary(writer, parts[0], parts[1], parts[2], parts[3])?;
+            }
+            return Ok(parts[0].chars().count());
+        }
+        let (emphasized, de_emphasized) = word.split_at(split);
+        if self.join_cursive && !state.verbatim && joins_across(emphasized, de_emphasized) {
+            // Keep the letters around the split in their joined forms, in a buffer reused across words
+            state.joined.clear();
+            state.joined.push_str(emphasized);
+            state.joined.push('\u{200D}');
+            let split = state.joined.len();
+            state.joined.push('\u{200D}');
+            state.joined.push_str(de_emphasized);
+            let (emphasized, de_emphasized) = state.joined.split_at(split);
+            renderer.word(writer, emphasized, de_emphasized)?;
+        } else {
+            renderer.word(writer, emphasized, de_emphasized)?;
+        }
+        Ok(emphasized.chars().count())
+    }
+    /// Process the buffered run of Chinese and Japanese characters (if any), emphasizing each word in it.
+    #[cfg_attr(not(feature = "cjk"), allow(unused_variables))]
+    fn end_cjk(&self, writer: &mut impl Write, state: &mut State<'_>) -> std::io::Result<()> {
+        #[cfg(feature = "cjk")]
+        if !state.cjk.is_empty() {
+            let run = std::mem::take(&mut state.cjk);
+            for word in segment::cut_cjk(&run) {
+                state.word.push_str(word);
+                state.letters = word.chars().filter(|&c| self.counts_as_letter(c)).count();
+                self.end_word(writer, state)?;
+            }
+            // Reuse the allocation
+            state.cjk = run;
+            state.cjk.clear();
         }
         Ok(())
     }
+    /// Whether `word` is one of the excluded words, lowercasing it into `folded`.
+    fn is_excluded(&self, word: &str, folded: &mut String) -> bool {
+        if self.folded_excluded_words.is_empty() {
+            return false;
+        }
+        self.language.lowercase_into(word, folded);
+        self.folded_excluded_words.contains(folded.as_str())
+    }
+    /// Lowercase the excluded words following the casing rules of the language.
+    fn fold_excluded_words(&mut self) {
+        self.folded_excluded_words = self.excluded_words.iter().map(|word| self.language.lowercase(word)).collect();
+    }
+    /// Count the letters in the current `word`, which has `letters` letters counted one character at a time. With the `unicode` feature, each grapheme cluster with letters in it counts once instead, like a Hangul syllable made up of conjoining jamo.
+    #[cfg_attr(not(feature = "unicode"), allow(unused_variables))]
+    fn word_letters(&self, word: &str, letters: usize) -> usize {
+        #[cfg(feature = "unicode")]
+        if self.unicode && !word.is_ascii() {
+            return self.count_letters(word);
+        }
+        letters
+    }
+    /// Get the byte range of the secondary emphasis of `word`, which has its first `emphasized_letters` letters emphasized, if it has one. See [`BioReader::secondary_fixation`].
+    fn secondary_range(&self, word: &str, emphasized_letters: usize) -> Option<Range<usize>> {
+        if !self.secondary_fixation || emphasized_letters == 0 {
+            return None;
+        }
+        let letters = self.count_letters(word);
+        if letters <= SECONDARY_FIXATION_LETTERS {
+            return None;
+        }
+        // A third of the rest, centered in it so that some of the word stays de-emphasized on both sides
+        let rest = letters.saturating_sub(emphasized_letters);
+        let len = rest / 3;
+        let start = emphasized_letters + (rest - len) / 2;
+        (len > 0).then(|| self.split_after_letters(word, start)..self.split_after_letters(word, start + len))
+    }
+    /// Count the letters in `word`, like [`BioReader::word_letters`] does for the current word.
+    fn count_letters(&self, word: &str) -> usize {
+        #[cfg(feature = "unicode")]
+        if self.unicode && !word.is_ascii() {
+            return word.graphemes(true).filter(|cluster| cluster.chars().any(|c| self.counts_as_letter(c))).count();
+        }
+        word.chars().filter(|&c| self.counts_as_letter(c)).count()
+    }
+    /// Get the byte index in `word` right after the first `letters` letters, along with the combining marks and joiners attached to them. With the `unicode` feature, letters are counted by grapheme cluster, so that a cluster is never split.
+    fn split_after_letters(&self, word: &str, letters: usize) -> usize {
+        #[cfg(feature = "unicode")]
+        if self.unicode && !word.is_ascii() {
+            let mut seen = 0;
+            for (i, cluster) in word.grapheme_indices(true) {
+                if cluster.chars().any(|c| self.counts_as_letter(c)) {
+                    if seen == letters {
+                        return i;
+                    }
+                    seen += 1;
+                }
+            }
+            return word.len();
+        }
+        let mut seen = 0;
+        for (i, c) in word.char_indices() {
+            if self.counts_as_letter(c) {
+                if seen == letters {
+                    return i;
+                }
+                seen += 1;
+            }
+        }
+        word.len()
+    }
+}
+
+/// The renderer used by a [`BioReader`].
+#[derive(Clone)]
+enum Renderer {
+    /// Wrapping strings, customizable via [`BioReader::emphasize`] and [`BioReader::de_emphasize`].
+    Wrap(Wrap),
+    /// A renderer set with [`BioReader::renderer`].
+    Custom(Arc<dyn EmphasisRenderer + Send + Sync>),
+}
+
+/// Whether `c` is a combining mark.
+fn is_mark(c: char) -> bool {
+    c.general_category_group() == GeneralCategoryGroup::Mark
+}
+
+/// Whether the last letter of `before` connects to the first letter of `after` in a cursive script, skipping combining marks in between.
+fn joins_across(before: &str, after: &str) -> bool {
+    let last = before.chars().rev().find(|&c| !is_mark(c));
+    let first = after.chars().next();
+    matches!((last, first), (Some(last), Some(first)) if segment::joins_next(last) && segment::joins_previous(first))
+}
+
+/// Whether `word` is written in all capitals, with at least two letters, like an acronym.
+fn is_acronym(word: &str) -> bool {
+    let mut letters = word.chars().filter(|c| c.is_alphabetic());
+    letters.clone().count() >= 2 && letters.all(char::is_uppercase)
 }
 
 /// Current state. Used internally for [`BioReader::bio_read`].
-struct State {
-    /// How many letters of the current word have been read.
-    read: usize,
-    /// How many letters of the current word have been written.
-    written: usize,
+struct State<'a> {
+    /// The renderer to write words with.
+    renderer: &'a (dyn EmphasisRenderer + Sync),
+    /// The current word, buffered until it ends or reaches the maximum length.
+    word: String,
+    /// Whether the current word has reached the maximum length with [`LongWordPolicy::Plain`], so that the rest of it is written as-is.
+    long_word: bool,
+    /// How many letters the current word has, not counting combining marks and joiners.
+    letters: usize,
+    /// A joiner right after the current word, which is only part of it if followed by a letter.
+    joiner: Option<char>,
+    /// How many words have been written.
+    words: usize,
+    /// The current run of non-whitespace characters, buffered until it ends if there are skip patterns or URLs are preserved.
+    token: String,
+    /// The current run of Chinese and Japanese characters, buffered until it ends if they are segmented into words.
+    cjk: String,
+    /// Whether to write words exactly as in the input, without inserting joiners, for recording spans.
+    verbatim: bool,
+    /// The emphasized and de-emphasized parts of the current word with joiners inserted, reused to avoid allocating for each word.
+    joined: String,
+    /// The current word lowercased, for matching excluded words, reused to avoid allocating for each word.
+    folded: String,
+    /// The observer notified of words and paragraphs, if any.
+    observer: Option<&'a mut (dyn Observer + Send)>,
+    /// Where the text is relative to sentence boundaries, tracked if the first words of sentences are boosted, paragraphs reset or an observer notified.
+    sentence: Sentence,
+    /// How many line breaks there have been since the last word or other non-whitespace character.
+    line_breaks: usize,
+    /// Whether a paragraph starts with the next word.
+    paragraph: bool,
+    /// Where the text is relative to terminal escape sequences, which may span pieces of text.
+    osc: Osc,
+}
+
+impl<'a> State<'a> {
+    /// Create a new state for processing a stream, writing words with `renderer`.
+    fn new(renderer: &'a (dyn EmphasisRenderer + Sync)) -> Self {
+        Self {
+            renderer,
+            word: String::new(),
+            long_word: false,
+            letters: 0,
+            joiner: None,
+            words: 0,
+            token: String::new(),
+            cjk: String::new(),
+            verbatim: false,
+            joined: String::new(),
+            folded: String::new(),
+            observer: None,
+            sentence: Sentence::Start,
+            line_breaks: 0,
+            paragraph: true,
+            osc: Osc::Text,
+        }
+    }
+    /// Track sentence and paragraph boundaries past `c`, a character between words.
+    fn pass(&mut self, c: char) {
+        self.sentence = self.sentence.advance(c);
+        if c == '\n' {
+            self.line_breaks += 1;
+            // A blank line separates paragraphs
+            self.paragraph |= self.line_breaks >= 2;
+        } else if !c.is_whitespace() {
+            self.line_breaks = 0;
+        }
+    }
 }
@@ -1,60 +1,712 @@
-use argh::FromArgs;
-use bio_read::BioReader;
+use argh::{ArgsInfo, FromArgs};
+#[cfg(feature = "epub")]
+use bio_read::{render::Html, BioReader};
+use bio_read::{
+    config::{ColorChoice, ThemeConfig},
+    cues::CueFormat,
+    encoding::Encoding,
+    reading_time::{average_wpm, ReadingSpeed},
+    render::Theme,
+    segment::{CompoundPolicy, LongWordPolicy, TokenPolicy},
+    BioReadStats, Error, InputFormat, OutputFormat,
+};
+use cli::{ErrorFormat, Failure, Filter, LanguageChoice, Mode};
+use completions::Shell;
+use std::{
+    io::{BufRead, BufReader, ErrorKind, IsTerminal, Read, Write},
+    path::{Path, PathBuf},
+};
 
-#[derive(FromArgs)]
+mod cli;
+mod completions;
+#[cfg(feature = "tui")]
+mod rsvp;
+#[cfg(feature = "tui")]
+mod tui;
+
+#[derive(ArgsInfo, FromArgs)]
 /// Bionic reading in terminal.
 #[argh(help_triggers("-h", "--help"))]
 pub struct Args {
-    /// the file to read from. Read from stdin if not specified.
+    /// the files to read from, or URLs of web pages to read the article of if built with the http feature. Read from stdin if not specified.
     #[argh(positional)]
-    input: Option<String>,
+    input: Vec<PathBuf>,
+    /// the directory to write to, one output file per input, named after it. Inputs are processed in parallel. Write to stdout if not specified.
+    #[argh(option)]
+    output_dir: Option<PathBuf>,
+    /// the file to write to, instead of stdout. "-" means stdout. Unlike shell redirection, the output is written as-is, without being re-encoded. With the compression feature, it is compressed if its name ends in .gz or .zst.
+    #[argh(option, short = 'o')]
+    output: Option<PathBuf>,
+    /// overwrite the file given with --output if it exists.
+    #[argh(switch)]
+    force: bool,
+    /// the number of files to process at once with --output-dir or --in-place. Default is the number of available CPUs.
+    #[argh(option, short = 'j')]
+    jobs: Option<usize>,
+    /// overwrite the input files with their output, instead of writing to stdout or --output-dir.
+    #[argh(switch, short = 'i')]
+    in_place: bool,
+    /// back up the input files before overwriting them with --in-place, appending this suffix to their names. Example: --backup .bak
+    #[argh(option)]
+    backup: Option<String>,
+    /// read directories among the inputs recursively, keeping their structure under --output-dir.
+    #[argh(switch, short = 'r')]
+    recursive: bool,
+    /// only read files in directories matching this glob pattern, like "*.md". Can be given multiple times.
+    #[argh(option)]
+    include: Vec<String>,
+    /// skip files and directories in directories matching this glob pattern. Can be given multiple times.
+    #[argh(option)]
+    exclude: Vec<String>,
+    /// flush the output after each line of input, for interactive use. Enabled automatically when reading from a terminal.
+    #[argh(switch, short = 'l')]
+    line_buffered: bool,
+    /// pipe the output into $PAGER, or "less" if unset. Enabled automatically when writing large files to a terminal.
+    #[argh(switch)]
+    pager: bool,
+    /// never pipe the output into a pager.
+    #[argh(switch)]
+    no_pager: bool,
+    /// read the input file in a terminal reader app instead, scrolling with arrow keys, changing the fixation point with 1 to 5, toggling the theme with t, and searching with /.
+    #[cfg(feature = "tui")]
+    #[argh(switch)]
+    tui: bool,
+    /// start --tui from the top of the file, instead of where it was left off last time.
+    #[cfg(feature = "tui")]
+    #[argh(switch)]
+    no_resume: bool,
+    /// show the input file one bio-read word at a time in the terminal instead, at this many words per minute, pausing with space, changing the speed with the up and down keys, and skipping words with the left and right keys. Example: --rsvp 300.
+    #[cfg(feature = "tui")]
+    #[argh(option)]
+    rsvp: Option<u32>,
+    /// keep running, bio-reading the input files again whenever they change, and clearing the terminal before each time.
+    #[cfg(feature = "watch")]
+    #[argh(switch, short = 'w')]
+    watch: bool,
+    /// keep running after the end of the input file, bio-reading lines as they are appended to it, like "tail -f". Starts from its last 10 lines.
+    #[argh(switch)]
+    follow: bool,
+    /// show a progress bar on stderr while reading the input files, with the throughput and the time left, when the output doesn't go to a terminal.
+    #[argh(switch)]
+    progress: bool,
+    /// report how the input would be emphasized with each fixation point, instead of bio-reading it: how much of the words is emphasized, how many words get how many characters emphasized, and a sample of the output.
+    #[argh(switch)]
+    analyze: bool,
+    /// export the words of the input instead of bio-reading it, as cues for highlighting them while text-to-speech plays: "json" (JSON Lines with the byte and character offsets of each word and its emphasized start), "vtt" or "srt" (subtitles with a cue per word). Words are timed at --wpm.
+    #[argh(option)]
+    cues: Option<CueFormat>,
+    /// print statistics of the words read to stderr when done: how many there are, how much of them is emphasized, how long they take to read, and the longest one.
+    #[argh(switch)]
+    stats: bool,
+    /// the reading speed in words per minute, for estimating the reading time with --stats, and timing the words with --cues. Default is the average for the language given with --lang, like 238 for English.
+    #[argh(option)]
+    wpm: Option<u32>,
+    /// the reading speed of bio-read text in words per minute, for estimating the time bio-reading saves with --stats. Default is the same as --wpm, as studies haven't found bio-reading reliably faster.
+    #[argh(option)]
+    bio_read_wpm: Option<u32>,
+    /// the config file to read options from. Default is ~/.config/bio-read/config.toml, if it exists. Options given on the command line take precedence.
+    #[argh(option)]
+    config: Option<PathBuf>,
+    /// how to report failures on stderr: "text", or "json" for a JSON object on a line with the kind of failure ("config", "io", "format" or "other"), the exit code, the message, and the path and option at fault if known. Default is text.
+    #[argh(option)]
+    error_format: Option<ErrorFormat>,
     /// the fixation point. Should be in range [1, 5]. Default is 3.
-    #[argh(option, short = 'f', default = "3")]
-    fixation_point: usize,
-    /// customize how to emphasize the text. The emphasized text will take the place of "{}". Example: --emphasize "<em>{}</em>". Default to ansi bold.
+    #[argh(option, short = 'f')]
+    fixation_point: Option<usize>,
+    /// the fraction of each word to emphasize, rounded up, instead of using a fixation point. Should be in range [0, 1]. Example: --ratio 0.5
+    #[argh(option)]
+    ratio: Option<f32>,
+    /// emphasize every n-th word only, starting from the first. Default is 1, emphasizing every word.
+    #[argh(option, short = 's')]
+    saccade: Option<usize>,
+    /// emphasize this many more letters in the first word of each sentence, anchoring the eye at sentence starts. Default is 0.
+    #[argh(option)]
+    sentence_boost: Option<usize>,
+    /// restart the saccade and sentence tracking at each paragraph, separated by blank lines, so that the first word of each paragraph is emphasized and boosted.
+    #[argh(switch)]
+    reset_at_paragraphs: bool,
+    /// pass text matching this regular expression through untouched, like URLs or identifiers. Can be given multiple times. Example: --skip '\w+_\w+'
+    #[argh(option)]
+    skip: Vec<String>,
+    /// leave the words listed in this file un-emphasized, like "the" or "of". Words are separated by whitespace and matched case-insensitively, and lines starting with "#" are ignored. Can be given multiple times.
+    #[argh(option)]
+    stopwords: Vec<PathBuf>,
+    /// how to handle digits in words: "split-at-digits" to split words at them, "include-digits" to read them like letters, so that "IPv6" and "2023" are emphasized as a whole, or "skip-numeric" to leave words with digits un-emphasized. Default is split-at-digits.
+    #[argh(option)]
+    token_policy: Option<TokenPolicy>,
+    /// how to emphasize compound words joined by hyphens, like "state-of-the-art": "whole" to emphasize the start of the whole compound, or "each-part" to emphasize each part on its own. Default is whole.
+    #[argh(option)]
+    compound_policy: Option<CompoundPolicy>,
+    /// maximum length of words in bytes, past which they aren't buffered so that memory stays bounded. Default is 65536.
+    #[argh(option)]
+    max_word_len: Option<usize>,
+    /// what to do with words longer than the maximum length: "plain" to write them as they are, or "split" to split them into pieces emphasized as words. Default is plain.
+    #[argh(option)]
+    long_word_policy: Option<LongWordPolicy>,
+    /// leave words written in all capitals, like "NASA" or "HTTP", un-emphasized, as acronyms are read letter by letter.
+    #[argh(switch)]
+    skip_acronyms: bool,
+    /// pass URLs, emails and file paths through untouched.
+    #[argh(switch, short = 'u')]
+    preserve_urls: bool,
+    /// emphasize words longer than 12 letters a second time midway, more weakly, as a secondary fixation point.
+    #[argh(switch)]
+    secondary_fixation: bool,
+    /// reflow plain text to fit lines into this many columns, joining the lines of each paragraph and breaking them again between words. The width leaves out the markup and ANSI codes added. Example: --wrap 80.
+    #[argh(option)]
+    wrap: Option<usize>,
+    /// justify lines reflowed with --wrap, stretching the spaces between words so that lines fill the width.
+    #[argh(switch)]
+    justify: bool,
+    /// indent lines reflowed with --wrap after the first of each paragraph by this many columns, as a hanging indent.
+    #[argh(option)]
+    indent: Option<usize>,
+    /// lay out text reflowed with --wrap in this many columns side by side, like a newspaper, sharing its width.
+    #[argh(option)]
+    columns: Option<usize>,
+    /// how many lines tall each page of --columns is, like the height of the terminal. Default is to balance the whole text over the columns.
+    #[argh(option)]
+    page_height: Option<usize>,
+    /// turn CRLF line endings into LF, like those of files written on Windows. Default is to preserve the input exactly.
+    #[argh(switch)]
+    normalize_line_endings: bool,
+    /// remove whitespace at the ends of lines.
+    #[argh(switch)]
+    trim_trailing_whitespace: bool,
+    /// collapse runs of blank lines into a single one.
+    #[argh(switch)]
+    collapse_blank_lines: bool,
+    /// replace typographic punctuation with ASCII: curly quotes with straight ones, dashes with "-" and "--", and ellipses with "...".
+    #[argh(switch)]
+    ascii_punctuation: bool,
+    /// customize how to emphasize the text. The emphasized text will take the place of "{}". Example: --emphasize "<em>{}</em>". Takes precedence over --to.
     #[argh(option, short = 'e')]
     emphasize: Option<String>,
-    /// customize how to de-emphasize the text. The de-emphasized text will take the place of "{}". Example: --de-emphasize "<de>{}</de>". Default to ansi dimmed.
+    /// customize how to de-emphasize the text. The de-emphasized text will take the place of "{}". Example: --de-emphasize "<de>{}</de>". Takes precedence over --to.
     #[argh(option, short = 'd')]
     de_emphasize: Option<String>,
+    /// the format of the input, which determines what gets bio-read. Can be "auto", "plain", "markdown", "html", "latex", "rst", "asciidoc", "org", "man" (terminal output of man, with bold text overstruck), "diff" (only bio-reading changed lines), "email" (messages and mbox files, only bio-reading text bodies), "srt" or "vtt" (subtitles, only bio-reading captions), "code:rust" or another programming language, like "code:py" (source code, only bio-reading comments and strings), or "docx" if built with the docx feature. Default is auto, which detects the format of each input from its extension or content.
+    #[argh(option)]
+    format: Option<InputFormat>,
+    /// the language of the text, which determines how words are split and matched: "en", "de", "nl", "es", "fr", "it" or "tr", or "auto" to detect it for each input if built with the lang-detect feature. Default is en.
+    #[argh(option)]
+    lang: Option<LanguageChoice>,
+    /// characters kept inside words when between two letters, like apostrophes and hyphens, or "" to split words at all of them. Default is "'’-‐‑", without the apostrophes for fr, it and tr.
+    #[argh(option)]
+    joiners: Option<String>,
+    /// the character encoding of the input: "utf-8", or "utf-16le", "utf-16be" or "latin1" if built with the encoding feature. Default is auto, which reads input as UTF-8 (stripping its byte order mark), or as UTF-16 if it starts with a UTF-16 byte order mark and built with the encoding feature.
+    #[argh(option)]
+    encoding: Option<Encoding>,
+    /// the format of the output, which determines how words are emphasized. Can be "ansi", "html", "markdown", "bbcode", "latex", "typst", "rst", "org", "rtf" (a document for word processors, for plain text and man input only), "plain" or "json" (a JSON object for each word, for plain text and man input only). Default is the input format for html, latex, rst and org input, markdown for asciidoc input, html for subtitles, and ansi otherwise.
+    #[argh(option, short = 't')]
+    to: Option<OutputFormat>,
+    /// the preset styles of ansi output, for terminals where dimmed text is hard to read. Can be "bold-dim", "color-contrast" (bright white and gray), "underline" or "inverse". Default is bold-dim.
+    #[argh(option)]
+    theme: Option<Theme>,
+    /// when to style ansi output: "auto", "always" or "never". Default is auto, styling only when writing to a terminal, unless NO_COLOR is set or CLICOLOR is 0. CLICOLOR_FORCE forces it.
+    #[argh(option)]
+    color: Option<ColorChoice>,
+    /// emphasize with graded intensity instead: bold then regular for ansi output, or spans with classes "fixation-1" to "fixation-3" for html output.
+    #[argh(switch, short = 'g')]
+    gradient: bool,
+    /// with html output, write a standalone web page with inline CSS for each input instead, to share it as is. Not supported for html input.
+    #[argh(switch)]
+    standalone: bool,
+    /// undo bio-reading instead, removing the wrappers given with --emphasize and --de-emphasize, or else ansi escape codes, to recover the original text.
+    #[argh(switch)]
+    strip: bool,
+    #[argh(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand)]
+enum Command {
+    Completions(CompletionsArgs),
+    #[cfg(feature = "epub")]
+    Epub(EpubArgs),
+    #[cfg(feature = "http")]
+    Feed(FeedArgs),
+}
+
+#[cfg(feature = "epub")]
+#[derive(ArgsInfo, FromArgs)]
+/// Bio-read an EPUB e-book, emphasizing with bold tags. Options given before "epub", like -f, still apply.
+#[argh(subcommand, name = "epub", help_triggers("-h", "--help"))]
+struct EpubArgs {
+    /// the EPUB to read from.
+    #[argh(positional)]
+    input: PathBuf,
+    /// the EPUB to write to.
+    #[argh(option, short = 'o')]
+    output: PathBuf,
+    /// the class to add to the emphasized parts, for styling them with CSS. Example: --class fixation
+    #[argh(option)]
+    class: Option<String>,
+}
+
+#[cfg(feature = "http")]
+#[derive(ArgsInfo, FromArgs)]
+/// List the entries of an RSS or Atom feed, or bio-read one of them. Options given before "feed", like -f and --tui, still apply.
+#[argh(subcommand, name = "feed", help_triggers("-h", "--help"))]
+struct FeedArgs {
+    /// the URL of the feed.
+    #[argh(positional)]
+    url: String,
+    /// the number of the entry to read, as listed. Lists the entries if not specified.
+    #[argh(positional)]
+    entry: Option<usize>,
+}
+
+#[derive(ArgsInfo, FromArgs)]
+/// Print a script completing the options of br, and the values of those taking names like --format and --theme, for a shell. Load it in the shell's startup file, like with 'source <(br completions bash)' in ~/.bashrc.
+#[argh(subcommand, name = "completions", help_triggers("-h", "--help"))]
+struct CompletionsArgs {
+    /// the shell to complete for: "bash", "zsh", "fish" or "powershell".
+    #[argh(positional)]
+    shell: Shell,
+}
+
+fn main() {
+    let args = parse_args();
+    let error_format = args.error_format.unwrap_or_default();
+    if let Err(failure) = run(args) {
+        failure.report(error_format);
+        std::process::exit(failure.exit_code().into());
+    }
+}
+
+/// Parse the command line like [`argh::from_env`], but exiting with the exit code of invalid options if it is invalid, reported as JSON if asked for.
+fn parse_args() -> Args {
+    let strings: Vec<String> = std::env::args_os().map(|arg| arg.to_string_lossy().into_owned()).collect();
+    let command = Path::new(&strings[0]).file_name().map_or(strings[0].as_str(), |name| name.to_str().unwrap_or_default());
+    let strs: Vec<&str> = strings.iter().map(String::as_str).collect();
+    Args::from_args(&[command], &strs[1..]).unwrap_or_else(|early_exit| {
+        if early_exit.status.is_ok() {
+            // Help
+            println!("{}", early_exit.output);
+            std::process::exit(0);
+        }
+        if strs.windows(2).any(|pair| pair[0] == "--error-format" && pair[1].eq_ignore_ascii_case("json")) {
+            Failure::usage(early_exit.output.trim_end()).report(ErrorFormat::Json);
+        } else {
+            eprintln!("{}\nRun {command} --help for more information.", early_exit.output);
+        }
+        std::process::exit(Failure::CONFIG.into());
+    })
 }
 
-fn main() -> std::io::Result<()> {
-    let args: Args = argh::from_env();
-    let fixation_point = args.fixation_point;
-    if fixation_point < 1 || fixation_point > 5 {
-        eprintln!(
-            "Fixation point should be in range [1, 5], but got {}",
-            fixation_point
-        );
-        std::process::exit(1);
-    }
-    let mut reader = BioReader::new().fixation_point(fixation_point);
-    if let Some(emphasize) = args.emphasize {
-        let Some((left, right)) = emphasize.split_once("{}") else {
-            eprintln!("Invalid emphasize format: {}", emphasize);
-            std::process::exit(1);
+/// Run the command given by `args`, returning the failure to report if it fails.
+fn run(args: Args) -> Result<(), Failure> {
+    if let Some(Command::Completions(completions)) = &args.command {
+        let accepts = |strs: &[&str]| Args::from_args(&["br"], strs).is_ok();
+        completions::write_completions(completions.shell, &Args::get_args_info(), accepts, &mut std::io::stdout().lock())?;
+        return Ok(());
+    }
+    let mut config = cli::load_config(args.config.as_deref())?;
+    if args.to.is_some() {
+        // An explicit output format overrides wrappers from the config file
+        config.emphasize = None;
+        config.de_emphasize = None;
+    }
+    if args.fixation_point.is_some() || args.ratio.is_some() {
+        // Fixation options on the command line override those in the config file
+        config.fixation_point = args.fixation_point;
+        config.fixation_ratio = args.ratio;
+    }
+    config.saccade = args.saccade.or(config.saccade);
+    config.sentence_boost = args.sentence_boost.or(config.sentence_boost);
+    if args.reset_at_paragraphs {
+        config.reset_at_paragraphs = Some(true);
+    }
+    config.token_policy = args.token_policy.or(config.token_policy);
+    config.compound_policy = args.compound_policy.or(config.compound_policy);
+    config.max_word_len = args.max_word_len.or(config.max_word_len);
+    config.long_word_policy = args.long_word_policy.or(config.long_word_policy);
+    if args.skip_acronyms {
+        config.skip_acronyms = Some(true);
+    }
+    if args.preserve_urls {
+        config.preserve_urls = Some(true);
+    }
+    if args.secondary_fixation {
+        config.secondary_fixation = Some(true);
+    }
+    config.reflow = args.wrap.or(config.reflow);
+    if args.justify {
+        config.justify = Some(true);
+    }
+    config.hanging_indent = args.indent.or(config.hanging_indent);
+    config.columns = args.columns.or(config.columns);
+    config.page_height = args.page_height.or(config.page_height);
+    if args.normalize_line_endings {
+        config.normalize_line_endings = Some(true);
+    }
+    if args.trim_trailing_whitespace {
+        config.trim_trailing_whitespace = Some(true);
+    }
+    if args.collapse_blank_lines {
+        config.collapse_blank_lines = Some(true);
+    }
+    if args.ascii_punctuation {
+        config.ascii_punctuation = Some(true);
+    }
+    if !args.skip.is_empty() {
+        config.skip.get_or_insert_default().extend(args.skip);
+    }
+    for path in &args.stopwords {
+        let words = cli::load_stopwords(path)?;
+        config.exclude_words.get_or_insert_default().extend(words);
+    }
+    config.emphasize = args.emphasize.or(config.emphasize);
+    config.de_emphasize = args.de_emphasize.or(config.de_emphasize);
+    config.to = args.to.or(config.to);
+    // JSON and RTF output only support plain text, so formats aren't detected for them
+    let default_format = if matches!(config.to, Some(OutputFormat::Json | OutputFormat::Rtf)) { InputFormat::Plain } else { InputFormat::Auto };
+    let format = args.format.or(config.format).unwrap_or(default_format);
+    config.format = Some(format);
+    config.encoding = args.encoding.or(config.encoding);
+    config.joiners = args.joiners.or(config.joiners);
+    if let Some(theme) = args.theme {
+        // A preset on the command line overrides styles from the config file
+        config.theme = Some(ThemeConfig {
+            preset: Some(theme),
+            ..ThemeConfig::default()
+        });
+    }
+    if args.gradient {
+        config.gradient = Some(true);
+    }
+    if let Some(LanguageChoice::Fixed(language)) = args.lang {
+        config.language = Some(language);
+    }
+    // - writes to stdout, like no --output at all
+    let output = args.output.clone().filter(|output| output.as_os_str() != "-");
+    let to_terminal = output.is_none() && args.output_dir.is_none() && !args.in_place && std::io::stdout().is_terminal();
+    config.color = Some(cli::resolve_color(args.color.or(config.color).unwrap_or_default(), to_terminal));
+    // On Windows, enable escape codes in the console, or translate styles into console calls where unsupported
+    let stream = match config.color {
+        Some(ColorChoice::Always) => anstream::ColorChoice::Always,
+        _ => anstream::ColorChoice::AlwaysAnsi,
+    };
+    #[cfg(feature = "epub")]
+    if let Some(Command::Epub(epub)) = &args.command {
+        if !args.input.is_empty() || args.in_place || args.output_dir.is_some() {
+            return Err(Failure::usage("epub takes its own input and output, which cannot be given before it"));
+        }
+        let reader = BioReader::from_config(&config)?;
+        let html = epub.class.as_deref().map_or_else(Html::default, Html::with_class);
+        cli::bio_read_epub(&reader.renderer(html), &epub.input, &epub.output).map_err(|e| Error::from(e).with_path(&epub.input))?;
+        return Ok(());
+    }
+    #[cfg(feature = "http")]
+    if let Some(Command::Feed(feed)) = &args.command {
+        if !args.input.is_empty() || output.is_some() || args.in_place || args.output_dir.is_some() {
+            return Err(Failure::usage("feed takes its own input and writes to stdout, so no input or output can be given before it"));
+        }
+        let language = args.lang.unwrap_or(LanguageChoice::Fixed(config.language.unwrap_or_default()));
+        let result = cli::fetch_feed(&feed.url).and_then(|entries| {
+            let Some(number) = feed.entry else {
+                return cli::list_entries(&entries, &mut std::io::stdout().lock());
+            };
+            #[cfg_attr(not(feature = "tui"), allow(unused_variables))]
+            let (title, text) = cli::entry_text(&entries, number)?;
+            let kind = (InputFormat::Plain, language.resolve(text.as_bytes()));
+            #[cfg(feature = "tui")]
+            if args.tui || args.rsvp.is_some() {
+                if !to_terminal || !std::io::stdin().is_terminal() {
+                    return Err(std::io::Error::other("--tui and --rsvp require a terminal"));
+                }
+                if let Some(wpm) = args.rsvp {
+                    return rsvp::run_text(&config, kind, title, text, wpm);
+                }
+                return tui::run_text(&config, kind, title, text);
+            }
+            let readers = cli::Readers::new(&config, [kind])?;
+            Ok(readers.get(kind).bio_read(text.as_bytes(), &mut anstream::AutoStream::new(std::io::stdout().lock(), stream))?)
+        });
+        return Ok(result.map_err(|e| Error::from(e).with_path(&feed.url))?);
+    }
+    let filter = Filter::new(&args.include, &args.exclude).map_err(|e| Failure::usage(format!("Invalid glob pattern: {e}")))?;
+    let input_given = !args.input.is_empty();
+    let inputs = cli::collect_inputs(args.input, args.recursive, &filter)?;
+    let has_url = inputs.iter().any(|input| cli::is_url(&input.path));
+    // Detect the format of each input, so that the output format can follow it, and its language
+    let language = args.lang.unwrap_or(LanguageChoice::Fixed(config.language.unwrap_or_default()));
+    let kinds: Vec<_> = inputs
+        .iter()
+        .map(|input| {
+            let format = match format {
+                InputFormat::Auto => cli::detect_format(&input.path),
+                format => format,
+            };
+            (format, cli::detect_language(language, &input.path))
+        })
+        .collect();
+    #[cfg(feature = "tui")]
+    if args.no_resume && !args.tui {
+        return Err(Failure::usage("--no-resume requires --tui"));
+    }
+    #[cfg(feature = "tui")]
+    if args.tui || args.rsvp.is_some() {
+        if args.tui && args.rsvp.is_some() {
+            return Err(Failure::usage("--tui and --rsvp cannot be used together"));
+        }
+        if inputs.len() != 1 || cli::is_url(&inputs[0].path) || output.is_some() || args.in_place || args.output_dir.is_some() || args.strip || args.pager {
+            return Err(Failure::usage("--tui and --rsvp require exactly one input file, and cannot be used together with --output, --in-place, --output-dir, --strip or --pager"));
+        }
+        if !to_terminal || !std::io::stdin().is_terminal() {
+            return Err(Failure::usage("--tui and --rsvp require a terminal"));
+        }
+        let path = &inputs[0].path;
+        let result = match args.rsvp {
+            Some(wpm) => rsvp::run(&config, kinds[0], path, wpm),
+            None => tui::run(&config, kinds[0], path, !args.no_resume),
         };
-        reader = reader.emphasize(left.to_string(), right.to_string());
+        return Ok(result.map_err(|e| Error::from(e).with_path(path))?);
+    }
+    if args.in_place && args.output_dir.is_some() {
+        return Err(Failure::usage("--in-place and --output-dir cannot be used together"));
+    }
+    if args.backup.is_some() && !args.in_place {
+        return Err(Failure::usage("--backup requires --in-place"));
+    }
+    if output.is_some() && (args.in_place || args.output_dir.is_some()) {
+        return Err(Failure::usage("--output cannot be used together with --in-place or --output-dir"));
+    }
+    if has_url && (args.in_place || args.output_dir.is_some()) {
+        return Err(Failure::usage("URLs cannot be used together with --in-place or --output-dir"));
+    }
+    if args.standalone && (config.to != Some(OutputFormat::Html) || args.strip || args.analyze) {
+        return Err(Failure::usage("--standalone requires --to html, and cannot be used together with --strip or --analyze"));
+    }
+    let mode = if args.strip {
+        Mode::Strip
+    } else if args.standalone {
+        Mode::Standalone(OutputFormat::Html)
+    } else if config.to == Some(OutputFormat::Rtf) {
+        // RTF output is only valid as a whole document
+        Mode::Standalone(OutputFormat::Rtf)
+    } else {
+        Mode::BioRead
+    };
+    if matches!(mode, Mode::Standalone(_)) && inputs.len() > 1 && !args.in_place && args.output_dir.is_none() {
+        return Err(Failure::usage("--standalone and rtf output write a document for each input, so several inputs require --in-place or --output-dir"));
+    }
+    if args.force && args.output.is_none() {
+        return Err(Failure::usage("--force requires --output"));
+    }
+    if args.pager && args.no_pager {
+        return Err(Failure::usage("--pager and --no-pager cannot be used together"));
+    }
+    if args.pager && (output.is_some() || args.in_place || args.output_dir.is_some()) {
+        return Err(Failure::usage("--pager cannot be used together with --output, --in-place or --output-dir"));
+    }
+    #[cfg(feature = "watch")]
+    if args.watch && (!input_given || has_url || args.in_place || args.output_dir.is_some() || args.pager) {
+        return Err(Failure::usage("--watch requires at least one input file, and cannot be used together with URLs, --in-place, --output-dir or --pager"));
+    }
+    if args.follow && (inputs.len() != 1 || has_url || cli::is_pdf(&inputs[0].path) || args.in_place || args.output_dir.is_some() || args.pager || args.analyze || args.stats || mode != Mode::BioRead) {
+        return Err(Failure::usage("--follow requires exactly one input file, and cannot be used together with URLs, PDF documents, --in-place, --output-dir, --pager, --analyze, --stats, --strip, --standalone or rtf output"));
+    }
+    #[cfg(feature = "watch")]
+    if args.follow && args.watch {
+        return Err(Failure::usage("--follow cannot be used together with --watch"));
+    }
+    if args.analyze && (args.in_place || args.output_dir.is_some() || args.strip || args.stats) {
+        return Err(Failure::usage("--analyze cannot be used together with --in-place, --output-dir, --strip or --stats"));
+    }
+    if args.cues.is_some() && (inputs.len() > 1 || args.in_place || args.output_dir.is_some() || args.strip || args.stats || args.analyze || args.follow || args.standalone) {
+        return Err(Failure::usage("--cues takes at most one input, and cannot be used together with --in-place, --output-dir, --strip, --stats, --analyze, --follow or --standalone"));
     }
-    if let Some(de_emphasize) = args.de_emphasize {
-        let Some((left, right)) = de_emphasize.split_once("{}") else {
-            eprintln!("Invalid de-emphasize format: {}", de_emphasize);
-            std::process::exit(1);
+    #[cfg(feature = "watch")]
+    if args.cues.is_some() && args.watch {
+        return Err(Failure::usage("--cues cannot be used together with --watch"));
+    }
+    if args.stats && (args.in_place || args.output_dir.is_some() || args.strip || args.line_buffered) {
+        return Err(Failure::usage("--stats cannot be used together with --in-place, --output-dir, --strip or --line-buffered"));
+    }
+    #[cfg(feature = "watch")]
+    if args.stats && args.watch {
+        return Err(Failure::usage("--stats cannot be used together with --watch"));
+    }
+    if (args.wpm.is_some() && !args.stats && args.cues.is_none()) || (args.bio_read_wpm.is_some() && !args.stats) {
+        return Err(Failure::usage("--wpm requires --stats or --cues, and --bio-read-wpm requires --stats"));
+    }
+    if args.wpm == Some(0) || args.bio_read_wpm == Some(0) {
+        return Err(Failure::usage("Words per minute should be at least 1"));
+    }
+    if args.columns == Some(0) {
+        return Err(Failure::usage("Number of columns should be at least 1"));
+    }
+    if (args.justify || args.indent.is_some() || args.columns.is_some()) && config.reflow.unwrap_or(0) == 0 {
+        return Err(Failure::usage("--justify, --indent and --columns require --wrap"));
+    }
+    if args.page_height.is_some() && config.columns.unwrap_or(1) <= 1 {
+        return Err(Failure::usage("--page-height requires --columns"));
+    }
+    if (args.in_place || args.output_dir.is_some()) && !input_given {
+        return Err(Failure::usage("--in-place and --output-dir require at least one input file"));
+    }
+    if args.jobs == Some(0) {
+        return Err(Failure::usage("Number of jobs should be at least 1"));
+    }
+    let mut stdin = std::io::stdin().lock();
+    let stdin_kind = if input_given {
+        (format, language.resolve(&[]))
+    } else {
+        let head = stdin.fill_buf()?;
+        let stdin_format = match format {
+            InputFormat::Auto => InputFormat::sniff(head),
+            format => format,
         };
-        reader = reader.de_emphasize(left.to_string(), right.to_string());
+        (stdin_format, language.resolve(head))
+    };
+    let readers = cli::Readers::new(&config, kinds.iter().copied().chain([stdin_kind]))?;
+    if args.standalone && kinds.iter().chain([&stdin_kind]).any(|(format, _)| *format == InputFormat::Html) {
+        return Err(Failure::usage("--standalone does not support html input, which is a web page already"));
     }
-    let mut lock = std::io::stdout().lock();
-    match args.input {
-        Some(path) => {
-            // Read from file
-            let file = std::fs::File::open(path)?;
-            reader.bio_read(file, &mut lock)?;
+    // The bar would be mixed up with output to the terminal, and files are the only inputs of known size
+    let show_progress = args.progress && input_given && !to_terminal && std::io::stderr().is_terminal();
+    if args.in_place || args.output_dir.is_some() {
+        let jobs = args
+            .jobs
+            .or_else(|| std::thread::available_parallelism().ok().map(usize::from))
+            .unwrap_or(1);
+        let error_format = args.error_format.unwrap_or_default();
+        let failed = if let Some(output_dir) = args.output_dir {
+            let outputs = cli::output_paths(&inputs, &output_dir, config.to.unwrap_or_default(), mode)?;
+            std::fs::create_dir_all(&output_dir).map_err(|e| Error::from(e).with_path(&output_dir))?;
+            let progress = show_progress.then(|| cli::Progress::start(cli::total_size(&inputs)));
+            let failed = cli::process_files(&inputs, jobs, error_format, |i, input| {
+                cli::bio_read_file(readers.get(kinds[i]), mode, &input.path, &outputs[i], progress.as_ref())
+            });
+            if let Some(progress) = progress {
+                progress.finish();
+            }
+            failed
+        } else {
+            let backup = args.backup.as_deref();
+            let progress = show_progress.then(|| cli::Progress::start(cli::total_size(&inputs)));
+            let failed = cli::process_files(&inputs, jobs, error_format, |i, input| {
+                cli::bio_read_in_place(readers.get(kinds[i]), mode, &input.path, backup, progress.as_ref())
+            });
+            if let Some(progress) = progress {
+                progress.finish();
+            }
+            failed
+        };
+        if let Some(exit_code) = failed {
+            // Each failure has been reported already
+            std::process::exit(exit_code.into());
+        }
+        return Ok(());
+    }
+    #[cfg(feature = "watch")]
+    if args.watch {
+        if let Some(output) = &output {
+            // Fail early if the output cannot be created, since it's recreated on every change
+            cli::create_output(output, &inputs, args.force)?;
         }
-        None => {
+        let paths: Vec<_> = inputs.iter().map(|input| input.path.clone()).collect();
+        return Ok(cli::watch(&paths, || {
+            let mut lock: Box<dyn Write> = match &output {
+                Some(output) => Box::new(std::io::BufWriter::new(cli::Compressed::new(std::fs::File::create(output)?, output)?)),
+                None => Box::new(anstream::AutoStream::new(std::io::stdout().lock(), stream)),
+            };
+            if to_terminal {
+                // Clear the screen and move the cursor home, so that the output is shown from the top
+                lock.write_all(b"\x1b[2J\x1b[H")?;
+            }
+            for (input, kind) in inputs.iter().zip(&kinds) {
+                cli::bio_read_input(readers.get(*kind), mode, &input.path, None, &mut lock).map_err(|e| Error::from(e).with_path(&input.path))?;
+            }
+            lock.flush()
+        })?);
+    }
+    if args.follow {
+        let path = &inputs[0].path;
+        let input = cli::Follow::open(path).map_err(|e| Error::from(e).with_path(path))?;
+        let mut lock: Box<dyn Write> = match &output {
+            Some(output) => Box::new(cli::create_output(output, &inputs, args.force)?),
+            None => Box::new(anstream::AutoStream::new(std::io::stdout().lock(), stream)),
+        };
+        return Ok(readers.get(kinds[0]).bio_read_lines(BufReader::new(input), &mut lock).map_err(|e| e.with_path(path))?);
+    }
+    // Page large files, unless reading from a terminal, whose input the pager would compete for
+    let page = args.pager || (!args.no_pager && to_terminal && input_given && cli::is_large(&inputs));
+    let mut pager = if page { cli::Pager::spawn() } else { None }
+        .transpose()
+        .map_err(|e| std::io::Error::new(e.kind(), format!("Cannot start pager: {e}")))?;
+    let mut lock: Box<dyn Write> = match (&output, &mut pager) {
+        (Some(output), _) => Box::new(cli::create_output(output, &inputs, args.force)?),
+        (None, Some(pager)) => Box::new(pager.take_stdin()),
+        (None, None) => Box::new(anstream::AutoStream::new(std::io::stdout().lock(), stream)),
+    };
+    let progress = show_progress.then(|| cli::Progress::start(cli::total_size(&inputs)));
+    let mut stats = BioReadStats::default();
+    let cue_wpm = |(_, language)| args.wpm.unwrap_or_else(|| average_wpm(language));
+    let result = (|| -> std::io::Result<()> {
+        if !input_given {
             // Read from stdin
-            reader.bio_read(std::io::stdin().lock(), &mut lock)?;
+            let reader = readers.get(stdin_kind);
+            if args.analyze {
+                let mut text = Vec::new();
+                stdin.read_to_end(&mut text)?;
+                cli::analyze(&config, stdin_kind, &String::from_utf8_lossy(&text), &mut lock)?;
+            } else if let Some(format) = args.cues {
+                let mut text = Vec::new();
+                stdin.read_to_end(&mut text)?;
+                let text = String::from_utf8_lossy(&text);
+                bio_read::cues::write_cues(&text, &reader.bio_read_words(&text), format, cue_wpm(stdin_kind), &mut lock)?;
+            } else if args.stats {
+                mode.run_with_stats(reader, "stdin", stdin, &mut lock, &mut stats)?;
+            } else if mode == Mode::BioRead && (args.line_buffered || stdin.is_terminal()) {
+                reader.bio_read_lines(stdin, &mut lock)?;
+            } else {
+                mode.run(reader, "stdin", stdin, &mut lock)?;
+            }
+        }
+        let several = inputs.len() > 1;
+        for (i, (input, kind)) in inputs.into_iter().zip(kinds).enumerate() {
+            // Read from files, one after another
+            let reader = readers.get(kind);
+            if args.analyze && several {
+                let separator = if i == 0 { "" } else { "\n" };
+                writeln!(lock, "{separator}{}:", input.path.display())?;
+            }
+            let result = if args.analyze {
+                cli::read_text(&input.path).and_then(|text| cli::analyze(&config, kind, &text, &mut lock))
+            } else if let Some(format) = args.cues {
+                cli::read_text(&input.path).and_then(|text| bio_read::cues::write_cues(&text, &reader.bio_read_words(&text), format, cue_wpm(kind), &mut lock))
+            } else if args.stats {
+                cli::bio_read_input_with_stats(reader, mode, &input.path, progress.as_ref(), &mut lock, &mut stats)
+            } else if mode == Mode::BioRead && args.line_buffered && !cli::is_pdf(&input.path) && !cli::is_url(&input.path) {
+                cli::open(&input.path, progress.as_ref()).and_then(|file| Ok(reader.bio_read_lines(BufReader::new(file), &mut lock)?))
+            } else {
+                cli::bio_read_input(reader, mode, &input.path, progress.as_ref(), &mut lock)
+            };
+            // Errors carry the input they happened on
+            result.map_err(|e| Error::from(e).with_path(&input.path))?;
+        }
+        lock.flush()
+    })();
+    if let Some(progress) = progress {
+        progress.finish();
+    }
+    // Close the input of the pager, so that it knows the output is complete
+    drop(lock);
+    let result = match pager {
+        Some(pager) => {
+            pager.wait()?;
+            // Quitting the pager early closes its input, which is fine
+            result.or_else(|e| if e.kind() == ErrorKind::BrokenPipe { Ok(()) } else { Err(e) })
         }
+        None => result,
+    };
+    if args.stats && result.is_ok() {
+        let speed = args.wpm.map_or_else(|| ReadingSpeed::for_language(config.language.unwrap_or_default()), ReadingSpeed::new);
+        cli::print_stats(&stats, speed.bio_read(args.bio_read_wpm.unwrap_or(speed.original)));
     }
-    Ok(())
+    Ok(result?)
 }
@@ -0,0 +1,143 @@
+//! Incremental normalization of line endings, the whitespace at the ends of lines and typographic punctuation. See [`BioReader::normalize_line_endings`](crate::BioReader::normalize_line_endings) and [`BioReader::ascii_punctuation`](crate::BioReader::ascii_punctuation).
+
+use crate::BioReader;
+
+/// Normalizes the line endings, whitespace and punctuation of input incrementally, from chunks of bytes that may end anywhere, before it is decoded.
+///
+/// Only ASCII whitespace is touched, which never occurs inside multi-byte UTF-8 sequences, so the bytes don't need to be decoded. Whitespace is held back until the next character tells whether it ends its line, and so is a carriage return until the next byte tells whether a line feed follows it. The typographic punctuation replaced all starts with the same two bytes, so they are held back until the third tells which character it is.
+#[derive(Debug)]
+pub(crate) struct Normalizer {
+    /// Whether to turn CRLF line endings into LF.
+    line_endings: bool,
+    /// Whether to remove whitespace at the ends of lines.
+    trim: bool,
+    /// Whether to collapse runs of blank lines into one.
+    collapse: bool,
+    /// Whether to replace typographic punctuation with ASCII.
+    punctuation: bool,
+    /// The start of a UTF-8 sequence held back, to tell whether it is typographic punctuation.
+    sequence: Vec<u8>,
+    /// Whether a carriage return is held back, to tell whether it ends a line.
+    cr: bool,
+    /// Whitespace held back, to tell whether it ends a line.
+    space: Vec<u8>,
+    /// Whether the current line has anything but whitespace.
+    text: bool,
+    /// How many blank lines there have been in a row.
+    blank_lines: usize,
+}
+
+impl Normalizer {
+    /// Create a normalizer with the options of `bio_reader`, if any are set.
+    pub(crate) fn new(bio_reader: &BioReader) -> Option<Self> {
+        (bio_reader.normalize_line_endings || bio_reader.trim_trailing_whitespace || bio_reader.collapse_blank_lines || bio_reader.ascii_punctuation).then(|| Self {
+            line_endings: bio_reader.normalize_line_endings,
+            trim: bio_reader.trim_trailing_whitespace,
+            collapse: bio_reader.collapse_blank_lines,
+            punctuation: bio_reader.ascii_punctuation,
+            sequence: Vec::new(),
+            cr: false,
+            space: Vec::new(),
+            text: false,
+            blank_lines: 0,
+        })
+    }
+    /// Normalize a chunk of bytes, returning what is ready.
+    pub(crate) fn push(&mut self, bytes: &[u8]) -> Vec<u8> {
+        let mut output = Vec::with_capacity(bytes.len());
+        for &byte in bytes {
+            if self.punctuation {
+                self.push_punctuation(byte, &mut output);
+            } else {
+                self.push_line_ending(byte, &mut output);
+            }
+        }
+        output
+    }
+    /// Return what is held back, at the end of the input, which ends the last line.
+    pub(crate) fn finish(&mut self) -> Vec<u8> {
+        let mut output = Vec::new();
+        for byte in std::mem::take(&mut self.sequence) {
+            self.push_line_ending(byte, &mut output);
+        }
+        if std::mem::take(&mut self.cr) {
+            self.push_byte(b'\r', &mut output);
+        }
+        if !self.trim {
+            output.append(&mut self.space);
+        }
+        output
+    }
+    /// Normalize a byte, replacing it with ASCII if it ends typographic punctuation.
+    fn push_punctuation(&mut self, byte: u8, output: &mut Vec<u8>) {
+        self.sequence.push(byte);
+        let replacement: &[u8] = match self.sequence[..] {
+            // Still too short to tell
+            [0xe2] | [0xe2, 0x80] => return,
+            // ‘ and ’
+            [0xe2, 0x80, 0x98 | 0x99] => b"'",
+            // “ and ”
+            [0xe2, 0x80, 0x9c | 0x9d] => b"\"",
+            // –
+            [0xe2, 0x80, 0x93] => b"-",
+            // —
+            [0xe2, 0x80, 0x94] => b"--",
+            // …
+            [0xe2, 0x80, 0xa6] => b"...",
+            _ => {
+                // Anything else, except for what may start the next sequence
+                let mut sequence = std::mem::take(&mut self.sequence);
+                if sequence.last() == Some(&0xe2) {
+                    self.sequence.extend(sequence.pop());
+                }
+                for byte in sequence {
+                    self.push_line_ending(byte, output);
+                }
+                return;
+            }
+        };
+        self.sequence.clear();
+        for &byte in replacement {
+            self.push_line_ending(byte, output);
+        }
+    }
+    /// Normalize a byte, turning CRLF line endings into LF if they are.
+    fn push_line_ending(&mut self, byte: u8, output: &mut Vec<u8>) {
+        if std::mem::take(&mut self.cr) && byte != b'\n' {
+            // A lone carriage return, which is whitespace like any other
+            self.push_byte(b'\r', output);
+        }
+        if byte == b'\r' && self.line_endings {
+            self.cr = true;
+        } else {
+            self.push_byte(byte, output);
+        }
+    }
+    /// Normalize a byte, after the carriage return before it is dealt with.
+    fn push_byte(&mut self, byte: u8, output: &mut Vec<u8>) {
+        match byte {
+            b'\n' => {
+                self.blank_lines = if self.text { 0 } else { self.blank_lines + 1 };
+                // The blank lines after the first of a run are dropped, whitespace and all
+                if !(self.collapse && self.blank_lines > 1) {
+                    if !self.trim {
+                        output.extend_from_slice(&self.space);
+                    } else if self.space.ends_with(b"\r") {
+                        // The carriage return of a CRLF line ending that is kept isn't trailing whitespace
+                        output.push(b'\r');
+                    }
+                    output.push(b'\n');
+                }
+                self.space.clear();
+                self.text = false;
+            }
+            b' ' | b'\t' | b'\r' | b'\x0b' | b'\x0c' if self.trim || self.collapse => self.space.push(byte),
+            b' ' | b'\t' | b'\r' | b'\x0b' | b'\x0c' => output.push(byte),
+            _ => {
+                self.text = true;
+                output.append(&mut self.space);
+                output.push(byte);
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+//! Callbacks notified of the words and paragraphs in text as it is bio-read. See [`Observer`].
+
+/// Notified of each word and paragraph as text is bio-read with [`BioReader::bio_read_with_observer`](crate::BioReader::bio_read_with_observer). All methods do nothing by default, so implement only those needed. [`BioReadStats`](crate::BioReadStats) is an observer counting words.
+///
+/// Paragraphs are separated by blank lines, that is, lines with nothing but whitespace on them. Markup of other formats, like HTML paragraph tags, doesn't separate paragraphs.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{observer::Observer, BioReader};
+/// #[derive(Default)]
+/// struct FirstWords(Vec<String>);
+/// impl Observer for FirstWords {
+///     fn on_paragraph(&mut self) {
+///         self.0.push(String::new());
+///     }
+///     fn on_word(&mut self, word: &str, _emphasized: usize) {
+///         let first = self.0.last_mut().unwrap();
+///         if first.is_empty() {
+///             first.push_str(word);
+///         }
+///     }
+/// }
+/// let mut first_words = FirstWords::default();
+/// let text = "One paragraph.\n\nAnother one,\nwith two lines.\n";
+/// BioReader::new().bio_read_with_observer(text.as_bytes(), &mut Vec::new(), &mut first_words).unwrap();
+/// assert_eq!(first_words.0, ["One", "Another"]);
+/// ```
+pub trait Observer {
+    /// Called after `word` is written, `emphasized` characters of which are emphasized. Words skipped by the saccade or excluded are reported with nothing emphasized.
+    fn on_word(&mut self, word: &str, emphasized: usize) {
+        let _ = (word, emphasized);
+    }
+    /// Called when a paragraph starts, right before its first word, including the first paragraph of the text. Paragraphs without words are not reported.
+    fn on_paragraph(&mut self) {}
+}
@@ -0,0 +1,29 @@
+//! PDF text extraction, bio-reading the text layer of PDF documents. Requires the `pdf` feature.
+
+use crate::{processor::Processor, BioReader, Error, InputFormat};
+use std::io::{Read, Write};
+
+/// Extract the text of the PDF `input`, and do bio-reading on it as plain text, regardless of the [format](BioReader::format) of `reader`. Write the result to `writer`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use bio_read::{pdf, BioReader, OutputFormat};
+/// use std::fs::File;
+/// let reader = BioReader::new().renderer(OutputFormat::Markdown.renderer());
+/// let input = File::open("paper.pdf").unwrap();
+/// pdf::bio_read_pdf(&reader, input, &mut std::io::stdout()).unwrap();
+/// ```
+pub fn bio_read_pdf(reader: &BioReader, mut input: impl Read, writer: &mut impl Write) -> Result<(), Error> {
+    let mut document = Vec::new();
+    input.read_to_end(&mut document)?;
+    let text = extract_text(&document)?;
+    let mut processor = Processor::with_format(reader, reader.renderer_ref(), InputFormat::Plain).decoded();
+    processor.push_bytes(text.as_bytes(), writer)?;
+    Ok(processor.finish(writer)?)
+}
+
+/// Extract the text layer of the PDF `document`. Fails with [`Error::Format`] if it is not a valid PDF.
+pub fn extract_text(document: &[u8]) -> Result<String, Error> {
+    pdf_extract::extract_text_from_mem(document).map_err(|e| Error::format(format!("Invalid PDF: {e}")))
+}
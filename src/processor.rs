@@ -0,0 +1,359 @@
+//! Incremental processing, shared by all the ways of feeding input to a [`BioReader`]. See [`BioReadProcessor`].
+
+use crate::{decode::Utf8Decoder, encoding::Transcoder, format::asciidoc, format::code, format::diff::Diff, format::email, format::html::Scanner, format::latex, format::man::Overstrike, format::markdown, format::org, format::reflow::Reflow, format::rst, format::subtitles, normalize::Normalizer, observer::Observer, BioReader, CodeLanguage, EmphasisRenderer, InputFormat, State};
+use std::io::{Result, Write};
+
+/// Bio-reads input pushed into it a chunk of bytes at a time, for input that doesn't come from a [`Read`](std::io::Read)er, like network frames, channel messages or edits in a GUI editor.
+///
+/// Each chunk may end anywhere, even in the middle of a word or a UTF-8 sequence, so the end of the last word is held back until more is pushed or the processor is finished. Markdown, LaTeX, reStructuredText, AsciiDoc, Org, email, subtitles and source code can't be processed as they come, so they're collected until finished.
+///
+/// Output is written piece by piece, so wrap unbuffered writers in a [`BufWriter`](std::io::BufWriter).
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{BioReadProcessor, BioReader};
+/// let reader = BioReader::new()
+///     .emphasize(String::from("<em>"), String::from("</em>"))
+///     .de_emphasize(String::from(""), String::from(""));
+/// let mut processor = BioReadProcessor::new(&reader);
+/// let mut output = Vec::new();
+/// for frame in ["hello ", "wor", "ld"] {
+///     processor.push_bytes(frame.as_bytes(), &mut output).unwrap();
+/// }
+/// assert_eq!(String::from_utf8_lossy(&output), "<em>hel</em>lo ");
+/// processor.finish(&mut output).unwrap();
+/// assert_eq!(String::from_utf8(output).unwrap(), "<em>hel</em>lo <em>wor</em>ld");
+/// ```
+///
+/// # See also
+///
+/// - [`BioWriter`](crate::BioWriter): Bio-reads everything written into it, forwarding the output to a single inner writer.
+/// - [`BioRead`](crate::BioRead): Bio-reads an inner reader as it is read from.
+pub struct BioReadProcessor<'a> {
+    /// The processor holding the state of the current word.
+    processor: Processor<'a>,
+}
+
+impl<'a> BioReadProcessor<'a> {
+    /// Create a processor bio-reading with `reader`.
+    pub fn new(reader: &'a BioReader) -> Self {
+        Self {
+            processor: Processor::new(reader),
+        }
+    }
+    /// Process a chunk of `bytes`, writing whatever output is ready to `writer`. Output can go to a different writer for each chunk.
+    pub fn push_bytes(&mut self, bytes: &[u8], writer: &mut impl Write) -> Result<()> {
+        self.processor.push_bytes(bytes, writer)
+    }
+    /// Finish processing, writing whatever is held back to `writer`. The `writer` is not flushed.
+    pub fn finish(self, writer: &mut impl Write) -> Result<()> {
+        self.processor.finish(writer)
+    }
+}
+
+impl std::fmt::Debug for BioReadProcessor<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BioReadProcessor").field("reader", &self.processor.bio_reader).finish_non_exhaustive()
+    }
+}
+
+/// Drives the bio-reading state machine incrementally, from chunks of bytes.
+pub(crate) struct Processor<'a> {
+    /// The [`BioReader`] to process with.
+    bio_reader: &'a BioReader,
+    /// State of the current word.
+    state: State<'a>,
+    /// The transcoder of the input to UTF-8, unless it is text decoded already.
+    transcoder: Option<Transcoder>,
+    /// Decoder for the input.
+    decoder: Utf8Decoder,
+    /// Format-specific state.
+    input: Input,
+    /// The scanner reflowing plain text, if lines are reflowed.
+    reflow: Option<Reflow>,
+    /// The normalizer of line endings and whitespace, if any normalization is set.
+    normalizer: Option<Normalizer>,
+}
+
+/// Format-specific state of a [`Processor`].
+enum Input {
+    /// Input of an unknown format, collected until there's enough of it to detect the format.
+    Auto(Vec<u8>),
+    /// Plain text, processed as it comes.
+    Plain,
+    /// HTML, processed as it comes with the help of a scanner.
+    Html(Scanner),
+    /// A unified diff, processed a line at a time.
+    Diff(Diff),
+    /// Org, collected until the end of input.
+    Org(Vec<u8>),
+    /// Subtitles, collected until the end of input.
+    Subtitles(Vec<u8>),
+    /// Email, collected until the end of input.
+    Email(Vec<u8>),
+    /// Source code, collected until the end of input.
+    Code(Vec<u8>, CodeLanguage),
+    /// A man page, processed as it comes with the help of a scanner removing overstriking.
+    Man(Overstrike),
+    /// Markdown, collected until the end of input.
+    Markdown(Vec<u8>),
+    /// LaTeX, collected until the end of input.
+    Latex(Vec<u8>),
+    /// reStructuredText, collected until the end of input.
+    Rst(Vec<u8>),
+    /// AsciiDoc, collected until the end of input.
+    AsciiDoc(Vec<u8>),
+    /// A Word document, collected until the end of input.
+    #[cfg(feature = "docx")]
+    Docx(Vec<u8>),
+}
+
+impl<'a> Processor<'a> {
+    /// Create a processor for `bio_reader`.
+    pub(crate) fn new(bio_reader: &'a BioReader) -> Self {
+        Self::with_renderer(bio_reader, bio_reader.renderer_ref())
+    }
+    /// Create a processor for `bio_reader`, writing words with `renderer` instead of its own renderer.
+    pub(crate) fn with_renderer(bio_reader: &'a BioReader, renderer: &'a (dyn EmphasisRenderer + Sync)) -> Self {
+        Self::with_format(bio_reader, renderer, bio_reader.format)
+    }
+    /// Create a processor for `bio_reader`, writing words with `renderer` and reading input in `format` instead of its own.
+    pub(crate) fn with_format(bio_reader: &'a BioReader, renderer: &'a (dyn EmphasisRenderer + Sync), format: InputFormat) -> Self {
+        Self {
+            bio_reader,
+            state: State::new(renderer),
+            transcoder: Some(Transcoder::new(bio_reader.encoding)),
+            decoder: Utf8Decoder::default(),
+            input: Input::new(format),
+            reflow: (bio_reader.reflow > 0).then(|| Reflow::new(bio_reader)),
+            normalizer: Normalizer::new(bio_reader),
+        }
+    }
+    /// Write words exactly as in the input, so that the output lines up with it.
+    pub(crate) fn verbatim(mut self) -> Self {
+        self.state.verbatim = true;
+        self.transcoder = None;
+        self.reflow = None;
+        self.normalizer = None;
+        self
+    }
+    /// Take the input to be text decoded already, like a `str`, whatever the encoding of the [`BioReader`].
+    pub(crate) fn decoded(mut self) -> Self {
+        self.transcoder = None;
+        self
+    }
+    /// Notify `observer` of the words and paragraphs written.
+    pub(crate) fn with_observer(mut self, observer: &'a mut (dyn Observer + Send)) -> Self {
+        self.state.observer = Some(observer);
+        self
+    }
+    /// Process a chunk of bytes, writing whatever output is ready to `writer`.
+    pub(crate) fn push_bytes(&mut self, bytes: &[u8], writer: &mut impl Write) -> Result<()> {
+        match &mut self.transcoder {
+            // Word documents aren't text
+            Some(transcoder) if !self.input.is_docx() => {
+                let bytes = transcoder.push(bytes);
+                self.push_transcoded(&bytes, writer)
+            }
+            _ => self.push_transcoded(bytes, writer),
+        }
+    }
+    /// Process a chunk of bytes that is transcoded to UTF-8 already, if it needs to be.
+    fn push_transcoded(&mut self, bytes: &[u8], writer: &mut impl Write) -> Result<()> {
+        match &mut self.normalizer {
+            // Input of an unknown format is normalized once it is detected, and Word documents aren't text
+            Some(normalizer) if !self.input.is_binary() => {
+                let bytes = normalizer.push(bytes);
+                self.push_normalized(&bytes, writer)
+            }
+            _ => self.push_normalized(bytes, writer),
+        }
+    }
+    /// Process a chunk of bytes that is normalized already, if it needs to be.
+    fn push_normalized(&mut self, bytes: &[u8], writer: &mut impl Write) -> Result<()> {
+        let Self {
+            bio_reader,
+            state,
+            decoder,
+            input,
+            reflow,
+            ..
+        } = self;
+        match input {
+            Input::Auto(collected) => {
+                collected.extend_from_slice(bytes);
+                if collected.len() >= InputFormat::SNIFF_LEN {
+                    self.detect(writer)?;
+                }
+                Ok(())
+            }
+            Input::Markdown(collected) | Input::Latex(collected) | Input::Rst(collected) | Input::AsciiDoc(collected) | Input::Org(collected) | Input::Email(collected) | Input::Subtitles(collected) | Input::Code(collected, _) => {
+                collected.extend_from_slice(bytes);
+                Ok(())
+            }
+            #[cfg(feature = "docx")]
+            Input::Docx(collected) => {
+                collected.extend_from_slice(bytes);
+                Ok(())
+            }
+            Input::Plain => decoder.decode(bytes, |decoded| push_plain(bio_reader, reflow, decoded, writer, state)),
+            Input::Html(scanner) => decoder.decode(bytes, |decoded| match decoded {
+                Ok(text) => scanner.push_str(bio_reader, text, writer, state),
+                Err(bytes) => bio_reader.push_raw(bytes, writer, state),
+            }),
+            Input::Diff(diff) => decoder.decode(bytes, |decoded| match decoded {
+                Ok(text) => diff.push_str(bio_reader, text, writer, state),
+                Err(bytes) => {
+                    diff.finish(bio_reader, writer, state)?;
+                    bio_reader.push_raw(bytes, writer, state)
+                }
+            }),
+            Input::Man(overstrike) => decoder.decode(bytes, |decoded| match decoded {
+                Ok(text) => overstrike.push_str(bio_reader, text, writer, state),
+                Err(bytes) => {
+                    overstrike.finish(bio_reader, writer, state)?;
+                    bio_reader.push_raw(bytes, writer, state)
+                }
+            }),
+        }
+    }
+    /// Process a line of bytes, including its line ending, writing all of its output to `writer`. Markdown is collected until a blank line, and processed a block at a time.
+    pub(crate) fn push_line(&mut self, line: &[u8], writer: &mut impl Write) -> Result<()> {
+        self.push_bytes(line, writer)?;
+        // A line is all there is to go by, to avoid holding back output
+        self.detect(writer)?;
+        if let Input::Markdown(collected) = &mut self.input {
+            if line.trim_ascii().is_empty() {
+                markdown::bio_read(self.bio_reader, collected, writer, &mut self.state)?;
+                collected.clear();
+            }
+        }
+        Ok(())
+    }
+    /// Detect the format of the collected input, if it is still unknown, and process what has been collected.
+    fn detect(&mut self, writer: &mut impl Write) -> Result<()> {
+        if let Input::Auto(collected) = &mut self.input {
+            let collected = std::mem::take(collected);
+            self.input = Input::new(InputFormat::sniff(&collected));
+            self.push_transcoded(&collected, writer)?;
+        }
+        Ok(())
+    }
+    /// Finish processing, writing the rest of the output to `writer`.
+    pub(crate) fn finish(mut self, writer: &mut impl Write) -> Result<()> {
+        if let Some(transcoder) = self.transcoder.as_mut().filter(|_| !self.input.is_docx()) {
+            let bytes = transcoder.finish();
+            self.push_transcoded(&bytes, writer)?;
+        }
+        self.detect(writer)?;
+        if let Some(normalizer) = self.normalizer.as_mut().filter(|_| !self.input.is_binary()) {
+            let bytes = normalizer.finish();
+            self.push_normalized(&bytes, writer)?;
+        }
+        let Self {
+            bio_reader,
+            state,
+            decoder,
+            input,
+            reflow,
+            ..
+        } = &mut self;
+        match input {
+            Input::Markdown(collected) => markdown::bio_read(bio_reader, collected, writer, state),
+            Input::Latex(collected) => latex::bio_read(bio_reader, collected, writer, state),
+            Input::Rst(collected) => rst::bio_read(bio_reader, collected, writer, state),
+            Input::AsciiDoc(collected) => asciidoc::bio_read(bio_reader, collected, writer, state),
+            Input::Org(collected) => org::bio_read(bio_reader, collected, writer, state),
+            Input::Subtitles(collected) => subtitles::bio_read(bio_reader, collected, writer, state),
+            Input::Email(collected) => email::bio_read(bio_reader, collected, writer, state),
+            Input::Code(collected, language) => code::bio_read(bio_reader, collected, *language, writer, state),
+            #[cfg(feature = "docx")]
+            Input::Docx(collected) => crate::format::docx::bio_read(bio_reader, collected, writer),
+            Input::Diff(diff) => {
+                decoder.finish(|decoded| match decoded {
+                    Ok(text) => diff.push_str(bio_reader, text, writer, state),
+                    Err(bytes) => {
+                        diff.finish(bio_reader, writer, state)?;
+                        bio_reader.push_raw(bytes, writer, state)
+                    }
+                })?;
+                diff.finish(bio_reader, writer, state)?;
+                bio_reader.end_text(writer, state)
+            }
+            Input::Man(overstrike) => {
+                decoder.finish(|decoded| match decoded {
+                    Ok(text) => overstrike.push_str(bio_reader, text, writer, state),
+                    Err(bytes) => {
+                        overstrike.finish(bio_reader, writer, state)?;
+                        bio_reader.push_raw(bytes, writer, state)
+                    }
+                })?;
+                overstrike.finish(bio_reader, writer, state)?;
+                bio_reader.end_text(writer, state)
+            }
+            Input::Plain => {
+                decoder.finish(|decoded| push_plain(bio_reader, reflow, decoded, writer, state))?;
+                if let Some(reflow) = reflow {
+                    reflow.finish(bio_reader, writer, state)?;
+                }
+                bio_reader.end_text(writer, state)
+            }
+            _ => {
+                // Write the trailing incomplete sequence, if any
+                decoder.finish(|decoded| match decoded {
+                    Ok(text) => bio_reader.push_str(text, writer, state),
+                    Err(bytes) => bio_reader.push_raw(bytes, writer, state),
+                })?;
+                // Write the unfinished word
+                bio_reader.end_text(writer, state)
+            }
+        }
+    }
+}
+
+/// Process a piece of decoded plain text, reflowed if lines are, or invalid bytes.
+fn push_plain(bio_reader: &BioReader, reflow: &mut Option<Reflow>, decoded: std::result::Result<&str, &[u8]>, writer: &mut impl Write, state: &mut State<'_>) -> Result<()> {
+    match (decoded, reflow) {
+        (Ok(text), Some(reflow)) => reflow.push_str(bio_reader, text, writer, state),
+        (Ok(text), None) => bio_reader.push_str(text, writer, state),
+        // Pass invalid sequences through untouched
+        (Err(bytes), Some(reflow)) => reflow.push_raw(bio_reader, bytes, writer, state),
+        (Err(bytes), None) => bio_reader.push_raw(bytes, writer, state),
+    }
+}
+
+impl Input {
+    /// Whether the input isn't known to be text, being of an unknown format or a Word document, so that it can't be normalized.
+    fn is_binary(&self) -> bool {
+        matches!(self, Self::Auto(_)) || self.is_docx()
+    }
+    /// Whether the input is a Word document, which isn't text.
+    fn is_docx(&self) -> bool {
+        #[cfg(feature = "docx")]
+        if let Self::Docx(_) = self {
+            return true;
+        }
+        false
+    }
+    /// Create the initial state for input in `format`.
+    fn new(format: InputFormat) -> Self {
+        match format {
+            InputFormat::Auto => Self::Auto(Vec::new()),
+            InputFormat::Plain => Self::Plain,
+            InputFormat::Html => Self::Html(Scanner::default()),
+            InputFormat::Markdown => Self::Markdown(Vec::new()),
+            InputFormat::Latex => Self::Latex(Vec::new()),
+            InputFormat::Rst => Self::Rst(Vec::new()),
+            InputFormat::AsciiDoc => Self::AsciiDoc(Vec::new()),
+            InputFormat::Code(language) => Self::Code(Vec::new(), language),
+            InputFormat::Diff => Self::Diff(Diff::default()),
+            InputFormat::Org => Self::Org(Vec::new()),
+            InputFormat::Email => Self::Email(Vec::new()),
+            InputFormat::Subtitles => Self::Subtitles(Vec::new()),
+            InputFormat::Man => Self::Man(Overstrike::default()),
+            #[cfg(feature = "docx")]
+            InputFormat::Docx => Self::Docx(Vec::new()),
+        }
+    }
+}
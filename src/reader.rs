@@ -0,0 +1,100 @@
+//! A [`Read`] adapter, bio-reading everything read from it. See [`BioRead`].
+
+use crate::{processor::Processor, BioReader, CHUNK_SIZE};
+use std::io::{BufRead, Read, Result};
+
+/// Bio-reads an inner reader on demand, yielding the output when read from. Place it anywhere in a chain of readers, like between decompressing and hashing, without buffering the whole output.
+///
+/// Output is produced a chunk of input at a time, so memory usage is proportional to the chunk size and the longest word, like [`BioReader::bio_read`].
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{BioRead, BioReader};
+/// use std::io::{BufRead, Read};
+/// let reader = BioReader::new()
+///     .emphasize(String::from("<em>"), String::from("</em>"))
+///     .de_emphasize(String::from(""), String::from(""));
+/// let mut output = String::new();
+/// BioRead::new(&reader, "hello world".as_bytes()).read_to_string(&mut output).unwrap();
+/// assert_eq!(output, "<em>hel</em>lo <em>wor</em>ld");
+/// let lines: Vec<_> = BioRead::new(&reader, "hello\nworld".as_bytes()).lines().collect::<Result<_, _>>().unwrap();
+/// assert_eq!(lines, ["<em>hel</em>lo", "<em>wor</em>ld"]);
+/// ```
+pub struct BioRead<'a, R: Read> {
+    /// The reader to read input from.
+    inner: R,
+    /// The processor holding the state of the current word, taken when the input ends.
+    processor: Option<Processor<'a>>,
+    /// Input read from the inner reader, to be processed.
+    input: Box<[u8]>,
+    /// Output ready to be read.
+    output: Vec<u8>,
+    /// How much of the output has been read.
+    position: usize,
+}
+
+impl<'a, R: Read> BioRead<'a, R> {
+    /// Create a reader yielding `inner` bio-read with `reader`.
+    pub fn new(reader: &'a BioReader, inner: R) -> Self {
+        Self {
+            inner,
+            processor: Some(Processor::new(reader)),
+            input: vec![0; CHUNK_SIZE].into_boxed_slice(),
+            output: Vec::new(),
+            position: 0,
+        }
+    }
+    /// Get a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+    /// Get a mutable reference to the inner reader. Reading from it directly skips bio-reading what is read.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+    /// Unwrap the inner reader, discarding output that hasn't been read yet.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for BioRead<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let available = self.fill_buf()?;
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl<R: Read> BufRead for BioRead<'_, R> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        // Words may span chunks, so reading a chunk doesn't always produce output
+        while self.position == self.output.len() {
+            let Some(processor) = self.processor.as_mut() else {
+                break;
+            };
+            self.output.clear();
+            self.position = 0;
+            let n = self.inner.read(&mut self.input)?;
+            if n == 0 {
+                let processor = self.processor.take().expect("Processor is only taken when the input ends");
+                processor.finish(&mut self.output)?;
+            } else {
+                processor.push_bytes(&self.input[..n], &mut self.output)?;
+            }
+        }
+        Ok(&self.output[self.position..])
+    }
+    fn consume(&mut self, amount: usize) {
+        self.position = (self.position + amount).min(self.output.len());
+    }
+}
+
+impl<R: Read + std::fmt::Debug> std::fmt::Debug for BioRead<'_, R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BioRead").field("inner", &self.inner).finish_non_exhaustive()
+    }
+}
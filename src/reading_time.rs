@@ -0,0 +1,86 @@
+//! Estimates of how long text takes to read, as is and bio-read, at reading speeds that depend on its language. See [`BioReadStats::estimated_minutes`](crate::BioReadStats::estimated_minutes).
+
+use crate::segment::Language;
+
+/// Get the average silent reading speed of adults for text in `language`, in words per minute.
+///
+/// English is read at 238 words per minute (Brysbaert, 2019), and other languages at their speed relative to English as measured with the International Reading Speed Texts (Trauzettel-Klosinski and Dietz, 2012). Languages with longer words, like German, are read in fewer words per minute.
+///
+/// ```rust
+/// use bio_read::{reading_time::average_wpm, segment::Language};
+/// assert_eq!(average_wpm(Language::English), 238);
+/// assert!(average_wpm(Language::German) < average_wpm(Language::Spanish));
+/// ```
+pub const fn average_wpm(language: Language) -> u32 {
+    match language {
+        Language::English => 238,
+        Language::German => 187,
+        Language::Dutch => 211,
+        Language::Spanish => 228,
+        Language::French => 204,
+        Language::Italian => 196,
+        Language::Turkish => 173,
+    }
+}
+
+/// The reading speeds of text as is and bio-read, in words per minute.
+///
+/// Studies haven't found bionic reading to be reliably faster, so both are the same by default. Measure your own speeds and set them to estimate the time bio-reading saves you.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ReadingSpeed {
+    /// The speed of reading the original text.
+    pub original: u32,
+    /// The speed of reading the bio-read text.
+    pub bio_read: u32,
+}
+
+impl ReadingSpeed {
+    /// Read at `wpm` words per minute, bio-read or not.
+    pub const fn new(wpm: u32) -> Self {
+        Self { original: wpm, bio_read: wpm }
+    }
+    /// Read at the average speed for text in `language`, bio-read or not. See [`average_wpm`].
+    pub const fn for_language(language: Language) -> Self {
+        Self::new(average_wpm(language))
+    }
+    /// Set the speed of reading the bio-read text.
+    pub const fn bio_read(mut self, wpm: u32) -> Self {
+        self.bio_read = wpm;
+        self
+    }
+    /// Estimate how many minutes reading `words` words takes, as is and bio-read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either speed is 0.
+    pub fn estimate(&self, words: usize) -> ReadingEstimate {
+        assert!(self.original > 0 && self.bio_read > 0, "Words per minute should be positive");
+        ReadingEstimate {
+            original: words as f64 / f64::from(self.original),
+            bio_read: words as f64 / f64::from(self.bio_read),
+        }
+    }
+}
+
+impl Default for ReadingSpeed {
+    /// Read at the average speed for English text.
+    fn default() -> Self {
+        Self::for_language(Language::default())
+    }
+}
+
+/// How many minutes reading some text takes, as is and bio-read. See [`ReadingSpeed::estimate`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ReadingEstimate {
+    /// Minutes taken to read the original text.
+    pub original: f64,
+    /// Minutes taken to read the bio-read text.
+    pub bio_read: f64,
+}
+
+impl ReadingEstimate {
+    /// Minutes saved by reading the bio-read text instead of the original. Negative if it takes longer.
+    pub fn saved(&self) -> f64 {
+        self.original - self.bio_read
+    }
+}
@@ -0,0 +1,1099 @@
+//! Renderers, determining how the emphasized and de-emphasized parts of words are written.
+//!
+//! A [`BioReader`](crate::BioReader) splits each word into an emphasized part and a de-emphasized part, and hands them to an [`EmphasisRenderer`], along with the text in between words. Long words may have a weaker, secondary emphasis in the middle as well, with [`BioReader::secondary_fixation`](crate::BioReader::secondary_fixation), which [`Ansi`], [`Html`], [`Wrap`] and [`Gradient`] style as a third class. Built-in renderers include:
+//!
+//! - [`Ansi`]: ANSI escape codes for terminals, with styles picked from a [`Theme`] or customized.
+//! - [`Html`]: `<b>` tags, optionally with a class.
+//! - [`Markdown`]: `**` markers, escaping the text for CommonMark.
+//! - [`BBCode`]: `[b]` tags.
+//! - [`Latex`]: `\textbf{}` commands, escaping special characters.
+//! - [`Typst`]: `#strong[]` calls, escaping special characters.
+//! - [`Rst`]: `**` markers, escaping the rest of the word for reStructuredText.
+//! - [`Org`]: `*` markers, separated from the rest of the word by a zero width space.
+//! - [`Rtf`]: `\b` groups, escaping special and non-ASCII characters.
+//! - [`Plain`]: No emphasis at all.
+//! - [`Json`]: A JSON object for each word, for tools consuming structured results.
+//! - [`Wrap`]: Arbitrary strings wrapped around both parts.
+//! - [`Gradient`]: Graded intensity across the emphasized part, with ANSI escape codes or HTML classes.
+//!
+//! [`OutputFormat`] names the built-in renderers, for picking one at runtime. [`HtmlPage`] turns HTML output into a standalone web page, and [`Rtf`] writes the start and end of a document around its output.
+
+use anstyle::{AnsiColor, Color, Style};
+use regex::Regex;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    io::{Result, Write},
+    str::FromStr,
+    sync::LazyLock,
+};
+use unicode_properties::{GeneralCategoryGroup, UnicodeGeneralCategory};
+
+/// Determines how words are written.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{BioReader, EmphasisRenderer};
+/// use std::io::{Result, Write};
+///
+/// /// Uppercase the emphasized part
+/// struct Uppercase;
+///
+/// impl EmphasisRenderer for Uppercase {
+///     fn emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+///         writer.write_all(text.to_uppercase().as_bytes())
+///     }
+///     fn de_emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+///         writer.write_all(text.as_bytes())
+///     }
+/// }
+///
+/// let reader = BioReader::new().renderer(Uppercase);
+/// assert_eq!(reader.bio_read_text("hello world").unwrap(), "HELlo WORld");
+/// ```
+pub trait EmphasisRenderer {
+    /// Write the emphasized part of a word, which is never empty.
+    fn emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()>;
+    /// Write the de-emphasized part of a word, which is never empty.
+    fn de_emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()>;
+    /// Write text between words, like whitespace and punctuation. Written as-is by default.
+    fn plain(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        writer.write_all(text.as_bytes())
+    }
+    /// Write a whole word, given its emphasized and de-emphasized parts, either (but not both) of which may be empty. Calls [`EmphasisRenderer::emphasized`] and [`EmphasisRenderer::de_emphasized`] with the non-empty parts by default.
+    fn word(&self, writer: &mut dyn Write, emphasized: &str, de_emphasized: &str) -> Result<()> {
+        if !emphasized.is_empty() {
+            self.emphasized(writer, emphasized)?;
+        }
+        if !de_emphasized.is_empty() {
+            self.de_emphasized(writer, de_emphasized)?;
+        }
+        Ok(())
+    }
+    /// Write the secondary emphasized part in the middle of a long word, which should be weaker than the emphasized part. See [`BioReader::secondary_fixation`](crate::BioReader::secondary_fixation). Calls [`EmphasisRenderer::de_emphasized`] by default, writing it like the rest of the word.
+    fn secondary(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        self.de_emphasized(writer, text)
+    }
+    /// Write a long word with a secondary emphasis, given its emphasized start, the de-emphasized part after it, the secondary emphasized part and the de-emphasized rest, of which only the rest may be empty. Calls [`EmphasisRenderer::word`] with the first two parts, then [`EmphasisRenderer::secondary`] and [`EmphasisRenderer::de_emphasized`] with the others by default.
+    fn word_with_secondary(&self, writer: &mut dyn Write, emphasized: &str, de_emphasized: &str, secondary: &str, rest: &str) -> Result<()> {
+        self.word(writer, emphasized, de_emphasized)?;
+        self.secondary(writer, secondary)?;
+        if !rest.is_empty() {
+            self.de_emphasized(writer, rest)?;
+        }
+        Ok(())
+    }
+    /// Remove the markup written by this renderer from `output`, recovering the text that was bio-read, along with the zero-width joiners inserted around the split (see [`BioReader::join_cursive`](crate::BioReader::join_cursive)). Removes ANSI escape codes by default. See [`BioReader::strip`](crate::BioReader::strip).
+    fn strip(&self, output: &str) -> String {
+        ANSI.replace_all(output, "").into_owned()
+    }
+}
+
+/// Matches ANSI escape codes setting styles, and the zero-width joiners inserted on both sides of the split, with or without escape codes in between.
+static ANSI: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\x{200D}(?:\x1b\[[0-9;:]*m)*\x{200D}|\x1b\[[0-9;:]*m").unwrap());
+
+impl<R: EmphasisRenderer + ?Sized> EmphasisRenderer for Box<R> {
+    fn emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        (**self).emphasized(writer, text)
+    }
+    fn de_emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        (**self).de_emphasized(writer, text)
+    }
+    fn plain(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        (**self).plain(writer, text)
+    }
+    fn word(&self, writer: &mut dyn Write, emphasized: &str, de_emphasized: &str) -> Result<()> {
+        (**self).word(writer, emphasized, de_emphasized)
+    }
+    fn secondary(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        (**self).secondary(writer, text)
+    }
+    fn word_with_secondary(&self, writer: &mut dyn Write, emphasized: &str, de_emphasized: &str, secondary: &str, rest: &str) -> Result<()> {
+        (**self).word_with_secondary(writer, emphasized, de_emphasized, secondary, rest)
+    }
+    fn strip(&self, output: &str) -> String {
+        (**self).strip(output)
+    }
+}
+
+/// Renders words with ANSI escape codes. Emphasizes with bold and de-emphasizes with dimmed by default, leaving the secondary emphasis of long words in regular weight in between.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ansi {
+    /// The style of the emphasized part.
+    pub emphasize: Style,
+    /// The style of the de-emphasized part.
+    pub de_emphasize: Style,
+    /// The style of the secondary emphasized part of long words.
+    pub secondary: Style,
+}
+
+impl Default for Ansi {
+    fn default() -> Self {
+        Self {
+            emphasize: Style::new().bold(),
+            de_emphasize: Style::new().dimmed(),
+            secondary: Style::new(),
+        }
+    }
+}
+
+impl EmphasisRenderer for Ansi {
+    fn emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        let style = self.emphasize;
+        write!(writer, "{style}{text}{style:#}")
+    }
+    fn de_emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        let style = self.de_emphasize;
+        write!(writer, "{style}{text}{style:#}")
+    }
+    fn secondary(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        let style = self.secondary;
+        write!(writer, "{style}{text}{style:#}")
+    }
+}
+
+impl From<Theme> for Ansi {
+    fn from(theme: Theme) -> Self {
+        let (emphasize, de_emphasize, secondary) = match theme {
+            Theme::BoldDim => return Self::default(),
+            Theme::ColorContrast => (
+                Style::new().fg_color(Some(Color::Ansi(AnsiColor::BrightWhite))),
+                Style::new().fg_color(Some(Color::Ansi(AnsiColor::BrightBlack))),
+                Style::new().fg_color(Some(Color::Ansi(AnsiColor::White))),
+            ),
+            Theme::Underline => (Style::new().underline(), Style::new(), Style::new().italic()),
+            Theme::Inverse => (Style::new().invert(), Style::new(), Style::new().underline()),
+        };
+        Self { emphasize, de_emphasize, secondary }
+    }
+}
+
+/// Preset styles of [`Ansi`] output, for terminals where the default dimmed text is hard to read or invisible.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{render::Theme, BioReader};
+/// let theme: Theme = "underline".parse().unwrap();
+/// let reader = BioReader::new().theme(theme);
+/// assert_eq!(reader.bio_read_text("hello").unwrap(), "\x1b[4mhel\x1b[0mlo");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum Theme {
+    /// Bold emphasized part and dimmed de-emphasized part.
+    #[default]
+    BoldDim,
+    /// Bright white emphasized part and gray de-emphasized part.
+    ColorContrast,
+    /// Underlined emphasized part, leaving the de-emphasized part as-is.
+    Underline,
+    /// Emphasized part with inverted colors, leaving the de-emphasized part as-is.
+    Inverse,
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "bold-dim" => Ok(Self::BoldDim),
+            "color-contrast" => Ok(Self::ColorContrast),
+            "underline" => Ok(Self::Underline),
+            "inverse" => Ok(Self::Inverse),
+            _ => Err(format!("Unknown theme: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::BoldDim => "bold-dim",
+            Self::ColorContrast => "color-contrast",
+            Self::Underline => "underline",
+            Self::Inverse => "inverse",
+        })
+    }
+}
+
+/// Renders words as HTML, wrapping the emphasized part in `<b>` tags and escaping text. The secondary emphasis of long words is wrapped in `<b>` tags with class `secondary` too.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{render::Html, BioReader};
+/// let reader = BioReader::new().renderer(Html::default());
+/// assert_eq!(reader.bio_read_text("fish & chips").unwrap(), "<b>fi</b>sh &amp; <b>chi</b>ps");
+/// let reader = BioReader::new().renderer(Html::with_class("fixation"));
+/// assert_eq!(reader.bio_read_text("fish").unwrap(), "<b class=\"fixation\">fi</b>sh");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Html {
+    /// The class of the `<b>` tags, if any.
+    pub class: Option<String>,
+}
+
+impl Html {
+    /// Create an HTML renderer, adding `class` to the `<b>` tags.
+    pub fn with_class(class: impl Into<String>) -> Self {
+        Self {
+            class: Some(class.into()),
+        }
+    }
+}
+
+impl Html {
+    /// Write `text` in `<b>` tags with the class, and `secondary` as another class if given.
+    fn write_bold(&self, writer: &mut dyn Write, text: &str, secondary: Option<&str>) -> Result<()> {
+        match (&self.class, secondary) {
+            (Some(class), _) => {
+                writer.write_all(b"<b class=\"")?;
+                write_escaped_html(writer, class, true)?;
+                if let Some(secondary) = secondary {
+                    write!(writer, " {secondary}")?;
+                }
+                writer.write_all(b"\">")?;
+            }
+            (None, Some(secondary)) => write!(writer, "<b class=\"{secondary}\">")?,
+            (None, None) => writer.write_all(b"<b>")?,
+        }
+        write_escaped_html(writer, text, false)?;
+        writer.write_all(b"</b>")
+    }
+}
+
+impl EmphasisRenderer for Html {
+    fn emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        self.write_bold(writer, text, None)
+    }
+    fn secondary(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        self.write_bold(writer, text, Some("secondary"))
+    }
+    fn de_emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        write_escaped_html(writer, text, false)
+    }
+    fn plain(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        write_escaped_html(writer, text, false)
+    }
+}
+
+/// Write `text` with HTML special characters escaped. Double quotes are escaped as well if `attribute` is `true`.
+fn write_escaped_html(writer: &mut dyn Write, text: &str, attribute: bool) -> Result<()> {
+    let mut last = 0;
+    for (i, c) in text.char_indices() {
+        let escaped = match c {
+            '&' => "&amp;",
+            '<' => "&lt;",
+            '>' => "&gt;",
+            '"' if attribute => "&quot;",
+            _ => continue,
+        };
+        writer.write_all(&text.as_bytes()[last..i])?;
+        writer.write_all(escaped.as_bytes())?;
+        last = i + 1;
+    }
+    writer.write_all(&text.as_bytes()[last..])
+}
+
+/// A standalone web page to write HTML output into, with inline CSS setting the font and emphasis, so that bio-read documents can be shared and opened in any browser. Line breaks are kept, so it suits the output of plain text and lightweight markup, but not of HTML, which is a page already. The classes of [`Gradient::html`] with prefix `fixation` are styled too.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{render::{Html, HtmlPage}, BioReader};
+/// let reader = BioReader::new().renderer(Html::default());
+/// let page = HtmlPage::new("Fish & chips");
+/// let mut output = Vec::new();
+/// page.write_start(&mut output).unwrap();
+/// reader.bio_read("hello world".as_bytes(), &mut output).unwrap();
+/// page.write_end(&mut output).unwrap();
+/// let output = String::from_utf8(output).unwrap();
+/// assert!(output.starts_with("<!DOCTYPE html>"));
+/// assert!(output.contains("<title>Fish &amp; chips</title>"));
+/// assert!(output.ends_with("<main><b>hel</b>lo <b>wor</b>ld</main>\n</body>\n</html>\n"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HtmlPage {
+    /// The title of the page.
+    pub title: String,
+}
+
+/// The inline CSS of an [`HtmlPage`], for a comfortable line length and font, with a dark variant.
+const PAGE_STYLE: &str = r#"body { max-width: 42em; margin: 0 auto; padding: 2em 1em; font: 18px/1.6 Georgia, "Times New Roman", serif; color: #555; background: #fdfdfd; }
+main { white-space: pre-wrap; overflow-wrap: break-word; }
+b, .fixation-1, .fixation-2, .fixation-3 { color: #000; }
+b, .fixation-1 { font-weight: 800; }
+b.secondary { font-weight: 600; }
+.fixation-2 { font-weight: 700; }
+.fixation-3 { font-weight: 600; }
+@media (prefers-color-scheme: dark) {
+  body { color: #aaa; background: #1e1e1e; }
+  b, .fixation-1, .fixation-2, .fixation-3 { color: #fff; }
+}"#;
+
+impl HtmlPage {
+    /// Create a page titled `title`.
+    pub fn new(title: impl Into<String>) -> Self {
+        Self { title: title.into() }
+    }
+    /// Write the start of the page, up to where the output goes.
+    pub fn write_start(&self, writer: &mut dyn Write) -> Result<()> {
+        writer.write_all(b"<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>")?;
+        write_escaped_html(writer, &self.title, false)?;
+        writeln!(writer, "</title>\n<style>\n{PAGE_STYLE}\n</style>\n</head>\n<body>")?;
+        // No line break after the tag, as it would show
+        writer.write_all(b"<main>")
+    }
+    /// Write the end of the page, after the output.
+    pub fn write_end(&self, writer: &mut dyn Write) -> Result<()> {
+        writer.write_all(b"</main>\n</body>\n</html>\n")
+    }
+}
+
+/// Renders words as Markdown, wrapping the emphasized part in `**`. Backslashes, asterisks, underscores and backticks are escaped, so that they are never taken for markup.
+///
+/// In CommonMark, `**` next to punctuation only closes emphasis if followed by whitespace or punctuation, so punctuation at the end of the emphasized part (like an apostrophe or hyphen joining a word) is moved out of it, and likewise at the start.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{render::Markdown, BioReader};
+/// let reader = BioReader::new().renderer(Markdown).fixation_point(1);
+/// assert_eq!(reader.bio_read_text("2*3 = six").unwrap(), "2\\*3 = **si**x");
+/// assert_eq!(reader.bio_read_text("don't").unwrap(), "**don**'t");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Markdown;
+
+impl EmphasisRenderer for Markdown {
+    fn emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        writer.write_all(b"**")?;
+        write_escaped_markdown(writer, text)?;
+        writer.write_all(b"**")
+    }
+    fn de_emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        write_escaped_markdown(writer, text)
+    }
+    fn plain(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        write_escaped_markdown(writer, text)
+    }
+    fn word(&self, writer: &mut dyn Write, emphasized: &str, de_emphasized: &str) -> Result<()> {
+        let inner = emphasized.trim_start_matches(is_punctuation);
+        let (leading, inner) = emphasized.split_at(emphasized.len() - inner.len());
+        // Closing `**` followed by the end of the word needs no moving
+        let trimmed = if de_emphasized.is_empty() { inner } else { inner.trim_end_matches(is_punctuation) };
+        let (inner, trailing) = inner.split_at(trimmed.len());
+        write_escaped_markdown(writer, leading)?;
+        if !inner.is_empty() {
+            self.emphasized(writer, inner)?;
+        }
+        write_escaped_markdown(writer, trailing)?;
+        write_escaped_markdown(writer, de_emphasized)
+    }
+}
+
+/// Whether `c` is punctuation as far as CommonMark emphasis is concerned, that is, in Unicode general category P or S.
+fn is_punctuation(c: char) -> bool {
+    matches!(c.general_category_group(), GeneralCategoryGroup::Punctuation | GeneralCategoryGroup::Symbol)
+}
+
+/// Write `text` with backslashes, asterisks, underscores and backticks escaped for Markdown.
+fn write_escaped_markdown(writer: &mut dyn Write, text: &str) -> Result<()> {
+    let mut last = 0;
+    for (i, c) in text.char_indices() {
+        if matches!(c, '\\' | '*' | '_' | '`') {
+            writer.write_all(&text.as_bytes()[last..i])?;
+            writer.write_all(b"\\")?;
+            last = i;
+        }
+    }
+    writer.write_all(&text.as_bytes()[last..])
+}
+
+/// Renders words as BBCode, wrapping the emphasized part in `[b]` tags.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BBCode;
+
+impl EmphasisRenderer for BBCode {
+    fn emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        write!(writer, "[b]{text}[/b]")
+    }
+    fn de_emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        writer.write_all(text.as_bytes())
+    }
+}
+
+/// Renders words as LaTeX, wrapping the emphasized part in `\textbf{}`. Special characters like `#`, `%`, `&` and `\` are escaped, so that plain text can be pasted into documents. LaTeX input is unaffected, as only its prose is bio-read, which has no special characters.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{render::Latex, BioReader};
+/// let reader = BioReader::new().renderer(Latex);
+/// assert_eq!(reader.bio_read_text("hello").unwrap(), "\\textbf{hel}lo");
+/// assert_eq!(reader.bio_read_text("100% R&D").unwrap(), "100\\% \\textbf{R}\\&\\textbf{D}");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Latex;
+
+impl EmphasisRenderer for Latex {
+    fn emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        writer.write_all(b"\\textbf{")?;
+        write_escaped_latex(writer, text)?;
+        writer.write_all(b"}")
+    }
+    fn de_emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        write_escaped_latex(writer, text)
+    }
+    fn plain(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        write_escaped_latex(writer, text)
+    }
+}
+
+/// Write `text` with the special characters of LaTeX escaped.
+fn write_escaped_latex(writer: &mut dyn Write, text: &str) -> Result<()> {
+    let mut last = 0;
+    for (i, c) in text.char_indices() {
+        let escaped = match c {
+            '#' => "\\#",
+            '$' => "\\$",
+            '%' => "\\%",
+            '&' => "\\&",
+            '_' => "\\_",
+            '{' => "\\{",
+            '}' => "\\}",
+            '\\' => "\\textbackslash{}",
+            '~' => "\\textasciitilde{}",
+            '^' => "\\textasciicircum{}",
+            _ => continue,
+        };
+        writer.write_all(&text.as_bytes()[last..i])?;
+        writer.write_all(escaped.as_bytes())?;
+        last = i + 1;
+    }
+    writer.write_all(&text.as_bytes()[last..])
+}
+
+/// Renders words as [Typst](https://typst.app) markup, wrapping the emphasized part in `#strong[]`. Characters with a special meaning anywhere in markup, like `#`, `*`, `$`, `\` and `/`, are escaped with a backslash, while markers only special at the start of a line, like `=` for headings, are left as they are.
+///
+/// A call swallows a following `.`, `(` or `[`, so a semicolon ends it when nothing of the word follows.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{render::Typst, BioReader};
+/// let reader = BioReader::new().renderer(Typst);
+/// assert_eq!(reader.bio_read_text("hello #1 a.").unwrap(), "#strong[hel]lo \\#1 #strong[a];.");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Typst;
+
+impl EmphasisRenderer for Typst {
+    fn emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        writer.write_all(b"#strong[")?;
+        write_escaped_typst(writer, text)?;
+        writer.write_all(b"];")
+    }
+    fn de_emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        write_escaped_typst(writer, text)
+    }
+    fn plain(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        write_escaped_typst(writer, text)
+    }
+    fn word(&self, writer: &mut dyn Write, emphasized: &str, de_emphasized: &str) -> Result<()> {
+        if emphasized.is_empty() {
+            return self.de_emphasized(writer, de_emphasized);
+        }
+        if de_emphasized.is_empty() {
+            return self.emphasized(writer, emphasized);
+        }
+        // Letters right after the call end it
+        writer.write_all(b"#strong[")?;
+        write_escaped_typst(writer, emphasized)?;
+        writer.write_all(b"]")?;
+        write_escaped_typst(writer, de_emphasized)
+    }
+}
+
+/// Write `text` with the characters special anywhere in Typst markup escaped.
+fn write_escaped_typst(writer: &mut dyn Write, text: &str) -> Result<()> {
+    let mut last = 0;
+    for (i, c) in text.char_indices() {
+        if matches!(c, '\\' | '#' | '$' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '@' | '~' | '/') {
+            writer.write_all(&text.as_bytes()[last..i])?;
+            writer.write_all(b"\\")?;
+            last = i;
+        }
+    }
+    writer.write_all(&text.as_bytes()[last..])
+}
+
+/// Renders words as reStructuredText, wrapping the emphasized part in `**`. As inline markup must end at a word boundary, the de-emphasized part is separated from it by an escaped space, which reStructuredText removes.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{render::Rst, BioReader};
+/// let reader = BioReader::new().renderer(Rst);
+/// assert_eq!(reader.bio_read_text("hello, a").unwrap(), "**hel**\\ lo, **a**");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rst;
+
+impl EmphasisRenderer for Rst {
+    fn emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        write!(writer, "**{text}**")
+    }
+    fn de_emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        writer.write_all(text.as_bytes())
+    }
+    fn word(&self, writer: &mut dyn Write, emphasized: &str, de_emphasized: &str) -> Result<()> {
+        if !emphasized.is_empty() {
+            self.emphasized(writer, emphasized)?;
+            if !de_emphasized.is_empty() {
+                writer.write_all(b"\\ ")?;
+            }
+        }
+        if !de_emphasized.is_empty() {
+            self.de_emphasized(writer, de_emphasized)?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders words as Org, wrapping the emphasized part in `*`. As emphasis must be followed by whitespace or punctuation, the de-emphasized part is separated from it by a zero width space, as the Org manual suggests.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{render::Org, BioReader};
+/// let reader = BioReader::new().renderer(Org);
+/// assert_eq!(reader.bio_read_text("hello, a").unwrap(), "*hel*\u{200B}lo, *a*");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Org;
+
+impl EmphasisRenderer for Org {
+    fn emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        write!(writer, "*{text}*")
+    }
+    fn de_emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        writer.write_all(text.as_bytes())
+    }
+    fn word(&self, writer: &mut dyn Write, emphasized: &str, de_emphasized: &str) -> Result<()> {
+        if !emphasized.is_empty() {
+            self.emphasized(writer, emphasized)?;
+            if !de_emphasized.is_empty() {
+                writer.write_all("\u{200B}".as_bytes())?;
+            }
+        }
+        if !de_emphasized.is_empty() {
+            self.de_emphasized(writer, de_emphasized)?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders words as [RTF](https://en.wikipedia.org/wiki/Rich_Text_Format), putting the emphasized part in a bold group, so that the output can be opened in or pasted into word processors like Word or LibreOffice. Backslashes and braces are escaped, line breaks start a new paragraph, and characters outside of ASCII are written as Unicode escapes, so the output is plain ASCII.
+///
+/// The output of the renderer goes between [`Rtf::write_start`] and [`Rtf::write_end`], which make a whole document of it.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{render::Rtf, BioReader};
+/// let reader = BioReader::new().renderer(Rtf);
+/// let mut output = Vec::new();
+/// Rtf.write_start(&mut output).unwrap();
+/// reader.bio_read("héllo {a}\n".as_bytes(), &mut output).unwrap();
+/// Rtf.write_end(&mut output).unwrap();
+/// let output = String::from_utf8(output).unwrap();
+/// assert!(output.starts_with("{\\rtf1\\ansi"));
+/// assert!(output.ends_with("{\\b h\\u233?l}lo \\{{\\b a}\\}\\par\n}\n"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rtf;
+
+impl Rtf {
+    /// Write the start of an RTF document, with a font table, up to where the output goes.
+    pub fn write_start(&self, writer: &mut dyn Write) -> Result<()> {
+        writer.write_all(b"{\\rtf1\\ansi\\deff0{\\fonttbl{\\f0\\froman Times New Roman;}}\n")
+    }
+    /// Write the end of an RTF document, after the output.
+    pub fn write_end(&self, writer: &mut dyn Write) -> Result<()> {
+        writer.write_all(b"}\n")
+    }
+}
+
+impl EmphasisRenderer for Rtf {
+    fn emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        writer.write_all(b"{\\b ")?;
+        write_escaped_rtf(writer, text)?;
+        writer.write_all(b"}")
+    }
+    fn de_emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        write_escaped_rtf(writer, text)
+    }
+    fn plain(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        write_escaped_rtf(writer, text)
+    }
+}
+
+/// Write `text` as RTF, escaping backslashes and braces, turning line breaks into paragraphs and tabs into tab stops, and writing characters outside of ASCII as Unicode escapes with `?` for readers that don't support them.
+fn write_escaped_rtf(writer: &mut dyn Write, text: &str) -> Result<()> {
+    let mut last = 0;
+    for (i, c) in text.char_indices() {
+        if c.is_ascii() && !matches!(c, '\\' | '{' | '}' | '\n' | '\r' | '\t') {
+            continue;
+        }
+        writer.write_all(&text.as_bytes()[last..i])?;
+        last = i + c.len_utf8();
+        match c {
+            '\\' | '{' | '}' => write!(writer, "\\{c}")?,
+            // A line break in RTF source is ignored, but ends the control word
+            '\n' => writer.write_all(b"\\par\n")?,
+            // Part of a line break
+            '\r' => {}
+            '\t' => writer.write_all(b"\\tab ")?,
+            // RTF takes the UTF-16 code units, as signed 16-bit numbers
+            _ => {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    write!(writer, "\\u{}?", *unit as i16)?;
+                }
+            }
+        }
+    }
+    writer.write_all(&text.as_bytes()[last..])
+}
+
+/// Renders words as-is, without any emphasis.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Plain;
+
+impl EmphasisRenderer for Plain {
+    fn emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        writer.write_all(text.as_bytes())
+    }
+    fn de_emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        writer.write_all(text.as_bytes())
+    }
+}
+
+/// Renders words as [JSON Lines](https://jsonlines.org), for tools that consume structured results instead of parsing markup. Each word is an object with the whole `word`, its `emphasized` part and the `rest`, plus the `secondary` emphasized part within the rest for long words with [`BioReader::secondary_fixation`](crate::BioReader::secondary_fixation), and the text between words is an object with just the `text`, so that nothing is lost. Words skipped by the saccade or excluded are text as well.
+///
+/// Only plain text input makes sense, as the markup of other formats is written as-is. Likewise, invalid UTF-8 in the input is written as-is, making the output invalid.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{render::Json, BioReader};
+/// let reader = BioReader::new().renderer(Json);
+/// assert_eq!(
+///     reader.bio_read_text("Say \"hi\"").unwrap(),
+///     concat!(
+///         "{\"word\":\"Say\",\"emphasized\":\"S\",\"rest\":\"ay\"}\n",
+///         "{\"text\":\" \\\"\"}\n",
+///         "{\"word\":\"hi\",\"emphasized\":\"h\",\"rest\":\"i\"}\n",
+///         "{\"text\":\"\\\"\"}\n",
+///     )
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Json;
+
+impl EmphasisRenderer for Json {
+    fn emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        self.word(writer, text, "")
+    }
+    fn de_emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        self.word(writer, "", text)
+    }
+    fn plain(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        writer.write_all(b"{\"text\":\"")?;
+        write_escaped_json(writer, text)?;
+        writer.write_all(b"\"}\n")
+    }
+    fn word(&self, writer: &mut dyn Write, emphasized: &str, de_emphasized: &str) -> Result<()> {
+        self.word_with_secondary(writer, emphasized, de_emphasized, "", "")
+    }
+    fn word_with_secondary(&self, writer: &mut dyn Write, emphasized: &str, de_emphasized: &str, secondary: &str, rest: &str) -> Result<()> {
+        writer.write_all(b"{\"word\":\"")?;
+        for part in [emphasized, de_emphasized, secondary, rest] {
+            write_escaped_json(writer, part)?;
+        }
+        writer.write_all(b"\",\"emphasized\":\"")?;
+        write_escaped_json(writer, emphasized)?;
+        writer.write_all(b"\",\"rest\":\"")?;
+        for part in [de_emphasized, secondary, rest] {
+            write_escaped_json(writer, part)?;
+        }
+        if !secondary.is_empty() {
+            writer.write_all(b"\",\"secondary\":\"")?;
+            write_escaped_json(writer, secondary)?;
+        }
+        writer.write_all(b"\"}\n")
+    }
+    fn strip(&self, output: &str) -> String {
+        let mut text = String::new();
+        // The text of each object is in its first field
+        for line in output.lines() {
+            if let Some(json) = line.strip_prefix("{\"word\":\"").or_else(|| line.strip_prefix("{\"text\":\"")) {
+                unescape_json(json, &mut text);
+            }
+        }
+        ANSI.replace_all(&text, "").into_owned()
+    }
+}
+
+/// Write `text` as the inside of a JSON string, escaping quotes, backslashes and control characters.
+pub(crate) fn write_escaped_json(writer: &mut dyn Write, text: &str) -> Result<()> {
+    let mut last = 0;
+    for (i, c) in text.char_indices() {
+        let escaped = match c {
+            '"' => "\\\"",
+            '\\' => "\\\\",
+            '\n' => "\\n",
+            '\r' => "\\r",
+            '\t' => "\\t",
+            _ if c.is_ascii_control() && c != '\x7f' => "",
+            _ => continue,
+        };
+        writer.write_all(&text.as_bytes()[last..i])?;
+        if escaped.is_empty() {
+            write!(writer, "\\u{:04x}", u32::from(c))?;
+        } else {
+            writer.write_all(escaped.as_bytes())?;
+        }
+        last = i + c.len_utf8();
+    }
+    writer.write_all(&text.as_bytes()[last..])
+}
+
+/// Append the JSON string at the start of `json`, right after its opening quote, to `text`, unescaped.
+fn unescape_json(json: &str, text: &mut String) {
+    let mut chars = json.chars();
+    while let Some(c) = chars.next() {
+        let c = match c {
+            '"' => return,
+            '\\' => match chars.next() {
+                Some('n') => '\n',
+                Some('r') => '\r',
+                Some('t') => '\t',
+                Some('b') => '\u{8}',
+                Some('f') => '\u{c}',
+                Some('u') => {
+                    let Some(unit) = hex_unit(&mut chars) else { return };
+                    // Characters outside the basic multilingual plane are escaped as surrogate pairs
+                    let unit = match unit {
+                        0xD800..0xDC00 if chars.as_str().starts_with("\\u") => {
+                            chars.nth(1);
+                            let Some(low) = hex_unit(&mut chars) else { return };
+                            0x10000 + ((unit - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF)
+                        }
+                        _ => unit,
+                    };
+                    char::from_u32(unit).unwrap_or(char::REPLACEMENT_CHARACTER)
+                }
+                Some(c) => c,
+                None => return,
+            },
+            c => c,
+        };
+        text.push(c);
+    }
+}
+
+/// Parse the 4 hexadecimal digits of a `\u` escape from `chars`.
+fn hex_unit(chars: &mut std::str::Chars<'_>) -> Option<u32> {
+    let digits = chars.as_str().get(..4)?;
+    let unit = u32::from_str_radix(digits, 16).ok()?;
+    chars.nth(3);
+    Some(unit)
+}
+
+/// Renders words by wrapping both parts with arbitrary strings. All strings are empty by default.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{render::Wrap, BioReader};
+/// let wrap = Wrap {
+///     emphasize: [String::from("<em>"), String::from("</em>")],
+///     de_emphasize: [String::from("<de>"), String::from("</de>")],
+///     ..Wrap::default()
+/// };
+/// let reader = BioReader::new().renderer(wrap);
+/// assert_eq!(reader.bio_read_text("hello").unwrap(), "<em>hel</em><de>lo</de>");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Wrap {
+    /// The strings to be wrapped around the emphasized part of a word.
+    pub emphasize: [String; 2],
+    /// The strings to be wrapped around the de-emphasized part of a word.
+    pub de_emphasize: [String; 2],
+    /// The strings to be wrapped around the secondary emphasized part of a long word.
+    pub secondary: [String; 2],
+}
+
+impl From<Ansi> for Wrap {
+    fn from(ansi: Ansi) -> Self {
+        let Ansi {
+            emphasize,
+            de_emphasize,
+            secondary,
+        } = ansi;
+        Self {
+            emphasize: [format!("{emphasize}"), format!("{emphasize:#}")],
+            de_emphasize: [format!("{de_emphasize}"), format!("{de_emphasize:#}")],
+            secondary: [format!("{secondary}"), format!("{secondary:#}")],
+        }
+    }
+}
+
+impl EmphasisRenderer for Wrap {
+    fn emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        let [left, right] = &self.emphasize;
+        write!(writer, "{left}{text}{right}")
+    }
+    fn de_emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        let [left, right] = &self.de_emphasize;
+        write!(writer, "{left}{text}{right}")
+    }
+    fn secondary(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        let [left, right] = &self.secondary;
+        write!(writer, "{left}{text}{right}")
+    }
+    fn strip(&self, output: &str) -> String {
+        let [emphasize_left, emphasize_right] = &self.emphasize;
+        let [de_emphasize_left, de_emphasize_right] = &self.de_emphasize;
+        let [secondary_left, secondary_right] = &self.secondary;
+        // Splits between the parts of words, after the emphasized part, and before and after the secondary emphasized part
+        let splits = [
+            format!("{emphasize_right}{de_emphasize_left}"),
+            format!("{de_emphasize_right}{secondary_left}"),
+            format!("{secondary_right}{de_emphasize_left}"),
+        ];
+        // Joiners around the splits first, then the longest wrappers, so that one containing another is removed whole
+        let joined = splits.map(|split| format!("\u{200D}{split}\u{200D}"));
+        let mut wrappers: Vec<_> = [emphasize_left, emphasize_right, de_emphasize_left, de_emphasize_right, secondary_left, secondary_right]
+            .into_iter()
+            .filter(|wrapper| !wrapper.is_empty())
+            .collect();
+        wrappers.sort_unstable_by_key(|wrapper| std::cmp::Reverse(wrapper.len()));
+        let pattern = joined.iter().chain(wrappers).map(|pattern| regex::escape(pattern)).collect::<Vec<_>>().join("|");
+        Regex::new(&pattern).expect("Escaped patterns should be valid").replace_all(output, "").into_owned()
+    }
+}
+
+/// Renders the emphasized part of words with graded intensity, by splitting it into as many segments as there are levels (at most one per letter), each wrapped with the strings of its level. Longer segments come first. The secondary emphasis of long words is wrapped with the strings of the least intense level.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{render::Gradient, BioReader};
+/// let reader = BioReader::new().renderer(Gradient::html("fixation"));
+/// assert_eq!(
+///     reader.bio_read_text("bionic & reading").unwrap(),
+///     "<span class=\"fixation-1\">b</span><span class=\"fixation-2\">i</span><span class=\"fixation-3\">o</span>nic &amp; <span class=\"fixation-1\">re</span><span class=\"fixation-2\">a</span><span class=\"fixation-3\">d</span>ing"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Gradient {
+    /// The strings to be wrapped around each segment of the emphasized part of a word, from the most intense to the least.
+    pub levels: Vec<[String; 2]>,
+    /// The strings to be wrapped around the de-emphasized part of a word.
+    pub de_emphasize: [String; 2],
+    /// Whether to escape text as HTML.
+    pub escape_html: bool,
+}
+
+impl Gradient {
+    /// Create a gradient with ANSI escape codes: bold, then regular for the emphasized part, and dimmed for the de-emphasized part.
+    pub fn ansi() -> Self {
+        let bold = Style::new().bold();
+        let dimmed = Style::new().dimmed();
+        Self {
+            levels: vec![
+                [format!("{bold}"), format!("{bold:#}")],
+                [String::new(), String::new()],
+            ],
+            de_emphasize: [format!("{dimmed}"), format!("{dimmed:#}")],
+            escape_html: false,
+        }
+    }
+    /// Create a gradient with HTML `<span>` tags with classes `{prefix}-1`, `{prefix}-2` and `{prefix}-3`, from the most intense to the least, leaving the de-emphasized part as-is. Style them with CSS, like `opacity` or `font-weight`.
+    pub fn html(prefix: &str) -> Self {
+        let mut escaped = Vec::new();
+        write_escaped_html(&mut escaped, prefix, true).expect("Writing to a Vec never fails");
+        let prefix = String::from_utf8(escaped).expect("Escaped HTML is valid UTF-8");
+        Self {
+            levels: (1..=3)
+                .map(|i| [format!("<span class=\"{prefix}-{i}\">"), String::from("</span>")])
+                .collect(),
+            de_emphasize: Default::default(),
+            escape_html: true,
+        }
+    }
+    /// Write `text`, escaping it if needed.
+    fn write_text(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        if self.escape_html {
+            write_escaped_html(writer, text, false)
+        } else {
+            writer.write_all(text.as_bytes())
+        }
+    }
+    /// Write `text` wrapped with `wrapper`.
+    fn write_wrapped(&self, writer: &mut dyn Write, wrapper: &[String; 2], text: &str) -> Result<()> {
+        let [left, right] = wrapper;
+        writer.write_all(left.as_bytes())?;
+        self.write_text(writer, text)?;
+        writer.write_all(right.as_bytes())
+    }
+}
+
+impl EmphasisRenderer for Gradient {
+    fn emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        let Some(first) = self.levels.first() else {
+            return self.write_text(writer, text);
+        };
+        if self.levels.len() == 1 {
+            return self.write_wrapped(writer, first, text);
+        }
+        // Split before letters only, keeping combining marks attached
+        let starts: Vec<usize> = text
+            .char_indices()
+            .filter(|(_, c)| !crate::is_mark(*c))
+            .map(|(i, _)| i)
+            .collect();
+        let segments = self.levels.len().min(starts.len()).max(1);
+        let mut start = 0;
+        for (level, wrapper) in self.levels.iter().take(segments).enumerate() {
+            // Distribute letters evenly, with longer segments first
+            let letters = ((level + 1) * starts.len()).div_ceil(segments);
+            let end = starts.get(letters).copied().unwrap_or(text.len());
+            self.write_wrapped(writer, wrapper, &text[start..end])?;
+            start = end;
+        }
+        Ok(())
+    }
+    fn de_emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        self.write_wrapped(writer, &self.de_emphasize, text)
+    }
+    fn secondary(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        match self.levels.last() {
+            Some(last) => self.write_wrapped(writer, last, text),
+            None => self.write_text(writer, text),
+        }
+    }
+    fn plain(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        self.write_text(writer, text)
+    }
+}
+
+/// The format of the output, naming a built-in renderer.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{BioReader, OutputFormat};
+/// let format: OutputFormat = "bbcode".parse().unwrap();
+/// let reader = BioReader::new().renderer(format.renderer());
+/// assert_eq!(reader.bio_read_text("hello").unwrap(), "[b]hel[/b]lo");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum OutputFormat {
+    /// The [`Ansi`] renderer.
+    #[default]
+    Ansi,
+    /// The [`Html`] renderer.
+    Html,
+    /// The [`Markdown`] renderer.
+    #[cfg_attr(feature = "serde", serde(alias = "md"))]
+    Markdown,
+    /// The [`BBCode`] renderer.
+    BBCode,
+    /// The [`Latex`] renderer.
+    #[cfg_attr(feature = "serde", serde(alias = "tex"))]
+    Latex,
+    /// The [`Typst`] renderer.
+    #[cfg_attr(feature = "serde", serde(alias = "typ"))]
+    Typst,
+    /// The [`Rst`] renderer.
+    #[cfg_attr(feature = "serde", serde(alias = "rest", alias = "restructuredtext"))]
+    Rst,
+    /// The [`Org`] renderer.
+    Org,
+    /// The [`Rtf`] renderer.
+    Rtf,
+    /// The [`Plain`] renderer.
+    Plain,
+    /// The [`Json`] renderer.
+    Json,
+}
+
+impl OutputFormat {
+    /// Get the default renderer for this format.
+    pub fn renderer(self) -> Box<dyn EmphasisRenderer + Send + Sync> {
+        match self {
+            Self::Ansi => Box::new(Ansi::default()),
+            Self::Html => Box::new(Html::default()),
+            Self::Markdown => Box::new(Markdown),
+            Self::BBCode => Box::new(BBCode),
+            Self::Latex => Box::new(Latex),
+            Self::Typst => Box::new(Typst),
+            Self::Rst => Box::new(Rst),
+            Self::Org => Box::new(Org),
+            Self::Rtf => Box::new(Rtf),
+            Self::Plain => Box::new(Plain),
+            Self::Json => Box::new(Json),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "ansi" => Ok(Self::Ansi),
+            "html" => Ok(Self::Html),
+            "markdown" | "md" => Ok(Self::Markdown),
+            "bbcode" => Ok(Self::BBCode),
+            "latex" | "tex" => Ok(Self::Latex),
+            "typst" | "typ" => Ok(Self::Typst),
+            "rst" | "rest" | "restructuredtext" => Ok(Self::Rst),
+            "org" => Ok(Self::Org),
+            "rtf" => Ok(Self::Rtf),
+            "plain" => Ok(Self::Plain),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Unknown output format: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Ansi => "ansi",
+            Self::Html => "html",
+            Self::Markdown => "markdown",
+            Self::BBCode => "bbcode",
+            Self::Latex => "latex",
+            Self::Typst => "typst",
+            Self::Rst => "rst",
+            Self::Org => "org",
+            Self::Rtf => "rtf",
+            Self::Plain => "plain",
+            Self::Json => "json",
+        })
+    }
+}
@@ -0,0 +1,178 @@
+//! The speed-reading mode of `br --rsvp`, showing one bio-read word at a time in place (rapid serial visual presentation). Requires the `tui` feature.
+
+use crate::{
+    cli::{self, Kind},
+    tui,
+};
+use bio_read::{BioReader, Config, Token};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    DefaultTerminal, Frame,
+};
+use std::{
+    io::Result,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// How many words per minute the speed keys change the speed by.
+const WPM_STEP: u32 = 25;
+
+/// The slowest and fastest speeds the speed keys go to, in words per minute.
+const WPM_RANGE: (u32, u32) = (50, 1500);
+
+/// Show the words of the file at `path` of `kind` one at a time at `wpm` words per minute, bio-read with `config`, until the user quits. PDF documents have their text extracted first.
+pub fn run(config: &Config, kind: Kind, path: &Path, wpm: u32) -> Result<()> {
+    let title = path.file_name().unwrap_or(path.as_os_str()).to_string_lossy().into_owned();
+    run_text(config, kind, title, cli::read_text(path)?, wpm)
+}
+
+/// Show the words of `text` of `kind` titled `title`, like [`run`] does with a file.
+pub fn run_text(config: &Config, kind: Kind, title: String, text: String, wpm: u32) -> Result<()> {
+    let (format, language) = kind;
+    let config = Config {
+        format: Some(format),
+        language: Some(language),
+        ..config.clone()
+    };
+    let reader = BioReader::from_config(&config)?;
+    let theme = config.theme.as_ref().and_then(|theme| theme.preset).unwrap_or_default();
+    let (emphasized, de_emphasized, _) = tui::styles(theme);
+    let mut app = App::new(title, words(&reader, &text), wpm, emphasized, de_emphasized);
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+/// Split `text` into the words to show, bio-read with `reader`, each made of parts that are emphasized or not. Punctuation sticks to the words next to it, and so do the other parts of compound words.
+fn words(reader: &BioReader, text: &str) -> Vec<Vec<(String, bool)>> {
+    let mut words = Vec::new();
+    let mut word = Vec::new();
+    reader.bio_read_visit(text, |token| match token {
+        Token::Word { emphasized, rest } => {
+            word.push((emphasized.to_owned(), true));
+            word.push((rest.to_owned(), false));
+        }
+        Token::Separator(separator) => {
+            for (i, part) in separator.split(char::is_whitespace).enumerate() {
+                if i > 0 && !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+                if !part.is_empty() {
+                    word.push((part.to_owned(), false));
+                }
+            }
+        }
+    });
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// The state of the speed-reading mode.
+struct App {
+    /// The name of the file, shown in the status bar.
+    title: String,
+    /// The words of the text, as made by [`words`].
+    words: Vec<Vec<(String, bool)>>,
+    /// The index of the word shown.
+    index: usize,
+    /// The speed set with the speed keys, in words per minute.
+    wpm: u32,
+    /// Whether the words are paused with `Space`.
+    paused: bool,
+    /// The style of emphasized parts.
+    emphasized: Style,
+    /// The style of de-emphasized parts.
+    de_emphasized: Style,
+}
+
+impl App {
+    /// Create the app, showing `words` titled `title` at `wpm` words per minute.
+    fn new(title: String, words: Vec<Vec<(String, bool)>>, wpm: u32, emphasized: Style, de_emphasized: Style) -> Self {
+        Self {
+            title,
+            words,
+            index: 0,
+            wpm: wpm.clamp(WPM_RANGE.0, WPM_RANGE.1),
+            paused: false,
+            emphasized,
+            de_emphasized,
+        }
+    }
+    /// Draw frames, moving on to the next word whenever it is due, and handle key presses until the user quits.
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        let mut due = Instant::now() + self.duration();
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            if self.paused || event::poll(due.saturating_duration_since(Instant::now()))? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press && !self.handle_key(key) {
+                        return Ok(());
+                    }
+                    // Each word is shown for its whole time after a key, like after a pause or a skip
+                    due = Instant::now() + self.duration();
+                }
+            } else if self.index + 1 < self.words.len() {
+                self.index += 1;
+                due += self.duration();
+            } else {
+                // The last word stays on screen until the user quits or goes back
+                self.paused = true;
+            }
+        }
+    }
+    /// Handle `key`, returning whether to keep running.
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Char(' ' | 'p') => self.paused = !self.paused,
+            KeyCode::Up | KeyCode::Char('k' | '+') => self.wpm = (self.wpm + WPM_STEP).min(WPM_RANGE.1),
+            KeyCode::Down | KeyCode::Char('j' | '-') => self.wpm = self.wpm.saturating_sub(WPM_STEP).max(WPM_RANGE.0),
+            KeyCode::Left | KeyCode::Char('h') => self.index = self.index.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => self.index = (self.index + 1).min(self.words.len().saturating_sub(1)),
+            KeyCode::Home | KeyCode::Char('g') => self.index = 0,
+            _ => {}
+        }
+        true
+    }
+    /// Get how long to show the current word for. Words ending a sentence are shown twice as long, to take in its end, as readers pause there too.
+    fn duration(&self) -> Duration {
+        let duration = Duration::from_secs(60) / self.wpm;
+        let last = self.words.get(self.index).and_then(|word| word.last()).map_or("", |(part, _)| part.as_str());
+        if last.trim_end_matches(['"', '\'', ')', '»', '”', '’']).ends_with(['.', '!', '?', ':', ';']) {
+            duration * 2
+        } else {
+            duration
+        }
+    }
+    /// Draw the current word in the middle of the screen, and the status bar below it.
+    fn draw(&self, frame: &mut Frame) {
+        let [_, body, _, status] = Layout::vertical([Constraint::Fill(1), Constraint::Length(1), Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let spans: Vec<_> = self
+            .words
+            .get(self.index)
+            .into_iter()
+            .flatten()
+            .map(|(part, emphasized)| Span::styled(part.as_str(), if *emphasized { self.emphasized } else { self.de_emphasized }))
+            .collect();
+        frame.render_widget(Paragraph::new(Line::from(spans).centered()), body);
+
+        let state = if self.paused { "paused" } else { "playing" };
+        let status_line = format!(
+            " {} | word {} of {} | {} wpm | {state} | q quit, space pause, up/down speed, left/right skip",
+            self.title,
+            (self.index + 1).min(self.words.len()),
+            self.words.len(),
+            self.wpm
+        );
+        frame.render_widget(Paragraph::new(status_line).style(Style::new().add_modifier(Modifier::REVERSED)), status);
+    }
+}
@@ -0,0 +1,576 @@
+//! Word segmentation, deciding which characters make up words.
+
+use regex::Regex;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr, sync::LazyLock};
+
+/// Matches URLs, emails and file paths, which are preserved with [`BioReader::preserve_urls`](crate::BioReader::preserve_urls). Trailing punctuation is left out, and paths must start a run of non-whitespace characters, possibly after an opening bracket or quote.
+pub(crate) static PRESERVED: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        // URLs, with a scheme or starting with "www."
+        r#"(?:[a-zA-Z][a-zA-Z0-9+.-]*://|www\.)\S*[^\s.,;:!?'")\]}>]"#,
+        // Emails
+        r"|[\w.+-]+@[\w-]+(?:\.[\w-]+)+",
+        // Unix paths, absolute or relative to the home or current directory
+        r#"|(?:^|[(\[{"'<])(?:~|\.\.?)?(?:/(?:[\w.~-]*[\w~-]|\.\.?))+/?"#,
+        // Windows paths
+        r#"|(?:^|[(\[{"'<])[a-zA-Z]:\\(?:[\w.~-]*[\w~-]\\?)*"#,
+    ))
+    .unwrap()
+});
+
+/// Decides which characters, other than letters, make up words. See [`BioReader::word_chars`](crate::BioReader::word_chars).
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{segment::WordChars, BioReader};
+/// let reader = BioReader::new()
+///     .emphasize(String::from("<em>"), String::from("</em>"))
+///     .de_emphasize(String::from(""), String::from(""));
+/// assert_eq!(reader.bio_read_text("don't").unwrap(), "<em>do</em>n't");
+/// let reader = reader.word_chars(WordChars::none());
+/// assert_eq!(reader.bio_read_text("don't").unwrap(), "<em>d</em>on'<em>t</em>");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WordChars {
+    /// Characters kept inside a word when between two letters, like the apostrophe in "don't" or the hyphen in "well-known". They don't count towards the length of the word. Defaults to [`WordChars::DEFAULT_JOINERS`].
+    pub joiners: Vec<char>,
+}
+
+impl WordChars {
+    /// The default joiners: apostrophes (`'` and `’`) and hyphens (`-`, `‐` and `‑`).
+    pub const DEFAULT_JOINERS: [char; 5] = ['\'', '\u{2019}', '-', '\u{2010}', '\u{2011}'];
+
+    /// Words made up of letters only, splitting words at apostrophes and hyphens.
+    pub fn none() -> Self {
+        Self { joiners: Vec::new() }
+    }
+    /// Whether `c` is kept inside a word when between two letters.
+    pub fn is_joiner(&self, c: char) -> bool {
+        self.joiners.contains(&c)
+    }
+}
+
+impl Default for WordChars {
+    fn default() -> Self {
+        Self {
+            joiners: Self::DEFAULT_JOINERS.to_vec(),
+        }
+    }
+}
+
+/// How digits are handled, deciding how tokens like "IPv6", "2023" and "x86" are read. See [`BioReader::token_policy`](crate::BioReader::token_policy).
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{segment::TokenPolicy, BioReader};
+/// let reader = BioReader::new()
+///     .emphasize(String::from("<em>"), String::from("</em>"))
+///     .de_emphasize(String::from(""), String::from(""));
+/// assert_eq!(reader.bio_read_text("IPv6 in 2023").unwrap(), "<em>I</em>Pv6 <em>i</em>n 2023");
+/// let reader = reader.token_policy(TokenPolicy::IncludeDigits);
+/// assert_eq!(reader.bio_read_text("IPv6 in 2023").unwrap(), "<em>IP</em>v6 <em>i</em>n <em>20</em>23");
+/// let reader = reader.token_policy(TokenPolicy::SkipNumeric);
+/// assert_eq!(reader.bio_read_text("IPv6 in 2023").unwrap(), "IPv6 <em>i</em>n 2023");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum TokenPolicy {
+    /// Digits are not part of words, so words are split at them, like "IPv6" into "IPv" and "6", and numbers are left as they are.
+    #[default]
+    SplitAtDigits,
+    /// Digits are part of words and count towards their length, like letters, so "IPv6" and "2023" are emphasized as a whole.
+    IncludeDigits,
+    /// Digits are part of words, and words with digits in them, like "IPv6" and "2023", are left un-emphasized. Like excluded words, they don't count towards the [`BioReader::saccade`](crate::BioReader::saccade).
+    SkipNumeric,
+}
+
+impl FromStr for TokenPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "split-at-digits" => Ok(Self::SplitAtDigits),
+            "include-digits" => Ok(Self::IncludeDigits),
+            "skip-numeric" => Ok(Self::SkipNumeric),
+            _ => Err(format!("Unknown token policy: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for TokenPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::SplitAtDigits => "split-at-digits",
+            Self::IncludeDigits => "include-digits",
+            Self::SkipNumeric => "skip-numeric",
+        })
+    }
+}
+
+/// How compound words joined by hyphens, like "state-of-the-art", are emphasized. See [`BioReader::compound_policy`](crate::BioReader::compound_policy).
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{segment::CompoundPolicy, BioReader};
+/// let reader = BioReader::new()
+///     .emphasize(String::from("<em>"), String::from("</em>"))
+///     .de_emphasize(String::from(""), String::from(""));
+/// assert_eq!(reader.bio_read_text("state-of-the-art").unwrap(), "<em>state-of-</em>the-art");
+/// let reader = reader.compound_policy(CompoundPolicy::EachPart);
+/// assert_eq!(reader.bio_read_text("state-of-the-art").unwrap(), "<em>sta</em>te-<em>o</em>f-<em>t</em>he-<em>a</em>rt");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum CompoundPolicy {
+    /// The compound is emphasized as a whole, with the letters of all its parts counting towards its length.
+    #[default]
+    Whole,
+    /// Each part of the compound is emphasized on its own, as if it were a word. The compound still counts as one word, for the [`BioReader::saccade`](crate::BioReader::saccade) and excluded words.
+    EachPart,
+}
+
+impl FromStr for CompoundPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "whole" => Ok(Self::Whole),
+            "each-part" => Ok(Self::EachPart),
+            _ => Err(format!("Unknown compound policy: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for CompoundPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Whole => "whole",
+            Self::EachPart => "each-part",
+        })
+    }
+}
+
+/// What happens to words longer than the maximum length, which are not buffered whole, so that memory stays bounded. See [`BioReader::long_word_policy`](crate::BioReader::long_word_policy).
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{segment::LongWordPolicy, BioReader};
+/// let reader = BioReader::new()
+///     .emphasize(String::from("<em>"), String::from("</em>"))
+///     .de_emphasize(String::from(""), String::from(""))
+///     .max_word_len(8);
+/// assert_eq!(reader.bio_read_text("an extraordinarily long word").unwrap(), "<em>a</em>n extraordinarily <em>lo</em>ng <em>wo</em>rd");
+/// let reader = reader.long_word_policy(LongWordPolicy::Split);
+/// assert_eq!(reader.bio_read_text("an extraordinarily long word").unwrap(), "<em>a</em>n <em>extr</em>aord<em>inar</em>ily <em>lo</em>ng <em>wo</em>rd");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum LongWordPolicy {
+    /// Long words are written as they are, like text between words, and don't count towards the [`BioReader::saccade`](crate::BioReader::saccade). They are unlikely to be words at all, like encoded data.
+    #[default]
+    Plain,
+    /// Long words are split into pieces of the maximum length, each emphasized as if it were a word.
+    Split,
+}
+
+impl FromStr for LongWordPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "plain" => Ok(Self::Plain),
+            "split" => Ok(Self::Split),
+            _ => Err(format!("Unknown long word policy: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for LongWordPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Plain => "plain",
+            Self::Split => "split",
+        })
+    }
+}
+
+/// Where the text between words is relative to sentence boundaries, for [`BioReader::sentence_boost`](crate::BioReader::sentence_boost).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Sentence {
+    /// No word has been written since the sentence started.
+    Start,
+    /// Inside a sentence.
+    Middle,
+    /// Right after punctuation ending the sentence, which only ends it if followed by whitespace.
+    End,
+}
+
+impl Sentence {
+    /// Advance past `c`, a character between words.
+    pub(crate) fn advance(self, c: char) -> Self {
+        match (self, c) {
+            (_, '。' | '！' | '？') => Self::Start,
+            (Self::Start, _) => Self::Start,
+            (_, '.' | '!' | '?' | '…' | '‼' | '⁇' | '⁈' | '⁉') => Self::End,
+            (Self::End, _) if c.is_whitespace() => Self::Start,
+            // Closing quotes and brackets, like in "(See below.) Then"
+            (Self::End, '"' | '\'' | ')' | ']' | '}' | '”' | '’' | '»' | '›') => Self::End,
+            (Self::End, _) => Self::Middle,
+            (Self::Middle, _) => Self::Middle,
+        }
+    }
+}
+
+/// Where the text is relative to the terminal escape sequences of operating system commands, like OSC 8 hyperlinks (`ESC ] 8 ; ; URI ST`), which are passed through untouched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Osc {
+    /// Outside of sequences.
+    Text,
+    /// Right after an escape character, which starts a sequence if followed by `]`.
+    Escape,
+    /// Inside a sequence, which ends with a bell character or a string terminator (`ESC \`).
+    Command,
+    /// Right after an escape character inside a sequence, which ends it if followed by `\`.
+    CommandEscape,
+}
+
+impl Osc {
+    /// Get the length of the start of `text` that is part of a sequence, which is all of it if the sequence continues past it, and the state after it. Sequences are cut short at line breaks, so that a stray one doesn't take the rest of the text with it.
+    pub(crate) fn sequence_len(self, text: &str) -> (usize, Self) {
+        let mut osc = self;
+        for (i, &byte) in text.as_bytes().iter().enumerate() {
+            osc = match (osc, byte) {
+                (Self::Text, b'\x1b') => Self::Escape,
+                (Self::Text, _) => return (i, Self::Text),
+                (Self::Escape, b']') => Self::Command,
+                // A lone escape character
+                (Self::Escape, _) => return (i, Self::Text),
+                (Self::Command | Self::CommandEscape, b'\n') => return (i, Self::Text),
+                (Self::Command, b'\x07') | (Self::CommandEscape, b'\\') => return (i + 1, Self::Text),
+                (Self::Command, b'\x1b') => Self::CommandEscape,
+                (Self::Command | Self::CommandEscape, _) => Self::Command,
+            };
+        }
+        (text.len(), osc)
+    }
+}
+
+/// Split `text` into chunks of more than `size` bytes, except for the last one, each ending right after a blank line, so that each chunk after the first starts a paragraph.
+#[cfg(feature = "rayon")]
+pub(crate) fn paragraph_chunks(text: &str, size: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = text;
+    while rest.len() > size {
+        let Some(end) = blank_line_end(rest.as_bytes(), size) else {
+            break;
+        };
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    if !rest.is_empty() || chunks.is_empty() {
+        chunks.push(rest);
+    }
+    chunks
+}
+
+/// Find the end of the first blank line after byte `from` in `bytes`, that is, right after a line break following another line break and nothing but whitespace.
+#[cfg(feature = "rayon")]
+fn blank_line_end(bytes: &[u8], from: usize) -> Option<usize> {
+    // Whether there's been a line break, followed by nothing but whitespace
+    let mut after_break = false;
+    for (i, &byte) in bytes.iter().enumerate().skip(from) {
+        match byte {
+            b'\n' if after_break => return Some(i + 1),
+            b'\n' => after_break = true,
+            b' ' | b'\t' | b'\r' => {}
+            _ => after_break = false,
+        }
+    }
+    None
+}
+
+/// Whether `c` is a hyphen joining the parts of a compound word.
+pub(crate) fn is_hyphen(c: char) -> bool {
+    matches!(c, '-' | '\u{2010}' | '\u{2011}')
+}
+
+/// The language of the text, determining how words are segmented and matched. See [`BioReader::language`](crate::BioReader::language).
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{segment::Language, BioReader};
+/// let reader = BioReader::new()
+///     .emphasize(String::from("<em>"), String::from("</em>"))
+///     .de_emphasize(String::from(""), String::from(""))
+///     .language("fr".parse().unwrap());
+/// assert_eq!(reader.bio_read_text("l'homme").unwrap(), "<em>l</em>'<em>hom</em>me");
+/// assert_eq!(Language::Turkish.lowercase("IŞIK"), "ışık");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Language {
+    /// English, keeping apostrophes and hyphens inside words, like "don't" and "well-known".
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "en"))]
+    English,
+    /// German, keeping apostrophes and hyphens inside words, like "geht's".
+    #[cfg_attr(feature = "serde", serde(rename = "de"))]
+    German,
+    /// Dutch, keeping apostrophes and hyphens inside words, like "zo'n".
+    #[cfg_attr(feature = "serde", serde(rename = "nl"))]
+    Dutch,
+    /// Spanish, keeping apostrophes and hyphens inside words.
+    #[cfg_attr(feature = "serde", serde(rename = "es"))]
+    Spanish,
+    /// French, splitting elisions like "l'homme" at the apostrophe, and keeping hyphens inside words.
+    #[cfg_attr(feature = "serde", serde(rename = "fr"))]
+    French,
+    /// Italian, splitting elisions like "dell'arte" at the apostrophe, and keeping hyphens inside words.
+    #[cfg_attr(feature = "serde", serde(rename = "it"))]
+    Italian,
+    /// Turkish, splitting suffixes like "Ankara'da" at the apostrophe, keeping hyphens inside words, and lowercasing "I" to dotless "ı" and "İ" to "i".
+    #[cfg_attr(feature = "serde", serde(rename = "tr"))]
+    Turkish,
+}
+
+impl Language {
+    /// All supported languages.
+    pub const ALL: [Self; 7] = [Self::English, Self::German, Self::Dutch, Self::Spanish, Self::French, Self::Italian, Self::Turkish];
+
+    /// Get the ISO 639-1 code of the language, like `en`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::German => "de",
+            Self::Dutch => "nl",
+            Self::Spanish => "es",
+            Self::French => "fr",
+            Self::Italian => "it",
+            Self::Turkish => "tr",
+        }
+    }
+    /// Get the characters, other than letters, that make up words in this language.
+    pub fn word_chars(self) -> WordChars {
+        match self {
+            Self::English | Self::German | Self::Dutch | Self::Spanish => WordChars::default(),
+            Self::French | Self::Italian | Self::Turkish => WordChars {
+                joiners: WordChars::DEFAULT_JOINERS.into_iter().filter(|c| !matches!(c, '\'' | '\u{2019}')).collect(),
+            },
+        }
+    }
+    /// Lowercase `word` following the casing rules of this language, for matching words case-insensitively.
+    pub fn lowercase(self, word: &str) -> String {
+        match self {
+            Self::Turkish => word.replace('I', "ı").replace('İ', "i").to_lowercase(),
+            _ => word.to_lowercase(),
+        }
+    }
+    /// Lowercase `word` like [`Language::lowercase`] into `buffer`, replacing its contents but reusing its allocation.
+    pub(crate) fn lowercase_into(self, word: &str, buffer: &mut String) {
+        buffer.clear();
+        if word.contains('Σ') {
+            // Whether sigma is final depends on the letters around it, which only `str::to_lowercase` handles
+            buffer.push_str(&self.lowercase(word));
+            return;
+        }
+        for c in word.chars() {
+            match (self, c) {
+                (Self::Turkish, 'I') => buffer.push('ı'),
+                (Self::Turkish, 'İ') => buffer.push('i'),
+                _ => buffer.extend(c.to_lowercase()),
+            }
+        }
+    }
+    /// Detect the language of `text`, if it is reliably one of the supported languages. Requires the `lang-detect` feature.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bio_read::segment::Language;
+    /// assert_eq!(Language::detect("Le renard brun rapide saute par-dessus le chien paresseux."), Some(Language::French));
+    /// ```
+    #[cfg(feature = "lang-detect")]
+    pub fn detect(text: &str) -> Option<Self> {
+        let info = whatlang::detect(text).filter(whatlang::Info::is_reliable)?;
+        match info.lang() {
+            whatlang::Lang::Eng => Some(Self::English),
+            whatlang::Lang::Deu => Some(Self::German),
+            whatlang::Lang::Nld => Some(Self::Dutch),
+            whatlang::Lang::Spa => Some(Self::Spanish),
+            whatlang::Lang::Fra => Some(Self::French),
+            whatlang::Lang::Ita => Some(Self::Italian),
+            whatlang::Lang::Tur => Some(Self::Turkish),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_ascii_lowercase();
+        Self::ALL.into_iter().find(|language| language.code() == s).ok_or_else(|| format!("Unknown language: {s}"))
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// How to handle runs of Chinese and Japanese characters, which aren't separated by spaces. See [`BioReader::cjk`](crate::BioReader::cjk).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum Cjk {
+    /// Pass them through untouched, rather than emphasizing a whole paragraph as one word.
+    #[default]
+    PassThrough,
+    /// Split them into words with a Chinese dictionary, emphasizing each word. Requires the `cjk` feature.
+    #[cfg(feature = "cjk")]
+    Segment,
+}
+
+/// Whether `c` is a Chinese or Japanese character, i.e. a Han ideograph, kana or an iteration mark. Hangul isn't included, since Korean separates words with spaces.
+pub fn is_cjk(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3005}'..='\u{3007}' // Iteration mark, closing mark and ideographic zero
+            | '\u{3021}'..='\u{3029}' // Hangzhou numerals
+            | '\u{3031}'..='\u{3035}' // Kana repeat marks
+            | '\u{303B}' // Vertical ideographic iteration mark
+            | '\u{3041}'..='\u{309F}' // Hiragana
+            | '\u{30A1}'..='\u{30FF}' // Katakana, including the prolonged sound mark
+            | '\u{31F0}'..='\u{31FF}' // Katakana phonetic extensions
+            | '\u{3400}'..='\u{4DBF}' // CJK unified ideographs extension A
+            | '\u{4E00}'..='\u{9FFF}' // CJK unified ideographs
+            | '\u{F900}'..='\u{FAFF}' // CJK compatibility ideographs
+            | '\u{FF66}'..='\u{FF9F}' // Halfwidth katakana
+            | '\u{1B000}'..='\u{1B16F}' // Kana supplement and extensions
+            | '\u{20000}'..='\u{3FFFF}' // Supplementary and tertiary ideographic planes
+    )
+}
+
+/// Whether `c` is attached to the letter before it without counting as a letter, like the Arabic tatweel, zero-width (non-)joiners, bidi marks and emoji skin tone modifiers, which shouldn't split a word.
+pub(crate) fn is_attached(c: char) -> bool {
+    matches!(c, '\u{0640}' | '\u{200C}'..='\u{200F}' | '\u{061C}' | '\u{1F3FB}'..='\u{1F3FF}')
+}
+
+/// Whether `c` is an Arabic letter connecting to the letter after it, i.e. dual-joining or the tatweel.
+pub(crate) fn joins_next(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0620}'
+            | '\u{0626}'
+            | '\u{0628}'
+            | '\u{062A}'..='\u{062E}'
+            | '\u{0633}'..='\u{063F}'
+            | '\u{0640}'..='\u{0647}'
+            | '\u{0649}'..='\u{064A}'
+            | '\u{066E}'..='\u{066F}'
+            | '\u{0678}'..='\u{0687}'
+            | '\u{069A}'..='\u{06BF}'
+            | '\u{06C1}'..='\u{06C2}'
+            | '\u{06CC}'
+            | '\u{06CE}'
+            | '\u{06D0}'..='\u{06D1}'
+            | '\u{06FA}'..='\u{06FC}'
+            | '\u{06FF}'
+            | '\u{0750}'..='\u{0758}'
+            | '\u{075C}'..='\u{076A}'
+            | '\u{076D}'..='\u{0770}'
+            | '\u{0772}'
+            | '\u{0775}'..='\u{0777}'
+            | '\u{077A}'..='\u{077F}'
+    )
+}
+
+/// Whether `c` is an Arabic letter connecting to the letter before it, i.e. dual-joining, right-joining or the tatweel.
+pub(crate) fn joins_previous(c: char) -> bool {
+    joins_next(c)
+        || matches!(
+            c,
+            '\u{0622}'..='\u{0625}'
+                | '\u{0627}'
+                | '\u{0629}'
+                | '\u{062F}'..='\u{0632}'
+                | '\u{0648}'
+                | '\u{0671}'..='\u{0673}'
+                | '\u{0675}'..='\u{0677}'
+                | '\u{0688}'..='\u{0699}'
+                | '\u{06C0}'
+                | '\u{06C3}'..='\u{06CB}'
+                | '\u{06CD}'
+                | '\u{06CF}'
+                | '\u{06D2}'..='\u{06D3}'
+                | '\u{06D5}'
+                | '\u{06EE}'..='\u{06EF}'
+                | '\u{0759}'..='\u{075B}'
+                | '\u{076B}'..='\u{076C}'
+                | '\u{0771}'
+                | '\u{0773}'..='\u{0774}'
+                | '\u{0778}'..='\u{0779}'
+        )
+}
+
+/// The dictionary-based segmenter for Chinese text, loaded on first use.
+#[cfg(feature = "cjk")]
+static JIEBA: LazyLock<jieba_rs::Jieba> = LazyLock::new(jieba_rs::Jieba::new);
+
+/// Split a run of Chinese or Japanese characters into words.
+#[cfg(feature = "cjk")]
+pub(crate) fn cut_cjk(text: &str) -> Vec<&str> {
+    JIEBA.cut(text, true)
+}
+
+/// The high bit of each byte in a word.
+const HIGH_BITS: u64 = 0x8080_8080_8080_8080;
+
+/// Get a mask with the high bit set for each byte in the little-endian `chunk` that is an ASCII letter, classifying all 8 bytes at once.
+fn ascii_letter_mask(chunk: u64) -> u64 {
+    // Fold to lowercase, which maps letters and only letters to 'a'..='z'
+    let folded = chunk | 0x2020_2020_2020_2020;
+    // Clear high bits, so that adding to a byte never carries into the next one
+    let low = folded & !HIGH_BITS;
+    let at_least_a = low.wrapping_add(0x1f1f_1f1f_1f1f_1f1f);
+    let after_z = low.wrapping_add(0x0505_0505_0505_0505);
+    at_least_a & !after_z & !folded & HIGH_BITS
+}
+
+/// Count the bytes at the start of `bytes` for which `mask` of each 8-byte chunk has the high bit set, and `matches` holds for each remaining byte.
+fn leading_len(bytes: &[u8], mask: impl Fn(u64) -> u64, matches: impl Fn(u8) -> bool) -> usize {
+    let mut chunks = bytes.chunks_exact(8);
+    let mut len = 0;
+    for chunk in &mut chunks {
+        let chunk = u64::from_le_bytes(chunk.try_into().expect("Chunks should be 8 bytes long"));
+        let mismatches = !mask(chunk) & HIGH_BITS;
+        if mismatches != 0 {
+            return len + mismatches.trailing_zeros() as usize / 8;
+        }
+        len += 8;
+    }
+    len + chunks.remainder().iter().take_while(|&&byte| matches(byte)).count()
+}
+
+/// Count the ASCII letters at the start of `bytes`, 8 bytes at a time.
+pub(crate) fn ascii_letters_len(bytes: &[u8]) -> usize {
+    leading_len(bytes, ascii_letter_mask, |byte| byte.is_ascii_alphabetic())
+}
+
+/// Count the ASCII characters other than letters at the start of `bytes`, 8 bytes at a time.
+pub(crate) fn ascii_non_letters_len(bytes: &[u8]) -> usize {
+    leading_len(bytes, |chunk| !ascii_letter_mask(chunk) & !chunk, |byte| byte.is_ascii() && !byte.is_ascii_alphabetic())
+}
@@ -0,0 +1,240 @@
+//! Spans of emphasized and de-emphasized text, for applying custom styling, and tokens of words and the text between them.
+
+use crate::{observer::Observer, EmphasisRenderer};
+use std::{
+    io::{Result, Write},
+    ops::Range,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+/// Whether a span is emphasized, de-emphasized or a secondary emphasis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SpanKind {
+    /// The emphasized part of a word.
+    Emphasized,
+    /// The de-emphasized part of a word.
+    DeEmphasized,
+    /// The secondary emphasized part in the middle of a long word. See [`BioReader::secondary_fixation`](crate::BioReader::secondary_fixation).
+    Secondary,
+}
+
+/// A part of a word, as a byte range into the input. See [`BioReader::bio_read_spans`](crate::BioReader::bio_read_spans).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EmphasisSpan {
+    /// The byte range of the span in the input.
+    pub range: Range<usize>,
+    /// Whether the span is emphasized, de-emphasized or a secondary emphasis.
+    pub kind: SpanKind,
+}
+
+/// A word, as byte and character ranges into the input. See [`BioReader::bio_read_words`](crate::BioReader::bio_read_words).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WordSpan {
+    /// The byte range of the word in the input.
+    pub range: Range<usize>,
+    /// The range of the word in the input, in characters, for languages that index strings by character.
+    pub chars: Range<usize>,
+    /// The byte range of the emphasized start of the word, which is empty if nothing is emphasized.
+    pub emphasized: Range<usize>,
+}
+
+/// A piece of bio-read input, borrowed from it, as yielded by [`BioReader::bio_read_segments`](crate::BioReader::bio_read_segments).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Segment<'a> {
+    /// The emphasized start of a word.
+    Emphasized(&'a str),
+    /// The rest of a word, which is de-emphasized.
+    Plain(&'a str),
+    /// The secondary emphasized part in the middle of a long word. See [`BioReader::secondary_fixation`](crate::BioReader::secondary_fixation).
+    Secondary(&'a str),
+    /// Text between words, like whitespace, punctuation, markup and words left un-emphasized.
+    Separator(&'a str),
+    /// Bytes of plain text input that aren't valid UTF-8, passed through untouched.
+    Invalid(&'a [u8]),
+}
+
+impl Segment<'_> {
+    /// Get the bytes of the input that the segment is made of.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::Emphasized(text) | Self::Plain(text) | Self::Secondary(text) | Self::Separator(text) => text.as_bytes(),
+            Self::Invalid(bytes) => bytes,
+        }
+    }
+    /// Whether the segment is empty.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.as_bytes().is_empty()
+    }
+}
+
+/// A renderer that writes text as-is and records spans, relying on a [`PositionWriter`] to track where it is in the output (which is the same as the input).
+#[derive(Debug, Default)]
+pub(crate) struct SpanRecorder {
+    /// How many bytes have been written.
+    pub(crate) position: AtomicUsize,
+    /// The spans recorded so far.
+    spans: Mutex<Vec<EmphasisSpan>>,
+}
+
+impl SpanRecorder {
+    /// Write `text`, recording it as a span of `kind`.
+    fn record(&self, writer: &mut dyn Write, text: &str, kind: SpanKind) -> Result<()> {
+        let start = self.position.load(Ordering::Relaxed);
+        writer.write_all(text.as_bytes())?;
+        let span = EmphasisSpan {
+            range: start..start + text.len(),
+            kind,
+        };
+        self.spans.lock().expect("Span recorder poisoned").push(span);
+        Ok(())
+    }
+    /// Get the recorded spans.
+    pub(crate) fn into_spans(self) -> Vec<EmphasisSpan> {
+        self.spans.into_inner().expect("Span recorder poisoned")
+    }
+}
+
+impl EmphasisRenderer for SpanRecorder {
+    fn emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        self.record(writer, text, SpanKind::Emphasized)
+    }
+    fn de_emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        self.record(writer, text, SpanKind::DeEmphasized)
+    }
+    fn secondary(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        self.record(writer, text, SpanKind::Secondary)
+    }
+}
+
+/// A writer that discards everything, only counting the bytes written.
+pub(crate) struct PositionWriter<'a>(pub(crate) &'a AtomicUsize);
+
+impl Write for PositionWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.0.fetch_add(buf.len(), Ordering::Relaxed);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A piece of bio-read text, as given to the visitor of [`BioReader::bio_read_visit`](crate::BioReader::bio_read_visit).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Token<'a> {
+    /// A word, split into its emphasized start and the rest, either of which may be empty.
+    Word {
+        /// The emphasized start of the word.
+        emphasized: &'a str,
+        /// The rest of the word, which is de-emphasized.
+        rest: &'a str,
+    },
+    /// Text between words, like whitespace, punctuation and markup.
+    Separator(&'a str),
+}
+
+/// A renderer that writes text as-is and records words, relying on a [`CollectingWriter`] to track where it is in the output. Words written without the renderer, which have nothing emphasized, are recorded by a [`WordObserver`].
+#[derive(Debug, Default)]
+pub(crate) struct WordRecorder {
+    /// How many bytes have been written.
+    pub(crate) position: AtomicUsize,
+    /// The byte ranges of the words recorded so far in the output, and where they are split.
+    words: Mutex<Vec<(Range<usize>, usize)>>,
+}
+
+impl WordRecorder {
+    /// Record the word at `range` in the output, split at `split`, unless it has been recorded already.
+    fn record(&self, range: Range<usize>, split: usize) {
+        let mut words = self.words.lock().expect("Word recorder poisoned");
+        if words.last().is_none_or(|(last, _)| last.end <= range.start) {
+            words.push((range, split));
+        }
+    }
+    /// Call `visitor` with the tokens making up `output`, the recorded words being in it.
+    pub(crate) fn visit(self, output: &str, mut visitor: impl FnMut(Token<'_>)) {
+        let mut last = 0;
+        for (range, split) in self.words.into_inner().expect("Word recorder poisoned") {
+            if range.start > last {
+                visitor(Token::Separator(&output[last..range.start]));
+            }
+            visitor(Token::Word {
+                emphasized: &output[range.start..split],
+                rest: &output[split..range.end],
+            });
+            last = range.end;
+        }
+        if last < output.len() {
+            visitor(Token::Separator(&output[last..]));
+        }
+    }
+}
+
+impl EmphasisRenderer for WordRecorder {
+    fn emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        writer.write_all(text.as_bytes())
+    }
+    fn de_emphasized(&self, writer: &mut dyn Write, text: &str) -> Result<()> {
+        writer.write_all(text.as_bytes())
+    }
+    fn word(&self, writer: &mut dyn Write, emphasized: &str, de_emphasized: &str) -> Result<()> {
+        let start = self.position.load(Ordering::Relaxed);
+        writer.write_all(emphasized.as_bytes())?;
+        writer.write_all(de_emphasized.as_bytes())?;
+        let split = start + emphasized.len();
+        self.record(start..split + de_emphasized.len(), split);
+        Ok(())
+    }
+    fn word_with_secondary(&self, writer: &mut dyn Write, emphasized: &str, de_emphasized: &str, secondary: &str, rest: &str) -> Result<()> {
+        // Tokens only tell the emphasized start apart from the rest
+        let start = self.position.load(Ordering::Relaxed);
+        for part in [emphasized, de_emphasized, secondary, rest] {
+            writer.write_all(part.as_bytes())?;
+        }
+        let split = start + emphasized.len();
+        self.record(start..split + de_emphasized.len() + secondary.len() + rest.len(), split);
+        Ok(())
+    }
+}
+
+/// An observer recording the words that are written as plain text, like those skipped by the saccade, to a [`WordRecorder`].
+pub(crate) struct WordObserver<'a>(pub(crate) &'a WordRecorder);
+
+impl Observer for WordObserver<'_> {
+    fn on_word(&mut self, word: &str, emphasized: usize) {
+        if emphasized == 0 {
+            // Words are reported right after they are written
+            let end = self.0.position.load(Ordering::Relaxed);
+            self.0.record(end - word.len()..end, end - word.len());
+        }
+    }
+}
+
+/// A writer that collects everything written, counting the bytes written like [`PositionWriter`].
+pub(crate) struct CollectingWriter<'a> {
+    /// The count of bytes written.
+    position: &'a AtomicUsize,
+    /// What has been written.
+    pub(crate) output: Vec<u8>,
+}
+
+impl<'a> CollectingWriter<'a> {
+    /// Create a writer counting the bytes written in `position`.
+    pub(crate) fn new(position: &'a AtomicUsize) -> Self {
+        Self { position, output: Vec::new() }
+    }
+}
+
+impl Write for CollectingWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.output.extend_from_slice(buf);
+        self.position.fetch_add(buf.len(), Ordering::Relaxed);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+//! Statistics of bio-read text, like how many words there are and how long they take to read. See [`BioReader::bio_read_with_stats`](crate::BioReader::bio_read_with_stats).
+
+use crate::{
+    observer::Observer,
+    reading_time::{ReadingEstimate, ReadingSpeed},
+};
+use std::{ops::AddAssign, time::Duration};
+
+/// Statistics of the words in bio-read text. Words skipped by the saccade or excluded are counted as words with nothing emphasized, while text passed through untouched, like URLs or code, is not counted.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::BioReader;
+/// use std::time::Duration;
+/// let reader = BioReader::new();
+/// let stats = reader.bio_read_with_stats("hello wonderful\n\nworld".as_bytes(), &mut Vec::new()).unwrap();
+/// assert_eq!(stats.words, 3);
+/// assert_eq!(stats.paragraphs, 2);
+/// assert_eq!(stats.characters, 19);
+/// assert_eq!(stats.emphasized_characters, 11);
+/// assert_eq!(stats.longest_word, "wonderful");
+/// assert_eq!(stats.reading_time(180), Duration::from_secs(1));
+/// ```
+///
+/// With the reading speeds of [`reading_time`](crate::reading_time), estimate the time bio-reading saves:
+///
+/// ```rust
+/// use bio_read::{reading_time::ReadingSpeed, segment::Language, BioReader};
+/// let stats = BioReader::new().bio_read_with_stats("word ".repeat(600).as_bytes(), &mut Vec::new()).unwrap();
+/// let estimate = stats.estimated_minutes(ReadingSpeed::new(200).bio_read(300));
+/// assert_eq!((estimate.original, estimate.bio_read, estimate.saved()), (3.0, 2.0, 1.0));
+/// assert!(stats.estimated_minutes(ReadingSpeed::for_language(Language::German)).original > 3.0);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BioReadStats {
+    /// How many words there are.
+    pub words: usize,
+    /// How many paragraphs with words there are, separated by blank lines.
+    pub paragraphs: usize,
+    /// How many characters there are in words, including combining marks.
+    pub characters: usize,
+    /// How many of the characters in words are emphasized.
+    pub emphasized_characters: usize,
+    /// The longest word by characters, the first one if there's a tie.
+    pub longest_word: String,
+}
+
+impl BioReadStats {
+    /// The fraction of the characters in words that are emphasized, in range \[0, 1\]. `0` if there are no words.
+    pub fn emphasized_ratio(&self) -> f64 {
+        if self.characters == 0 {
+            0.0
+        } else {
+            self.emphasized_characters as f64 / self.characters as f64
+        }
+    }
+    /// Estimate how long reading the words takes at `wpm` words per minute.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `wpm` is 0.
+    pub fn reading_time(&self, wpm: u32) -> Duration {
+        assert!(wpm > 0, "Words per minute should be positive");
+        Duration::from_secs_f64(self.words as f64 * 60.0 / f64::from(wpm))
+    }
+    /// Estimate how many minutes reading the words takes at `speed`, as is and bio-read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either speed is 0.
+    pub fn estimated_minutes(&self, speed: ReadingSpeed) -> ReadingEstimate {
+        speed.estimate(self.words)
+    }
+}
+
+impl Observer for BioReadStats {
+    fn on_word(&mut self, word: &str, emphasized: usize) {
+        let characters = word.chars().count();
+        self.words += 1;
+        self.characters += characters;
+        self.emphasized_characters += emphasized;
+        if characters > self.longest_word.chars().count() {
+            self.longest_word = word.to_string();
+        }
+    }
+    fn on_paragraph(&mut self) {
+        self.paragraphs += 1;
+    }
+}
+
+impl AddAssign for BioReadStats {
+    /// Add up the statistics of another piece of text, like another file.
+    fn add_assign(&mut self, other: Self) {
+        self.words += other.words;
+        self.paragraphs += other.paragraphs;
+        self.characters += other.characters;
+        self.emphasized_characters += other.emphasized_characters;
+        if other.longest_word.chars().count() > self.longest_word.chars().count() {
+            self.longest_word = other.longest_word;
+        }
+    }
+}
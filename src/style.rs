@@ -0,0 +1,71 @@
+//! Styling of individual words, overriding how much of them is emphasized or how they are rendered. See [`BioReader::word_style_hook`](crate::BioReader::word_style_hook).
+
+use crate::EmphasisRenderer;
+use std::{fmt, sync::Arc};
+
+/// A word about to be written, as given to the hook set with [`BioReader::word_style_hook`](crate::BioReader::word_style_hook).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WordInfo<'a> {
+    /// The word, whole, with compound words joined by hyphens given as one.
+    pub word: &'a str,
+    /// How many letters the word has, as counted for emphasis.
+    pub letters: usize,
+    /// Whether the word starts a sentence.
+    pub sentence_start: bool,
+    /// Whether the word would be left un-emphasized, as an excluded word, an acronym, a number or a word skipped by the saccade.
+    pub plain: bool,
+}
+
+/// How to write a word instead of the way the [`BioReader`](crate::BioReader) would, as returned by the hook set with [`BioReader::word_style_hook`](crate::BioReader::word_style_hook).
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{render::Html, style::StyleOverride};
+/// let style = StyleOverride::emphasize(2).with_renderer(Html::with_class("name"));
+/// assert_eq!(style.emphasized_letters, Some(2));
+/// assert!(!style.skip);
+/// ```
+#[derive(Clone, Default)]
+pub struct StyleOverride {
+    /// Write the word as it is, un-emphasized, like an excluded word, so that it doesn't count towards the saccade. Takes precedence over the other fields.
+    pub skip: bool,
+    /// How many letters to emphasize instead, even if the word would be left un-emphasized. `None` emphasizes as usual.
+    pub emphasized_letters: Option<usize>,
+    /// The renderer to write the word with instead, like one with other colors. `None` uses the renderer of the reader. Not used by [`BioReader::bio_read_spans`](crate::BioReader::bio_read_spans) and [`BioReader::bio_read_visit`](crate::BioReader::bio_read_visit), which don't render words.
+    pub renderer: Option<Arc<dyn EmphasisRenderer + Send + Sync>>,
+}
+
+impl StyleOverride {
+    /// Write the word as it is, un-emphasized.
+    pub fn skip() -> Self {
+        Self { skip: true, ..Self::default() }
+    }
+    /// Emphasize the first `letters` letters of the word.
+    pub fn emphasize(letters: usize) -> Self {
+        Self { emphasized_letters: Some(letters), ..Self::default() }
+    }
+    /// Write the word with `renderer`, emphasized as usual.
+    pub fn render(renderer: impl EmphasisRenderer + Send + Sync + 'static) -> Self {
+        Self::default().with_renderer(renderer)
+    }
+    /// Write the word with `renderer` as well.
+    pub fn with_renderer(mut self, renderer: impl EmphasisRenderer + Send + Sync + 'static) -> Self {
+        self.renderer = Some(Arc::new(renderer));
+        self
+    }
+}
+
+impl fmt::Debug for StyleOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StyleOverride")
+            .field("skip", &self.skip)
+            .field("emphasized_letters", &self.emphasized_letters)
+            .field("renderer", &self.renderer.as_ref().map(|_| format_args!("<custom>")))
+            .finish()
+    }
+}
+
+/// The hook set with [`BioReader::word_style_hook`](crate::BioReader::word_style_hook).
+pub(crate) type WordStyleHook = Arc<dyn Fn(&WordInfo<'_>) -> Option<StyleOverride> + Send + Sync>;
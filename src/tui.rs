@@ -0,0 +1,381 @@
+//! The terminal reader app of `br --tui`, for reading a file with scrolling and emphasis adjustable on the fly. Requires the `tui` feature.
+
+use crate::cli::{self, Kind};
+use bio_read::{reading_time::average_wpm, render::{Plain, Theme}, BioReadStats, BioReader, Config, SpanKind};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+    DefaultTerminal, Frame,
+};
+use regex::RegexBuilder;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, io::Result, ops::Range, path::Path};
+
+/// The preset themes, in the order they are toggled through with `t`.
+const THEMES: [Theme; 4] = [Theme::BoldDim, Theme::ColorContrast, Theme::Underline, Theme::Inverse];
+
+/// The style layered on top of emphasis to highlight matches of the search.
+const HIGHLIGHT: Style = Style::new().fg(Color::Black).bg(Color::Yellow);
+
+/// The reading positions of files, saved in the state directory when the reader quits, so that reading resumes where it was left off.
+///
+/// ```toml
+/// [lines]
+/// "/home/me/book.txt" = 120
+/// ```
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Positions {
+    /// The first line shown of each file, by its absolute path.
+    #[serde(default)]
+    lines: BTreeMap<String, usize>,
+}
+
+impl Positions {
+    /// Load the saved positions. A missing or invalid file has none.
+    fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path).ok().and_then(|content| toml::from_str(&content).ok()).unwrap_or_default()
+    }
+    /// Save the positions, creating the state directory if needed.
+    fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, toml::to_string(self).map_err(std::io::Error::other)?)
+    }
+}
+
+/// Read the file at `path` of `kind` in the terminal, bio-read with `config`, until the user quits. PDF documents have their text extracted first. Reading starts where the file was left off last time, unless `resume` is `false`, and where it is left off is saved.
+pub fn run(config: &Config, kind: Kind, path: &Path, resume: bool) -> Result<()> {
+    let title = path.file_name().unwrap_or(path.as_os_str()).to_string_lossy().into_owned();
+    let text = cli::read_text(path)?;
+    // Paths that aren't valid UTF-8 can't be keys, so their positions aren't kept
+    let key = std::fs::canonicalize(path)?.into_os_string().into_string().ok();
+    let Some((key, positions_path)) = key.zip(cli::positions_path()) else {
+        return run_text(config, kind, title, text);
+    };
+    let line = if resume { Positions::load(&positions_path).lines.get(&key).copied() } else { None };
+    let line = run_app(config, kind, title, text, line)?;
+    // Loaded again, as other files may have been read meanwhile
+    let mut positions = Positions::load(&positions_path);
+    positions.lines.insert(key, line);
+    positions
+        .save(&positions_path)
+        .map_err(|e| std::io::Error::new(e.kind(), format!("Cannot save reading position to {}: {e}", positions_path.display())))
+}
+
+/// Read `text` of `kind` titled `title` in the terminal, like [`run`] does with a file, from the top.
+pub fn run_text(config: &Config, kind: Kind, title: String, text: String) -> Result<()> {
+    run_app(config, kind, title, text, None).map(drop)
+}
+
+/// Read `text` of `kind` titled `title` in the terminal, starting at `line` if given, and returning the first line shown when the user quits.
+fn run_app(config: &Config, kind: Kind, title: String, text: String, line: Option<usize>) -> Result<usize> {
+    let (format, language) = kind;
+    let config = Config {
+        format: Some(format),
+        language: Some(language),
+        ..config.clone()
+    };
+    let reader = BioReader::from_config(&config)?;
+    // Spans are of the text as it is, so it is reflowed beforehand, like bio-reading it with nothing added would
+    let text = match config.reflow {
+        Some(width) if width > 0 => reader.clone().renderer(Plain).join_cursive(false).bio_read_text(&text)?,
+        _ => text,
+    };
+    let stats = reader.bio_read_with_stats(text.as_bytes(), &mut std::io::sink())?;
+    let theme = config.theme.as_ref().and_then(|theme| theme.preset).unwrap_or_default();
+    // A ratio replaces the fixation point, until one is chosen with keys 1 to 5
+    let fixation_point = match config.fixation_ratio {
+        Some(_) => None,
+        None => Some(config.fixation_point.unwrap_or(3)),
+    };
+    let mut app = App::new(title, text, stats, average_wpm(language), reader, fixation_point, theme);
+    app.resume = line;
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+    result.map(|()| app.line())
+}
+
+/// The state of the reader app.
+struct App {
+    /// The name of the file, shown in the status bar.
+    title: String,
+    /// The text of the file.
+    text: String,
+    /// Statistics of the words in the text, shown in the status bar.
+    stats: BioReadStats,
+    /// The reading speed for the language of the text in words per minute, for the reading time shown in the status bar.
+    wpm: u32,
+    /// The reader to bio-read the text with.
+    reader: BioReader,
+    /// The fixation point set with keys `1` to `5`, or `None` if a ratio is used instead.
+    fixation_point: Option<usize>,
+    /// The theme toggled with `t`.
+    theme: Theme,
+    /// The lines of the text, styled.
+    lines: Vec<Line<'static>>,
+    /// The width the text is wrapped at, which [`App::ends`] is computed for.
+    width: u16,
+    /// How many rows the text takes up to the end of each line, when wrapped at [`App::width`].
+    ends: Vec<usize>,
+    /// How many rows the text can be shown in.
+    height: usize,
+    /// The first row shown.
+    scroll: usize,
+    /// The line to scroll to once the lines are wrapped, when resuming reading.
+    resume: Option<usize>,
+    /// The search being typed after `/`, if any.
+    input: Option<String>,
+    /// The last search, or an empty string if none.
+    query: String,
+    /// The ranges of the matches of the search in the text, in order.
+    matches: Vec<Range<usize>>,
+    /// The line of each match.
+    match_lines: Vec<usize>,
+}
+
+impl App {
+    /// Create the app, reading `text` titled `title`, with `stats` of its words read at `wpm` words per minute.
+    fn new(title: String, text: String, stats: BioReadStats, wpm: u32, reader: BioReader, fixation_point: Option<usize>, theme: Theme) -> Self {
+        let mut app = Self {
+            title,
+            text,
+            stats,
+            wpm,
+            reader,
+            fixation_point,
+            theme,
+            lines: Vec::new(),
+            width: 0,
+            ends: Vec::new(),
+            height: 0,
+            scroll: 0,
+            resume: None,
+            input: None,
+            query: String::new(),
+            matches: Vec::new(),
+            match_lines: Vec::new(),
+        };
+        app.style_lines();
+        app
+    }
+    /// Draw frames and handle key presses until the user quits.
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && !self.handle_key(key) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+    /// Handle `key`, returning whether to keep running.
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if let Some(input) = &mut self.input {
+            // Typing a search
+            match key.code {
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+                KeyCode::Char(c) => input.push(c),
+                KeyCode::Backspace if !input.is_empty() => {
+                    input.pop();
+                }
+                KeyCode::Enter => {
+                    let input = self.input.take().unwrap_or_default();
+                    self.search(input);
+                }
+                KeyCode::Esc | KeyCode::Backspace => self.input = None,
+                _ => {}
+            }
+            return true;
+        }
+        let page = self.height.saturating_sub(1).max(1);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Down | KeyCode::Char('j') | KeyCode::Enter => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::PageDown | KeyCode::Char(' ' | 'f') => self.scroll = self.scroll.saturating_add(page),
+            KeyCode::PageUp | KeyCode::Char('b') => self.scroll = self.scroll.saturating_sub(page),
+            KeyCode::Home | KeyCode::Char('g') => self.scroll = 0,
+            KeyCode::End | KeyCode::Char('G') => self.scroll = usize::MAX,
+            KeyCode::Char('/') => self.input = Some(String::new()),
+            KeyCode::Char('n') => {
+                // The first match below the top line, or else the first one
+                let line = self.line();
+                if let Some(&next) = self.match_lines.iter().find(|&&next| next > line).or(self.match_lines.first()) {
+                    self.scroll_to(next);
+                }
+            }
+            KeyCode::Char('N') => {
+                let line = self.line();
+                if let Some(&previous) = self.match_lines.iter().rev().find(|&&previous| previous < line).or(self.match_lines.last()) {
+                    self.scroll_to(previous);
+                }
+            }
+            KeyCode::Char(digit @ '1'..='5') => {
+                let fixation_point = digit as usize - '0' as usize;
+                self.reader.set_fixation_point(fixation_point);
+                self.fixation_point = Some(fixation_point);
+                self.style_lines();
+            }
+            KeyCode::Char('t') => {
+                let index = THEMES.iter().position(|theme| *theme == self.theme).unwrap_or(0);
+                self.theme = THEMES[(index + 1) % THEMES.len()];
+                self.style_lines();
+            }
+            _ => {}
+        }
+        true
+    }
+    /// Search for `input`, ignoring case unless it has capitals, highlighting the matches and scrolling to the first one from the top line on. An empty search clears the highlights.
+    fn search(&mut self, input: String) {
+        let search = (!input.is_empty()).then(|| {
+            RegexBuilder::new(&regex::escape(&input))
+                .case_insensitive(!input.chars().any(char::is_uppercase))
+                .build()
+                .expect("Escaped text should be a valid pattern")
+        });
+        self.matches = search.iter().flat_map(|search| search.find_iter(&self.text)).map(|m| m.range()).collect();
+        self.query = input;
+        let mut line = 0;
+        let mut position = 0;
+        self.match_lines = self
+            .matches
+            .iter()
+            .map(|range| {
+                line += self.text[position..range.start].matches('\n').count();
+                position = range.start;
+                line
+            })
+            .collect();
+        self.style_lines();
+        let top = self.line();
+        if let Some(&first) = self.match_lines.iter().find(|&&first| first >= top).or(self.match_lines.first()) {
+            self.scroll_to(first);
+        }
+    }
+    /// Scroll so that `line` is the first line shown.
+    fn scroll_to(&mut self, line: usize) {
+        self.scroll = line.checked_sub(1).and_then(|previous| self.ends.get(previous)).copied().unwrap_or(0);
+    }
+    /// Bio-read the text into styled lines, with the matches of the search highlighted. Only styles change, so the lines wrap as before.
+    fn style_lines(&mut self) {
+        let (emphasized, de_emphasized, secondary) = styles(self.theme);
+        let mut lines = vec![Line::default()];
+        let mut position = 0;
+        for span in self.reader.bio_read_spans(&self.text) {
+            let style = match span.kind {
+                SpanKind::Emphasized => emphasized,
+                SpanKind::DeEmphasized => de_emphasized,
+                SpanKind::Secondary => secondary,
+            };
+            push_highlighted(&mut lines, &self.text, position..span.range.start, Style::new(), &self.matches);
+            push_highlighted(&mut lines, &self.text, span.range.clone(), style, &self.matches);
+            position = span.range.end;
+        }
+        push_highlighted(&mut lines, &self.text, position..self.text.len(), Style::new(), &self.matches);
+        if lines.len() > 1 && lines.last().is_some_and(|line| line.spans.is_empty()) {
+            // The line break ending the text doesn't start another line
+            lines.pop();
+        }
+        self.lines = lines;
+    }
+    /// Wrap the lines at `width`, if not done yet.
+    fn wrap(&mut self, width: u16) {
+        if self.width == width && self.ends.len() == self.lines.len() {
+            return;
+        }
+        let mut end = 0;
+        self.ends = self
+            .lines
+            .iter()
+            .map(|line| {
+                end += Paragraph::new(line.clone()).wrap(Wrap { trim: false }).line_count(width).max(1);
+                end
+            })
+            .collect();
+        self.width = width;
+    }
+    /// Get the first line shown, which is kept as the reading position, since rows change with the width of the terminal.
+    fn line(&self) -> usize {
+        self.ends.partition_point(|&end| end <= self.scroll)
+    }
+    /// Draw the visible part of the text, and the status bar below it.
+    fn draw(&mut self, frame: &mut Frame) {
+        let [body, status] = Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        self.wrap(body.width);
+        self.height = usize::from(body.height);
+        if let Some(line) = self.resume.take() {
+            self.scroll_to(line);
+        }
+        let total = self.ends.last().copied().unwrap_or(0);
+        self.scroll = self.scroll.min(total.saturating_sub(self.height));
+        // Only lay out the lines on screen, skipping the rows of the first one that are scrolled past
+        let first = self.line();
+        let last = self.ends.partition_point(|&end| end < self.scroll + self.height).min(self.lines.len().saturating_sub(1));
+        let start = first.checked_sub(1).map_or(0, |previous| self.ends[previous]);
+        let skip = u16::try_from(self.scroll - start).unwrap_or(u16::MAX);
+        let visible = self.lines.get(first..=last).unwrap_or_default().to_vec();
+        frame.render_widget(Paragraph::new(visible).wrap(Wrap { trim: false }).scroll((skip, 0)), body);
+
+        let progress = if total <= self.height {
+            100
+        } else {
+            (self.scroll + self.height) * 100 / total
+        };
+        let fixation = self.fixation_point.map_or_else(|| String::from("custom"), |point| point.to_string());
+        let minutes = self.stats.reading_time(self.wpm).as_secs().div_ceil(60);
+        let status_line = match &self.input {
+            Some(input) => format!("/{input}"),
+            None => {
+                let search = if self.query.is_empty() { String::new() } else { format!(" | /{} {} matches", self.query, self.matches.len()) };
+                format!(
+                    " {} | {} words, {} paragraphs, {minutes} min | fixation {fixation} | {}{search} | {progress}% | q quit, 1-5 fixation, t theme, / search",
+                    self.title, self.stats.words, self.stats.paragraphs, self.theme
+                )
+            }
+        };
+        frame.render_widget(Paragraph::new(status_line).style(Style::new().add_modifier(Modifier::REVERSED)), status);
+    }
+}
+
+/// Append the `range` of `text` in `style` to `lines`, like [`push_text`], with the parts inside `matches` highlighted on top of it.
+fn push_highlighted(lines: &mut Vec<Line<'static>>, text: &str, range: Range<usize>, style: Style, matches: &[Range<usize>]) {
+    let mut position = range.start;
+    let first = matches.partition_point(|m| m.end <= range.start);
+    for m in matches[first..].iter().take_while(|m| m.start < range.end) {
+        let end = m.end.min(range.end);
+        push_text(lines, &text[position..m.start.max(position)], style);
+        push_text(lines, &text[m.start.max(position)..end], style.patch(HIGHLIGHT));
+        position = end;
+    }
+    push_text(lines, &text[position..range.end], style);
+}
+
+/// Append `text` in `style` to `lines`, starting a new line at each line break. Tabs are expanded, as the terminal would otherwise skip cells.
+fn push_text(lines: &mut Vec<Line<'static>>, text: &str, style: Style) {
+    for (i, part) in text.split('\n').enumerate() {
+        if i > 0 {
+            lines.push(Line::default());
+        }
+        let part = part.trim_end_matches('\r').replace('\t', "    ");
+        if !part.is_empty() {
+            lines.last_mut().expect("Lines start with an empty line").push_span(Span::styled(part, style));
+        }
+    }
+}
+
+/// Get the styles of the emphasized, de-emphasized and secondary parts in `theme`, like [`Ansi`](bio_read::render::Ansi) output has.
+pub fn styles(theme: Theme) -> (Style, Style, Style) {
+    match theme {
+        Theme::BoldDim => (Style::new().add_modifier(Modifier::BOLD), Style::new().add_modifier(Modifier::DIM), Style::new()),
+        Theme::ColorContrast => (Style::new().fg(Color::White), Style::new().fg(Color::DarkGray), Style::new().fg(Color::Gray)),
+        Theme::Underline => (Style::new().add_modifier(Modifier::UNDERLINED), Style::new(), Style::new().add_modifier(Modifier::ITALIC)),
+        Theme::Inverse => (Style::new().add_modifier(Modifier::REVERSED), Style::new(), Style::new().add_modifier(Modifier::UNDERLINED)),
+    }
+}
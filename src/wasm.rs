@@ -0,0 +1,104 @@
+//! WebAssembly bindings, for using the same engine from JavaScript. Requires the `wasm-bindgen` feature.
+//!
+//! ```js
+//! import init, { BioReadOptions, bioReadText } from "bio-read";
+//!
+//! await init();
+//! const options = new BioReadOptions();
+//! options.fixationPoint = 2;
+//! options.format = "html";
+//! document.body.innerHTML = bioReadText(document.body.innerHTML, options);
+//! ```
+
+use crate::{BioReader, Config, OutputFormat};
+use wasm_bindgen::prelude::*;
+
+/// Options of [`bio_read_text`], with the same meaning as the fields of [`Config`]. Unset options keep their defaults, except that the output defaults to HTML.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, Debug, Default)]
+pub struct BioReadOptions {
+    /// The fixation point, in range \[1, 5\].
+    #[wasm_bindgen(js_name = fixationPoint)]
+    pub fixation_point: Option<usize>,
+    /// The fraction of each word to emphasize, in range \[0, 1\]. Cannot be used together with `fixationPoint`.
+    #[wasm_bindgen(js_name = fixationRatio)]
+    pub fixation_ratio: Option<f32>,
+    /// Emphasize every `saccade`-th word only.
+    pub saccade: Option<usize>,
+    /// Whether to pass URLs, emails and file paths through untouched.
+    #[wasm_bindgen(js_name = preserveUrls)]
+    pub preserve_urls: bool,
+    /// How to emphasize the text, with the emphasized text taking the place of `{}`.
+    pub emphasize: Option<String>,
+    /// How to de-emphasize the text, with the de-emphasized text taking the place of `{}`.
+    #[wasm_bindgen(js_name = deEmphasize)]
+    pub de_emphasize: Option<String>,
+    /// The format of the input: `"auto"`, `"plain"`, `"markdown"`, `"html"`, `"latex"`, `"rst"`, `"asciidoc"`, `"org"`, `"man"`, `"diff"`, `"email"`, `"subtitles"`, or `"code:"` followed by a programming language, like `"code:rust"`.
+    pub format: Option<String>,
+    /// The format of the output: `"ansi"`, `"html"`, `"markdown"`, `"bbcode"`, `"latex"`, `"typst"`, `"rst"`, `"org"`, `"rtf"`, `"plain"` or `"json"`.
+    pub to: Option<String>,
+    /// Whether to render the emphasized part with graded intensity.
+    pub gradient: bool,
+}
+
+#[wasm_bindgen]
+impl BioReadOptions {
+    /// Create options with every option unset.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl BioReadOptions {
+    /// Get the [`Config`] with these options.
+    fn config(&self) -> Result<Config, String> {
+        Ok(Config {
+            fixation_point: self.fixation_point,
+            fixation_ratio: self.fixation_ratio,
+            saccade: self.saccade,
+            sentence_boost: None,
+            reset_at_paragraphs: None,
+            skip: None,
+            exclude_words: None,
+            skip_acronyms: None,
+            token_policy: None,
+            compound_policy: None,
+            max_word_len: None,
+            long_word_policy: None,
+            language: None,
+            joiners: None,
+            cjk: None,
+            join_cursive: None,
+            preserve_urls: Some(self.preserve_urls),
+            secondary_fixation: None,
+            reflow: None,
+            justify: None,
+            hanging_indent: None,
+            columns: None,
+            page_height: None,
+            encoding: None,
+            normalize_line_endings: None,
+            trim_trailing_whitespace: None,
+            collapse_blank_lines: None,
+            ascii_punctuation: None,
+            emphasize: self.emphasize.clone(),
+            de_emphasize: self.de_emphasize.clone(),
+            format: self.format.as_deref().map(str::parse).transpose()?,
+            to: Some(self.to.as_deref().map_or(Ok(OutputFormat::Html), str::parse)?),
+            gradient: Some(self.gradient),
+            theme: None,
+            color: None,
+        })
+    }
+}
+
+/// Do bio-reading on a piece of text with `options`, writing HTML by default. Throws if the options are invalid.
+#[wasm_bindgen(js_name = bioReadText)]
+pub fn bio_read_text(text: &str, options: &BioReadOptions) -> Result<String, JsError> {
+    let reader = options
+        .config()
+        .and_then(|config| BioReader::from_config(&config).map_err(|e| e.to_string()))
+        .map_err(|e| JsError::new(&e))?;
+    reader.bio_read_text(text).map_err(|e| JsError::new(&e.to_string()))
+}
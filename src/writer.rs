@@ -0,0 +1,88 @@
+//! A [`Write`] adapter, bio-reading everything written into it. See [`BioWriter`].
+
+use crate::{processor::Processor, BioReader};
+use std::io::{Result, Write};
+
+/// Bio-reads everything written into it, forwarding the output to an inner writer. Wrap [`stdout`](std::io::stdout) with it once, and everything printed with [`write!`] or [`writeln!`] comes out bio-read.
+///
+/// As words can be split across writes, the end of the last word is held back until more is written or the writer is finished. Call [`BioWriter::finish`] to write it out and handle errors; dropping the writer finishes it as well, ignoring errors, like [`BufWriter`](std::io::BufWriter) does.
+///
+/// # Example
+///
+/// ```rust
+/// use bio_read::{BioReader, BioWriter};
+/// use std::io::Write;
+/// let reader = BioReader::new()
+///     .emphasize(String::from("<em>"), String::from("</em>"))
+///     .de_emphasize(String::from(""), String::from(""));
+/// let mut writer = BioWriter::new(&reader, Vec::new());
+/// write!(writer, "hello ").unwrap();
+/// write!(writer, "wor").unwrap();
+/// write!(writer, "ld").unwrap();
+/// let output = writer.finish().unwrap();
+/// assert_eq!(String::from_utf8(output).unwrap(), "<em>hel</em>lo <em>wor</em>ld");
+/// ```
+pub struct BioWriter<'a, W: Write> {
+    /// The processor holding the state of the current word, taken when finished.
+    processor: Option<Processor<'a>>,
+    /// The writer to forward the output to, taken when finished.
+    inner: Option<W>,
+}
+
+impl<'a, W: Write> BioWriter<'a, W> {
+    /// Create a writer bio-reading with `reader`, forwarding the output to `inner`.
+    pub fn new(reader: &'a BioReader, inner: W) -> Self {
+        Self {
+            processor: Some(Processor::new(reader)),
+            inner: Some(inner),
+        }
+    }
+    /// Get a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().expect("Inner writer is only taken when finished")
+    }
+    /// Get a mutable reference to the inner writer. Writing to it directly bypasses bio-reading, and may interleave with output held back.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.as_mut().expect("Inner writer is only taken when finished")
+    }
+    /// Write out whatever is held back, flush the inner writer, and return it.
+    pub fn finish(mut self) -> Result<W> {
+        self.finish_mut()?;
+        Ok(self.inner.take().expect("Inner writer is only taken when finished"))
+    }
+    /// Write out whatever is held back and flush the inner writer, if not finished yet.
+    fn finish_mut(&mut self) -> Result<()> {
+        let (Some(processor), Some(inner)) = (self.processor.take(), self.inner.as_mut()) else {
+            return Ok(());
+        };
+        processor.finish(inner)?;
+        inner.flush()
+    }
+}
+
+impl<W: Write> Write for BioWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let (Some(processor), Some(inner)) = (self.processor.as_mut(), self.inner.as_mut()) else {
+            unreachable!("Writer is only finished when consumed or dropped");
+        };
+        processor.push_bytes(buf, inner)?;
+        Ok(buf.len())
+    }
+    /// Flush the inner writer. The end of the last word is still held back, since it may continue in the next write.
+    fn flush(&mut self) -> Result<()> {
+        self.get_mut().flush()
+    }
+}
+
+impl<W: Write> Drop for BioWriter<'_, W> {
+    fn drop(&mut self) {
+        // Best effort, like `BufWriter`
+        let _ = self.finish_mut();
+    }
+}
+
+impl<W: Write + std::fmt::Debug> std::fmt::Debug for BioWriter<'_, W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BioWriter").field("inner", &self.inner).finish_non_exhaustive()
+    }
+}
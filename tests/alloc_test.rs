@@ -0,0 +1,83 @@
+//! Tests counting heap allocations, asserting that bio-reading doesn't allocate per word: once buffers have grown to fit the longest word, processing more text allocates nothing more.
+
+use bio_read::{render::Html, segment::LongWordPolicy, BioReader, InputFormat};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    collections::HashSet,
+};
+
+/// Counts the allocations made on each thread, so that tests running in parallel don't disturb each other.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Count the allocations made bio-reading `text` with `reader`, into an output buffer allocated beforehand.
+fn allocations(reader: &BioReader, text: &str) -> usize {
+    let mut output = Vec::with_capacity(text.len() * 16);
+    let before = ALLOCATIONS.with(Cell::get);
+    reader.bio_read(text.as_bytes(), &mut output).unwrap();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+/// Assert that bio-reading `paragraph` repeated many times allocates no more than bio-reading it a few times.
+fn assert_no_allocations_per_word(reader: &BioReader, paragraph: &str) {
+    // Warm up, so that lazily initialized statics like compiled patterns aren't counted
+    allocations(reader, paragraph);
+    let few = allocations(reader, &paragraph.repeat(4));
+    let many = allocations(reader, &paragraph.repeat(400));
+    assert_eq!(few, many, "{reader:?} allocates per word");
+}
+
+#[test]
+fn test_no_allocations_per_word() {
+    let paragraph = "The quick brown fox, who's well-known, jumps over 3 lazy dogs. Ça fait déjà naïve cafe\u{301}! مرحبا 世界 한국어\n\n";
+    let readers = [
+        BioReader::new(),
+        BioReader::new().emphasize(String::from("<b>"), String::from("</b>")).de_emphasize(String::new(), String::new()),
+        BioReader::new().renderer(Html::default()),
+        BioReader::new().fixation_ratio(0.5).saccade(2).sentence_boost(1).reset_at_paragraphs(true),
+        BioReader::new().exclude_words(HashSet::from([String::from("the")])).skip_acronyms(true),
+        BioReader::new().preserve_urls(true),
+        BioReader::new().format(InputFormat::Plain),
+    ];
+    for reader in &readers {
+        assert_no_allocations_per_word(reader, paragraph);
+    }
+}
+
+#[test]
+fn test_long_words_bounded() {
+    // Words longer than the maximum length aren't buffered whole, so buffers stop growing once they reach it
+    let readers = [
+        BioReader::new(),
+        BioReader::new().long_word_policy(LongWordPolicy::Split),
+        BioReader::new().preserve_urls(true),
+        BioReader::new().max_word_len(100),
+    ];
+    for reader in &readers {
+        allocations(reader, "warm up");
+        let long = allocations(reader, &"a".repeat(1 << 17));
+        let longer = allocations(reader, &"a".repeat(1 << 21));
+        assert_eq!(long, longer, "{reader:?} buffers long words whole");
+    }
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "async")]
+
+use bio_read::{BioReader, InputFormat};
+use common::{setup_reader, ByteByByte};
+use std::fs;
+
+mod common;
+
+#[tokio::test]
+async fn test_bio_read_async_matches_sync() -> std::io::Result<()> {
+    let reader = setup_reader(3);
+    for file in fs::read_dir("tests/input")? {
+        let text = fs::read(file?.path())?;
+        let mut expected = Vec::new();
+        reader.bio_read(text.as_slice(), &mut expected)?;
+        let mut output = Vec::new();
+        reader.bio_read_async(ByteByByte(&text), &mut output).await?;
+        assert_eq!(output, expected);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bio_read_async_formats() -> std::io::Result<()> {
+    let mut output = Vec::new();
+    let reader = setup_reader(3).format(InputFormat::Markdown);
+    reader.bio_read_async(ByteByByte("naïve `code`".as_bytes()), &mut output).await?;
+    assert_eq!(output, "<em>naï</em><de>ve</de> `code`".as_bytes());
+    let mut output = Vec::new();
+    let reader = setup_reader(3).format(InputFormat::Html);
+    reader.bio_read_async(ByteByByte("<i>naïve</i>".as_bytes()), &mut output).await?;
+    assert_eq!(output, "<i><em>naï</em><de>ve</de></i>".as_bytes());
+    Ok(())
+}
+
+/// The future can be spawned onto a multi-threaded runtime.
+#[allow(dead_code)]
+fn assert_send() {
+    fn is_send<T: Send>(_: T) {}
+    let reader = BioReader::new();
+    let mut output = Vec::new();
+    is_send(reader.bio_read_async(&b""[..], &mut output));
+}
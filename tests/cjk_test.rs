@@ -0,0 +1,25 @@
+#![cfg(feature = "cjk")]
+
+use bio_read::{segment::Cjk, BioReader, Config};
+use common::setup_reader;
+
+mod common;
+
+#[test]
+fn test_cjk_segment() -> std::io::Result<()> {
+    let reader = setup_reader(3).cjk(Cjk::Segment);
+    assert_eq!(reader.bio_read_text("我们中出了一个叛徒")?, "<em>我</em><de>们</de><em>中</em><de>出</de><em>了</em><em>一</em><de>个</de><em>叛</em><de>徒</de>");
+    // Runs end at letters and punctuation
+    assert_eq!(reader.bio_read_text("Rust语言, 真的很好!")?, "<em>Ru</em><de>st</de><em>语</em><de>言</de>, <em>真</em><de>的</de><em>很</em><em>好</em>!");
+    // When streaming
+    let mut output = Vec::new();
+    reader.bio_read("真的很好".as_bytes(), &mut output)?;
+    assert_eq!(String::from_utf8(output).unwrap(), "<em>真</em><de>的</de><em>很</em><em>好</em>");
+    // Words count towards the saccade
+    assert_eq!(reader.clone().saccade(2).bio_read_text("真的很好")?, "<em>真</em><de>的</de>很<em>好</em>");
+    // Nothing to segment without Unicode support
+    assert_eq!(reader.unicode(false).bio_read_text("真的很好")?, "真的很好");
+    let config: Config = toml::from_str("cjk = \"segment\"\nto = \"markdown\"").unwrap();
+    assert_eq!(BioReader::from_config(&config).unwrap().bio_read_text("很好")?, "**很****好**");
+    Ok(())
+}
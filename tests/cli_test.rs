@@ -0,0 +1,104 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Create an empty directory named `name` to run `br` in.
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("bio-read-cli-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Run `br` with `args` in `dir`, without any configuration file.
+fn br(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_br"))
+        .args(args)
+        .current_dir(dir)
+        .env("HOME", dir)
+        .env("XDG_CONFIG_HOME", dir)
+        .env("XDG_STATE_HOME", dir)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_output_stdout() {
+    let dir = temp_dir("stdout");
+    fs::write(dir.join("in.txt"), "hello world").unwrap();
+    let output = br(&dir, &["-t", "markdown", "-o", "-", "in.txt"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "**hel**lo **wor**ld");
+    assert!(!dir.join("-").exists());
+    // There is nothing to overwrite on stdout, so --force does nothing
+    let output = br(&dir, &["-t", "markdown", "-o", "-", "--force", "in.txt"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "**hel**lo **wor**ld");
+    assert!(!dir.join("-").exists());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_output_file() {
+    let dir = temp_dir("file");
+    fs::write(dir.join("in.txt"), "hello world").unwrap();
+    let output = br(&dir, &["-t", "markdown", "-o", "out.md", "in.txt"]);
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    assert_eq!(fs::read_to_string(dir.join("out.md")).unwrap(), "**hel**lo **wor**ld");
+    // Existing files are only overwritten with --force
+    fs::write(dir.join("in.txt"), "bye").unwrap();
+    let output = br(&dir, &["-t", "markdown", "-o", "out.md", "in.txt"]);
+    assert!(!output.status.success());
+    assert_eq!(fs::read_to_string(dir.join("out.md")).unwrap(), "**hel**lo **wor**ld");
+    let output = br(&dir, &["-t", "markdown", "-o", "out.md", "--force", "in.txt"]);
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(dir.join("out.md")).unwrap(), "**b**ye");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_usage_error_without_stdin() {
+    let dir = temp_dir("usage");
+    for args in [&["--in-place"][..], &["--output-dir", "out"], &["-o", "out.md", "--in-place"]] {
+        // Usage errors are reported without waiting for stdin, which is kept open
+        let mut child = Command::new(env!("CARGO_BIN_EXE_br"))
+            .args(args)
+            .current_dir(&dir)
+            .env("HOME", &dir)
+            .env("XDG_CONFIG_HOME", &dir)
+            .env("XDG_STATE_HOME", &dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let start = Instant::now();
+        let status = loop {
+            if let Some(status) = child.try_wait().unwrap() {
+                break status;
+            }
+            if start.elapsed() > Duration::from_secs(10) {
+                child.kill().unwrap();
+                panic!("br {args:?} waited on stdin");
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        assert!(!status.success());
+    }
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_columns_zero() {
+    let dir = temp_dir("columns");
+    fs::write(dir.join("in.txt"), "hello world").unwrap();
+    let output = br(&dir, &["--wrap", "80", "--columns", "0", "in.txt"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Number of columns should be at least 1"));
+    fs::remove_dir_all(dir).unwrap();
+}
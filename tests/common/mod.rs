@@ -0,0 +1,43 @@
+//! Fixtures shared by the test files, each of which uses some of them.
+#![allow(dead_code)]
+
+use bio_read::BioReader;
+
+#[allow(clippy::let_and_return)]
+pub fn setup_reader(fixation_point: usize) -> BioReader {
+    let reader = BioReader::new()
+        .emphasize("<em>".to_string(), "</em>".to_string())
+        .de_emphasize("<de>".to_string(), "</de>".to_string())
+        .fixation_point(fixation_point);
+    reader
+}
+
+/// A reader that yields one byte at a time, splitting multi-byte sequences across reads.
+pub struct ByteByByte<'a>(pub &'a [u8]);
+
+impl std::io::Read for ByteByByte<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self.0.split_first() {
+            Some((first, rest)) if !buf.is_empty() => {
+                buf[0] = *first;
+                self.0 = rest;
+                Ok(1)
+            }
+            _ => Ok(0),
+        }
+    }
+}
+
+impl tokio::io::AsyncRead for ByteByByte<'_> {
+    fn poll_read(
+        mut self: std::pin::Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        if let Some((first, rest)) = self.0.split_first() {
+            buf.put_slice(&[*first]);
+            self.0 = rest;
+        }
+        std::task::Poll::Ready(Ok(()))
+    }
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "docx")]
+
+use bio_read::{BioReader, InputFormat};
+use std::{
+    io::{Cursor, Read, Write},
+    process::Command,
+};
+use zip::{write::SimpleFileOptions, ZipArchive, ZipWriter};
+
+/// Wrap `body` in a minimal main document.
+fn document(body: &str) -> String {
+    format!(r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>{body}</w:body></w:document>"#)
+}
+
+/// Create a Word document with `document` as its main document.
+fn create_docx(document: &str) -> Vec<u8> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    writer.start_file("[Content_Types].xml", SimpleFileOptions::default()).unwrap();
+    writer.write_all(b"<Types/>").unwrap();
+    writer.start_file("word/document.xml", SimpleFileOptions::default()).unwrap();
+    writer.write_all(document.as_bytes()).unwrap();
+    writer.finish().unwrap().into_inner()
+}
+
+/// Bio-read the Word document `docx` with `reader`, returning the main document and the content types.
+fn bio_read_docx(reader: &BioReader, docx: &[u8]) -> std::io::Result<(String, String)> {
+    let mut output = Vec::new();
+    reader.bio_read(docx, &mut output)?;
+    let mut archive = ZipArchive::new(Cursor::new(output)).unwrap();
+    let mut document = String::new();
+    archive.by_name("word/document.xml").unwrap().read_to_string(&mut document)?;
+    let mut content_types = String::new();
+    archive.by_name("[Content_Types].xml").unwrap().read_to_string(&mut content_types)?;
+    Ok((document, content_types))
+}
+
+#[test]
+fn test_bio_read_docx() -> std::io::Result<()> {
+    let reader = BioReader::new().format(InputFormat::Docx);
+    // A word split across runs, existing properties, a tab and an entity
+    let input = document(concat!(
+        r#"<w:p><w:pPr><w:jc w:val="center"/></w:pPr><w:r w:rsidR="1"><w:t>Hello wo</w:t></w:r>"#,
+        r#"<w:r><w:rPr><w:rStyle w:val="Quote"/><w:b w:val="0"/><w:i/></w:rPr><w:t>rld</w:t></w:r>"#,
+        r#"<w:r><w:tab/></w:r><w:r><w:t xml:space="preserve">A &amp; B</w:t></w:r></w:p>"#,
+    ));
+    let expected = document(concat!(
+        r#"<w:p><w:pPr><w:jc w:val="center"/></w:pPr>"#,
+        r#"<w:r w:rsidR="1"><w:rPr><w:b/><w:bCs/></w:rPr><w:t xml:space="preserve">Hel</w:t></w:r>"#,
+        r#"<w:r w:rsidR="1"><w:t xml:space="preserve">lo </w:t></w:r>"#,
+        r#"<w:r w:rsidR="1"><w:rPr><w:b/><w:bCs/></w:rPr><w:t xml:space="preserve">wo</w:t></w:r>"#,
+        r#"<w:r><w:rPr><w:rStyle w:val="Quote"/><w:b/><w:bCs/><w:i/></w:rPr><w:t xml:space="preserve">r</w:t></w:r>"#,
+        r#"<w:r><w:rPr><w:rStyle w:val="Quote"/><w:b w:val="0"/><w:i/></w:rPr><w:t xml:space="preserve">ld</w:t></w:r>"#,
+        r#"<w:r><w:tab/></w:r>"#,
+        r#"<w:r><w:rPr><w:b/><w:bCs/></w:rPr><w:t xml:space="preserve">A</w:t></w:r>"#,
+        r#"<w:r><w:t xml:space="preserve"> &amp; </w:t></w:r>"#,
+        r#"<w:r><w:rPr><w:b/><w:bCs/></w:rPr><w:t xml:space="preserve">B</w:t></w:r></w:p>"#,
+    ));
+    let (document, content_types) = bio_read_docx(&reader, &create_docx(&input))?;
+    assert_eq!(document, expected);
+    // Other files are copied as-is
+    assert_eq!(content_types, "<Types/>");
+    Ok(())
+}
+
+#[test]
+fn test_docx_output() -> std::io::Result<()> {
+    let dir = std::env::temp_dir().join(format!("bio-read-docx-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let docx = create_docx(&document("<w:p><w:r><w:t>Hello</w:t></w:r></w:p>"));
+    std::fs::write(dir.join("input.docx"), &docx)?;
+    let status = Command::new(env!("CARGO_BIN_EXE_br"))
+        .args(["--format", "docx", "input.docx", "-o", "output.docx"])
+        .current_dir(&dir)
+        .env("HOME", &dir)
+        .env("XDG_CONFIG_HOME", &dir)
+        .status()?;
+    assert!(status.success());
+    // Written to the file, like the library would
+    let mut archive = ZipArchive::new(std::fs::File::open(dir.join("output.docx"))?).unwrap();
+    let mut output = String::new();
+    archive.by_name("word/document.xml").unwrap().read_to_string(&mut output)?;
+    let (expected, _) = bio_read_docx(&BioReader::new().format(InputFormat::Docx), &docx)?;
+    assert_eq!(output, expected);
+    std::fs::remove_dir_all(dir)
+}
+
+#[test]
+fn test_docx_text() {
+    // Text can't be a Word document, so it is read as plain text
+    let reader = BioReader::new().format(InputFormat::Docx);
+    let plain = BioReader::new();
+    assert_eq!(reader.bio_read_spans("hello world"), plain.bio_read_spans("hello world"));
+    assert_eq!(reader.bio_read_words("hello world"), plain.bio_read_words("hello world"));
+    assert_eq!(reader.bio_read_segments(b"hello world").collect::<Vec<_>>(), plain.bio_read_segments(b"hello world").collect::<Vec<_>>());
+}
+
+#[test]
+fn test_bio_read_docx_invalid() {
+    let reader = BioReader::new().format(InputFormat::Docx);
+    assert!(reader.bio_read(b"not a zip".as_slice(), &mut Vec::new()).is_err());
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    writer.start_file("word/other.xml", SimpleFileOptions::default()).unwrap();
+    let docx = writer.finish().unwrap().into_inner();
+    let error = reader.bio_read(docx.as_slice(), &mut Vec::new()).unwrap_err();
+    assert_eq!(error.to_string(), "Invalid DOCX: missing word/document.xml");
+}
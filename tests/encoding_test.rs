@@ -0,0 +1,58 @@
+#![cfg(feature = "encoding")]
+
+use bio_read::{encoding::Encoding, BioReadProcessor, BioReader, Config};
+use common::setup_reader;
+
+mod common;
+
+/// Bio-read `input` with `reader` a byte at a time, returning the output with invalid UTF-8 replaced.
+fn bio_read_bytes(reader: &BioReader, input: &[u8]) -> std::io::Result<String> {
+    let mut processor = BioReadProcessor::new(reader);
+    let mut output = Vec::new();
+    for chunk in input.chunks(1) {
+        processor.push_bytes(chunk, &mut output)?;
+    }
+    processor.finish(&mut output)?;
+    Ok(String::from_utf8_lossy(&output).into_owned())
+}
+
+/// Encode `text` as UTF-16, little-endian or not, with a byte order mark.
+fn utf16(text: &str, little_endian: bool) -> Vec<u8> {
+    let units = std::iter::once(0xfeff).chain(text.encode_utf16());
+    units.flat_map(|unit| if little_endian { unit.to_le_bytes() } else { unit.to_be_bytes() }).collect()
+}
+
+#[test]
+fn test_detect_utf16() -> std::io::Result<()> {
+    let reader = setup_reader(3);
+    for little_endian in [true, false] {
+        assert_eq!(bio_read_bytes(&reader, &utf16("Café crème", little_endian))?, "<em>Ca</em><de>fé</de> <em>crè</em><de>me</de>");
+    }
+    // Not detected once the encoding is set
+    let reader = setup_reader(3).encoding(Encoding::Utf8);
+    assert!(bio_read_bytes(&reader, &utf16("Café", true))?.contains('\u{fffd}'));
+    Ok(())
+}
+
+#[test]
+fn test_encoding() -> std::io::Result<()> {
+    let reader = setup_reader(3).encoding(Encoding::Latin1);
+    assert_eq!(bio_read_bytes(&reader, b"Caf\xe9 cr\xe8me")?, "<em>Ca</em><de>fé</de> <em>crè</em><de>me</de>");
+    // Without a byte order mark
+    let reader = setup_reader(3).encoding(Encoding::Utf16Be);
+    assert_eq!(bio_read_bytes(&reader, &utf16("hello", false)[2..])?, "<em>hel</em><de>lo</de>");
+    // Malformed input is replaced, like a lone byte at the end
+    assert_eq!(bio_read_bytes(&reader, b"\0h\0i\0")?, "<em>h</em><de>i</de>\u{fffd}");
+    // Text is decoded already
+    let reader = setup_reader(3).encoding(Encoding::Latin1);
+    assert_eq!(reader.bio_read_text("Café")?, "<em>Ca</em><de>fé</de>");
+    Ok(())
+}
+
+#[test]
+fn test_encoding_config() {
+    let config: Config = toml::from_str(r#"encoding = "utf-16le""#).unwrap();
+    assert_eq!(config.encoding, Some(Encoding::Utf16Le));
+    assert_eq!("Latin-1".parse(), Ok(Encoding::Latin1));
+    assert_eq!(Encoding::Utf16Be.to_string(), "utf-16be");
+}
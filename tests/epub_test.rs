@@ -0,0 +1,87 @@
+#![cfg(feature = "epub")]
+
+use bio_read::{epub::bio_read_epub, render::Html, BioReader};
+use std::io::{Cursor, Read, Write};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+const CONTAINER: &str = r#"<?xml version="1.0"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>"#;
+
+const PACKAGE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0">
+  <manifest>
+    <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+    <item id="chapter" href="text/chapter%201.xhtml" media-type="application/xhtml+xml"/>
+    <item id="style" href="style.css" media-type="text/css"/>
+  </manifest>
+  <spine>
+    <itemref idref="chapter"/>
+  </spine>
+</package>"#;
+
+const CHAPTER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<html xmlns="http://www.w3.org/1999/xhtml"><body><p>Hello <i>world</i></p><pre>code here</pre></body></html>"#;
+
+/// Create an EPUB with the given files, after the mimetype.
+fn create_epub(files: &[(&str, &str)]) -> Vec<u8> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    writer.start_file("mimetype", stored).unwrap();
+    writer.write_all(b"application/epub+zip").unwrap();
+    for (name, content) in files {
+        writer.start_file(*name, SimpleFileOptions::default()).unwrap();
+        writer.write_all(content.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+/// Read the file named `name` in the EPUB `epub`.
+fn read_file(epub: &[u8], name: &str) -> String {
+    let mut archive = ZipArchive::new(Cursor::new(epub)).unwrap();
+    let mut content = String::new();
+    archive.by_name(name).unwrap().read_to_string(&mut content).unwrap();
+    content
+}
+
+#[test]
+fn test_bio_read_epub() {
+    let input = create_epub(&[
+        ("META-INF/container.xml", CONTAINER),
+        ("OEBPS/content.opf", PACKAGE),
+        ("OEBPS/nav.xhtml", "<p>Contents</p>"),
+        ("OEBPS/text/chapter 1.xhtml", CHAPTER),
+        ("OEBPS/style.css", "p { margin: 0; }"),
+    ]);
+    let reader = BioReader::new().renderer(Html::with_class("fixation"));
+    let mut output = Cursor::new(Vec::new());
+    bio_read_epub(&reader, Cursor::new(&input), &mut output).unwrap();
+    let output = output.into_inner();
+    // The mimetype comes first, uncompressed
+    let mut archive = ZipArchive::new(Cursor::new(&output)).unwrap();
+    let mimetype = archive.by_index(0).unwrap();
+    assert_eq!(mimetype.name().unwrap(), "mimetype");
+    assert_eq!(mimetype.compression(), CompressionMethod::Stored);
+    drop(mimetype);
+    assert_eq!(archive.len(), 6);
+    // Only documents in the spine are bio-read
+    assert_eq!(
+        read_file(&output, "OEBPS/text/chapter 1.xhtml"),
+        CHAPTER.replace("Hello <i>world", "<b class=\"fixation\">Hel</b>lo <i><b class=\"fixation\">wor</b>ld")
+    );
+    assert_eq!(read_file(&output, "OEBPS/nav.xhtml"), "<p>Contents</p>");
+    assert_eq!(read_file(&output, "OEBPS/style.css"), "p { margin: 0; }");
+}
+
+#[test]
+fn test_bio_read_epub_invalid() {
+    let reader = BioReader::new();
+    let mut output = Cursor::new(Vec::new());
+    assert!(bio_read_epub(&reader, Cursor::new(b"not a zip"), &mut output).is_err());
+    let input = create_epub(&[("OEBPS/content.opf", PACKAGE)]);
+    let error = bio_read_epub(&reader, Cursor::new(&input), &mut output).unwrap_err();
+    assert_eq!(error.to_string(), "Invalid EPUB: missing META-INF/container.xml");
+}
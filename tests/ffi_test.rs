@@ -0,0 +1,53 @@
+#![cfg(feature = "capi")]
+
+use bio_read::ffi::{br_free, br_new, br_process_utf8, br_set_fixation, br_set_format, br_set_output, br_string_free};
+use std::{ffi::CStr, ptr};
+
+/// Process `text` with `reader` through the C API.
+unsafe fn process(reader: *const bio_read::BioReader, text: &str) -> Option<String> {
+    let mut len = 0;
+    let output = br_process_utf8(reader, text.as_ptr().cast(), text.len(), &mut len);
+    if output.is_null() {
+        return None;
+    }
+    let bytes = CStr::from_ptr(output).to_bytes();
+    assert_eq!(bytes.len(), len);
+    let result = String::from_utf8(bytes.to_vec()).unwrap();
+    br_string_free(output, len);
+    Some(result)
+}
+
+#[test]
+fn test_ffi() {
+    unsafe {
+        let reader = br_new();
+        assert_eq!(process(reader, "hello world").unwrap(), "<b>hel</b>lo <b>wor</b>ld");
+        assert_eq!(br_set_fixation(reader, 2), 0);
+        assert_eq!(process(reader, "hello world").unwrap(), "<b>hel</b>lo <b>wor</b>ld");
+        assert_eq!(br_set_fixation(reader, 0), -1);
+        assert_eq!(br_set_fixation(reader, 6), -1);
+        assert_eq!(br_set_output(reader, c"markdown".as_ptr()), 0);
+        assert_eq!(process(reader, "hello").unwrap(), "**hel**lo");
+        assert_eq!(br_set_output(reader, c"unknown".as_ptr()), -1);
+        assert_eq!(br_set_format(reader, c"html".as_ptr()), 0);
+        assert_eq!(process(reader, "<i>hello</i>").unwrap(), "<i>**hel**lo</i>");
+        assert_eq!(br_set_format(reader, ptr::null()), -1);
+        br_free(reader);
+    }
+}
+
+#[test]
+fn test_ffi_null() {
+    unsafe {
+        let mut len = 0;
+        assert!(br_process_utf8(ptr::null(), c"hello".as_ptr(), 5, &mut len).is_null());
+        assert_eq!(br_set_fixation(ptr::null_mut(), 3), -1);
+        let reader = br_new();
+        assert!(br_process_utf8(reader, ptr::null(), 0, &mut len).is_null());
+        assert!(br_process_utf8(reader, c"hello".as_ptr(), 5, ptr::null_mut()).is_null());
+        assert_eq!(process(reader, "").unwrap(), "");
+        br_free(reader);
+        br_free(ptr::null_mut());
+        br_string_free(ptr::null_mut(), 0);
+    }
+}
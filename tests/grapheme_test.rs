@@ -0,0 +1,48 @@
+#![cfg(feature = "unicode")]
+
+use bio_read::render::Plain;
+use common::setup_reader;
+use proptest::prelude::*;
+use std::collections::HashSet;
+use unicode_segmentation::UnicodeSegmentation;
+
+mod common;
+
+#[test]
+fn test_grapheme_clusters() -> std::io::Result<()> {
+    let reader = setup_reader(3);
+    // Conjoining jamo make up a single syllable each
+    assert_eq!(reader.bio_read_text("\u{1112}\u{1161}\u{11AB}\u{1100}\u{1173}\u{11AF}")?, "<em>\u{1112}\u{1161}\u{11AB}</em><de>\u{1100}\u{1173}\u{11AF}</de>");
+    // As do Devanagari conjuncts with their vowel signs
+    assert_eq!(reader.bio_read_text("नमस्ते")?, "<em>न</em><de>मस्ते</de>");
+    // Decomposed and precomposed forms are split alike
+    assert_eq!(reader.bio_read_text("cafe\u{301}")?, "<em>ca</em><de>fe\u{301}</de>");
+    // Emoji sequences are passed through in one piece
+    assert_eq!(reader.bio_read_text("family 👨‍👩‍👧")?, "<em>fam</em><de>ily</de> 👨‍👩‍👧");
+    Ok(())
+}
+
+/// Pieces of text that make up tricky grapheme clusters.
+fn pieces() -> impl Strategy<Value = String> {
+    const PIECES: [&str; 26] = [
+        "a", "Z", "é", "e\u{301}", "\u{1112}", "\u{1161}", "\u{11AB}", "한", "न", "\u{94D}", "\u{93F}", "👨‍👩‍👧", "👍🏽", "🇫🇷", "\u{200D}", "\u{200C}", "\u{1F3FD}", "'", "-", " ", ",", "\r\n",
+        "م", "ـ", "\u{64E}", "世",
+    ];
+    proptest::collection::vec(proptest::sample::select(&PIECES[..]), 0..32).prop_map(|pieces| pieces.concat())
+}
+
+proptest! {
+    #[test]
+    fn spans_never_split_clusters(text in pieces()) {
+        let boundaries: HashSet<usize> = text.grapheme_indices(true).map(|(i, _)| i).chain([text.len()]).collect();
+        for span in setup_reader(3).bio_read_spans(&text) {
+            prop_assert!(boundaries.contains(&span.range.start) && boundaries.contains(&span.range.end), "{span:?} splits a cluster in {text:?}");
+        }
+    }
+
+    #[test]
+    fn output_concatenates_back_to_input(text in pieces()) {
+        let reader = setup_reader(3).renderer(Plain).join_cursive(false);
+        prop_assert_eq!(reader.bio_read_text(&text)?, text);
+    }
+}
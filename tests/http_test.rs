@@ -0,0 +1,98 @@
+#![cfg(feature = "http")]
+
+use bio_read::http::{extract_text, html_to_text, Feed};
+
+#[test]
+fn test_extract_article() {
+    let page = include_str!("fixtures/article.html");
+    assert_eq!(
+        extract_text(page),
+        "Why octopuses dream
+
+Octopuses change color while they sleep, cycling through patterns in a way that looks, to researchers, remarkably like dreaming.
+
+In a new study, biologists filmed sleeping octopuses for weeks, and found two distinct stages of sleep: a quiet one, and an active one with twitches, color changes & eye movements.
+
+What they learned:
+
+1. Active sleep recurs every hour or so.
+2. It lasts about a minute.
+
+stage  minutes
+quiet  60
+active 1
+
+The findings suggest that dream-like states evolved more than once.
+More research is needed…
+"
+    );
+}
+
+#[test]
+fn test_extract_without_paragraphs() {
+    // The whole body is taken, headed by the title, leaving out navigation
+    let page = "<html><head><title>Notes</title></head><body><nav><a href=\"/\">Home</a></nav><div>Buy <b>milk</b>\n  and&nbsp;eggs</div><ul><li>Call Bob<li>Water plants</ul></body></html>";
+    assert_eq!(extract_text(page), "Notes\n\nBuy milk and\u{a0}eggs\n\n- Call Bob\n- Water plants\n");
+    assert_eq!(extract_text(""), "");
+}
+
+#[test]
+fn test_parse_feeds() {
+    let rss = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/">
+<channel>
+  <title>Ocean Weekly</title>
+  <image><title>Logo</title></image>
+  <item>
+    <title>Why octopuses dream</title>
+    <link>https://example.com/octopuses</link>
+    <description>Octopuses change color while they sleep.</description>
+  </item>
+  <item>
+    <title>Squid &amp; ink</title>
+    <description>A summary.</description>
+    <content:encoded><![CDATA[<p>Squid squirt ink.</p><p>It holds&nbsp;melanin.</p>]]></content:encoded>
+  </item>
+</channel>
+</rss>"#;
+    let feed = Feed::parse(rss).unwrap();
+    assert_eq!(feed.title, "Ocean Weekly");
+    assert_eq!(feed.entries.len(), 2);
+    assert_eq!(feed.entries[0].link.as_deref(), Some("https://example.com/octopuses"));
+    assert_eq!(feed.entries[0].text(), "Why octopuses dream\n\nOctopuses change color while they sleep.\n");
+    // Content is preferred over the summary
+    assert_eq!(feed.entries[1].link, None);
+    assert_eq!(feed.entries[1].text(), "Squid & ink\n\nSquid squirt ink.\n\nIt holds\u{a0}melanin.\n");
+
+    let atom = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Deep Sea</title>
+  <link href="https://example.com/"/>
+  <entry>
+    <title type="html">Anglerfish &lt;3</title>
+    <link rel="self" href="https://example.com/feed/1"/>
+    <link href="https://example.com/anglerfish"/>
+    <summary>They glow.</summary>
+  </entry>
+  <entry>
+    <title>Untold</title>
+  </entry>
+</feed>"#;
+    let feed = Feed::parse(atom).unwrap();
+    assert_eq!(feed.title, "Deep Sea");
+    assert_eq!(feed.entries[0].title, "Anglerfish <3");
+    assert_eq!(feed.entries[0].link.as_deref(), Some("https://example.com/anglerfish"));
+    assert_eq!(feed.entries[0].content, "They glow.");
+    assert_eq!(feed.entries[1].text(), "Untold\n");
+    assert!(Feed::parse("<rss><channel></rss>").is_err());
+}
+
+#[test]
+fn test_html_to_text() {
+    // Unlike articles, short paragraphs and lists are kept as a whole
+    assert_eq!(
+        html_to_text("<h2>Tips</h2>\n<p>Read <em>slowly</em>,<br>then fast.</p><ol><li>One<li>Two</ol><pre>\n a\n  b\n</pre>"),
+        "Tips\n\nRead slowly,\nthen fast.\n\n1. One\n2. Two\n\n a\n  b\n"
+    );
+    assert_eq!(html_to_text("Just text &#169; &#x263A; &bogus;"), "Just text © ☺ &bogus;\n");
+}
@@ -1,13 +1,17 @@
-use bio_read::BioReader;
-use std::{fs, path::Path};
+use bio_read::{
+    cues::{write_cues, CueFormat},
+    fixation::{FixationStrategy, Frequency},
+    observer::Observer,
+    reading_time::ReadingSpeed,
+    render::{Ansi, BBCode, Gradient, Html, Json, Latex, Markdown, Org, Plain, Rst, Rtf, Theme, Typst, Wrap},
+    segment::{is_cjk, CompoundPolicy, Language, LongWordPolicy, TokenPolicy, WordChars},
+    style::StyleOverride,
+    BioRead, BioReadExt, BioReadProcessor, BioReadStats, BioReader, BioWriter, CodeLanguage, Config, EmphasisRenderer, EmphasisSpan, Error, InputFormat, OutputFormat, Regex, Segment, SpanKind, Token, WordSpan,
+};
+use common::{setup_reader, ByteByByte};
+use std::{collections::HashSet, fs, path::Path};
 
-fn setup_reader(fixation_point: usize) -> BioReader {
-    let reader = BioReader::new()
-        .emphasize("<em>".to_string(), "</em>".to_string())
-        .de_emphasize("<de>".to_string(), "</de>".to_string())
-        .fixation_point(fixation_point);
-    reader
-}
+mod common;
 
 #[test]
 fn test_bio_read_simple() {
@@ -50,3 +54,1494 @@ fn test_bio_read() -> std::io::Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_bio_read_unicode() {
+    let reader = setup_reader(3);
+    // Latin-1 supplement
+    assert_eq!(reader.bio_read_text("naïve café").unwrap(), "<em>naï</em><de>ve</de> <em>ca</em><de>fé</de>");
+    assert_eq!(reader.bio_read_text("Ünïcödé").unwrap(), "<em>Ünïc</em><de>ödé</de>");
+    // Combining diacritics (decomposed "café" and "naïve")
+    assert_eq!(reader.bio_read_text("cafe\u{301} nai\u{308}ve").unwrap(), "<em>ca</em><de>fe\u{301}</de> <em>nai\u{308}</em><de>ve</de>");
+    // Other scripts
+    assert_eq!(reader.bio_read_text("привет, мир").unwrap(), "<em>при</em><de>вет</de>, <em>м</em><de>ир</de>");
+    // Non-letters, as well as Chinese and Japanese, are passed through
+    assert_eq!(reader.bio_read_text("hello, 世界! 🎉").unwrap(), "<em>hel</em><de>lo</de>, 世界! 🎉");
+}
+
+#[test]
+fn test_bio_read_ascii_only() {
+    let reader = setup_reader(3).unicode(false);
+    assert_eq!(reader.bio_read_text("naïve").unwrap(), "<em>n</em><de>a</de>ï<em>v</em><de>e</de>");
+}
+
+#[test]
+fn test_bio_read_utf8_boundaries() -> std::io::Result<()> {
+    let reader = setup_reader(3);
+    // Multi-byte sequences split across reads
+    let mut output = Vec::new();
+    reader.bio_read(ByteByByte("naïve café".as_bytes()), &mut output)?;
+    assert_eq!(output, "<em>naï</em><de>ve</de> <em>ca</em><de>fé</de>".as_bytes());
+    // Invalid UTF-8 is passed through untouched
+    let mut output = Vec::new();
+    reader.bio_read(ByteByByte(b"hello\xffworld\xe4"), &mut output)?;
+    assert_eq!(output, b"<em>hel</em><de>lo</de>\xff<em>wor</em><de>ld</de>\xe4");
+    Ok(())
+}
+
+#[test]
+fn test_bio_read_long_runs() {
+    // Runs of ASCII letters and other characters longer than the chunks they are scanned in
+    let reader = BioReader::new()
+        .emphasize("<".to_string(), ">".to_string())
+        .de_emphasize("".to_string(), "".to_string());
+    assert_eq!(
+        reader.bio_read_text("abcdefghijklmnopqrsé ..............,,,,,,,,z\u{7f}Zabcdefgh-ijk ÄBCDEFGHIJK").unwrap(),
+        "<abcdefghij>klmnopqrsé ..............,,,,,,,,<z>\u{7f}<Zabcde>fgh-ijk <ÄBCDEF>GHIJK"
+    );
+}
+
+#[test]
+fn test_word_chars() -> std::io::Result<()> {
+    let reader = setup_reader(3);
+    // Joiners between letters are kept inside words, even across reads
+    let input = "Don’t use state-of-the-art 'quotes' -- or trailing- hyphens''";
+    let expected = "<em>Do</em><de>n’t</de> <em>u</em><de>se</de> <em>state-of-</em><de>the-art</de> '<em>quo</em><de>tes</de>' -- <em>o</em><de>r</de> <em>trai</em><de>ling</de>- <em>hyph</em><de>ens</de>''";
+    assert_eq!(reader.bio_read_text(input)?, expected);
+    let mut output = Vec::new();
+    reader.bio_read(ByteByByte(input.as_bytes()), &mut output)?;
+    assert_eq!(output, expected.as_bytes());
+    // Or split words at them
+    let reader = setup_reader(3).word_chars(WordChars::none());
+    assert_eq!(reader.bio_read_text("don't")?, "<em>d</em><de>on</de>'<em>t</em>");
+    Ok(())
+}
+
+/// Emphasizes a fixed number of letters, recording the words it's given.
+struct Fixed(usize, std::sync::Mutex<Vec<String>>);
+
+impl FixationStrategy for Fixed {
+    fn emphasized_len(&self, word: &str) -> usize {
+        self.1.lock().unwrap().push(word.to_string());
+        self.0
+    }
+}
+
+#[test]
+fn test_fixation_strategy() {
+    // Lengths are clamped, and empty parts are not wrapped
+    for (len, expected) in [
+        (0, "<de>a</de> <de>don't</de> <de>café</de>"),
+        (2, "<em>a</em> <em>do</em><de>n't</de> <em>ca</em><de>fé</de>"),
+        (9, "<em>a</em> <em>don't</em> <em>café</em>"),
+    ] {
+        let reader = setup_reader(3).fixation_strategy(Fixed(len, Default::default()));
+        assert_eq!(reader.bio_read_text("a don't cafe\u{301}").unwrap(), expected.replace('é', "e\u{301}"));
+    }
+    // Strategies see whole words, and are replaced by fixation points
+    let strategy = std::sync::Arc::new(Fixed(1, Default::default()));
+    struct Shared(std::sync::Arc<Fixed>);
+    impl FixationStrategy for Shared {
+        fn emphasized_len(&self, word: &str) -> usize {
+            self.0.emphasized_len(word)
+        }
+    }
+    let reader = setup_reader(3).fixation_strategy(Shared(strategy.clone()));
+    reader.bio_read_text("well-known, isn't it").unwrap();
+    assert_eq!(*strategy.1.lock().unwrap(), ["well-known", "isn't", "it"]);
+    let reader = reader.fixation_point(3);
+    assert_eq!(reader.bio_read_text("hello").unwrap(), "<em>hel</em><de>lo</de>");
+    assert_eq!(strategy.1.lock().unwrap().len(), 3);
+}
+
+#[test]
+fn test_fixation_ratio() {
+    let reader = setup_reader(3).fixation_ratio(0.3);
+    // 3 out of 10 letters, not rounded up by floating point errors, and 1 out of 2 letters, rounded up
+    assert_eq!(reader.bio_read_text("strawberry it's").unwrap(), "<em>str</em><de>awberry</de> <em>i</em><de>t's</de>");
+    let reader = setup_reader(3).fixation_ratio(1.0);
+    assert_eq!(reader.bio_read_text("hello").unwrap(), "<em>hello</em>");
+    let reader = setup_reader(3).fixation_ratio(0.0);
+    assert_eq!(reader.bio_read_text("hello").unwrap(), "<de>hello</de>");
+    let config: Config = toml::from_str("fixation-ratio = 0.5").unwrap();
+    let reader = BioReader::from_config(&config).unwrap().emphasize("<em>".to_string(), "</em>".to_string()).de_emphasize(String::new(), String::new());
+    assert_eq!(reader.bio_read_text("reading").unwrap(), "<em>read</em>ing");
+    let config: Config = toml::from_str("fixation-ratio = 0.5\nfixation-point = 1").unwrap();
+    assert!(BioReader::from_config(&config).is_err());
+}
+
+#[test]
+#[should_panic]
+fn test_fixation_ratio_out_of_range() {
+    setup_reader(3).fixation_ratio(1.5);
+}
+
+#[test]
+fn test_saccade() -> std::io::Result<()> {
+    let reader = setup_reader(3).saccade(3);
+    // Words are counted across reads and markup
+    let input = "one *two* three `code` four five six";
+    let expected = "<em>o</em><de>ne</de> *two* three `code` <em>fo</em><de>ur</de> five six";
+    let reader = reader.format(InputFormat::Markdown);
+    assert_eq!(reader.bio_read_text(input)?, expected);
+    let reader = reader.format(InputFormat::Plain);
+    let mut output = Vec::new();
+    reader.bio_read(ByteByByte(b"one two three four"), &mut output)?;
+    assert_eq!(output, b"<em>o</em><de>ne</de> two three <em>fo</em><de>ur</de>");
+    assert_eq!(reader.bio_read_spans("one two three four").len(), 4);
+    Ok(())
+}
+
+/// A writer recording what has been written at each flush, and how many writes there were.
+#[derive(Default)]
+struct Flushes {
+    written: Vec<u8>,
+    flushed: Vec<String>,
+    writes: usize,
+}
+
+impl std::io::Write for Flushes {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.writes += 1;
+        self.written.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.flushed.push(String::from_utf8(self.written.clone()).unwrap());
+        Ok(())
+    }
+}
+
+#[test]
+fn test_bio_read_buffered() -> std::io::Result<()> {
+    // Output is written a chunk at a time, instead of piece by piece
+    let reader = setup_reader(3);
+    let mut output = Flushes::default();
+    reader.bio_read("hello world\n".repeat(10_000).as_bytes(), &mut output)?;
+    assert_eq!(output.written, "<em>hel</em><de>lo</de> <em>wor</em><de>ld</de>\n".repeat(10_000).as_bytes());
+    assert!(output.writes <= output.written.len() / 8192 + 1);
+    assert!(output.flushed.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_bio_read_lines() -> std::io::Result<()> {
+    let reader = setup_reader(3);
+    let mut output = Flushes::default();
+    reader.bio_read_lines("hello\nworld\n".as_bytes(), &mut output)?;
+    assert_eq!(
+        output.flushed,
+        [
+            "<em>hel</em><de>lo</de>\n",
+            "<em>hel</em><de>lo</de>\n<em>wor</em><de>ld</de>\n",
+            "<em>hel</em><de>lo</de>\n<em>wor</em><de>ld</de>\n",
+        ]
+    );
+    // Markdown is flushed a block at a time
+    let reader = reader.format(InputFormat::Markdown);
+    let mut output = Flushes::default();
+    reader.bio_read_lines("*hello*\n\n`world`".as_bytes(), &mut output)?;
+    assert_eq!(output.flushed, ["", "*<em>hel</em><de>lo</de>*\n\n", "*<em>hel</em><de>lo</de>*\n\n", "*<em>hel</em><de>lo</de>*\n\n`world`"]);
+    Ok(())
+}
+
+#[test]
+fn test_skip_pattern() -> std::io::Result<()> {
+    let reader = setup_reader(3)
+        .skip_pattern(Regex::new(r"https?://\S+").unwrap())
+        .skip_pattern(Regex::new(r"\w+_\w+").unwrap());
+    let input = "see:https://example.com/a_b, then foo_bar\tbaz";
+    let expected = "<em>s</em><de>ee</de>:https://example.com/a_b, <em>th</em><de>en</de> foo_bar\t<em>b</em><de>az</de>";
+    assert_eq!(reader.bio_read_text(input)?, expected);
+    // Runs split across reads
+    let mut output = Vec::new();
+    reader.bio_read(ByteByByte(input.as_bytes()), &mut output)?;
+    assert_eq!(output, expected.as_bytes());
+    // Runs end at markup
+    let reader = reader.format(InputFormat::Html);
+    assert_eq!(reader.bio_read_text("<a>foo_bar</a>baz_")?, "<a>foo_bar</a><em>b</em><de>az</de>_");
+    Ok(())
+}
+
+#[test]
+fn test_preserve_urls() -> std::io::Result<()> {
+    let reader = setup_reader(3).emphasize("<b>".to_string(), "</b>".to_string()).de_emphasize("".to_string(), "".to_string());
+    let input = "and/or (see /usr/bin/env.) ../a/b C:\\Users\\x.txt, www.rust-lang.org. <https://x.io/a?b=c&d> user+tag@mail.co.uk!";
+    assert_eq!(
+        reader.bio_read_text(input)?,
+        "<b>a</b>nd/<b>o</b>r (<b>s</b>ee /<b>u</b>sr/<b>b</b>in/<b>e</b>nv.) ../<b>a</b>/<b>b</b> <b>C</b>:\\<b>Use</b>rs\\<b>x</b>.<b>t</b>xt, <b>w</b>ww.<b>rust-</b>lang.<b>o</b>rg. <<b>htt</b>ps://<b>x</b>.<b>i</b>o/<b>a</b>?<b>b</b>=<b>c</b>&<b>d</b>> <b>us</b>er+<b>t</b>ag@<b>ma</b>il.<b>c</b>o.<b>u</b>k!"
+    );
+    let reader = reader.preserve_urls(true);
+    assert_eq!(
+        reader.bio_read_text(input)?,
+        "<b>a</b>nd/<b>o</b>r (<b>s</b>ee /usr/bin/env.) ../a/b C:\\Users\\x.txt, www.rust-lang.org. <https://x.io/a?b=c&d> user+tag@mail.co.uk!"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_terminal_hyperlinks() -> std::io::Result<()> {
+    let reader = setup_reader(3).emphasize("<b>".to_string(), "</b>".to_string()).de_emphasize("".to_string(), "".to_string()).sentence_boost(1);
+    // Ended by BEL or ST, with only the visible text bio-read
+    let input = "Open \x1b]8;;https://example.com/read.me\x07the docs\x1b]8;;\x07. Then \x1b]8;id=1;file:///tmp/a\x1b\\here\x1b]8;;\x1b\\ now";
+    assert_eq!(
+        reader.bio_read_text(input)?,
+        "<b>Ope</b>n \x1b]8;;https://example.com/read.me\x07<b>t</b>he <b>do</b>cs\x1b]8;;\x07. <b>The</b>n \x1b]8;id=1;file:///tmp/a\x1b\\<b>he</b>re\x1b]8;;\x1b\\ <b>n</b>ow"
+    );
+    // Chunks may end anywhere, even inside a sequence
+    for size in 1..input.len() {
+        let mut processor = BioReadProcessor::new(&reader);
+        let mut output = Vec::new();
+        for chunk in input.as_bytes().chunks(size) {
+            processor.push_bytes(chunk, &mut output)?;
+        }
+        processor.finish(&mut output)?;
+        assert_eq!(String::from_utf8_lossy(&output), reader.bio_read_text(input)?, "{size}");
+    }
+    // URLs are not preserved as a whole past the sequence
+    assert_eq!(reader.clone().preserve_urls(true).bio_read_text("\x1b]8;;https://x.io\x07link\x1b]8;;\x07")?, "\x1b]8;;https://x.io\x07<b>lin</b>k\x1b]8;;\x07");
+    // Lone escape characters and unterminated sequences are left as they are, up to the end of the line
+    assert_eq!(reader.bio_read_text("one\x1b two \x1b]8;;oops\nthree")?, "<b>on</b>e\x1b <b>t</b>wo \x1b]8;;oops\n<b>thr</b>ee");
+    Ok(())
+}
+
+#[test]
+fn test_reflow() -> std::io::Result<()> {
+    let reader = setup_reader(3).reflow(10);
+    // Markup doesn't count, and paragraphs keep their blank lines and indentation
+    let input = "one two three\nfour\n\n  indented words here\n";
+    let output = reader.bio_read_text(input)?;
+    assert_eq!(reader.strip(&output), "one two\nthree four\n\n  indented\nwords here\n");
+    assert!(output.lines().any(|line| line.len() > 10));
+    // Wide characters take two columns, escape sequences none, and words wider than the width get lines of their own
+    let reader = BioReader::new().renderer(Plain).reflow(10);
+    assert_eq!(reader.bio_read_text("中文 字符 abc")?, "中文 字符\nabc");
+    assert_eq!(reader.bio_read_text("\x1b]8;;https://example.com\x07link\x1b]8;;\x07 a b c")?, "\x1b]8;;https://example.com\x07link\x1b]8;;\x07 a b c");
+    assert_eq!(reader.bio_read_text("a extraordinarily b")?, "a\nextraordinarily\nb");
+    // Chunks may end anywhere
+    let reader = setup_reader(3).reflow(10);
+    for size in 1..input.len() {
+        let mut processor = BioReadProcessor::new(&reader);
+        let mut output = Vec::new();
+        for chunk in input.as_bytes().chunks(size) {
+            processor.push_bytes(chunk, &mut output)?;
+        }
+        processor.finish(&mut output)?;
+        assert_eq!(String::from_utf8_lossy(&output), reader.bio_read_text(input)?, "{size}");
+    }
+    // Spans are into the input as it is, and other formats are left as they are
+    let spans = reader.bio_read_spans(input);
+    assert_eq!(&input[spans[4].range.clone()], "thr");
+    let reader = reader.format(InputFormat::Markdown);
+    assert_eq!(reader.strip(&reader.bio_read_text("one two three\nfour")?), "one two three\nfour");
+    Ok(())
+}
+
+#[test]
+fn test_justify() -> std::io::Result<()> {
+    let reader = BioReader::new().renderer(Plain).reflow(16).justify(true).hanging_indent(3);
+    // Full lines are stretched, the first gaps first, but not the last lines of paragraphs, nor lines of one word
+    let input = "1. a bb ccc dd eeeee\nffffff\n\n2. incomprehensibilities x";
+    let expected = "1.  a  bb ccc dd\n   eeeee ffffff\n\n2.\n   incomprehensibilities\n   x";
+    assert_eq!(reader.bio_read_text(input)?, expected);
+    // Lines are held back until full, whatever the chunks
+    let mut processor = BioReadProcessor::new(&reader);
+    let mut output = Vec::new();
+    processor.push_bytes(b"1. a bb ccc", &mut output)?;
+    assert!(output.is_empty());
+    for chunk in input.as_bytes()[11..].chunks(3) {
+        processor.push_bytes(chunk, &mut output)?;
+    }
+    processor.finish(&mut output)?;
+    assert_eq!(String::from_utf8_lossy(&output), expected);
+    // Emphasis doesn't change the layout
+    let reader = setup_reader(3).reflow(16).justify(true).hanging_indent(3);
+    assert_eq!(reader.strip(&reader.bio_read_text(input)?), expected);
+    Ok(())
+}
+
+#[test]
+fn test_columns() -> std::io::Result<()> {
+    let reader = BioReader::new().renderer(Plain).reflow(24).columns(2);
+    // Columns of 10 are filled one after another, balanced over the whole text
+    let input = "Columns are read like a newspaper.\n\nNew paragraph";
+    let expected = "Columns\nare read      New\nlike a        paragraph\nnewspaper.";
+    assert_eq!(reader.bio_read_text(input)?, expected);
+    // The line break at the end of the text is kept, and none is added without one
+    assert_eq!(reader.bio_read_text(&format!("{input}\n"))?, format!("{expected}\n"));
+    // Pages are written as soon as they are full, whatever the chunks
+    let reader = reader.page_height(2);
+    let mut processor = BioReadProcessor::new(&reader);
+    let mut output = Vec::new();
+    for chunk in input.as_bytes().chunks(3) {
+        processor.push_bytes(chunk, &mut output)?;
+    }
+    assert_eq!(String::from_utf8_lossy(&output), "Columns       like a\nare read      newspaper.\n");
+    processor.finish(&mut output)?;
+    assert_eq!(String::from_utf8_lossy(&output), "Columns       like a\nare read      newspaper.\n              paragraph\nNew");
+    // Emphasis doesn't change the layout
+    let reader = setup_reader(3).reflow(24).columns(2);
+    assert_eq!(reader.strip(&reader.bio_read_text(input)?), expected);
+    // Columns narrower than a word are widened to it, so that they still line up
+    let reader = BioReader::new().renderer(Plain).reflow(10).columns(3);
+    assert_eq!(reader.bio_read_text("a bcd e f")?, "a      e\nbcd    f");
+    // There is at least one column
+    assert!(BioReader::from_config(&Config { columns: Some(0), ..Config::default() }).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_normalize_whitespace() -> std::io::Result<()> {
+    let input = "One \r\n\r\n \t\r\n\r\nTwo\rthree\t\r\nfour  ";
+    // Preserved exactly by default
+    let reader = BioReader::new().renderer(Plain);
+    assert_eq!(reader.bio_read_text(input)?, input);
+    let reader = reader.normalize_line_endings(true);
+    assert_eq!(reader.bio_read_text(input)?, "One \n\n \t\n\nTwo\rthree\t\nfour  ");
+    let reader = reader.trim_trailing_whitespace(true);
+    assert_eq!(reader.bio_read_text(input)?, "One\n\n\n\nTwo\rthree\nfour");
+    let reader = reader.collapse_blank_lines(true);
+    let expected = "One\n\nTwo\rthree\nfour";
+    assert_eq!(reader.bio_read_text(input)?, expected);
+    // Whatever the chunks, even splitting CRLF line endings
+    let mut processor = BioReadProcessor::new(&reader);
+    let mut output = Vec::new();
+    for chunk in input.as_bytes().chunks(1) {
+        processor.push_bytes(chunk, &mut output)?;
+    }
+    processor.finish(&mut output)?;
+    assert_eq!(String::from_utf8_lossy(&output), expected);
+    // Before reflowing
+    let reader = reader.reflow(80);
+    assert_eq!(reader.bio_read_text("One \r\ntwo\r\n\r\n\r\nThree\r\n")?, "One two\n\nThree\n");
+    Ok(())
+}
+
+#[test]
+fn test_ascii_punctuation() -> std::io::Result<()> {
+    let reader = setup_reader(3).renderer(Plain).ascii_punctuation(true);
+    let input = "‘Don’t’ – “wait”… — ‚low‘ \u{2022}\u{20ac}";
+    let expected = "'Don't' - \"wait\"... -- ‚low' \u{2022}\u{20ac}";
+    assert_eq!(reader.bio_read_text(input)?, expected);
+    // Whatever the chunks, even splitting characters
+    let mut processor = BioReadProcessor::new(&reader);
+    let mut output = Vec::new();
+    for chunk in input.as_bytes().chunks(1) {
+        processor.push_bytes(chunk, &mut output)?;
+    }
+    processor.finish(&mut output)?;
+    assert_eq!(String::from_utf8_lossy(&output), expected);
+    // Truncated sequences are kept
+    let mut output = Vec::new();
+    reader.bio_read(&b"a\xe2\x80"[..], &mut output)?;
+    assert_eq!(output, b"a\xe2\x80");
+    Ok(())
+}
+
+#[test]
+fn test_typographic_punctuation() {
+    let reader = BioReader::new().renderer(Markdown);
+    // Curly apostrophes are kept inside words, while dashes, quotes and ellipses split them
+    let output = reader.bio_read_text("“Don’t”—it’s well–known…").unwrap();
+    assert_eq!(output, "“**Do**n’t”—**i**t’s **we**ll–**kno**wn…");
+    // Unless set otherwise
+    let config: Config = toml::from_str("joiners = \"’–\"").unwrap();
+    let reader = BioReader::from_config(&config).unwrap().renderer(Markdown);
+    assert_eq!(reader.bio_read_text("it’s well–known it's").unwrap(), "**i**t’s **well–k**nown **i**t'**s**");
+}
+
+#[test]
+fn test_byte_order_mark() -> std::io::Result<()> {
+    let reader = BioReader::new().renderer(Plain);
+    let mut output = Vec::new();
+    reader.bio_read(&b"\xef\xbb\xbfhello\xef\xbb\xbf"[..], &mut output)?;
+    // Only at the start of the input
+    assert_eq!(output, b"hello\xef\xbb\xbf");
+    // Whatever the chunks, even splitting the byte order mark
+    let mut processor = BioReadProcessor::new(&reader);
+    let mut output = Vec::new();
+    for chunk in b"\xef\xbb\xbf# Title\n".chunks(1) {
+        processor.push_bytes(chunk, &mut output)?;
+    }
+    processor.finish(&mut output)?;
+    assert_eq!(output, b"# Title\n");
+    // Input too short to be one is kept
+    let mut output = Vec::new();
+    reader.bio_read(&b"\xef\xbb"[..], &mut output)?;
+    assert_eq!(output, b"\xef\xbb");
+    // The format is detected after it
+    let reader = BioReader::new().format(InputFormat::Auto).emphasize(String::from("<b>"), String::from("</b>")).de_emphasize(String::new(), String::new());
+    let mut output = Vec::new();
+    reader.bio_read(&b"\xef\xbb\xbf<p>hello</p>"[..], &mut output)?;
+    assert_eq!(String::from_utf8_lossy(&output), "<p><b>hel</b>lo</p>");
+    Ok(())
+}
+
+#[test]
+fn test_bio_read_markdown() {
+    let reader = setup_reader(3).format(InputFormat::Markdown);
+    let input = "---\ntitle: front matter\n---\n\n# Heading\n\nSome **bold** text with `inline code`, a [link](https://example.com \"title\") and <https://example.com>.\n\n```rust\nfn main() {}\n```\n\n![image alt](image.png) caf\\*e &amp; more\n";
+    let expected = "---\ntitle: front matter\n---\n\n# <em>Head</em><de>ing</de>\n\n<em>So</em><de>me</de> **<em>bo</em><de>ld</de>** <em>te</em><de>xt</de> <em>wi</em><de>th</de> `inline code`, <em>a</em> [<em>li</em><de>nk</de>](https://example.com \"title\") <em>a</em><de>nd</de> <https://example.com>.\n\n```rust\nfn main() {}\n```\n\n![image alt](image.png) <em>c</em><de>af</de>\\*<em>e</em> &amp; <em>mo</em><de>re</de>\n";
+    assert_eq!(reader.bio_read_text(input).unwrap(), expected);
+}
+
+#[test]
+fn test_bio_read_html() -> std::io::Result<()> {
+    let reader = BioReader::new()
+        .emphasize("<b>".to_string(), "</b>".to_string())
+        .de_emphasize("".to_string(), "".to_string())
+        .format(InputFormat::Html);
+    let input = "<!DOCTYPE html>\n<html><head><title>Hello world</title><style>p > a { color: red; }</style>\n<script>if (a</b) { x = \"</p>\"; }</script></head>\n<body><!-- a comment --><p title=\"a > b\">Fish &amp; chips&nbsp;today</p>\n<pre><code>let answer = 42;</code></pre><p>Sweet <em>dreams</em></p></body></html>\n";
+    let expected = "<!DOCTYPE html>\n<html><head><title><b>Hel</b>lo <b>wor</b>ld</title><style>p > a { color: red; }</style>\n<script>if (a</b) { x = \"</p>\"; }</script></head>\n<body><!-- a comment --><p title=\"a > b\"><b>Fi</b>sh &amp; <b>chi</b>ps&nbsp;<b>tod</b>ay</p>\n<pre><code>let answer = 42;</code></pre><p><b>Swe</b>et <em><b>dre</b>ams</em></p></body></html>\n";
+    assert_eq!(reader.bio_read_text(input)?, expected);
+    // Tags split across reads
+    let mut output = Vec::new();
+    reader.bio_read(ByteByByte(input.as_bytes()), &mut output)?;
+    assert_eq!(output, expected.as_bytes());
+    Ok(())
+}
+
+#[test]
+fn test_bio_read_latex() {
+    let reader = BioReader::new().renderer(Latex).format(InputFormat::Latex);
+    let input = "\\documentclass{article}\n\\usepackage{amsmath} % Math\n\\title{Bionic paper}\n\\begin{document}\n\\section{Introduction}\\label{sec:intro}\nSee \\cite[p.~2]{knuth84} for $e = mc^2$, or \\emph{read} it 50\\% faster:\n\\begin{equation}\n  x + y\n\\end{equation}\n% Comment text\n\\begin{tabular}{|c|c|} Fish & chips \\\\ \\end{tabular}\n\\verb|raw text| and \\url{https://example.com}.\n\\end{document}\n";
+    let expected = "\\documentclass{article}\n\\usepackage{amsmath} % Math\n\\title{Bionic paper}\n\\begin{document}\n\\section{\\textbf{Introd}uction}\\label{sec:intro}\n\\textbf{S}ee \\cite[p.~2]{knuth84} \\textbf{f}or $e = mc^2$, \\textbf{o}r \\emph{\\textbf{re}ad} \\textbf{i}t 50\\% \\textbf{fas}ter:\n\\begin{equation}\n  x + y\n\\end{equation}\n% Comment text\n\\begin{tabular}{|c|c|} \\textbf{Fi}sh & \\textbf{chi}ps \\\\ \\end{tabular}\n\\verb|raw text| \\textbf{a}nd \\url{https://example.com}.\n\\end{document}\n";
+    assert_eq!(reader.bio_read_text(input).unwrap(), expected);
+    // Fragments without a preamble are bio-read as a whole
+    assert_eq!(reader.bio_read_text("Hello \\textit{world}").unwrap(), "\\textbf{Hel}lo \\textit{\\textbf{wor}ld}");
+}
+
+#[test]
+fn test_bio_read_rst() {
+    let reader = BioReader::new().renderer(Rst).format(InputFormat::Rst);
+    let input = "Title\n=====\n\n.. note:: Keep it short.\n   :class: tip\n\n   Note body.\n\n.. code-block:: python\n\n   print(\"hello world\")\n\nSee :ref:`intro`, ``code``, `Python <https://python.org>`_, Python_ and |sub|.\n\n:param name: The name\n\nExample::\n\n    literal text\n\n>>> print(1)\n1\n";
+    let expected = "Title\n=====\n\n.. note:: **Ke**\\ ep **i**\\ t **sho**\\ rt.\n   :class: tip\n\n   **No**\\ te **bo**\\ dy.\n\n.. code-block:: python\n\n   print(\"hello world\")\n\n**S**\\ ee :ref:`intro`, ``code``, `Python <https://python.org>`_, Python_ **a**\\ nd |sub|.\n\n:param name: **T**\\ he **na**\\ me\n\n**Exam**\\ ple::\n\n    literal text\n\n>>> print(1)\n1\n";
+    assert_eq!(reader.bio_read_text(input).unwrap(), expected);
+}
+
+#[test]
+fn test_bio_read_asciidoc() {
+    let reader = BioReader::new().renderer(Markdown).format(InputFormat::AsciiDoc);
+    let input = "= Document Title\n:toc: left\n\nNOTE: Remember this.\n\n[source,rust]\n----\nfn main() {}\n----\n\n[source]\nlet x = 1;\n\n Literal paragraph\n\nUse `code`, link:index.html[Home], <<intro,Intro>>, {version} and *bold* text.\n\nimage::cat.png[A cat]\n\n// A comment\n////\nBlock comment\n////\n";
+    let expected = "= **Docu**ment **Tit**le\n:toc: left\n\nNOTE: **Reme**mber **th**is.\n\n[source,rust]\n----\nfn main() {}\n----\n\n[source]\nlet x = 1;\n\n Literal paragraph\n\n**U**se `code`, link:index.html[Home], <<intro,Intro>>, {version} **a**nd *bold* **te**xt.\n\nimage::cat.png[A cat]\n\n// A comment\n////\nBlock comment\n////\n";
+    assert_eq!(reader.bio_read_text(input).unwrap(), expected);
+}
+
+#[test]
+fn test_bio_read_code() -> std::io::Result<()> {
+    let reader = BioReader::new().renderer(Markdown).format(InputFormat::Code(CodeLanguage::Rust));
+    let input = "/// Greets the `name` given.\nfn greet<'a>(name: &'a str) -> String {\n    let quote = '\\'';\n    format!(\"Hello, {name}!\\n\") + r#\"Raw \"text\"\"# // Trailing words\n}\n/* Block\n   comment */\n";
+    let expected = "/// **Gre**ets **t**he `name` **giv**en.\nfn greet<'a>(name: &'a str) -> String {\n    let quote = '\\'';\n    format!(\"**Hel**lo, {name}!\\n\") + r#\"**R**aw \"**te**xt\"\"# // **Trai**ling **wor**ds\n}\n/* **Blo**ck\n   **comm**ent */\n";
+    assert_eq!(reader.bio_read_text(input)?, expected);
+    let reader = reader.format("code:python".parse().unwrap());
+    let input = "def greet(name):\n    \"\"\"Greet someone.\"\"\"\n    print('Hello %s' % name)  # Say hello\n";
+    let expected = "def greet(name):\n    \"\"\"**Gre**et **some**one.\"\"\"\n    print('**Hel**lo %s' % name)  # **S**ay **hel**lo\n";
+    assert_eq!(reader.bio_read_text(input)?, expected);
+    // Detected from the extension, and named with the language
+    assert_eq!(InputFormat::from_path(Path::new("app.tsx")), Some(InputFormat::Code(CodeLanguage::TypeScript)));
+    assert_eq!(InputFormat::Code(CodeLanguage::Shell).to_string(), "code:shell");
+    assert!("code:cobol".parse::<InputFormat>().is_err());
+    let config: Config = toml::from_str("format = \"code:rs\"").unwrap();
+    assert_eq!(config.format, Some(InputFormat::Code(CodeLanguage::Rust)));
+    assert_eq!(toml::to_string(&config).unwrap(), "format = \"code:rust\"\n");
+    Ok(())
+}
+
+#[test]
+fn test_bio_read_org() {
+    let reader = BioReader::new().renderer(Org).fixation_point(1).format(InputFormat::Auto);
+    let input = "#+TITLE: Notes\n\n* TODO [#A] Read more :books:\n:PROPERTIES:\n:ID: abc\n:END:\nSCHEDULED: <2024-01-01 Mon>\nSee [[https://example.com][the site]], *bold* and =verbatim= text.\n- [X] Done\n#+BEGIN_SRC rust\nfn main() {}\n#+END_SRC\n#+begin_quote\nQuoted words\n#+end_quote\n| a | table |\n";
+    let expected = "#+TITLE: Notes\n\n* TODO [#A] *Rea*\u{200B}d *mor*\u{200B}e :books:\n:PROPERTIES:\n:ID: abc\n:END:\nSCHEDULED: <2024-01-01 Mon>\n*Se*\u{200B}e [[https://example.com][*th*\u{200B}e *sit*\u{200B}e]], *bold* *an*\u{200B}d =verbatim= *tex*\u{200B}t.\n- [X] *Don*\u{200B}e\n#+BEGIN_SRC rust\nfn main() {}\n#+END_SRC\n#+begin_quote\n*Quot*\u{200B}ed *wor*\u{200B}ds\n#+end_quote\n| a | table |\n";
+    assert_eq!(reader.bio_read_text(input).unwrap(), expected);
+}
+
+#[test]
+fn test_bio_read_subtitles() -> std::io::Result<()> {
+    let reader = BioReader::new().renderer(Markdown).format(InputFormat::Auto);
+    let input = "1\n00:00:01,000 --> 00:00:02,500\n{\\an8}<i>Hello there</i>\n\n2\n00:00:03,000 --> 00:00:04,000\nFish & chips\n";
+    let expected = "1\n00:00:01,000 --> 00:00:02,500\n{\\an8}<i>**Hel**lo **the**re</i>\n\n2\n00:00:03,000 --> 00:00:04,000\n**Fi**sh & **chi**ps\n";
+    assert_eq!(reader.bio_read_text(input)?, expected);
+    let input = "WEBVTT - Movie\n\nNOTE Written by hand\n\nintro\n00:01.000 --> 00:02.000 align:start\n<v Alice>Good morning &amp; welcome\n";
+    let expected = "WEBVTT - Movie\n\nNOTE Written by hand\n\nintro\n00:01.000 --> 00:02.000 align:start\n<v Alice>**Go**od **morn**ing &amp; **welc**ome\n";
+    assert_eq!(reader.bio_read_text(input)?, expected);
+    // Fixations are wrapped in <b> tags by default, without escaping
+    let config = Config {
+        format: Some("srt".parse().unwrap()),
+        ..Config::default()
+    };
+    let reader = BioReader::from_config(&config).unwrap();
+    assert_eq!(reader.bio_read_text("1\n00:00:01,000 --> 00:00:02,000\nFish & chips\n")?, "1\n00:00:01,000 --> 00:00:02,000\n<b>Fi</b>sh & <b>chi</b>ps\n");
+    Ok(())
+}
+
+#[test]
+fn test_bio_read_email() -> std::io::Result<()> {
+    let reader = BioReader::new().renderer(Markdown).format(InputFormat::Auto);
+    // A multipart message with a quoted-printable text part, a base64 HTML part and an attachment
+    let input = "From alice@example.com Mon Jan  1 00:00:00 2024\nMessage-ID: <1@example.com>\nSubject: Hello there\nContent-Type: multipart/alternative; boundary=\"b\"\n\nPreamble\n--b\nContent-Type: text/plain; charset=utf-8\nContent-Transfer-Encoding: quoted-printable\n\nCaf=C3=A9 is =\nopen\n--b\nContent-Type: text/html\nContent-Transfer-Encoding: base64\n\nPHA+SGVsbG88L3A+\n--b\nContent-Type: text/plain\nContent-Disposition: attachment; filename=notes.txt\n\nAttached text\n--b--\nEpilogue\n";
+    let expected = "From alice@example.com Mon Jan  1 00:00:00 2024\nMessage-ID: <1@example.com>\nSubject: Hello there\nContent-Type: multipart/alternative; boundary=\"b\"\n\nPreamble\n--b\nContent-Type: text/plain; charset=utf-8\nContent-Transfer-Encoding: 8bit\n\n**Ca**fé **i**s **op**en\n--b\nContent-Type: text/html\nContent-Transfer-Encoding: 8bit\n\n<p>**Hel**lo</p>\n--b\nContent-Type: text/plain\nContent-Disposition: attachment; filename=notes.txt\n\nAttached text\n--b--\nEpilogue\n";
+    assert_eq!(reader.bio_read_text(input)?, expected);
+    // Messages of an mbox file, in other character sets or not
+    let input = "From a@b Mon Jan  1 00:00:00 2024\nMessage-ID: <2@example.com>\n\nFirst body\n\nFrom a@b Mon Jan  1 00:00:00 2024\nContent-Type: text/plain; charset=iso-8859-1\n\nSecond body\n";
+    let expected = "From a@b Mon Jan  1 00:00:00 2024\nMessage-ID: <2@example.com>\n\n**Fir**st **bo**dy\n\nFrom a@b Mon Jan  1 00:00:00 2024\nContent-Type: text/plain; charset=iso-8859-1\n\nSecond body\n";
+    assert_eq!(reader.bio_read_text(input)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_bio_read_diff() -> std::io::Result<()> {
+    let reader = BioReader::new().renderer(Markdown).format(InputFormat::Diff);
+    // The last hunk ends before a header that looks like a removed line
+    let input = "diff --git a/notes.txt b/notes.txt\n--- a/notes.txt\n+++ b/notes.txt\n@@ -1,2 +1,2 @@\n Some context\n-Old words\n+New words\n\\ No newline at end of file\n--- a/other.txt\n+++ b/other.txt\n@@ -1 +1 @@\n-Gone\n+Here\n";
+    let expected = "diff --git a/notes.txt b/notes.txt\n--- a/notes.txt\n+++ b/notes.txt\n@@ -1,2 +1,2 @@\n Some context\n-**O**ld **wor**ds\n+**N**ew **wor**ds\n\\ No newline at end of file\n--- a/other.txt\n+++ b/other.txt\n@@ -1 +1 @@\n-**Go**ne\n+**He**re\n";
+    assert_eq!(reader.bio_read_text(input)?, expected);
+    let mut output = Vec::new();
+    reader.bio_read(ByteByByte(input.as_bytes()), &mut output)?;
+    assert_eq!(output, expected.as_bytes());
+    // Colored markers are kept, and reset before the content
+    let input = "\x1b[1mdiff --git a/a b/a\x1b[m\n\x1b[36m@@ -1 +1 @@\x1b[m\n\x1b[31m-Old\x1b[m\n\x1b[32m+\x1b[m\x1b[32mNew\x1b[m\n";
+    let expected = "\x1b[1mdiff --git a/a b/a\x1b[m\n\x1b[36m@@ -1 +1 @@\x1b[m\n\x1b[31m-\x1b[m**O**ld\n\x1b[32m+\x1b[m\x1b[32m\x1b[m**N**ew\n";
+    assert_eq!(reader.clone().format(InputFormat::Auto).bio_read_text(input)?, expected);
+    assert_eq!(InputFormat::from_path(Path::new("fix.patch")), Some(InputFormat::Diff));
+    Ok(())
+}
+
+#[test]
+fn test_bio_read_man() -> std::io::Result<()> {
+    let reader = BioReader::new().renderer(Markdown).format(InputFormat::Man);
+    // Bold headings and options, underlined arguments and an overstruck bullet
+    let input = "N\x08NA\x08AM\x08ME\x08E\n     ls - list directory contents\n\n     -\x08--\x08-a\x08al\x08ll\x08l   do not ignore _\x08f_\x08i_\x08l_\x08e_\x08s\n     +\x08o item\n";
+    let expected = "**NAME**\n     **l**s - **li**st **direc**tory **cont**ents\n\n     **--all**   **d**o **n**ot **ign**ore **fil**es\n     **o** **it**em\n";
+    assert_eq!(reader.bio_read_text(input)?, expected);
+    // Split across reads, even between a character and the backspaces overstriking it
+    let mut output = Vec::new();
+    reader.bio_read(ByteByByte(input.as_bytes()), &mut output)?;
+    assert_eq!(output, expected.as_bytes());
+    // Detected from the overstriking
+    assert_eq!(reader.clone().format(InputFormat::Auto).bio_read_text(input)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_auto_format() -> std::io::Result<()> {
+    let reader = BioReader::new()
+        .emphasize("<b>".to_string(), "</b>".to_string())
+        .de_emphasize("".to_string(), "".to_string())
+        .format(InputFormat::Auto);
+    assert_eq!(reader.bio_read_text("<p>Hello <code>world</code></p>")?, "<p><b>Hel</b>lo <code>world</code></p>");
+    assert_eq!(reader.bio_read_text("# Hello `world`")?, "# <b>Hel</b>lo `world`");
+    assert_eq!(reader.bio_read_text("Hello `world`")?, "<b>Hel</b>lo `<b>wor</b>ld`");
+    // Input longer than what is sniffed, split across reads
+    let input = format!("<p>{}</p><code>world</code>", "Hello ".repeat(InputFormat::SNIFF_LEN));
+    let expected = format!("<p>{}</p><code>world</code>", "<b>Hel</b>lo ".repeat(InputFormat::SNIFF_LEN));
+    let mut output = Vec::new();
+    reader.bio_read(ByteByByte(input.as_bytes()), &mut output)?;
+    assert_eq!(output, expected.as_bytes());
+    // Line by line, the format is detected from the first line
+    let mut output = Flushes::default();
+    reader.bio_read_lines("<p>Hello</p>\n<code>world</code>\n".as_bytes(), &mut output)?;
+    assert_eq!(output.flushed.last().unwrap(), "<p><b>Hel</b>lo</p>\n<code>world</code>\n");
+    assert_eq!(output.flushed.first().unwrap(), "<p><b>Hel</b>lo</p>\n");
+    Ok(())
+}
+
+#[test]
+fn test_clone_and_setters() {
+    let mut reader = setup_reader(3).renderer(Markdown).fixation_ratio(0.5);
+    let clone = reader.clone();
+    reader.set_fixation_point(1).set_saccade(2).set_renderer(BBCode);
+    assert_eq!(reader.bio_read_text("reading is fun").unwrap(), "[b]readi[/b]ng is [b]fu[/b]n");
+    // The clone is unaffected, and shares the renderer and strategy
+    assert_eq!(clone.bio_read_text("reading is fun").unwrap(), "**read**ing **i**s **fu**n");
+    let debug = format!("{clone:?}");
+    assert!(debug.starts_with("BioReader { renderer: <custom>, fixation_strategy: <custom>, unicode: true, saccade: 1"), "{debug}");
+    assert_eq!(format!("{:?}", BioReader::default()), format!("{:?}", BioReader::new()));
+}
+
+#[test]
+fn test_renderers() {
+    let text = "Fish & chips";
+    fn render(renderer: impl EmphasisRenderer + Send + Sync + 'static) -> String {
+        BioReader::new().renderer(renderer).bio_read_text("Fish & chips").unwrap()
+    }
+    assert_eq!(render(Ansi::default()), "\x1b[1mFi\x1b[0m\x1b[2msh\x1b[0m & \x1b[1mchi\x1b[0m\x1b[2mps\x1b[0m");
+    assert_eq!(render(Html::default()), "<b>Fi</b>sh &amp; <b>chi</b>ps");
+    assert_eq!(render(Markdown), "**Fi**sh & **chi**ps");
+    assert_eq!(render(BBCode), "[b]Fi[/b]sh & [b]chi[/b]ps");
+    assert_eq!(render(Latex), "\\textbf{Fi}sh \\& \\textbf{chi}ps");
+    assert_eq!(render(Typst), "#strong[Fi]sh & #strong[chi]ps");
+    assert_eq!(render(Rst), "**Fi**\\ sh & **chi**\\ ps");
+    assert_eq!(render(Org), "*Fi*\u{200B}sh & *chi*\u{200B}ps");
+    assert_eq!(render(Rtf), "{\\b Fi}sh & {\\b chi}ps");
+    assert_eq!(render(Plain), text);
+    // Ansi defaults are the same as the default wrappers
+    assert_eq!(render(Ansi::default()), BioReader::new().bio_read_text(text).unwrap());
+}
+
+#[test]
+fn test_latex_and_typst_escaping() {
+    let text = "#1: 50% of {ab_cd} costs $5 & ~^ \\oh/ <ex@why> [*`zed`*] an.";
+    let reader = BioReader::new().renderer(Latex).fixation_point(1);
+    assert_eq!(
+        reader.bio_read_text(text).unwrap(),
+        "\\#1: 50\\% \\textbf{o}f \\{\\textbf{a}b\\_\\textbf{c}d\\} \\textbf{cos}ts \\$5 \\& \\textasciitilde{}\\textasciicircum{} \\textbackslash{}\\textbf{o}h/ <\\textbf{e}x@\\textbf{wh}y> [*`\\textbf{ze}d`*] \\textbf{a}n."
+    );
+    let reader = reader.renderer(Typst);
+    assert_eq!(
+        reader.bio_read_text(text).unwrap(),
+        "\\#1: 50% #strong[o]f {#strong[a]b\\_#strong[c]d} #strong[cos]ts \\$5 & \\~^ \\\\#strong[o]h\\/ \\<#strong[e]x\\@#strong[wh]y\\> \\[\\*\\`#strong[ze]d\\`\\*\\] #strong[a]n."
+    );
+    // LaTeX input has nothing to escape in its prose
+    let reader = BioReader::new().renderer(Latex).format(InputFormat::Latex);
+    assert_eq!(reader.bio_read_text("\\emph{Some} 50\\% \\& more").unwrap(), "\\emph{\\textbf{So}me} 50\\% \\& \\textbf{mo}re");
+}
+
+#[test]
+fn test_rtf_output() {
+    let reader = BioReader::new().renderer(Rtf);
+    // Characters outside of the Basic Multilingual Plane take a surrogate pair
+    assert_eq!(
+        reader.bio_read_text("{über}\tcafé \u{1F600}\r\nok\\").unwrap(),
+        "\\{{\\b \\u252?b}er\\}\\tab {\\b ca}f\\u233? \\u-10179?\\u-8704?\\par\n{\\b o}k\\\\"
+    );
+    // The markup of other formats would make the document invalid, so input is plain text
+    let config = Config {
+        to: Some(OutputFormat::Rtf),
+        ..Config::default()
+    };
+    let reader = BioReader::from_config(&config).unwrap();
+    assert_eq!(reader.bio_read_text("<b>{x}</b>").unwrap(), "<{\\b b}>\\{{\\b x}\\}</{\\b b}>");
+    let config = Config {
+        format: Some(InputFormat::Latex),
+        ..config
+    };
+    assert_eq!(BioReader::from_config(&config).unwrap_err().to_string(), "rtf output only supports plain text input, not latex");
+}
+
+#[test]
+fn test_markdown_escaping() {
+    use pulldown_cmark::{Event, Parser, Tag};
+    let text = "It's a well-known *fact*: snake_case, `code`, \\o/ and 2*3 don't mix with l'été or Straße.";
+    for compound_policy in [CompoundPolicy::Whole, CompoundPolicy::EachPart] {
+        for fixation_point in 1..=5 {
+            let reader = BioReader::new().renderer(Markdown).fixation_point(fixation_point).compound_policy(compound_policy);
+            let output = reader.bio_read_text(text).unwrap();
+            // Parsed back, the text is intact, with each word emphasized
+            let mut parsed = String::new();
+            let mut strong = 0;
+            for event in Parser::new(&output) {
+                match event {
+                    Event::Text(text) => parsed.push_str(&text),
+                    Event::Start(Tag::Strong) => strong += 1,
+                    _ => {}
+                }
+            }
+            assert_eq!(parsed, text, "{output}");
+            assert_eq!(strong, reader.bio_read_spans(text).into_iter().filter(|span| span.kind == SpanKind::Emphasized).count(), "{output}");
+        }
+    }
+}
+
+#[test]
+fn test_markdown_round_trip() {
+    use pulldown_cmark::{Event, Parser};
+    let text = "a\\_b, 2\\*3 and `co_de`, \\\\x";
+    let reader = BioReader::new().renderer(Markdown).format(InputFormat::Markdown);
+    let output = reader.bio_read_text(text).unwrap();
+    // Escapes in the input aren't escaped again
+    assert_eq!(output, "**a**\\_**b**, 2\\*3 **a**nd `co_de`, \\\\**x**");
+    let parse = |markdown: &str| {
+        let events = Parser::new(markdown).filter_map(|event| match event {
+            Event::Text(text) | Event::Code(text) => Some(text.into_string()),
+            _ => None,
+        });
+        events.collect::<String>()
+    };
+    assert_eq!(parse(&output), parse(text));
+}
+
+#[test]
+fn test_json_output() -> std::io::Result<()> {
+    let reader = BioReader::new().renderer(Json).exclude_words(HashSet::from([String::from("the")]));
+    assert_eq!(
+        reader.bio_read_text("the tab\tنصف")?,
+        concat!(
+            "{\"text\":\"the\"}\n",
+            "{\"text\":\" \"}\n",
+            "{\"word\":\"tab\",\"emphasized\":\"t\",\"rest\":\"ab\"}\n",
+            "{\"text\":\"\\t\"}\n",
+            "{\"word\":\"ن\u{200D}\u{200D}صف\",\"emphasized\":\"ن\u{200D}\",\"rest\":\"\u{200D}صف\"}\n",
+        )
+    );
+    // Nothing is lost, not even control characters
+    for file in fs::read_dir("tests/input")? {
+        let text = fs::read_to_string(file?.path())?.replace('\n', "\u{1}\r\n");
+        let output = reader.bio_read_text(&text)?;
+        assert!(output.lines().all(|line| line.starts_with('{') && line.ends_with('}') && !line.contains(char::is_control)));
+        assert_eq!(reader.strip(&output), text);
+    }
+    // Escapes that aren't written are understood too
+    assert_eq!(reader.strip("{\"text\":\"\\ud83d\\ude00\\/\\u00e9\"}\n"), "😀/é");
+    // Input is plain text, which is the only format supported
+    let config: Config = toml::from_str("to = \"json\"").unwrap();
+    assert_eq!(BioReader::from_config(&config).unwrap().bio_read_text("<b>")?, "{\"text\":\"<\"}\n{\"word\":\"b\",\"emphasized\":\"b\",\"rest\":\"\"}\n{\"text\":\">\"}\n");
+    let config: Config = toml::from_str("to = \"json\"\nformat = \"html\"").unwrap();
+    assert_eq!(BioReader::from_config(&config).unwrap_err().to_string(), "json output only supports plain text input, not html");
+    Ok(())
+}
+
+#[test]
+fn test_gradient() {
+    let gradient = Gradient {
+        levels: vec![["<1>".to_string(), "</1>".to_string()], ["<2>".to_string(), "</2>".to_string()]],
+        de_emphasize: ["<de>".to_string(), "</de>".to_string()],
+        escape_html: false,
+    };
+    let reader = BioReader::new().renderer(gradient.clone());
+    // Longer segments first, at most one per letter, and combining marks kept attached
+    assert_eq!(reader.bio_read_text("a ab reading").unwrap(), "<1>a</1> <1>a</1><de>b</de> <1>re</1><2>ad</2><de>ing</de>");
+    assert_eq!(reader.bio_read_text("e\u{301}a\u{301}rs").unwrap(), "<1>e\u{301}</1><2>a\u{301}</2><de>rs</de>");
+    let reader = BioReader::new().renderer(gradient).fixation_ratio(1.0);
+    assert_eq!(reader.bio_read_text("abcde").unwrap(), "<1>abc</1><2>de</2>");
+    let reader = BioReader::new().renderer(Gradient::ansi());
+    assert_eq!(reader.bio_read_text("Fish").unwrap(), "\x1b[1mF\x1b[0mi\x1b[2msh\x1b[0m");
+    let reader = BioReader::new().renderer(Gradient::html("a\"b"));
+    assert_eq!(reader.bio_read_text("<").unwrap(), "&lt;");
+    assert_eq!(reader.bio_read_text("f").unwrap(), "<span class=\"a&quot;b-1\">f</span>");
+}
+
+/// Apply spans to `text` by inserting wrappers, just like [`setup_reader`] does.
+fn apply_spans(text: &str, spans: &[EmphasisSpan]) -> String {
+    let mut output = String::new();
+    let mut last = 0;
+    for span in spans {
+        output.push_str(&text[last..span.range.start]);
+        let (left, right) = match span.kind {
+            SpanKind::Emphasized => ("<em>", "</em>"),
+            SpanKind::DeEmphasized => ("<de>", "</de>"),
+            SpanKind::Secondary => ("<se>", "</se>"),
+        };
+        output.push_str(left);
+        output.push_str(&text[span.range.clone()]);
+        output.push_str(right);
+        last = span.range.end;
+    }
+    output.push_str(&text[last..]);
+    output
+}
+
+#[test]
+fn test_bio_read_spans() -> std::io::Result<()> {
+    let reader = setup_reader(3);
+    for file in fs::read_dir("tests/input")? {
+        let text = fs::read_to_string(file?.path())?;
+        let spans = reader.bio_read_spans(&text);
+        assert_eq!(apply_spans(&text, &spans), reader.bio_read_text(&text)?);
+    }
+    // Byte offsets with multi-byte characters and markup
+    let reader = setup_reader(3).format(InputFormat::Markdown);
+    let text = "`naïve` **café**";
+    let spans = reader.bio_read_spans(text);
+    assert_eq!(
+        spans,
+        vec![
+            EmphasisSpan { range: 11..13, kind: SpanKind::Emphasized },
+            EmphasisSpan { range: 13..16, kind: SpanKind::DeEmphasized },
+        ]
+    );
+    assert_eq!(apply_spans(text, &spans), reader.bio_read_text(text)?);
+    Ok(())
+}
+
+#[test]
+fn test_bio_read_visit() -> std::io::Result<()> {
+    let reader = setup_reader(3);
+    for file in fs::read_dir("tests/input")? {
+        let text = fs::read_to_string(file?.path())?;
+        let mut visited = String::new();
+        let mut emphasized_words = 0;
+        reader.bio_read_visit(&text, |token| match token {
+            Token::Word { emphasized, rest } => {
+                visited.push_str(emphasized);
+                visited.push_str(rest);
+                emphasized_words += usize::from(!emphasized.is_empty());
+            }
+            Token::Separator(separator) => visited.push_str(separator),
+        });
+        assert_eq!(visited, text);
+        let spans = reader.bio_read_spans(&text);
+        assert_eq!(emphasized_words, spans.iter().filter(|span| span.kind == SpanKind::Emphasized).count());
+    }
+    // Skipped words and markup
+    let reader = setup_reader(3).saccade(2).format(InputFormat::Markdown);
+    let mut tokens = Vec::new();
+    reader.bio_read_visit("`naïve` **café** au lait", |token| tokens.push(format!("{token:?}")));
+    assert_eq!(
+        tokens,
+        [
+            r#"Separator("`naïve` **")"#,
+            r#"Word { emphasized: "ca", rest: "fé" }"#,
+            r#"Separator("** ")"#,
+            r#"Word { emphasized: "", rest: "au" }"#,
+            r#"Separator(" ")"#,
+            r#"Word { emphasized: "la", rest: "it" }"#,
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_bio_read_segments() -> std::io::Result<()> {
+    let reader = setup_reader(3);
+    for file in fs::read_dir("tests/input")? {
+        let input = fs::read(file?.path())?;
+        // Segments borrow from the input, and make it up
+        let segments: Vec<_> = reader.bio_read_segments(&input).collect();
+        assert_eq!(segments.iter().flat_map(|segment| segment.as_bytes()).copied().collect::<Vec<_>>(), input);
+        let emphasized = segments.iter().filter(|segment| matches!(segment, Segment::Emphasized(_))).count();
+        assert_eq!(emphasized, reader.bio_read_spans(&String::from_utf8_lossy(&input)).iter().filter(|span| span.kind == SpanKind::Emphasized).count());
+    }
+    // Markup and skipped words are separators
+    let reader = setup_reader(3).saccade(2).format(InputFormat::Markdown);
+    let segments: Vec<_> = reader.bio_read_segments("`naïve` **café** au lait".as_bytes()).collect();
+    assert_eq!(
+        segments,
+        [
+            Segment::Separator("`naïve` **"),
+            Segment::Emphasized("ca"),
+            Segment::Plain("fé"),
+            Segment::Separator("** au "),
+            Segment::Emphasized("la"),
+            Segment::Plain("it"),
+        ]
+    );
+    // Markdown must be valid UTF-8, so invalid input is given as it is
+    let segments: Vec<_> = reader.bio_read_segments(b"caf\xe9 au lait").collect();
+    assert_eq!(segments, [Segment::Separator("caf"), Segment::Invalid(b"\xe9"), Segment::Separator(" au lait")]);
+    Ok(())
+}
+
+#[test]
+fn test_bio_read_words() -> std::io::Result<()> {
+    let reader = setup_reader(3);
+    for file in fs::read_dir("tests/input")? {
+        let text = fs::read_to_string(file?.path())?;
+        for word in reader.bio_read_words(&text) {
+            // Character ranges count the same text as byte ranges
+            assert_eq!(text[..word.range.start].chars().count(), word.chars.start);
+            assert_eq!(text[word.range.clone()].chars().count(), word.chars.len());
+            assert!(word.emphasized.start == word.range.start && word.emphasized.end <= word.range.end);
+        }
+    }
+    // Skipped words and markup
+    let reader = setup_reader(3).saccade(2).format(InputFormat::Markdown);
+    let text = "`naïve` **café** au lait";
+    let words = reader.bio_read_words(text);
+    assert_eq!(
+        words,
+        [
+            WordSpan { range: 11..16, chars: 10..14, emphasized: 11..13 },
+            WordSpan { range: 19..21, chars: 17..19, emphasized: 19..19 },
+            WordSpan { range: 22..26, chars: 20..24, emphasized: 22..24 },
+        ]
+    );
+    let mut output = Vec::new();
+    write_cues(text, &words, CueFormat::Srt, 60, &mut output)?;
+    assert_eq!(
+        String::from_utf8_lossy(&output),
+        "1\n00:00:00,000 --> 00:00:01,000\n<b>ca</b>fé\n\n2\n00:00:01,000 --> 00:00:02,000\nau\n\n3\n00:00:02,000 --> 00:00:03,000\n<b>la</b>it\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_config() -> std::io::Result<()> {
+    let config: Config = toml::from_str(
+        r#"
+        fixation-point = 1
+        emphasize = "<em>{}</em>"
+        de-emphasize = "<de>{}</de>"
+        format = "md"
+
+        [theme]
+        emphasize = "bold bright-red on-black"
+        "#,
+    )
+    .unwrap();
+    let reader = BioReader::from_config(&config).unwrap();
+    let expected = setup_reader(1).format(InputFormat::Markdown);
+    let text = "Read the `docs` please";
+    assert_eq!(reader.bio_read_text(text)?, expected.bio_read_text(text)?);
+    // Round trip
+    let serialized = toml::to_string(&config).unwrap();
+    assert_eq!(toml::from_str::<Config>(&serialized).unwrap(), config);
+    // Invalid configurations
+    assert!(toml::from_str::<Config>("fixation = 1").is_err());
+    for config in [
+        "fixation-point = 0",
+        "emphasize = \"<em>\"",
+        "theme = { emphasize = \"sparkly\" }",
+        "skip = [\"(\"]",
+    ] {
+        let config: Config = toml::from_str(config).unwrap();
+        assert!(BioReader::from_config(&config).is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_themes() -> std::io::Result<()> {
+    let text = "hello";
+    let cases = [
+        ("bold-dim", "\x1b[1mhel\x1b[0m\x1b[2mlo\x1b[0m"),
+        ("color-contrast", "\x1b[97mhel\x1b[0m\x1b[90mlo\x1b[0m"),
+        ("underline", "\x1b[4mhel\x1b[0mlo"),
+        ("inverse", "\x1b[7mhel\x1b[0mlo"),
+    ];
+    for (name, expected) in cases {
+        let theme: Theme = name.parse().unwrap();
+        assert_eq!(theme.to_string(), name);
+        assert_eq!(BioReader::new().theme(theme).bio_read_text(text)?, expected);
+        // Presets in the config
+        let config: Config = toml::from_str(&format!("theme = {{ preset = \"{name}\" }}")).unwrap();
+        assert_eq!(BioReader::from_config(&config).unwrap().bio_read_text(text)?, expected);
+    }
+    assert_eq!(Theme::default(), Theme::BoldDim);
+    assert_eq!(BioReader::new().theme(Theme::BoldDim).bio_read_text(text)?, BioReader::new().bio_read_text(text)?);
+    // Wrappers keep the other half of the preset
+    let reader = BioReader::new().theme(Theme::Underline).de_emphasize(String::from("<de>"), String::from("</de>"));
+    assert_eq!(reader.bio_read_text(text)?, "\x1b[4mhel\x1b[0m<de>lo</de>");
+    let config: Config = toml::from_str("theme = { preset = \"inverse\", de-emphasize = \"italic\" }").unwrap();
+    assert_eq!(BioReader::from_config(&config).unwrap().bio_read_text(text)?, "\x1b[7mhel\x1b[0m\x1b[3mlo\x1b[0m");
+    assert!("sparkly".parse::<Theme>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_color_choice() -> std::io::Result<()> {
+    let text = "hello";
+    let render = |config: &str| BioReader::from_config(&toml::from_str(config).unwrap()).unwrap().bio_read_text(text);
+    assert_eq!(render("color = \"never\"")?, "hello");
+    assert_eq!(render("color = \"never\"\ngradient = true")?, "hello");
+    assert_eq!(render("color = \"never\"\ntheme = { preset = \"inverse\" }")?, "hello");
+    // Other output formats and custom wrappers are kept
+    assert_eq!(render("color = \"never\"\nto = \"markdown\"")?, "**hel**lo");
+    assert_eq!(render("color = \"never\"\nemphasize = \"<em>{}</em>\"")?, "<em>hel</em>lo");
+    for choice in ["auto", "always"] {
+        assert_eq!(render(&format!("color = \"{choice}\""))?, BioReader::new().bio_read_text(text)?);
+    }
+    assert!(toml::from_str::<Config>("color = \"sometimes\"").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_exclude_words() -> std::io::Result<()> {
+    let words = HashSet::from([String::from("THE"), String::from("don't"), String::from("café")]);
+    let reader = setup_reader(3).exclude_words(words.clone());
+    assert_eq!(reader.bio_read_text("The cat, the CAFÉ. Don't!")?, "The <em>c</em><de>at</de>, the CAFÉ. Don't!");
+    // Excluded words don't count towards the saccade
+    let reader = setup_reader(3).saccade(2).exclude_words(words);
+    assert_eq!(reader.bio_read_text("one the two three")?, "<em>o</em><de>ne</de> the two <em>thr</em><de>ee</de>");
+    // Setting them again replaces them
+    let mut reader = reader;
+    reader.set_exclude_words(HashSet::new());
+    assert_eq!(reader.bio_read_text("the end")?, "<em>t</em><de>he</de> end");
+    let config: Config = toml::from_str("exclude-words = [\"a\"]\nto = \"markdown\"").unwrap();
+    assert_eq!(BioReader::from_config(&config).unwrap().bio_read_text("A cat")?, "A **c**at");
+    Ok(())
+}
+
+#[test]
+fn test_frequency_model() -> std::io::Result<()> {
+    let reader = setup_reader(3).frequency_model("tests/fixtures/frequency.txt")?;
+    assert_eq!(
+        reader.bio_read_text("The bionic reading of ravioli")?,
+        "<em>T</em><de>he</de> <em>bion</em><de>ic</de> <em>rea</em><de>ding</de> <em>o</em><de>f</de> <em>ravio</em><de>li</de>"
+    );
+    // Clones share the model
+    assert_eq!(reader.clone().bio_read_text("the")?, "<em>t</em><de>he</de>");
+    let model = Frequency::from_reader("the 100\nTHE 900\nfish 10".as_bytes())?;
+    assert_eq!(model.commonness("The"), 1.0);
+    assert_eq!(model.commonness("fish"), 1.0 / 3.0);
+    assert_eq!(model.commonness("chips"), 0.0);
+    let model = model.ratios(0.0, 1.0);
+    assert_eq!(model.emphasized_len("the"), 1);
+    assert_eq!(model.emphasized_len("chips"), 5);
+    assert_eq!(model.emphasized_len("..."), 0);
+    // Invalid lists
+    for list in ["the", "the many", "the 1 2"] {
+        let error = Frequency::from_reader(list.as_bytes()).unwrap_err();
+        assert!(matches!(error, Error::Format { path: None, .. }));
+    }
+    let error = setup_reader(3).frequency_model("tests/fixtures/missing.txt").unwrap_err();
+    assert!(matches!(error, Error::Io { .. }));
+    assert_eq!(error.path(), Some(Path::new("tests/fixtures/missing.txt")));
+    Ok(())
+}
+
+#[test]
+fn test_language() -> std::io::Result<()> {
+    for language in Language::ALL {
+        assert_eq!(language.code().parse::<Language>().unwrap(), language);
+        assert_eq!(language.to_string(), language.code());
+    }
+    assert_eq!("FR".parse::<Language>().unwrap(), Language::French);
+    assert!("xx".parse::<Language>().is_err());
+    // Apostrophes are kept inside words in English, but not in French
+    assert_eq!(setup_reader(3).bio_read_text("l'homme")?, "<em>l'ho</em><de>mme</de>");
+    assert_eq!(setup_reader(3).language(Language::French).bio_read_text("l'homme")?, "<em>l</em>'<em>hom</em><de>me</de>");
+    assert_eq!(setup_reader(3).language(Language::French).bio_read_text("arc-en-ciel")?, setup_reader(3).bio_read_text("arc-en-ciel")?);
+    // Casing rules apply to excluded words, whichever is set first
+    let words = HashSet::from([String::from("ILIK")]);
+    let reader = setup_reader(3).exclude_words(words.clone()).language(Language::Turkish);
+    assert_eq!(reader.bio_read_text("ılık ilik")?, "ılık <em>il</em><de>ik</de>");
+    let reader = setup_reader(3).exclude_words(words).language(Language::English);
+    assert_eq!(reader.bio_read_text("ılık ilik")?, "<em>ıl</em><de>ık</de> ilik");
+    let config: Config = toml::from_str("language = \"it\"\nto = \"markdown\"").unwrap();
+    assert_eq!(BioReader::from_config(&config).unwrap().bio_read_text("dell'arte")?, "**de**ll'**ar**te");
+    assert!(toml::from_str::<Config>("language = \"italian\"").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_cjk_pass_through() -> std::io::Result<()> {
+    // Chinese and Japanese are passed through, rather than emphasized as one long word
+    assert_eq!(setup_reader(3).bio_read_text("我们是朋友")?, "我们是朋友");
+    assert_eq!(setup_reader(3).bio_read_text("日本語のテキスト")?, "日本語のテキスト");
+    assert_eq!(setup_reader(3).bio_read_text("Rust语言")?, "<em>Ru</em><de>st</de>语言");
+    // Korean is separated into words by spaces
+    assert_eq!(setup_reader(3).bio_read_text("안녕하세요 세계")?, "<em>안녕하</em><de>세요</de> <em>세</em><de>계</de>");
+    assert!(!is_cjk('a') && !is_cjk('한') && is_cjk('ー'));
+    Ok(())
+}
+
+#[test]
+fn test_skip_acronyms() -> std::io::Result<()> {
+    let reader = setup_reader(3).skip_acronyms(true);
+    assert_eq!(reader.bio_read_text("NASA and ΝΑΤΟ")?, "NASA <em>a</em><de>nd</de> ΝΑΤΟ");
+    // Single letters, mixed case and lowercase letters after an apostrophe are not acronyms
+    assert_eq!(reader.bio_read_text("I")?, "<em>I</em>");
+    assert_eq!(reader.bio_read_text("Nasa")?, "<em>Na</em><de>sa</de>");
+    assert_eq!(reader.bio_read_text("NASA's")?, "<em>NAS</em><de>A's</de>");
+    // Acronyms don't count towards the saccade
+    let reader = reader.saccade(2);
+    assert_eq!(reader.bio_read_text("one HTTP two three")?, "<em>o</em><de>ne</de> HTTP two <em>thr</em><de>ee</de>");
+    // Disabled by default
+    assert_eq!(setup_reader(3).bio_read_text("HTTP")?, "<em>HT</em><de>TP</de>");
+    Ok(())
+}
+
+#[test]
+fn test_word_style_hook() -> std::io::Result<()> {
+    let dictionary = HashSet::from(["gonna"]);
+    let reader = setup_reader(3).saccade(2).word_style_hook(move |info| {
+        if dictionary.contains(info.word) {
+            Some(StyleOverride::skip())
+        } else if info.word == "forced" && info.plain {
+            Some(StyleOverride::emphasize(99))
+        } else if info.word.starts_with(char::is_uppercase) && !info.sentence_start {
+            Some(StyleOverride::render(Html::with_class("name")))
+        } else {
+            None
+        }
+    });
+    // Skipped words don't count towards the saccade, and words given letters are emphasized regardless of it
+    assert_eq!(reader.bio_read_text("we gonna read now")?, "<em>w</em><de>e</de> gonna read <em>n</em><de>ow</de>");
+    assert_eq!(reader.bio_read_text("one forced")?, "<em>o</em><de>ne</de> <em>forced</em>");
+    assert_eq!(reader.bio_read_text("Hi. Meet Ada")?, "<em>H</em><de>i</de>. Meet <b class=\"name\">A</b>da");
+    // The hook is given words whole, and the observer what was emphasized
+    let reader = setup_reader(3).compound_policy(CompoundPolicy::EachPart).word_style_hook(|info| {
+        assert_eq!(info.word, "state-of-the-art");
+        assert_eq!(info.letters, 13);
+        Some(StyleOverride::emphasize(2))
+    });
+    let stats = reader.bio_read_with_stats("state-of-the-art".as_bytes(), &mut Vec::new())?;
+    assert_eq!(stats.emphasized_characters, 2);
+    // Spans follow the emphasis, but not the renderer
+    let reader = setup_reader(3).word_style_hook(|_| Some(StyleOverride::emphasize(1).with_renderer(Html::default())));
+    assert_eq!(reader.bio_read_spans("hello")[0], EmphasisSpan { range: 0..1, kind: SpanKind::Emphasized });
+    Ok(())
+}
+
+#[test]
+fn test_secondary_fixation() -> std::io::Result<()> {
+    let wrap = Wrap {
+        emphasize: ["<em>".to_string(), "</em>".to_string()],
+        de_emphasize: ["<de>".to_string(), "</de>".to_string()],
+        secondary: ["<se>".to_string(), "</se>".to_string()],
+    };
+    let text = "an incomprehensible internationalization, bio-readability";
+    let reader = BioReader::new().renderer(wrap.clone()).secondary_fixation(true);
+    // Only words longer than 12 letters, with compound words counted whole
+    assert_eq!(
+        reader.bio_read_text(text)?,
+        "<em>a</em><de>n</de> <em>incompre</em><de>hen</de><se>si</se><de>ble</de> <em>internatio</em><de>nal</de><se>iza</se><de>tion</de>, <em>bio-read</em><de>ab</de><se>il</se><de>ity</de>"
+    );
+    assert_eq!(reader.strip(&reader.bio_read_text(text)?), text);
+    assert_eq!(apply_spans(text, &reader.bio_read_spans(text)), reader.bio_read_text(text)?);
+    let mut words = Vec::new();
+    reader.bio_read_visit(text, |token| {
+        if let Token::Word { emphasized, rest } = token {
+            words.push((emphasized.to_string(), rest.to_string()));
+        }
+    });
+    assert_eq!(words[1], ("incompre".to_string(), "hensible".to_string()));
+    // Cursive scripts stay joined around each split, except after letters that don't join the next, like ر
+    let text = "بالاستراتيجيات";
+    let output = reader.bio_read_text(text)?;
+    assert_eq!(output, "<em>بالاستر</em><de>ات\u{200D}</de><se>\u{200D}يج\u{200D}</se><de>\u{200D}يات</de>");
+    assert_eq!(reader.strip(&output), text);
+    let reader = reader.renderer(Json);
+    assert_eq!(
+        reader.bio_read_text("internationalization")?,
+        "{\"word\":\"internationalization\",\"emphasized\":\"internatio\",\"rest\":\"nalization\",\"secondary\":\"iza\"}\n"
+    );
+    // Off by default
+    assert_eq!(BioReader::new().renderer(wrap).bio_read_text("internationalization")?, "<em>internatio</em><de>nalization</de>");
+    Ok(())
+}
+
+#[test]
+fn test_token_policy() -> std::io::Result<()> {
+    let text = "IPv6 in 2023, x86_64 and the 3rd one";
+    let reader = setup_reader(3).emphasize(String::from("["), String::from("]")).de_emphasize(String::new(), String::new());
+    assert_eq!(reader.bio_read_text(text)?, "[I]Pv6 [i]n 2023, [x]86_64 [a]nd [t]he 3[r]d [o]ne");
+    let reader = reader.token_policy(TokenPolicy::IncludeDigits);
+    assert_eq!(reader.bio_read_text(text)?, "[IP]v6 [i]n [20]23, [x]86_[6]4 [a]nd [t]he [3]rd [o]ne");
+    // Digits right after other ASCII characters start words too
+    assert_eq!(reader.bio_read_text("(42)")?, "([4]2)");
+    let reader = reader.token_policy(TokenPolicy::SkipNumeric);
+    assert_eq!(reader.bio_read_text(text)?, "IPv6 [i]n 2023, x86_64 [a]nd [t]he 3rd [o]ne");
+    // Words with digits don't count towards the saccade
+    assert_eq!(reader.clone().saccade(2).bio_read_text("one 2 two three")?, "[o]ne 2 two [thr]ee");
+    // Without Unicode, only ASCII digits count
+    let reader = reader.token_policy(TokenPolicy::IncludeDigits).unicode(false);
+    assert_eq!(reader.bio_read_text("٣4")?, "٣[4]");
+    Ok(())
+}
+
+#[test]
+fn test_sentence_boost() -> std::io::Result<()> {
+    let reader = setup_reader(3).emphasize(String::from("["), String::from("]")).de_emphasize(String::new(), String::new());
+    let text = "(Stop here.) She said so. It costs 3.50 now\nand then? 好。 Yes";
+    assert_eq!(reader.bio_read_text(text)?, "([St]op [he]re.) [S]he [sa]id [s]o. [I]t [cos]ts 3.50 [n]ow\n[a]nd [th]en? 好。 [Y]es");
+    let reader = reader.sentence_boost(1);
+    assert_eq!(reader.bio_read_text(text)?, "([Sto]p [he]re.) [Sh]e [sa]id [s]o. [It] [cos]ts 3.50 [n]ow\n[a]nd [th]en? 好。 [Ye]s");
+    // The boost applies to emphasized words only
+    assert_eq!(reader.clone().saccade(2).bio_read_text("One two. Three four five.")?, "[On]e two. [Thre]e four [fi]ve.");
+    // Text passed through untouched is inside the sentence
+    let reader = reader.preserve_urls(true);
+    assert_eq!(reader.bio_read_text("See https://example.com. Then http://a.b/ again")?, "[Se]e https://example.com. [The]n http://a.b/ [aga]in");
+    Ok(())
+}
+
+#[test]
+fn test_compound_policy() -> std::io::Result<()> {
+    let text = "a state-of-the-art, well\u{2011}known tool";
+    let reader = setup_reader(3).emphasize(String::from("["), String::from("]")).de_emphasize(String::new(), String::new());
+    assert_eq!(reader.bio_read_text(text)?, "[a] [state-of-]the-art, [well\u{2011}k]nown [to]ol");
+    let reader = reader.compound_policy(CompoundPolicy::EachPart);
+    assert_eq!(reader.bio_read_text(text)?, "[a] [sta]te-[o]f-[t]he-[a]rt, [we]ll\u{2011}[kno]wn [to]ol");
+    // The compound counts as one word for the saccade and excluded words
+    assert_eq!(reader.clone().saccade(2).bio_read_text(text)?, "[a] state-of-the-art, [we]ll\u{2011}[kno]wn tool");
+    let reader = reader.exclude_words(HashSet::from([String::from("well-known")]));
+    assert_eq!(reader.bio_read_text("well-known tool")?, "well-known [to]ol");
+    // Statistics add up the emphasized characters of all parts
+    let stats = reader.bio_read_with_stats("state-of-the-art".as_bytes(), &mut Vec::new())?;
+    assert_eq!((stats.words, stats.characters, stats.emphasized_characters), (1, 16, 6));
+    // Other joiners don't split words
+    assert_eq!(reader.bio_read_text("don't")?, "[do]n't");
+    Ok(())
+}
+
+#[test]
+fn test_max_word_len() -> std::io::Result<()> {
+    let reader = setup_reader(3).emphasize(String::from("["), String::from("]")).de_emphasize(String::new(), String::new()).max_word_len(5);
+    // Long words are written as-is, up to the next character between words, and don't count towards the saccade
+    assert_eq!(reader.bio_read_text("big wonderful, world")?, "[b]ig wonderful, [wor]ld");
+    assert_eq!(reader.clone().saccade(2).bio_read_text("one wonderful two three")?, "[o]ne wonderful two [thr]ee");
+    // Combining marks and joiners don't end them
+    assert_eq!(reader.bio_read_text("cafe\u{301}e\u{301}e\u{301} don't-stop me")?, "cafe\u{301}e\u{301}e\u{301} don't-stop [m]e");
+    // Chunks may end anywhere
+    let mut processor = BioReadProcessor::new(&reader);
+    let mut output = Vec::new();
+    for chunk in ["won", "der", "ful", " world"] {
+        processor.push_bytes(chunk.as_bytes(), &mut output)?;
+    }
+    processor.finish(&mut output)?;
+    assert_eq!(String::from_utf8_lossy(&output), "wonderful [wor]ld");
+    // Or they are split into pieces emphasized as words
+    let reader = reader.long_word_policy(LongWordPolicy::Split);
+    assert_eq!(reader.bio_read_text("big wonderful, world")?, "[b]ig [won]de[rf]ul, [wor]ld");
+    // Runs of non-whitespace characters up to the maximum length are still matched against skip patterns
+    let reader = setup_reader(3).max_word_len(32).preserve_urls(true);
+    let long = "a".repeat(100);
+    assert_eq!(
+        reader.bio_read_text(&format!("https://example.com/page {long} word"))?,
+        format!("https://example.com/page {long} <em>wo</em><de>rd</de>")
+    );
+    assert!(BioReader::from_config(&Config { max_word_len: Some(0), ..Config::default() }).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_rtl() -> std::io::Result<()> {
+    let reader = setup_reader(3);
+    // The logically first letters are emphasized, and text between words stays where it is
+    assert_eq!(reader.bio_read_text("Hello שלום, world!")?, "<em>Hel</em><de>lo</de> <em>של</em><de>ום</de>, <em>wor</em><de>ld</de>!");
+    // Joiners keep connected Arabic letters joined, unless they don't connect
+    assert_eq!(reader.bio_read_text("مرحبا")?, "<em>مرح\u{200D}</em><de>\u{200D}با</de>");
+    assert_eq!(reader.bio_read_text("دار")?, "<em>د</em><de>ار</de>");
+    // Combining marks are skipped when deciding, and stay with their letter
+    assert_eq!(reader.bio_read_text("كَتَبَ")?, "<em>كَ\u{200D}</em><de>\u{200D}تَبَ</de>");
+    // Tatweels, zero-width non-joiners and bidi marks don't split words or count as letters
+    assert_eq!(reader.bio_read_text("مـــرحبا")?, reader.bio_read_text("مرحبا")?.replacen('م', "مـــ", 1));
+    assert_eq!(reader.bio_read_text("می\u{200C}خواهم")?, "<em>می\u{200C}خو</em><de>اهم</de>");
+    assert_eq!(reader.bio_read_text("\u{200F}שלום\u{200F} hi")?, "\u{200F}<em>של</em><de>ום\u{200F}</de> <em>h</em><de>i</de>");
+    // Spans still line up with the input
+    let text = "مرحبا";
+    let spans = reader.bio_read_spans(text);
+    assert_eq!(spans.iter().map(|span| &text[span.range.clone()]).collect::<String>(), text);
+    assert_eq!(reader.join_cursive(false).bio_read_text(text)?, "<em>مرح</em><de>با</de>");
+    Ok(())
+}
+
+#[test]
+fn test_strip() -> std::io::Result<()> {
+    let text = "Hello, wonderful world! مرحبا naïve";
+    let readers = [
+        setup_reader(3),
+        BioReader::new(),
+        BioReader::new().theme(Theme::Inverse),
+        BioReader::new().renderer(Ansi::default()),
+        BioReader::new().renderer(Gradient::ansi()),
+        BioReader::new().renderer(Plain),
+        // Wrappers containing one another
+        BioReader::new().emphasize(String::from("**"), String::from("**")).de_emphasize(String::from("*"), String::from("*")),
+    ];
+    for reader in readers {
+        assert_eq!(reader.strip(&reader.bio_read_text(text)?), text);
+    }
+    // Other markup is left as-is
+    let reader = BioReader::new().renderer(Html::default());
+    assert_eq!(reader.strip(&reader.bio_read_text("hello")?), "<b>hel</b>lo");
+    // Text without markup is unchanged
+    assert_eq!(setup_reader(3).strip("<b>hello</b> \u{200D}"), "<b>hello</b> \u{200D}");
+    Ok(())
+}
+
+#[test]
+fn test_iterator_adapter() {
+    let reader = setup_reader(3);
+    let lines = ["hello world", "", "bionic"];
+    let expected = ["<em>hel</em><de>lo</de> <em>wor</em><de>ld</de>", "", "<em>bio</em><de>nic</de>"];
+    assert_eq!(lines.iter().copied().bio_read(&reader).collect::<Vec<_>>(), expected);
+    assert_eq!(lines.map(String::from).iter().bio_read(&reader).rev().collect::<Vec<_>>(), expected.iter().rev().copied().collect::<Vec<_>>());
+    assert_eq!(lines.into_iter().bio_read(&reader).len(), 3);
+    // Errors are passed through
+    let results = [Ok("hello"), Err("oops")];
+    assert_eq!(results.into_iter().bio_read(&reader).collect::<Vec<_>>(), [Ok(String::from("<em>hel</em><de>lo</de>")), Err("oops")]);
+}
+
+#[test]
+fn test_display() -> std::io::Result<()> {
+    let reader = setup_reader(3);
+    for file in fs::read_dir("tests/input")? {
+        let text = fs::read_to_string(file?.path())?;
+        assert_eq!(reader.display(&text).to_string(), reader.bio_read_text(&text)?);
+    }
+    let reader = setup_reader(3).format(InputFormat::Html);
+    assert_eq!(format!("{}", reader.display("<p>hello</p>")), "<p><em>hel</em><de>lo</de></p>");
+    assert_eq!(format!("{}", reader.display("")), "");
+    Ok(())
+}
+
+#[test]
+fn test_bio_writer() -> std::io::Result<()> {
+    use std::io::Write;
+    let reader = setup_reader(3);
+    for file in fs::read_dir("tests/input")? {
+        let text = fs::read_to_string(file?.path())?;
+        // Written a byte at a time, splitting words and characters
+        let mut writer = BioWriter::new(&reader, Vec::new());
+        for byte in text.as_bytes() {
+            writer.write_all(&[*byte])?;
+        }
+        writer.flush()?;
+        assert_eq!(String::from_utf8(writer.finish()?).unwrap(), reader.bio_read_text(&text)?);
+    }
+    // Dropping the writer writes out the last word
+    let mut output = Vec::new();
+    {
+        let mut writer = BioWriter::new(&reader, &mut output);
+        let word = "café";
+        write!(writer, "naïve {word}")?;
+        assert_eq!(writer.get_ref().len(), "<em>naï</em><de>ve</de> ".len());
+    }
+    assert_eq!(String::from_utf8(output).unwrap(), "<em>naï</em><de>ve</de> <em>ca</em><de>fé</de>");
+    Ok(())
+}
+
+#[test]
+fn test_bio_read_processor() -> std::io::Result<()> {
+    let reader = setup_reader(3);
+    for file in fs::read_dir("tests/input")? {
+        let text = fs::read_to_string(file?.path())?;
+        // Pushed in frames of a few bytes, each written to its own buffer
+        let mut processor = BioReadProcessor::new(&reader);
+        let mut output = Vec::new();
+        for frame in text.as_bytes().chunks(5) {
+            let mut buffer = Vec::new();
+            processor.push_bytes(frame, &mut buffer)?;
+            output.extend_from_slice(&buffer);
+        }
+        processor.finish(&mut output)?;
+        assert_eq!(String::from_utf8(output).unwrap(), reader.bio_read_text(&text)?);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_bio_read_adapter() -> std::io::Result<()> {
+    use std::io::{BufReader, Read};
+    let reader = setup_reader(3);
+    for file in fs::read_dir("tests/input")? {
+        let text = fs::read_to_string(file?.path())?;
+        // Read from a byte at a time, and into a few bytes at a time
+        let mut source = BioRead::new(&reader, BufReader::with_capacity(1, text.as_bytes()));
+        let mut output = Vec::new();
+        let mut buf = [0; 3];
+        loop {
+            match source.read(&mut buf)? {
+                0 => break,
+                n => output.extend_from_slice(&buf[..n]),
+            }
+        }
+        assert_eq!(String::from_utf8(output).unwrap(), reader.bio_read_text(&text)?);
+    }
+    // Nothing to read
+    let mut output = String::new();
+    BioRead::new(&reader, "".as_bytes()).read_to_string(&mut output)?;
+    assert_eq!(output, "");
+    Ok(())
+}
+
+#[test]
+fn test_try_fixation_point() {
+    let reader = setup_reader(3).try_fixation_point(1).unwrap();
+    assert_eq!(reader.bio_read_text("reading").unwrap(), setup_reader(1).bio_read_text("reading").unwrap());
+    for fixation_point in [0, 6, usize::MAX] {
+        let error = BioReader::new().try_fixation_point(fixation_point).unwrap_err();
+        assert_eq!(error.fixation_point(), fixation_point);
+        let error = Error::from(error);
+        assert!(matches!(error, Error::FixationPoint(_)));
+        assert!(std::error::Error::source(&error).is_some());
+    }
+}
+
+#[test]
+fn test_errors() {
+    // Invalid options are named
+    let error = BioReader::from_config(&Config { saccade: Some(0), ..Config::default() }).unwrap_err();
+    assert!(matches!(error, Error::Config { option: "saccade", .. }));
+    assert_eq!(error.to_string(), "Saccade should be at least 1");
+    let error = BioReader::from_config(&Config { fixation_point: Some(9), ..Config::default() }).unwrap_err();
+    assert_eq!(error.option(), Some("fixation-point"));
+    let error = BioReader::from_config(&Config { emphasize: Some(String::from("*")), ..Config::default() }).unwrap_err();
+    assert_eq!(error.option(), Some("emphasize"));
+    // Input that isn't valid in its format, which may be given a path
+    let error = setup_reader(3).format(InputFormat::Org).bio_read(&b"* \xff"[..], &mut std::io::sink()).unwrap_err();
+    assert!(matches!(error, Error::Encoding { path: None, .. }));
+    assert_eq!(error.option(), None);
+    let error = error.with_path("notes.org").with_path("other.org");
+    assert_eq!(error.path(), Some(Path::new("notes.org")));
+    assert_eq!(error.to_string(), "notes.org: Org input is not valid UTF-8");
+    // Errors go through I/O errors intact, while bare I/O errors are unwrapped
+    let io_error = std::io::Error::from(error);
+    assert_eq!(io_error.kind(), std::io::ErrorKind::InvalidData);
+    assert!(matches!(Error::from(io_error), Error::Encoding { path: Some(_), .. }));
+    struct Failing;
+    impl std::io::Write for Failing {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(std::io::ErrorKind::BrokenPipe, "closed"))
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    let error = setup_reader(3).bio_read("hello".as_bytes(), &mut Failing).unwrap_err();
+    assert!(matches!(&error, Error::Io { path: None, source } if source.kind() == std::io::ErrorKind::BrokenPipe));
+    assert_eq!(std::io::Error::from(error).kind(), std::io::ErrorKind::BrokenPipe);
+}
+
+#[test]
+fn test_bio_read_with_stats() -> std::io::Result<()> {
+    let reader = setup_reader(3);
+    for file in fs::read_dir("tests/input")? {
+        let text = fs::read_to_string(file?.path())?;
+        // Output is the same as without statistics
+        let mut output = Vec::new();
+        let stats = reader.bio_read_with_stats(text.as_bytes(), &mut output)?;
+        assert_eq!(String::from_utf8(output).unwrap(), reader.bio_read_text(&text)?);
+        assert!(stats.emphasized_characters <= stats.characters);
+        assert!(stats.longest_word.chars().count() <= stats.characters);
+    }
+    // Excluded words and words skipped by the saccade are counted, with nothing emphasized
+    let reader = setup_reader(3).saccade(2).exclude_words(HashSet::from([String::from("the")]));
+    let mut stats = reader.bio_read_with_stats("the reading of the text".as_bytes(), &mut Vec::new())?;
+    assert_eq!(stats.words, 5);
+    assert_eq!(stats.characters, 19);
+    assert_eq!(stats.emphasized_characters, 4 + 2);
+    assert_eq!(stats.longest_word, "reading");
+    // Adding up statistics keeps the first longest word
+    stats += reader.bio_read_with_stats("writing".as_bytes(), &mut Vec::new())?;
+    assert_eq!(stats.words, 6);
+    assert_eq!(stats.longest_word, "reading");
+    let empty = BioReadStats::default();
+    assert_eq!(empty.emphasized_ratio(), 0.0);
+    assert_eq!(empty.reading_time(200), std::time::Duration::ZERO);
+    assert_eq!(empty.estimated_minutes(ReadingSpeed::default()).saved(), 0.0);
+    // Reading speeds depend on the language, and bio-read text can be read at another speed
+    let estimate = stats.estimated_minutes(ReadingSpeed::new(120).bio_read(100));
+    assert_eq!((estimate.original, estimate.bio_read), (0.05, 0.06));
+    assert!(estimate.saved() < 0.0);
+    assert_eq!(ReadingSpeed::default(), ReadingSpeed::for_language(Language::English));
+    for language in Language::ALL {
+        let speed = ReadingSpeed::for_language(language);
+        assert_eq!(speed.original, speed.bio_read);
+        assert!((150..=250).contains(&speed.original), "{language}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_paragraphs() -> std::io::Result<()> {
+    /// Records words and paragraph starts.
+    #[derive(Default)]
+    struct Events(Vec<String>);
+    impl Observer for Events {
+        fn on_word(&mut self, word: &str, emphasized: usize) {
+            self.0.push(format!("{word}:{emphasized}"));
+        }
+        fn on_paragraph(&mut self) {
+            self.0.push(String::from("¶"));
+        }
+    }
+    let reader = setup_reader(3).emphasize(String::from("["), String::from("]")).de_emphasize(String::new(), String::new());
+    // Line breaks alone don't separate paragraphs, while lines with nothing but whitespace do
+    let text = "\n\nA title\nline\r\n \t\r\n--\n\n\nnext 好\n\n";
+    let mut events = Events::default();
+    reader.bio_read_with_observer(text.as_bytes(), &mut Vec::new(), &mut events)?;
+    assert_eq!(events.0, ["¶", "A:1", "title:3", "line:2", "¶", "next:2"]);
+    // Paragraphs restart the saccade and sentence tracking, keeping blank lines as they are
+    let reader = reader.saccade(2).sentence_boost(1);
+    let text = "# Some title\n\nIt starts. Then\n\n\nends";
+    assert_eq!(reader.bio_read_text(text)?, "# [Som]e title\n\n[I]t starts. [The]n\n\n\nends");
+    let reader = reader.reset_at_paragraphs(true);
+    assert_eq!(reader.bio_read_text(text)?, "# [Som]e title\n\n[It] starts. [The]n\n\n\n[end]s");
+    // Including blank lines in the markup of other formats
+    let reader = reader.format(InputFormat::Markdown);
+    let stats = reader.bio_read_with_stats("# Title\n\n- one\n- two\n\n> three".as_bytes(), &mut Vec::new())?;
+    assert_eq!((stats.words, stats.paragraphs), (4, 3));
+    Ok(())
+}
@@ -0,0 +1,20 @@
+#![cfg(feature = "pdf")]
+
+use bio_read::{pdf, BioReader, InputFormat};
+use std::fs;
+
+#[test]
+fn test_bio_read_pdf() -> std::io::Result<()> {
+    let document = fs::read("tests/fixtures/hello.pdf")?;
+    assert_eq!(pdf::extract_text(&document)?.trim(), "Hello world");
+    // The text is bio-read as plain text, whatever the format of the reader
+    let reader = BioReader::new()
+        .emphasize("<em>".to_string(), "</em>".to_string())
+        .de_emphasize("".to_string(), "".to_string())
+        .format(InputFormat::Html);
+    let mut output = Vec::new();
+    pdf::bio_read_pdf(&reader, document.as_slice(), &mut output)?;
+    assert_eq!(String::from_utf8(output).unwrap().trim(), "<em>Hel</em>lo <em>wor</em>ld");
+    assert!(pdf::extract_text(b"not a pdf").is_err());
+    Ok(())
+}
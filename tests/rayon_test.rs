@@ -0,0 +1,50 @@
+#![cfg(feature = "rayon")]
+
+use bio_read::InputFormat;
+use common::setup_reader;
+use std::fs;
+
+mod common;
+
+/// Half a megabyte of paragraphs in various scripts, separated by blank lines of all kinds.
+fn large_text() -> String {
+    let paragraph = "It's a well-known fact. Visit https://example.com/ for café, naïve and مرحبا!\nSecond line, 世界 and 한국어";
+    let separators = ["\n\n", "\r\n\r\n", "\n \t\n", "\n\n\n"];
+    (0..4_000).map(|i| format!("{paragraph} {i}{}", separators[i % separators.len()])).collect()
+}
+
+#[test]
+fn test_bio_read_text_parallel() -> std::io::Result<()> {
+    let text = large_text();
+    let readers = [
+        setup_reader(3),
+        setup_reader(1).preserve_urls(true),
+        // Carrying over from one paragraph to the next, which isn't split
+        setup_reader(3).saccade(2),
+        setup_reader(3).sentence_boost(1),
+        // Unless restarting at each paragraph
+        setup_reader(3).saccade(3).sentence_boost(2).reset_at_paragraphs(true),
+        // Other formats aren't split either
+        setup_reader(3).format(InputFormat::Markdown),
+    ];
+    for reader in readers {
+        assert_eq!(reader.bio_read_text_parallel(&text)?, reader.bio_read_text(&text)?);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_bio_read_text_parallel_edge_cases() -> std::io::Result<()> {
+    let reader = setup_reader(3).saccade(2).reset_at_paragraphs(true);
+    let long_line = "word ".repeat(60_000);
+    let html = format!("<p>{}</p>\n\n<p>more</p>", "text ".repeat(60_000));
+    let texts = [String::new(), String::from("hello"), long_line.clone(), format!("{long_line}\n\n{long_line}\n\n"), html];
+    for text in texts {
+        assert_eq!(reader.bio_read_text_parallel(&text)?, reader.bio_read_text(&text)?);
+    }
+    for file in fs::read_dir("tests/input")? {
+        let text = fs::read_to_string(file?.path())?.repeat(12);
+        assert_eq!(reader.bio_read_text_parallel(&text)?, reader.bio_read_text(&text)?);
+    }
+    Ok(())
+}
@@ -0,0 +1,141 @@
+//! Property tests asserting that bio-reading never loses or corrupts input: stripping the emphasis markers from the output always reproduces the input byte-for-byte.
+
+use bio_read::{
+    segment::{CompoundPolicy, Language, TokenPolicy},
+    BioReader,
+};
+use proptest::prelude::*;
+use std::{
+    collections::HashSet,
+    io::{Read, Result},
+};
+
+/// Markers wrapped around the parts of words, from the Private Use Area so that they're easy to tell apart from the input.
+const MARKERS: [&str; 4] = ["\u{E000}", "\u{E001}", "\u{E002}", "\u{E003}"];
+
+/// Remove the markers from `output`, along with the zero-width joiners inserted around them to keep cursive scripts joined, like [`BioReader::strip`] does for text that may not be valid UTF-8.
+fn strip_bytes(output: &[u8]) -> Vec<u8> {
+    let joined = ["\u{200D}", MARKERS[1], MARKERS[2], "\u{200D}"].concat();
+    let mut output = replace_all(output, joined.as_bytes(), MARKERS[1].as_bytes());
+    for marker in MARKERS {
+        output = replace_all(&output, marker.as_bytes(), b"");
+    }
+    output
+}
+
+/// Replace all occurrences of `from` in `bytes` with `to`.
+fn replace_all(bytes: &[u8], from: &[u8], to: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(from) {
+            result.extend_from_slice(to);
+            i += from.len();
+        } else {
+            result.push(bytes[i]);
+            i += 1;
+        }
+    }
+    result
+}
+
+/// Whether `bytes` contains any of the markers, in which case stripping would remove input too.
+fn has_markers(bytes: &[u8]) -> bool {
+    MARKERS.iter().any(|marker| bytes.windows(marker.len()).any(|window| window == marker.as_bytes()))
+}
+
+/// A reader handing out `chunk_size` bytes at a time, to exercise words and characters split across chunks.
+struct Chunked<'a> {
+    bytes: &'a [u8],
+    chunk_size: usize,
+}
+
+impl Read for Chunked<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let len = self.chunk_size.min(buf.len()).min(self.bytes.len());
+        buf[..len].copy_from_slice(&self.bytes[..len]);
+        self.bytes = &self.bytes[len..];
+        Ok(len)
+    }
+}
+
+/// Readers with arbitrary settings that affect where words are and how they are split.
+fn readers() -> impl Strategy<Value = BioReader> {
+    let token_policies = [TokenPolicy::SplitAtDigits, TokenPolicy::IncludeDigits, TokenPolicy::SkipNumeric];
+    (
+        1..=5usize,
+        1..=3usize,
+        any::<bool>(),
+        any::<bool>(),
+        any::<bool>(),
+        proptest::sample::select(&Language::ALL[..]),
+        any::<bool>(),
+        proptest::sample::select(token_policies.to_vec()),
+        proptest::sample::select(vec![CompoundPolicy::Whole, CompoundPolicy::EachPart]),
+        0..=2usize,
+        any::<bool>(),
+    )
+        .prop_map(
+            |(fixation_point, saccade, unicode, preserve_urls, join_cursive, language, skip_acronyms, token_policy, compound_policy, sentence_boost, reset_at_paragraphs)| {
+                BioReader::new()
+                    .emphasize(MARKERS[0].to_string(), MARKERS[1].to_string())
+                    .de_emphasize(MARKERS[2].to_string(), MARKERS[3].to_string())
+                    .fixation_point(fixation_point)
+                    .saccade(saccade)
+                    .unicode(unicode)
+                    .preserve_urls(preserve_urls)
+                    .join_cursive(join_cursive)
+                    .language(language)
+                    .exclude_words(HashSet::from([String::from("the")]))
+                    .skip_acronyms(skip_acronyms)
+                    .token_policy(token_policy)
+                    .compound_policy(compound_policy)
+                    .sentence_boost(sentence_boost)
+                    .reset_at_paragraphs(reset_at_paragraphs)
+            },
+        )
+}
+
+/// Text made up of tricky pieces: ASCII words, joiners, combining marks, cursive and right-to-left scripts, CJK, emoji and URLs.
+fn tricky_text() -> impl Strategy<Value = String> {
+    const PIECES: [&str; 28] = [
+        "the", "word", "don't", "well-known", " ", "\n", "\r\n", ".", "é", "e\u{301}", "\u{301}", "İ", "ı", "مرحبا", "ـ", "\u{200D}", "שלום", "世界",
+        "한국어", "नमस्ते", "👨‍👩‍👧", "🏽", "https://example.com/", "~/notes.txt", "NASA", "42", "٣", "x86",
+    ];
+    proptest::collection::vec(proptest::sample::select(&PIECES[..]), 0..24).prop_map(|pieces| pieces.concat())
+}
+
+/// Bio-read `input` with `reader`, fed `chunk_size` bytes at a time.
+fn bio_read(reader: &BioReader, input: &[u8], chunk_size: usize) -> Result<Vec<u8>> {
+    let mut output = Vec::new();
+    reader.bio_read(Chunked { bytes: input, chunk_size }, &mut output)?;
+    Ok(output)
+}
+
+proptest! {
+    #[test]
+    fn ascii_round_trips(reader in readers(), text in "[ -~\t\n]{0,64}", chunk_size in 1..16usize) {
+        let output = String::from_utf8(bio_read(&reader, text.as_bytes(), chunk_size)?).unwrap();
+        prop_assert_eq!(reader.strip(&output), text);
+    }
+
+    #[test]
+    fn unicode_round_trips(reader in readers(), text in any::<String>(), chunk_size in 1..16usize) {
+        prop_assume!(!has_markers(text.as_bytes()));
+        let output = String::from_utf8(bio_read(&reader, text.as_bytes(), chunk_size)?).unwrap();
+        prop_assert_eq!(reader.strip(&output), text.as_str());
+        prop_assert_eq!(reader.strip(&reader.bio_read_text(&text)?), text);
+    }
+
+    #[test]
+    fn tricky_text_round_trips(reader in readers(), text in tricky_text(), chunk_size in 1..16usize) {
+        let output = String::from_utf8(bio_read(&reader, text.as_bytes(), chunk_size)?).unwrap();
+        prop_assert_eq!(reader.strip(&output), text);
+    }
+
+    #[test]
+    fn binary_round_trips(reader in readers(), bytes in proptest::collection::vec(any::<u8>(), 0..64), chunk_size in 1..16usize) {
+        prop_assume!(!has_markers(&bytes));
+        prop_assert_eq!(strip_bytes(&bio_read(&reader, &bytes, chunk_size)?), bytes);
+    }
+}
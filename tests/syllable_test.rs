@@ -0,0 +1,24 @@
+#![cfg(feature = "syllable")]
+
+use bio_read::{
+    fixation::{FixationStrategy, Syllable},
+    BioReader,
+};
+
+#[test]
+fn test_syllable() {
+    let english = Syllable::default();
+    assert_eq!(english.emphasized_len("reading"), 4);
+    // Words of a single syllable fall back to half of them
+    assert_eq!(english.emphasized_len("the"), 2);
+    assert_eq!(english.emphasized_len("a"), 1);
+    // Joiners and combining marks don't count as letters
+    assert_eq!(english.emphasized_len("well-known"), 4);
+    assert_eq!(Syllable::with_language("EN"), Some(english));
+    assert_eq!(Syllable::with_language("eng"), None);
+    let reader = BioReader::new()
+        .emphasize("<em>".to_string(), "</em>".to_string())
+        .de_emphasize("".to_string(), "".to_string())
+        .fixation_strategy(Syllable::with_language("fr").unwrap());
+    assert_eq!(reader.bio_read_text("la lecture rapide").unwrap(), "<em>l</em>a <em>lec</em>ture <em>ra</em>pide");
+}
@@ -0,0 +1,35 @@
+#![cfg(all(feature = "wasm-bindgen", target_arch = "wasm32"))]
+
+use bio_read::wasm::{bio_read_text, BioReadOptions};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn test_bio_read_text() {
+    // Html output by default
+    let options = BioReadOptions::new();
+    assert_eq!(bio_read_text("fish & chips", &options).unwrap(), "<b>fi</b>sh &amp; <b>chi</b>ps");
+    // Options are reusable
+    assert_eq!(bio_read_text("hello", &options).unwrap(), "<b>hel</b>lo");
+}
+
+#[wasm_bindgen_test]
+fn test_options() {
+    let mut options = BioReadOptions::new();
+    options.fixation_point = Some(1);
+    options.format = Some(String::from("html"));
+    options.emphasize = Some(String::from("<em>{}</em>"));
+    assert_eq!(bio_read_text("<p>hello</p>", &options).unwrap(), "<p><em>hel</em>lo</p>");
+    options.to = Some(String::from("markdown"));
+    options.emphasize = None;
+    assert_eq!(bio_read_text("<p>hello</p>", &options).unwrap(), "<p>**hel**lo</p>");
+}
+
+#[wasm_bindgen_test]
+fn test_invalid_options() {
+    let mut options = BioReadOptions::new();
+    options.fixation_point = Some(6);
+    assert!(bio_read_text("hello", &options).is_err());
+    let mut options = BioReadOptions::new();
+    options.to = Some(String::from("pdf"));
+    assert!(bio_read_text("hello", &options).is_err());
+}